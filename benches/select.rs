@@ -0,0 +1,97 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use parse_css_to_stylesheet::{parse, ParseOptions};
+
+// 生成含 nodes 个节点的组件，每个节点使用一个不同的类名
+fn generate_component(nodes: usize) -> String {
+  let children = (0..nodes)
+    .map(|index| format!("<View className=\"item-{} common\" />", index))
+    .collect::<Vec<_>>()
+    .join("\n");
+  format!(
+    "import {{ View }} from '@tarojs/components'\nexport default function Index() {{\n  return <View className=\"container\">\n{}\n</View>\n}}",
+    children
+  )
+}
+
+// 生成 rules 条规则，大部分规则不会匹配任何节点
+fn generate_styles(rules: usize) -> String {
+  (0..rules)
+    .map(|index| format!(".item-{} {{ width: {}px; height: 10px; }}\n.common.item-{} {{ color: red; }}", index, index, index))
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+fn get_options() -> ParseOptions {
+  ParseOptions {
+    platform_string: "Harmony".to_string(),
+    is_entry: false,
+    root_font_size: None,
+    em_font_size: None,
+    direction: None,
+    mirror_rtl: None,
+    font_family_map: None,
+    extract_theme: None,
+    supports_features: None,
+    passthrough_unrecognized: None,
+    rn_style_sheet_create: None,
+    tree_shaking: None,
+    minify: None,
+    scoped: None,
+    scope_id: None,
+    scope_name_template: None,
+    scope_hash_algorithm: None,
+    scope_hash_length: None,
+    scope_path: None,
+    inherit_properties: None,
+    tag_map: None,
+    component_sources: None,
+    color_format: None,
+    container_queries: None,
+    precision: None,
+    rounding: None,
+    rn_os: None,
+    harmony_api_version: None,
+    style_key_map: None,
+    lint_rules: None,
+    fallback_rules: None,
+    strict: None,
+    hoist_static_styles: None,
+    comments: None,
+    ascii_only: None,
+    target: None,
+    quote_style: None,
+    rename_keyframes: None,
+    scrollable_hint: None,
+    render_props: None,
+    global_style_count: None,
+    style_precedence: None,
+    platforms: None,
+    syntax: None,
+    stylesheet: None,
+    arkui: None,
+    dts: None,
+    match_report: None,
+    ir: None,
+    filename: None,
+  }
+}
+
+fn bench_select(c: &mut Criterion) {
+  let mut group = c.benchmark_group("select");
+  group.sample_size(10);
+  for (nodes, rules) in [(100, 1000), (500, 5000), (1000, 10000)] {
+    let component = generate_component(nodes);
+    let styles = generate_styles(rules);
+    group.bench_with_input(
+      BenchmarkId::from_parameter(format!("{}nodes_{}rules", nodes, rules)),
+      &(component, styles),
+      |b, (component, styles)| {
+        b.iter(|| parse(component.clone(), vec![styles.clone()], get_options()))
+      },
+    );
+  }
+  group.finish();
+}
+
+criterion_group!(benches, bench_select);
+criterion_main!(benches);
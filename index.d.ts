@@ -6,8 +6,116 @@
 export interface ParseOptions {
   platformString: string
   isEntry?: boolean
+  rootFontSize?: number
+  emFontSize?: number
+  direction?: string
+  mirrorRtl?: boolean
+  fontFamilyMap?: Record<string, string>
+  extractTheme?: boolean
+  supportsFeatures?: Record<string, boolean>
+  passthroughUnrecognized?: boolean
+  rnStyleSheetCreate?: boolean
+  treeShaking?: boolean
+  minify?: boolean
+  scoped?: boolean
+  scopeId?: string
+  scopeNameTemplate?: string
+  scopeHashAlgorithm?: string
+  scopeHashLength?: number
+  scopePath?: string
+  inheritProperties?: Array<string>
+  tagMap?: Record<string, string>
+  componentSources?: Array<string>
+  colorFormat?: string
+  containerQueries?: boolean
+  precision?: number
+  rounding?: string
+  rnOs?: string
+  harmonyApiVersion?: number
+  styleKeyMap?: Record<string, string>
+  lintRules?: Record<string, string>
+  fallbackRules?: Record<string, string>
+  strict?: boolean
+  hoistStaticStyles?: boolean
+  comments?: string
+  asciiOnly?: boolean
+  target?: string
+  quoteStyle?: string
+  renameKeyframes?: boolean
+  scrollableHint?: boolean
+  renderProps?: boolean
+  globalStyleCount?: number
+  stylePrecedence?: string
+  platforms?: Array<string>
+  syntax?: string
+  stylesheet?: boolean
+  arkui?: boolean
+  dts?: boolean
+  matchReport?: boolean
+  ir?: boolean
+  filename?: string
+}
+export interface ParseDiagnostic {
+  property: string
+  message: string
+  severity: string
+  rule?: string
+}
+export interface ParseFontFace {
+  familyName: string
+  src: Array<string>
+  fontWeight?: string
+  fontStyle?: string
 }
 export interface ParseResult {
   code: string
+  fonts: Array<ParseFontFace>
+  diagnostics: Array<ParseDiagnostic>
+  stylesheet?: string
+  arkui?: string
+  dts?: string
+  matchReport?: string
+  ir?: string
+  scopedClasses?: Record<string, string>
+  stats: ParseStats
+}
+export interface ParsePhase {
+  name: string
+  durationMs: number
+}
+export interface ParseStats {
+  rulesParsed: number
+  rulesMatched: number
+  nodesVisited: number
+  selectorHitRate: number
+  internerHitRate: number
+  phases: Array<ParsePhase>
+}
+export interface BundlerMeta {
+  usedClasses: Array<string>
+  unmatchedClasses: Array<string>
+  unusedClasses: Array<string>
+  diagnostics: Array<ParseDiagnostic>
+}
+export interface BundlerResult {
+  code: string
+  map?: string
+  meta: BundlerMeta
 }
 export function parse(component: string, styles: Array<string>, options: ParseOptions): ParseResult
+export function parsePlatforms(component: string, styles: Array<string>, options: ParseOptions): Record<string, ParseResult>
+export function transformForBundler(code: string, id: string, styles: Array<string>, options: ParseOptions): BundlerResult
+export interface SessionArtifact {
+  path: string
+  result: ParseResult
+}
+export class Session {
+  constructor(options: ParseOptions)
+  addComponent(path: string, component: string, stylePaths: Array<string>): Array<SessionArtifact>
+  addApp(path: string, component: string, stylePaths: Array<string>): Array<SessionArtifact>
+  addPage(path: string, component: string, stylePaths: Array<string>): Array<SessionArtifact>
+  removeComponent(path: string): Array<SessionArtifact>
+  jsxChanged(path: string, newContent: string): Array<SessionArtifact>
+  cssChanged(path: string, newContent: string): Array<SessionArtifact>
+  cssRemoved(path: string): Array<SessionArtifact>
+}
@@ -310,6 +310,9 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`)
 }
 
-const { parse } = nativeBinding
+const { parse, parsePlatforms, transformForBundler, Session } = nativeBinding
 
 module.exports.parse = parse
+module.exports.parsePlatforms = parsePlatforms
+module.exports.transformForBundler = transformForBundler
+module.exports.Session = Session
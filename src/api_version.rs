@@ -0,0 +1,74 @@
+use swc_core::ecma::ast::*;
+
+use crate::{diagnostics::push_diagnostic, options::with_transform_options};
+
+// 鸿蒙样式属性所需的最低 API 版本，未列出的属性视为所有版本均支持
+const HARMONY_API_LEVELS: [(&str, u32); 8] = [
+  ("textShadow", 10),
+  ("nestedScroll", 10),
+  ("outlineWidth", 11),
+  ("outlineStyle", 11),
+  ("outlineColor", 11),
+  ("outlineRadius", 11),
+  ("wordBreak", 11),
+  ("textStroke", 20),
+];
+
+// textShadow 支持阴影数组的最低 API 版本
+const TEXT_SHADOW_ARRAY_API_LEVEL: u32 = 11;
+// textDecoration 支持 style 的最低 API 版本
+const TEXT_DECORATION_STYLE_API_LEVEL: u32 = 12;
+
+// 按配置的鸿蒙 API 版本检查属性，低版本不支持的属性值尽量降级，无法降级时丢弃并输出诊断信息，未配置版本时不做检查
+pub fn check_harmony_api_level(key: &str, expr: Expr) -> Option<Expr> {
+  let api_version = match with_transform_options(|options| options.harmony_api_version) {
+    Some(api_version) => api_version,
+    None => return Some(expr)
+  };
+  if let Some((_, level)) = HARMONY_API_LEVELS.iter().find(|(name, _)| *name == key) {
+    if api_version < *level {
+      push_diagnostic(key, format!("{} requires HarmonyOS API {} or later, but the target is API {}", key, level, api_version));
+      return None
+    }
+  }
+  Some(downgrade_harmony_expr(key, expr, api_version))
+}
+
+fn downgrade_harmony_expr(key: &str, expr: Expr, api_version: u32) -> Expr {
+  match (key, expr) {
+    // 低版本只支持单个阴影，取最先声明（绘制在最上层）的阴影
+    ("textShadow", Expr::Array(array)) if api_version < TEXT_SHADOW_ARRAY_API_LEVEL => {
+      push_diagnostic(key, format!("multiple text shadows require HarmonyOS API {} or later, only the first one will be used", TEXT_SHADOW_ARRAY_API_LEVEL));
+      match array.elems.into_iter().flatten().next() {
+        Some(first) => *first.expr,
+        None => Expr::Invalid(Invalid { span: array.span })
+      }
+    },
+    // 低版本的装饰线不支持 style，移除后按实线绘制，非实线时输出诊断信息
+    ("textDecoration", Expr::Object(mut object)) if api_version < TEXT_DECORATION_STYLE_API_LEVEL => {
+      let mut is_solid = true;
+      object.props.retain(|prop| match get_key_value_prop(prop, "style") {
+        Some(value) => {
+          is_solid = matches!(value, Expr::Member(MemberExpr { prop: MemberProp::Ident(ident), .. }) if ident.sym == *"SOLID");
+          false
+        },
+        None => true
+      });
+      if !is_solid {
+        push_diagnostic(key, format!("text-decoration-style requires HarmonyOS API {} or later and will be ignored", TEXT_DECORATION_STYLE_API_LEVEL));
+      }
+      Expr::Object(object)
+    },
+    (_, expr) => expr
+  }
+}
+
+fn get_key_value_prop<'a>(prop: &'a PropOrSpread, name: &str) -> Option<&'a Expr> {
+  match prop {
+    PropOrSpread::Prop(prop) => match &**prop {
+      Prop::KeyValue(KeyValueProp { key: PropName::Ident(ident), value }) if ident.sym == *name => Some(value),
+      _ => None
+    },
+    _ => None
+  }
+}
@@ -0,0 +1,210 @@
+use std::collections::BTreeMap;
+
+use swc_core::{
+  common::DUMMY_SP,
+  ecma::{ast::*, visit::{VisitMut, VisitMutWith}},
+};
+
+use crate::{
+  constants::CONVERT_STYLE_PX_FN,
+  diagnostics::push_diagnostic,
+  style_parser::{StyleMap, StyleValue},
+  style_propetries::unit::Platform,
+  stylesheet::emit_expr,
+  utils::{is_class_selector, is_compound_class_selector, to_camel_case},
+  visitor::parse_raw_style_values,
+};
+
+// ArkUI 中 px 转 vp 的内置方法
+const ARKUI_PX_FN: &str = "px2vp";
+
+// 通用属性，可在 @Styles 中使用，(样式属性, ArkUI 属性方法)
+const UNIVERSAL_ATTRIBUTES: [(&str, &str); 13] = [
+  ("width", "width"),
+  ("height", "height"),
+  ("backgroundColor", "backgroundColor"),
+  ("opacity", "opacity"),
+  ("zIndex", "zIndex"),
+  ("flexGrow", "flexGrow"),
+  ("flexShrink", "flexShrink"),
+  ("flexBasis", "flexBasis"),
+  ("alignSelf", "alignSelf"),
+  ("aspectRatio", "aspectRatio"),
+  ("visibility", "visibility"),
+  ("boxShadow", "shadow"),
+  ("backgroundImage", "backgroundImage"),
+];
+
+// 文本属性，仅 Text 组件可用，生成 @Extend(Text)
+const TEXT_ATTRIBUTES: [(&str, &str); 10] = [
+  ("color", "fontColor"),
+  ("fontSize", "fontSize"),
+  ("fontWeight", "fontWeight"),
+  ("fontStyle", "fontStyle"),
+  ("fontFamily", "fontFamily"),
+  ("lineHeight", "lineHeight"),
+  ("textAlign", "textAlign"),
+  ("letterSpacing", "letterSpacing"),
+  ("textOverflow", "textOverflow"),
+  ("textDecoration", "decoration"),
+];
+
+// 按方向拆分的属性合并为一个对象参数，(样式属性, ArkUI 属性方法, 对象的 key)
+const GROUPED_ATTRIBUTES: [(&str, &str, &str); 26] = [
+  ("marginTop", "margin", "top"),
+  ("marginRight", "margin", "right"),
+  ("marginBottom", "margin", "bottom"),
+  ("marginLeft", "margin", "left"),
+  ("paddingTop", "padding", "top"),
+  ("paddingRight", "padding", "right"),
+  ("paddingBottom", "padding", "bottom"),
+  ("paddingLeft", "padding", "left"),
+  ("borderTopLeftRadius", "borderRadius", "topLeft"),
+  ("borderTopRightRadius", "borderRadius", "topRight"),
+  ("borderBottomLeftRadius", "borderRadius", "bottomLeft"),
+  ("borderBottomRightRadius", "borderRadius", "bottomRight"),
+  ("borderTopWidth", "borderWidth", "top"),
+  ("borderRightWidth", "borderWidth", "right"),
+  ("borderBottomWidth", "borderWidth", "bottom"),
+  ("borderLeftWidth", "borderWidth", "left"),
+  ("borderTopColor", "borderColor", "top"),
+  ("borderRightColor", "borderColor", "right"),
+  ("borderBottomColor", "borderColor", "bottom"),
+  ("borderLeftColor", "borderColor", "left"),
+  ("borderTopStyle", "borderStyle", "top"),
+  ("borderRightStyle", "borderStyle", "right"),
+  ("borderBottomStyle", "borderStyle", "bottom"),
+  ("borderLeftStyle", "borderStyle", "left"),
+  ("minWidth", "constraintSize", "minWidth"),
+  ("maxWidth", "constraintSize", "maxWidth"),
+];
+
+// 对象参数的字段，如 margin({ top: px2vp(10) }) => ("top", px2vp(10))
+type ObjectFields = Vec<(String, Box<Expr>)>;
+
+// convertNumber2VP(10) => px2vp(10)
+struct PxFnRenamer;
+
+impl VisitMut for PxFnRenamer {
+  fn visit_mut_ident(&mut self, ident: &mut Ident) {
+    if ident.sym == CONVERT_STYLE_PX_FN {
+      ident.sym = ARKUI_PX_FN.into();
+    }
+  }
+}
+
+fn emit_arkui_expr(expr: &Expr) -> String {
+  let mut expr = expr.clone();
+  expr.visit_mut_with(&mut PxFnRenamer);
+  emit_expr(&expr)
+}
+
+fn generate_object_expr(props: ObjectFields) -> Expr {
+  Expr::Object(ObjectLit {
+    span: DUMMY_SP,
+    props: props.into_iter().map(|(key, value)| {
+      PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+        key: PropName::Ident(Ident::new(key.into(), DUMMY_SP)),
+        value,
+      })))
+    }).collect()
+  })
+}
+
+// 将一个类的样式转换为 ArkUI 属性方法链，返回 (通用属性, 文本属性)
+fn generate_attribute_chain(selector: &str, value: &StyleValue) -> (Vec<String>, Vec<String>) {
+  let mut universal_attributes = vec![];
+  let mut text_attributes = vec![];
+  // 合并的对象参数，保持首次出现的顺序
+  let mut grouped_attributes: Vec<(&str, ObjectFields)> = vec![];
+  let mut position = None;
+  let mut offsets: ObjectFields = vec![];
+
+  parse_raw_style_values(value.to_vec(), Platform::Harmony).into_iter().for_each(|prop| {
+    let (key, value) = match prop {
+      PropOrSpread::Prop(prop) => match *prop {
+        Prop::KeyValue(KeyValueProp { key: PropName::Ident(ident), value }) => (ident.sym.to_string(), value),
+        _ => return
+      },
+      _ => return
+    };
+    if let Some((_, method)) = UNIVERSAL_ATTRIBUTES.iter().find(|(name, _)| *name == key) {
+      universal_attributes.push(format!(".{}({})", method, emit_arkui_expr(&value)));
+    } else if let Some((_, method)) = TEXT_ATTRIBUTES.iter().find(|(name, _)| *name == key) {
+      text_attributes.push(format!(".{}({})", method, emit_arkui_expr(&value)));
+    } else if let Some((_, method, field)) = GROUPED_ATTRIBUTES.iter().find(|(name, _, _)| *name == key) {
+      match grouped_attributes.iter_mut().find(|(name, _)| name == method) {
+        Some((_, fields)) => fields.push((field.to_string(), value)),
+        None => grouped_attributes.push((method, vec![(field.to_string(), value)]))
+      }
+    } else if key == "minHeight" || key == "maxHeight" {
+      match grouped_attributes.iter_mut().find(|(name, _)| *name == "constraintSize") {
+        Some((_, fields)) => fields.push((key, value)),
+        None => grouped_attributes.push(("constraintSize", vec![(key, value)]))
+      }
+    } else if key == "overflow" {
+      // overflow: hidden => .clip(true)
+      if let Expr::Lit(Lit::Str(str)) = &*value {
+        universal_attributes.push(format!(".clip({})", str.value == "hidden"));
+      }
+    } else if key == "transform" {
+      // { Rotate: {...}, Translate: {...} } => .rotate({...}).translate({...})
+      if let Expr::Object(ObjectLit { props, .. }) = &*value {
+        props.iter().for_each(|prop| {
+          if let PropOrSpread::Prop(prop) = prop {
+            if let Prop::KeyValue(KeyValueProp { key: PropName::Ident(ident), value }) = &**prop {
+              universal_attributes.push(format!(".{}({})", ident.sym.to_lowercase(), emit_arkui_expr(value)));
+            }
+          }
+        });
+      }
+    } else if key == "position" {
+      if let Expr::Lit(Lit::Str(str)) = &*value {
+        position = Some(str.value.to_string());
+      }
+    } else if key == "left" || key == "top" {
+      offsets.push((if key == "left" { "x".to_string() } else { "y".to_string() }, value));
+    } else {
+      // display、flexDirection 等容器属性需要在 Flex、Column、Row 等组件的参数中设置
+      push_diagnostic(&key, format!("{} in {} is not supported in ArkUI @Styles and will be ignored", key, selector));
+    }
+  });
+
+  grouped_attributes.into_iter().for_each(|(method, fields)| {
+    universal_attributes.push(format!(".{}({})", method, emit_arkui_expr(&generate_object_expr(fields))));
+  });
+  if !offsets.is_empty() {
+    // 绝对定位使用 position，相对定位使用 offset
+    let method = match position.as_deref() {
+      Some("absolute") | Some("fixed") => "position",
+      _ => "offset"
+    };
+    universal_attributes.push(format!(".{}({})", method, emit_arkui_expr(&generate_object_expr(offsets))));
+  }
+
+  (universal_attributes, text_attributes)
+}
+
+// 为每个类生成 @Styles 函数，文本属性生成 @Extend(Text) 函数，如：
+// @Styles function containerStyles() { .width(px2vp(100)) }
+// @Extend(Text) function containerText() { .fontColor("#FF0000") }
+pub fn generate_arkui_styles(all_style: &StyleMap) -> String {
+  let style_entries: BTreeMap<_, _> = all_style.iter().collect();
+  let mut code = vec![];
+  style_entries.into_iter().for_each(|(selector, value)| {
+    // 仅支持单个类选择器，嵌套选择器、伪类等无法用 @Styles 表示
+    if !is_class_selector(selector) || is_compound_class_selector(selector) {
+      push_diagnostic("selector", format!("{} is not supported in ArkUI @Styles and will be ignored", selector));
+      return
+    }
+    let name = to_camel_case(&selector[1..], false);
+    let (universal_attributes, text_attributes) = generate_attribute_chain(selector, value);
+    if !universal_attributes.is_empty() {
+      code.push(format!("@Styles function {}Styles() {{\n  {}\n}}", name, universal_attributes.join("\n  ")));
+    }
+    if !text_attributes.is_empty() {
+      code.push(format!("@Extend(Text) function {}Text() {{\n  {}\n}}", name, text_attributes.join("\n  ")));
+    }
+  });
+  code.join("\n\n")
+}
@@ -4,15 +4,28 @@ pub const HM_STYLE: &'static str = "__hmStyle";
 pub const INNER_STYLE: &'static str = "__inner_style__";
 pub const INNER_STYLE_DATA: &'static str = "__inner_style_data__";
 pub const NESTING_STYLE: &'static str = "__nesting_style__";
+// 类组件中提升到模块作用域的静态样式对象
+pub const HOISTED_STYLE: &str = "__hoisted_style__";
+pub const HOISTED_STYLE_DATA: &str = "__hoisted_style_data__";
 pub const COMBINE_NESTING_STYLE: &'static str = "__combine_nesting_style__";
 pub const NESTINT_STYLE_DATA: &'static str = "__nesting_style_data__";
 pub const ENV_FUN: &'static str = "__env__";
 pub const VAR_FUN: &'static str = "__var__";
 pub const GLOBAL_SHARED: &'static str = "__global_shared__";
+pub const THEME: &str = "theme";
+pub const COLOR_SCHEME_FUN: &str = "__color_scheme__";
+// 容器查询的条件样式，由运行时根据容器尺寸判断是否生效
+pub const CONTAINER_QUERY_FUN: &str = "__container_query__";
+// 无法静态求值的 currentColor 的占位值，由运行时替换为元素的 color
+pub const CURRENT_COLOR: &str = "__current_color__";
+// overflow: scroll、auto 的可滚动方向，由运行时将容器替换为 Scroll 组件
+pub const SCROLLABLE: &str = "__scrollable__";
 
 // pub const CALC_DYMAMIC_STYLE: &'static str = "calcDynamicStyle";
 pub const CALC_STATIC_STYLE: &'static str = "calcStaticStyle";
 pub static SUPPORT_PSEUDO_KEYS: [&'static str; 6] = [":before", ":after", ":first-child", ":last-child", ":nth-child", ":empty"];
+// 常见的高阶组件包装函数，如 memo(App)、React.forwardRef(App)、connect(mapState)(App)
+pub static HOC_WRAPPERS: [&str; 6] = ["memo", "forwardRef", "observer", "connect", "inject", "withRouter"];
 
 
 pub const RN_CONVERT_STYLE_PX_FN: &'static str = "scalePx2dp";
@@ -0,0 +1,173 @@
+use lightningcss::{
+  media_query::{MediaFeatureComparison, MediaFeatureName, MediaFeatureValue, Operator, QueryFeature},
+  properties::{contain::{ContainerNameList, ContainerType}, Property},
+  rules::container::{ContainerCondition, ContainerSizeFeatureId},
+};
+
+use crate::{style_parser::StyleRecord, style_propetries::gap::get_px_size};
+
+// 样式中声明了 container-type 的查询容器，宽高仅记录以 px 声明的值
+pub struct QueryContainer {
+  pub selector: String,
+  pub names: Vec<String>,
+  pub container_type: ContainerType,
+  pub width: Option<f32>,
+  pub height: Option<f32>,
+}
+
+// 无法在编译期求值的 @container 规则，如 @container card (width >= 300px) => { name: Some("card"), condition: "(width >= 300px)" }
+#[derive(Debug, Clone)]
+pub struct ContainerQuery {
+  pub name: Option<String>,
+  pub condition: String,
+}
+
+// @container 规则的求值结果
+pub enum ContainerMatch {
+  Matched,
+  Unmatched,
+  // 无法在编译期求值，包含尺寸未知或匹配结果不一致的容器
+  Unresolved(Vec<String>),
+}
+
+// 收集已声明的查询容器，同一选择器中后声明的属性覆盖先声明的
+pub fn collect_query_containers(all_style: &StyleRecord) -> Vec<QueryContainer> {
+  all_style.iter().filter_map(|(selector, declarations)| {
+    let mut container = QueryContainer {
+      selector: selector.clone(),
+      names: vec![],
+      container_type: ContainerType::Normal,
+      width: None,
+      height: None,
+    };
+    declarations
+      .iter()
+      .flat_map(|declaration| declaration.declaration.declarations.iter().chain(declaration.declaration.important_declarations.iter()))
+      .for_each(|property| match property {
+        Property::Container(value) => {
+          container.names = get_container_names(&value.name);
+          container.container_type = value.container_type;
+        }
+        Property::ContainerName(names) => container.names = get_container_names(names),
+        Property::ContainerType(container_type) => container.container_type = *container_type,
+        Property::Width(_) => container.width = get_px_size(property),
+        Property::Height(_) => container.height = get_px_size(property),
+        _ => {}
+      });
+    match container.container_type {
+      ContainerType::Normal => None,
+      _ => Some(container)
+    }
+  }).collect()
+}
+
+fn get_container_names(names: &ContainerNameList) -> Vec<String> {
+  match names {
+    ContainerNameList::None => vec![],
+    ContainerNameList::Names(names) => names.iter().map(|name| name.0.0.to_string()).collect()
+  }
+}
+
+// 按容器名查找容器并求值，未指定容器名时所有容器均可能是最近的查询容器，结果一致时才能在编译期确定
+pub fn evaluate_container_rule(name: Option<&str>, condition: &ContainerCondition, containers: &[QueryContainer]) -> ContainerMatch {
+  let candidates = containers
+    .iter()
+    .filter(|container| name.is_none_or(|name| container.names.iter().any(|container_name| container_name == name)))
+    .collect::<Vec<_>>();
+  if candidates.is_empty() {
+    return ContainerMatch::Unresolved(vec![name.unwrap_or("<anonymous>").to_string()])
+  }
+  let results = candidates.iter().map(|container| evaluate_condition(condition, container)).collect::<Vec<_>>();
+  if results.iter().all(|result| *result == Some(true)) {
+    return ContainerMatch::Matched
+  }
+  if results.iter().all(|result| *result == Some(false)) {
+    return ContainerMatch::Unmatched
+  }
+  let unresolved = candidates
+    .iter()
+    .zip(results.iter())
+    .filter(|(_, result)| result.is_none() || results.iter().any(|other| other != *result))
+    .map(|(container, _)| container.selector.clone())
+    .collect();
+  ContainerMatch::Unresolved(unresolved)
+}
+
+// 三值逻辑求值，None 表示无法在编译期确定
+fn evaluate_condition(condition: &ContainerCondition, container: &QueryContainer) -> Option<bool> {
+  match condition {
+    ContainerCondition::Feature(feature) => evaluate_feature(feature, container),
+    ContainerCondition::Not(condition) => evaluate_condition(condition, container).map(|result| !result),
+    ContainerCondition::Operation { operator, conditions } => {
+      let results = conditions.iter().map(|condition| evaluate_condition(condition, container)).collect::<Vec<_>>();
+      let (decisive, default) = match operator {
+        Operator::And => (false, true),
+        Operator::Or => (true, false),
+      };
+      if results.contains(&Some(decisive)) {
+        Some(decisive)
+      } else if results.iter().all(|result| result.is_some()) {
+        Some(default)
+      } else {
+        None
+      }
+    }
+    // 样式查询依赖容器的计算样式，由运行时判断
+    ContainerCondition::Style(_) => None,
+  }
+}
+
+fn evaluate_feature(feature: &QueryFeature<ContainerSizeFeatureId>, container: &QueryContainer) -> Option<bool> {
+  let name = match feature {
+    QueryFeature::Plain { name, .. }
+    | QueryFeature::Boolean { name }
+    | QueryFeature::Range { name, .. }
+    | QueryFeature::Interval { name, .. } => name,
+  };
+  let MediaFeatureName::Standard(feature_id) = name else {
+    return None
+  };
+  let is_size = container.container_type == ContainerType::Size;
+  // inline-size 容器不能查询块方向的尺寸，按规范视为不匹配
+  let actual = match feature_id {
+    ContainerSizeFeatureId::Width | ContainerSizeFeatureId::InlineSize => container.width?,
+    ContainerSizeFeatureId::Height | ContainerSizeFeatureId::BlockSize if is_size => container.height?,
+    ContainerSizeFeatureId::AspectRatio if is_size => container.width? / container.height?,
+    ContainerSizeFeatureId::Orientation if is_size => {
+      let orientation = if container.height? >= container.width? { "portrait" } else { "landscape" };
+      return match feature {
+        QueryFeature::Plain { value: MediaFeatureValue::Ident(ident), .. } => Some(ident.0.as_ref() == orientation),
+        _ => None
+      }
+    }
+    _ => return Some(false)
+  };
+  match feature {
+    QueryFeature::Plain { value, .. } => Some(compare(actual, &MediaFeatureComparison::Equal, get_feature_value(value)?)),
+    QueryFeature::Boolean { .. } => Some(actual != 0.0),
+    QueryFeature::Range { operator, value, .. } => Some(compare(actual, operator, get_feature_value(value)?)),
+    QueryFeature::Interval { start, start_operator, end, end_operator, .. } => {
+      Some(compare(get_feature_value(start)?, start_operator, actual) && compare(actual, end_operator, get_feature_value(end)?))
+    }
+  }
+}
+
+fn get_feature_value(value: &MediaFeatureValue) -> Option<f32> {
+  match value {
+    MediaFeatureValue::Length(length) => length.to_px(),
+    MediaFeatureValue::Number(number) => Some(*number),
+    MediaFeatureValue::Integer(integer) => Some(*integer as f32),
+    MediaFeatureValue::Ratio(ratio) => Some(ratio.0 / ratio.1),
+    _ => None
+  }
+}
+
+fn compare(left: f32, operator: &MediaFeatureComparison, right: f32) -> bool {
+  match operator {
+    MediaFeatureComparison::Equal => (left - right).abs() < f32::EPSILON,
+    MediaFeatureComparison::GreaterThan => left > right,
+    MediaFeatureComparison::GreaterThanEqual => left >= right,
+    MediaFeatureComparison::LessThan => left < right,
+    MediaFeatureComparison::LessThanEqual => left <= right,
+  }
+}
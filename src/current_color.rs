@@ -0,0 +1,53 @@
+use std::convert::Infallible;
+
+use lightningcss::{properties::Property, values::color::CssColor, visit_types, visitor::{Visit, VisitTypes, Visitor}};
+use swc_core::atoms::Atom;
+
+use crate::{constants::CURRENT_COLOR, diagnostics::push_diagnostic};
+
+// 将属性值中的 currentColor 替换为规则中声明的 color
+struct CurrentColorVisitor {
+  color: Option<CssColor>,
+  // 是否存在无法静态求值的 currentColor
+  unresolved: bool,
+}
+
+impl<'i> Visitor<'i> for CurrentColorVisitor {
+  type Error = Infallible;
+  const TYPES: VisitTypes = visit_types!(COLORS);
+
+  fn visit_color(&mut self, color: &mut CssColor) -> Result<(), Self::Error> {
+    if *color == CssColor::CurrentColor {
+      match &self.color {
+        Some(current_color) => *color = current_color.clone(),
+        None => self.unresolved = true
+      }
+    }
+    Ok(())
+  }
+}
+
+// currentColor 按同一条规则中声明的 color 静态求值，如 .a { color: red; border-color: currentColor } => borderColor: red
+// 规则中没有声明 color 时无法得知元素的实际颜色，输出运行时占位值 __current_color__，由运行时替换为元素的 color
+pub fn resolve_current_color<'i>(properties: Vec<(Atom, Property<'i>)>) -> Vec<(Atom, Property<'i>)> {
+  // color: currentColor 等同于继承父节点的颜色，同样无法静态求值
+  let color = properties.iter().rev().find_map(|(_, property)| match property {
+    Property::Color(color) => Some(color.clone()),
+    _ => None
+  }).filter(|color| *color != CssColor::CurrentColor);
+
+  properties.into_iter().map(|(id, mut property)| {
+    match property {
+      // text-decoration 的颜色默认即为文字颜色，currentColor 时不设置颜色
+      Property::Color(_) | Property::TextDecoration(..) | Property::TextDecorationColor(..) => {}
+      _ => {
+        let mut visitor = CurrentColorVisitor { color: color.clone(), unresolved: false };
+        let _ = property.visit(&mut visitor);
+        if visitor.unresolved {
+          push_diagnostic(&id, format!("currentColor cannot be resolved statically, fallback to {}", CURRENT_COLOR));
+        }
+      }
+    }
+    (id, property)
+  }).collect()
+}
@@ -0,0 +1,69 @@
+use crate::{
+  constants::{CONVERT_STYLE_PX_FN, RN_CONVERT_STYLE_PX_FN, RN_CONVERT_STYLE_VU_FN},
+  style_parser::StyleMap,
+  style_propetries::unit::Platform,
+  stylesheet::{generate_rules, StyleSheetValue},
+  utils::{is_class_selector, is_tailwind_arbitrary},
+};
+
+// 生成 __inner_style__ 样式对象的 TypeScript 类型声明，key 为类名，值为各属性转换后的类型，如：
+// export interface InnerStyle {
+//   "item": {
+//     width: number | string;
+//   };
+// }
+// 仅包含类选择器，伪类、嵌套选择器的样式不在样式对象的顶层
+pub fn generate_style_declaration(all_style: &StyleMap, platform: Platform) -> String {
+  let entries = generate_rules(all_style, platform)
+    .iter()
+    .filter(|(selector, _)| is_class_selector(selector) || is_tailwind_arbitrary(selector))
+    .map(|(selector, declarations)| {
+      let properties = declarations
+        .iter()
+        .map(|(key, value)| format!("    {}: {};\n", get_property_key(key), get_value_type(value)))
+        .collect::<String>();
+      format!("  {}: {{\n{}  }};\n", serde_json::to_string(&selector[1..]).unwrap(), properties)
+    })
+    .collect::<String>();
+  format!("export interface InnerStyle {{\n{}}}\n\nexport type InnerStyleKey = keyof InnerStyle;\n", entries)
+}
+
+// 驼峰命名的属性名直接输出，其他如伪类的 ::after 需要加引号
+fn get_property_key(key: &str) -> String {
+  match key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$') && !key.starts_with(|c: char| c.is_ascii_digit()) {
+    true => key.to_string(),
+    false => serde_json::to_string(key).unwrap()
+  }
+}
+
+fn get_value_type(value: &StyleSheetValue) -> String {
+  match value {
+    StyleSheetValue::Null => "null".to_string(),
+    StyleSheetValue::Bool(_) => "boolean".to_string(),
+    StyleSheetValue::Number(_) => "number".to_string(),
+    StyleSheetValue::String(_) => "string".to_string(),
+    StyleSheetValue::Array(items) => {
+      let mut item_types = items.iter().map(get_value_type).collect::<Vec<_>>();
+      item_types.sort();
+      item_types.dedup();
+      match item_types.is_empty() {
+        true => "Array<unknown>".to_string(),
+        false => format!("Array<{}>", item_types.join(" | "))
+      }
+    }
+    StyleSheetValue::Object(entries) => {
+      let properties = entries
+        .iter()
+        .map(|(key, value)| format!("{}: {}", get_property_key(key), get_value_type(value)))
+        .collect::<Vec<_>>();
+      format!("{{ {} }}", properties.join("; "))
+    }
+    // 运行时的单位换算返回数值或带单位的字符串，其他表达式（如 css 变量）的类型在编译期无法确定
+    StyleSheetValue::Expr { expr } => {
+      match [CONVERT_STYLE_PX_FN, RN_CONVERT_STYLE_PX_FN, RN_CONVERT_STYLE_VU_FN].iter().any(|function| expr.starts_with(&format!("{}(", function))) {
+        true => "number | string".to_string(),
+        false => "any".to_string()
+      }
+    }
+  }
+}
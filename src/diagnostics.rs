@@ -0,0 +1,86 @@
+use std::cell::RefCell;
+
+use tracing::debug;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiagnosticSeverity {
+  Warning,
+  Error
+}
+
+impl DiagnosticSeverity {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      DiagnosticSeverity::Warning => "warning",
+      DiagnosticSeverity::Error => "error"
+    }
+  }
+}
+
+// 转换过程中产生的诊断信息，如目标平台不支持的属性或属性值
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+  // 触发诊断的 css 属性
+  pub property: String,
+  pub message: String,
+  pub severity: DiagnosticSeverity,
+  // 样式检查产生的诊断信息对应的规则名
+  pub rule: Option<String>,
+}
+
+thread_local! {
+  static DIAGNOSTICS: RefCell<Vec<Diagnostic>> = const { RefCell::new(Vec::new()) };
+}
+
+pub fn push_diagnostic(property: &str, message: String) {
+  record_diagnostic(Diagnostic {
+    property: property.to_string(),
+    message,
+    severity: DiagnosticSeverity::Warning,
+    rule: None,
+  });
+}
+
+// 错误级别的诊断信息，严格模式下导致转换失败
+pub fn push_error(property: &str, message: String) {
+  record_diagnostic(Diagnostic {
+    property: property.to_string(),
+    message,
+    severity: DiagnosticSeverity::Error,
+    rule: None,
+  });
+}
+
+// 样式检查规则产生的诊断信息
+pub fn push_lint_diagnostic(rule: &str, severity: DiagnosticSeverity, property: &str, message: String) {
+  record_diagnostic(Diagnostic {
+    property: property.to_string(),
+    message,
+    severity,
+    rule: Some(rule.to_string()),
+  });
+}
+
+// 同一属性的同一条信息只记录一次
+fn record_diagnostic(diagnostic: Diagnostic) {
+  DIAGNOSTICS.with(|cell| {
+    let mut diagnostics = cell.borrow_mut();
+    if !diagnostics.contains(&diagnostic) {
+      debug!(property = diagnostic.property.as_str(), "{}", diagnostic.message);
+      diagnostics.push(diagnostic);
+    }
+  });
+}
+
+// 取出并清空已收集的诊断信息
+pub fn take_diagnostics() -> Vec<Diagnostic> {
+  DIAGNOSTICS.with(|cell| std::mem::take(&mut *cell.borrow_mut()))
+}
+
+// 执行期间产生的诊断信息不记录，用于试探性的转换，如 @supports 条件的求值
+pub fn without_diagnostics<R>(f: impl FnOnce() -> R) -> R {
+  let diagnostics = take_diagnostics();
+  let result = f();
+  DIAGNOSTICS.with(|cell| *cell.borrow_mut() = diagnostics);
+  result
+}
@@ -1,12 +1,14 @@
-use std::collections::HashMap;
+use std::{cell::RefCell, collections::HashMap};
 
+use ego_tree::{NodeId, NodeRef, Tree};
+use indexmap::IndexMap;
 use selectors::attr::CaseSensitivity;
 
 use swc_core::{
   ecma::{
     ast::{EsVersion, Program},
-    parser::{lexer::Lexer, Parser, StringInput, Syntax, TsConfig},
-    visit::{FoldWith, VisitAllWith, VisitWith},
+    parser::{lexer::Lexer, EsConfig, Parser, StringInput, Syntax, TsConfig},
+    visit::{FoldWith, VisitAllWith, VisitMutWith, VisitWith},
     transforms::{
       base::{fixer::fixer, hygiene::hygiene, resolver},
       typescript::strip
@@ -22,13 +24,57 @@ use swc_core::{
 
 use crate::{
   scraper::Element,
-  visitor::{AstVisitor, CollectVisitor, JSXRecord},
+  utils::{is_class_selector, split_negation, split_top_level},
+  scope::is_scoped,
+  stats::record_selector_lookup,
+  tag::{get_element_tag, normalize_tag, split_tag},
+  visitor::{AstVisitor, CollectVisitor, ConstClassNameVisitor, JSXNode, JSXRecord, JSXTreeVisitor, ScopedClassNameVisitor, SpanKey, SpanNormalizeVisitor},
 };
 
+// 组件代码的语法，Taro 项目大多使用 TypeScript，默认按 TSX 解析
+#[derive(Debug, Clone, PartialEq)]
+pub enum SourceSyntax {
+  Tsx,
+  Jsx,
+}
+
+// :not() 在静态节点树上的求值结果
+#[derive(Debug, PartialEq)]
+pub enum NegationMatch {
+  // 不含 :not() 或匹配的节点均未被排除，等价于去除 :not() 后的选择器
+  Unaffected(String),
+  // 匹配的节点均被排除，规则不会生效
+  Excluded,
+  // 部分节点被排除，运行时按类名合并样式时无法区分
+  Partial,
+  // :not() 的参数不是类选择器
+  Unsupported,
+}
+
 pub struct JSXDocument {
   pub program: Option<Program>,
   pub jsx_record: Option<JSXRecord>,
   pub taro_components: Vec<String>,
+  pub syntax: SourceSyntax,
+  // 组件的文件名，用于 source map，未指定时为匿名文件
+  pub filename: Option<String>,
+  // 类名 => 含有该类名的节点，选择器匹配时直接查找候选节点，避免每条规则遍历所有节点
+  pub class_index: HashMap<String, Vec<SpanKey>>,
+  // 节点树，用于兄弟选择器、标签选择器的匹配
+  pub tree: Tree<JSXNode>,
+  pub tree_nodes: HashMap<SpanKey, NodeId>,
+  // 选择器 => (类名索引中是否存在候选节点, 匹配的节点)，多平台转换时各平台共用匹配结果
+  select_cache: RefCell<HashMap<String, (bool, Vec<SpanKey>)>>,
+  // 选择器 => 静态节点树上的匹配结果
+  static_match_cache: RefCell<HashMap<String, Option<StaticMatch>>>,
+}
+
+// 兄弟选择器、标签选择器在静态节点树上的匹配结果
+#[derive(Debug, Clone)]
+pub struct StaticMatch {
+  pub elements: Vec<SpanKey>,
+  // 存在编译期无法确定是否匹配的节点，如 list.map 渲染的节点
+  pub uncertain: bool,
 }
 
 impl JSXDocument {
@@ -37,6 +83,20 @@ impl JSXDocument {
       program: None,
       jsx_record: None,
       taro_components: Vec::new(),
+      syntax: SourceSyntax::Tsx,
+      filename: None,
+      class_index: HashMap::new(),
+      tree: Tree::new(JSXNode::Root),
+      tree_nodes: HashMap::new(),
+      select_cache: RefCell::new(HashMap::new()),
+      static_match_cache: RefCell::new(HashMap::new()),
+    }
+  }
+
+  pub fn with_syntax(syntax: SourceSyntax) -> Self {
+    JSXDocument {
+      syntax,
+      ..JSXDocument::new()
     }
   }
 
@@ -45,14 +105,27 @@ impl JSXDocument {
     let handler = Handler::with_tty_emitter(ColorConfig::Auto, true, false, Some(cm.clone()));
 
     // 将 JSX 代码转换为 SourceFile
-    let fm = cm.new_source_file(FileName::Anon, jsx);
+    let filename = match &self.filename {
+      Some(filename) => FileName::Custom(filename.clone()),
+      None => FileName::Anon
+    };
+    let fm = cm.new_source_file(filename, jsx);
     // 初始化 swc 的词法分析器
-    let lexer = Lexer::new(
-      Syntax::Typescript(TsConfig {
+    // TSX 中的类型注解、泛型、enum 等由后续的 strip 移除
+    let syntax = match self.syntax {
+      SourceSyntax::Tsx => Syntax::Typescript(TsConfig {
         tsx: true,
         decorators: true,
         ..Default::default()
       }),
+      SourceSyntax::Jsx => Syntax::Es(EsConfig {
+        jsx: true,
+        decorators: true,
+        ..Default::default()
+      }),
+    };
+    let lexer = Lexer::new(
+      syntax,
       EsVersion::Es2019,
       StringInput::from(&*fm),
       Some(comments),
@@ -71,39 +144,246 @@ impl JSXDocument {
 
   pub fn parse(&mut self, jsx: String, cm: Lrc<SourceMap>, comments: &SingleThreadedComments) {
     let program = self.jsx_parse(jsx, cm, comments);
+    self.parse_program(program, comments);
+  }
 
+  // 从已有的 AST 收集节点，AST 可以来自上游的 swc 插件，节点的 span 不要求与源码对应
+  pub fn parse_program(&mut self, program: Program, comments: &SingleThreadedComments) {
     let globals = Globals::default();
     GLOBALS.set(&globals, || {
       let unresolved_mark = Mark::new();
       let top_level_mark = Mark::new();
       let program = program.fold_with(&mut resolver(unresolved_mark, top_level_mark, true));
-      let program = program.fold_with(&mut strip(top_level_mark));
+      let mut program = program.fold_with(&mut strip(top_level_mark));
+      // className 引用字符串常量时，编译期替换为字面量，需在 hygiene 清除标识符的 ctxt 之前进行
+      let mut const_visitor = CollectVisitor::new();
+      program.visit_with(&mut const_visitor);
+      program.visit_mut_with(&mut ConstClassNameVisitor::new(&const_visitor.const_strings));
+      // 作用域模式下 className 添加作用域前缀，需在收集节点之前进行
+      if is_scoped() {
+        program.visit_mut_with(&mut ScopedClassNameVisitor);
+      }
       let program = program.fold_with(&mut hygiene());
-      let program = program.fold_with(&mut fixer(Some(comments)));
-      let mut jsx_record: JSXRecord = HashMap::new();
+      let mut program = program.fold_with(&mut fixer(Some(comments)));
+      // 节点以 span 作为标识，DUMMY_SP 或重复的 span 替换为合成的唯一 span
+      program.visit_mut_with(&mut SpanNormalizeVisitor::new());
+      let mut jsx_record: JSXRecord = IndexMap::new();
       // 收集使用的 Taro Component
       let mut visitor = CollectVisitor::new();
       program.visit_with(&mut visitor);
       self.taro_components = visitor.taro_components.to_vec();
-      let mut visitor = AstVisitor::new(&mut jsx_record, &visitor.taro_components);
+      let mut visitor = AstVisitor::new(&mut jsx_record, &visitor.taro_components, &visitor.component_names);
       program.visit_all_with(&mut visitor);
+      self.class_index = build_class_index(&jsx_record);
+      let mut visitor = JSXTreeVisitor::new();
+      program.visit_with(&mut visitor);
+      self.tree_nodes = visitor.tree.nodes().filter_map(|node| match node.value() {
+        JSXNode::Element(span) => Some((*span, node.id())),
+        _ => None
+      }).collect();
+      self.tree = visitor.tree;
       self.program = Some(program);
       self.jsx_record = Some(jsx_record);
+      self.select_cache.borrow_mut().clear();
+      self.static_match_cache.borrow_mut().clear();
     });
   }
 
   pub fn select<'a>(&self, selector: &'a str) -> Vec<Element> {
     match self.jsx_record {
       Some(ref jsx_record) => {
+        // 仅支持类选择器及复合类选择器，如 .a、.a.b，复合类选择器需要节点同时包含所有类名
+        if !is_class_selector(selector) {
+          return Vec::new();
+        }
+        // 已匹配过的选择器直接使用缓存的结果，查找次数仍按每次调用统计
+        if let Some((hit, spans)) = self.select_cache.borrow().get(selector) {
+          record_selector_lookup(*hit);
+          return spans.iter().filter_map(|span| jsx_record.get(span).cloned()).collect();
+        }
+        let classes = selector.split('.').filter(|class| !class.is_empty()).collect::<Vec<&str>>();
+        // 以最后一个类名查找候选节点，再校验其余类名
+        let candidates = classes.last().and_then(|class| self.class_index.get(*class));
+        record_selector_lookup(candidates.is_some());
         let mut elements = Vec::new();
-        for (_, element) in jsx_record.iter() {
-          if element.has_class(selector, CaseSensitivity::CaseSensitive) {
-            elements.push(element.clone());
+        for span in candidates.into_iter().flatten() {
+          if let Some(element) = jsx_record.get(span) {
+            if classes.iter().all(|class| element.has_class(class, CaseSensitivity::CaseSensitive)) {
+              elements.push(element.clone());
+            }
           }
         }
+        self.select_cache.borrow_mut().insert(selector.to_string(), (candidates.is_some(), elements.iter().map(|element| element.span).collect()));
         elements
       }
       None => Vec::new(),
     }
   }
+
+  // 在静态节点树上求值复合选择器中的 :not()，如 .a:not(.b)，含 .a 的节点均不含 .b 时等价于 .a
+  pub fn resolve_negation(&self, compound: &str) -> NegationMatch {
+    let Some((base, negations)) = split_negation(compound) else {
+      return NegationMatch::Unsupported
+    };
+    if negations.is_empty() {
+      return NegationMatch::Unaffected(base)
+    }
+    // 伪类如 .a:not(.b):active 按 : 之前的选择器查找节点
+    let element_selector = base.split(':').next().unwrap_or_default();
+    if !is_class_selector(element_selector) {
+      return NegationMatch::Unsupported
+    }
+    let elements = self.select(element_selector);
+    let excluded = elements
+      .iter()
+      .filter(|element| {
+        negations.iter().any(|negation| {
+          negation
+            .split('.')
+            .filter(|class| !class.is_empty())
+            .all(|class| element.has_class(class, CaseSensitivity::CaseSensitive))
+        })
+      })
+      .count();
+    match excluded {
+      0 => NegationMatch::Unaffected(base),
+      count if count == elements.len() => NegationMatch::Excluded,
+      _ => NegationMatch::Partial
+    }
+  }
+
+  // 在静态节点树上匹配含 +、~ 或标签选择器的选择器，如 .item + .item、view > text.title，返回匹配的节点
+  // 选择器中仅支持类选择器、标签选择器，含有其他选择器时返回 None
+  pub fn select_static(&self, selector: &str) -> Option<StaticMatch> {
+    if let Some(static_match) = self.static_match_cache.borrow().get(selector) {
+      return static_match.clone()
+    }
+    let static_match = self.match_static(selector);
+    self.static_match_cache.borrow_mut().insert(selector.to_string(), static_match.clone());
+    static_match
+  }
+
+  fn match_static(&self, selector: &str) -> Option<StaticMatch> {
+    // (与左侧复合选择器的组合符, 复合选择器)，如 .a > .b + .c => [(' ', .a), ('>', .b), ('+', .c)]
+    let mut parts: Vec<(char, String)> = vec![];
+    let mut combinator = ' ';
+    for token in split_top_level(selector, ' ') {
+      match token.as_str() {
+        ">" | "+" | "~" => combinator = token.chars().next().unwrap(),
+        compound if is_static_compound(compound) => {
+          parts.push((combinator, compound.to_string()));
+          combinator = ' ';
+        }
+        _ => return None
+      }
+    }
+    let (_, subject) = parts.last()?;
+    let mut result = StaticMatch { elements: vec![], uncertain: false };
+    // 以类名查找候选节点，仅有标签名时候选节点为所有节点
+    let candidates = match split_tag(subject) {
+      (_, "") => self.jsx_record.as_ref().map(|jsx_record| jsx_record.values().cloned().collect()).unwrap_or_default(),
+      (_, classes) => self.select(classes)
+    };
+    for element in candidates {
+      let matched = self.tree_nodes
+        .get(&element.span)
+        .and_then(|id| self.tree.get(*id))
+        .and_then(|node| self.match_parts(node, &parts));
+      match matched {
+        Some(true) => result.elements.push(element.span),
+        Some(false) => {},
+        None => result.uncertain = true
+      }
+    }
+    Some(result)
+  }
+
+  fn match_compound(&self, node: NodeRef<JSXNode>, compound: &str) -> bool {
+    let (JSXNode::Element(span), Some(jsx_record)) = (node.value(), &self.jsx_record) else {
+      return false
+    };
+    let (tag, classes) = split_tag(compound);
+    jsx_record.get(span).is_some_and(|element| {
+      tag.is_none_or(|tag| get_element_tag(element.name()) == normalize_tag(tag))
+        && classes.split('.').filter(|class| !class.is_empty()).all(|class| element.has_class(class, CaseSensitivity::CaseSensitive))
+    })
+  }
+
+  // 从右向左匹配，节点需匹配最后一个复合选择器，返回 None 表示编译期无法确定
+  fn match_parts(&self, node: NodeRef<JSXNode>, parts: &[(char, String)]) -> Option<bool> {
+    let Some(((combinator, compound), rest)) = parts.split_last() else {
+      return Some(true)
+    };
+    if !self.match_compound(node, compound) {
+      return Some(false)
+    }
+    if rest.is_empty() {
+      return Some(true)
+    }
+    // 表达式中的节点位于其所在 JSX 元素之内
+    let mut ancestors = node.ancestors().filter(|ancestor| matches!(ancestor.value(), JSXNode::Element(_)));
+    // 兄弟关系仅在 JSX 元素的直接子节点之间确定，属性中的 JSX 不是节点的兄弟
+    let siblings = match node.parent().map(|parent| parent.value().clone()) {
+      Some(JSXNode::Element(_) | JSXNode::RenderProp) => Some(node.prev_siblings().filter(|sibling| *sibling.value() != JSXNode::RenderProp)),
+      _ => None
+    };
+    let mut uncertain = false;
+    match combinator {
+      '>' => {
+        // 属性中的 JSX 由组件在其内部渲染，父节点在编译期无法确定
+        if node.ancestors().take_while(|ancestor| !matches!(ancestor.value(), JSXNode::Element(_))).any(|ancestor| *ancestor.value() == JSXNode::RenderProp) {
+          return None
+        }
+        return ancestors.next().map_or(Some(false), |parent| self.match_parts(parent, rest))
+      },
+      '+' => {
+        let previous = siblings?.next();
+        return match previous {
+          Some(previous) if *previous.value() == JSXNode::Expression => None,
+          Some(previous) => self.match_parts(previous, rest),
+          None => Some(false)
+        }
+      }
+      '~' => {
+        for previous in siblings? {
+          match previous.value() {
+            JSXNode::Expression => uncertain = true,
+            _ => match self.match_parts(previous, rest) {
+              Some(true) => return Some(true),
+              Some(false) => {},
+              None => uncertain = true
+            }
+          }
+        }
+      }
+      _ => {
+        for ancestor in ancestors {
+          match self.match_parts(ancestor, rest) {
+            Some(true) => return Some(true),
+            Some(false) => {},
+            None => uncertain = true
+          }
+        }
+      }
+    }
+    if uncertain { None } else { Some(false) }
+  }
+}
+
+// 标签名及类名组成的复合选择器，如 view、view.item、.item
+fn is_static_compound(compound: &str) -> bool {
+  match split_tag(compound) {
+    (Some(_), "") => true,
+    (_, classes) => is_class_selector(classes)
+  }
+}
+
+fn build_class_index(jsx_record: &JSXRecord) -> HashMap<String, Vec<SpanKey>> {
+  let mut class_index: HashMap<String, Vec<SpanKey>> = HashMap::new();
+  for (span, element) in jsx_record.iter() {
+    for class in element.classes() {
+      class_index.entry(class.to_string()).or_default().push(*span);
+    }
+  }
+  class_index
 }
@@ -0,0 +1,101 @@
+use std::{cell::RefCell, collections::HashMap, convert::Infallible};
+
+use lightningcss::{
+  declaration::DeclarationBlock,
+  properties::Property,
+  rules::CssRule,
+  stylesheet::{ParserOptions, PrinterOptions, StyleSheet},
+  visit_types,
+  visitor::{Visit, VisitTypes, Visitor},
+};
+
+use crate::{diagnostics::push_diagnostic, options::with_transform_options, style_propetries::unit::Platform, utils::normalize_style_key};
+
+// 内置的回退规则，(平台, "属性: 值", 替换的声明)，目标平台不支持的属性值替换为效果相近的声明
+const BUILTIN_FALLBACK_RULES: [(Platform, &str, &str); 3] = [
+  (Platform::ReactNative, "display: grid", "display: flex; flex-direction: column"),
+  (Platform::ReactNative, "display: inline-grid", "display: flex; flex-direction: column"),
+  (Platform::ReactNative, "display: inline-flex", "display: flex"),
+];
+
+// 按回退规则替换声明，配置的规则优先于内置规则，替换记录到诊断信息中
+pub fn apply_fallback_rules(stylesheet: &mut StyleSheet, platform: &Platform) {
+  let mut rules = with_transform_options(|options| {
+    options.fallback_rules.iter().filter_map(|(key, replacement)| {
+      let Some((property, value)) = key.split_once(':') else {
+        push_diagnostic("fallback", format!("invalid fallback rule {}, the key should be \"property: value\"", key));
+        return None
+      };
+      Some((property.trim().to_string(), value.trim().to_string(), replacement.clone()))
+    }).collect::<Vec<_>>()
+  });
+  BUILTIN_FALLBACK_RULES.iter().filter(|(rule_platform, _, _)| rule_platform == platform).for_each(|(_, key, replacement)| {
+    let (property, value) = key.split_once(": ").unwrap();
+    if !rules.iter().any(|(rule_property, rule_value, _)| rule_property == property && rule_value == value) {
+      rules.push((property.to_string(), value.to_string(), replacement.to_string()));
+    }
+  });
+  if rules.is_empty() {
+    return
+  }
+  let rules = rules.into_iter().filter_map(|(property, value, replacement)| {
+    let replacement = get_static_replacement(replacement);
+    match DeclarationBlock::parse_string(replacement, ParserOptions::default()) {
+      Ok(_) => Some((property, value, replacement)),
+      Err(_) => {
+        push_diagnostic("fallback", format!("invalid fallback declarations \"{}\" for {}: {}", replacement, property, value));
+        None
+      }
+    }
+  }).collect::<Vec<_>>();
+  let mut visitor = FallbackVisitor { platform, rules };
+  stylesheet.visit(&mut visitor).unwrap();
+}
+
+thread_local! {
+  static REPLACEMENTS: RefCell<HashMap<String, &'static str>> = RefCell::new(HashMap::new());
+}
+
+// 替换的声明解析后需与样式表的生命周期一致，因此保存为 'static 字符串，相同的字符串只保存一次
+fn get_static_replacement(replacement: String) -> &'static str {
+  REPLACEMENTS.with(|cell| {
+    *cell.borrow_mut().entry(replacement.clone()).or_insert_with(|| Box::leak(replacement.into_boxed_str()))
+  })
+}
+
+struct FallbackVisitor<'a> {
+  platform: &'a Platform,
+  // (属性, 值, 替换的声明)
+  rules: Vec<(String, String, &'static str)>,
+}
+
+impl<'a, 'i> Visitor<'i> for FallbackVisitor<'a> {
+  type Error = Infallible;
+  const TYPES: VisitTypes = visit_types!(RULES);
+
+  fn visit_rule(&mut self, rule: &mut CssRule<'i>) -> Result<(), Self::Error> {
+    if let CssRule::Style(style) = rule {
+      style.declarations.declarations = self.replace_declarations(&style.declarations.declarations);
+      style.declarations.important_declarations = self.replace_declarations(&style.declarations.important_declarations);
+    }
+    // 嵌套规则及 @media、@supports 等规则中的样式
+    rule.visit_children(self)
+  }
+}
+
+impl<'a> FallbackVisitor<'a> {
+  fn replace_declarations<'i>(&self, declarations: &[Property<'i>]) -> Vec<Property<'i>> {
+    declarations.iter().flat_map(|property| {
+      let name = property.property_id().name().to_string();
+      let value = property.value_to_css_string(PrinterOptions::default()).unwrap_or_default();
+      let replacement = self.rules.iter().find(|(rule_property, rule_value, _)| *rule_property == name && *rule_value == value).map(|(_, _, replacement)| *replacement);
+      match replacement.and_then(|replacement| DeclarationBlock::parse_string(replacement, ParserOptions::default()).ok().map(|block| (replacement, block))) {
+        Some((replacement, block)) => {
+          push_diagnostic(&normalize_style_key(&name), format!("{}: {} is not supported on {:?}, replaced with {}", name, value, self.platform, replacement));
+          block.declarations
+        },
+        None => vec![property.clone()]
+      }
+    }).collect()
+  }
+}
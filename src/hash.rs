@@ -0,0 +1,177 @@
+use crate::options::HashAlgorithm;
+
+// 计算字符串的 hash，输出为小写 hex，截取前 length 位
+pub fn hash_hex(content: &str, algorithm: &HashAlgorithm, length: usize) -> String {
+  let hex = match algorithm {
+    HashAlgorithm::Fnv1a => format!("{:08x}", fnv1a(content.as_bytes())),
+    HashAlgorithm::Xxhash64 => format!("{:016x}", xxhash64(content.as_bytes(), 0)),
+    HashAlgorithm::Sha1 => sha1(content.as_bytes()).iter().map(|byte| format!("{:02x}", byte)).collect(),
+  };
+  hex.chars().take(length).collect()
+}
+
+// FNV-1a，32 位
+fn fnv1a(data: &[u8]) -> u32 {
+  let mut hash: u32 = 0x811c9dc5;
+  for byte in data {
+    hash ^= *byte as u32;
+    hash = hash.wrapping_mul(0x01000193);
+  }
+  hash
+}
+
+const XXH_PRIME64_1: u64 = 0x9E3779B185EBCA87;
+const XXH_PRIME64_2: u64 = 0xC2B2AE3D27D4EB4F;
+const XXH_PRIME64_3: u64 = 0x165667B19E3779F9;
+const XXH_PRIME64_4: u64 = 0x85EBCA77C2B2AE63;
+const XXH_PRIME64_5: u64 = 0x27D4EB2F165667C5;
+
+fn xxh64_round(acc: u64, input: u64) -> u64 {
+  acc.wrapping_add(input.wrapping_mul(XXH_PRIME64_2)).rotate_left(31).wrapping_mul(XXH_PRIME64_1)
+}
+
+fn xxh64_merge_round(acc: u64, value: u64) -> u64 {
+  (acc ^ xxh64_round(0, value)).wrapping_mul(XXH_PRIME64_1).wrapping_add(XXH_PRIME64_4)
+}
+
+fn read_u64(data: &[u8]) -> u64 {
+  u64::from_le_bytes(data[..8].try_into().unwrap())
+}
+
+fn read_u32(data: &[u8]) -> u32 {
+  u32::from_le_bytes(data[..4].try_into().unwrap())
+}
+
+// XXH64，与 webpack 的 hashFunction: "xxhash64" 一致
+fn xxhash64(data: &[u8], seed: u64) -> u64 {
+  let mut rest = data;
+  let mut hash = match data.len() >= 32 {
+    true => {
+      let mut accs = [
+        seed.wrapping_add(XXH_PRIME64_1).wrapping_add(XXH_PRIME64_2),
+        seed.wrapping_add(XXH_PRIME64_2),
+        seed,
+        seed.wrapping_sub(XXH_PRIME64_1),
+      ];
+      while rest.len() >= 32 {
+        for (index, acc) in accs.iter_mut().enumerate() {
+          *acc = xxh64_round(*acc, read_u64(&rest[index * 8..]));
+        }
+        rest = &rest[32..];
+      }
+      let hash = accs[0].rotate_left(1)
+        .wrapping_add(accs[1].rotate_left(7))
+        .wrapping_add(accs[2].rotate_left(12))
+        .wrapping_add(accs[3].rotate_left(18));
+      accs.iter().fold(hash, |hash, acc| xxh64_merge_round(hash, *acc))
+    },
+    false => seed.wrapping_add(XXH_PRIME64_5),
+  };
+  hash = hash.wrapping_add(data.len() as u64);
+  while rest.len() >= 8 {
+    hash ^= xxh64_round(0, read_u64(rest));
+    hash = hash.rotate_left(27).wrapping_mul(XXH_PRIME64_1).wrapping_add(XXH_PRIME64_4);
+    rest = &rest[8..];
+  }
+  if rest.len() >= 4 {
+    hash ^= (read_u32(rest) as u64).wrapping_mul(XXH_PRIME64_1);
+    hash = hash.rotate_left(23).wrapping_mul(XXH_PRIME64_2).wrapping_add(XXH_PRIME64_3);
+    rest = &rest[4..];
+  }
+  for byte in rest {
+    hash ^= (*byte as u64).wrapping_mul(XXH_PRIME64_5);
+    hash = hash.rotate_left(11).wrapping_mul(XXH_PRIME64_1);
+  }
+  hash ^= hash >> 33;
+  hash = hash.wrapping_mul(XXH_PRIME64_2);
+  hash ^= hash >> 29;
+  hash = hash.wrapping_mul(XXH_PRIME64_3);
+  hash ^ (hash >> 32)
+}
+
+// SHA-1，与 webpack 的 hashFunction: "sha1" 一致
+fn sha1(data: &[u8]) -> [u8; 20] {
+  let mut state: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+  // 补位：0x80、若干 0，最后 8 字节为大端序的比特长度
+  let mut message = data.to_vec();
+  message.push(0x80);
+  while message.len() % 64 != 56 {
+    message.push(0);
+  }
+  message.extend_from_slice(&((data.len() as u64).wrapping_mul(8)).to_be_bytes());
+
+  for block in message.chunks(64) {
+    let mut words = [0u32; 80];
+    for (index, word) in block.chunks(4).enumerate() {
+      words[index] = u32::from_be_bytes(word.try_into().unwrap());
+    }
+    for index in 16..80 {
+      words[index] = (words[index - 3] ^ words[index - 8] ^ words[index - 14] ^ words[index - 16]).rotate_left(1);
+    }
+    let [mut a, mut b, mut c, mut d, mut e] = state;
+    for (index, word) in words.iter().enumerate() {
+      let (f, k) = match index {
+        0..=19 => ((b & c) | (!b & d), 0x5A827999),
+        20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+        40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+        _ => (b ^ c ^ d, 0xCA62C1D6),
+      };
+      let temp = a.rotate_left(5).wrapping_add(f).wrapping_add(e).wrapping_add(k).wrapping_add(*word);
+      e = d;
+      d = c;
+      c = b.rotate_left(30);
+      b = a;
+      a = temp;
+    }
+    for (value, add) in state.iter_mut().zip([a, b, c, d, e]) {
+      *value = value.wrapping_add(add);
+    }
+  }
+
+  let mut digest = [0u8; 20];
+  for (index, value) in state.iter().enumerate() {
+    digest[index * 4..index * 4 + 4].copy_from_slice(&value.to_be_bytes());
+  }
+  digest
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{fnv1a, hash_hex, sha1, xxhash64};
+  use crate::options::HashAlgorithm;
+
+  fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+  }
+
+  #[test]
+  fn sha1_reference_vectors() {
+    assert_eq!(hex(&sha1(b"")), "da39a3ee5e6b4b0d3255bfef95601890afd80709");
+    assert_eq!(hex(&sha1(b"abc")), "a9993e364706816aba3e25717850c26c9cd0d89d");
+    assert_eq!(hex(&sha1(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq")), "84983e441c3bd26ebaae4aa1f95129e5e54670f1");
+    assert_eq!(hex(&sha1("a".repeat(1000).as_bytes())), "291e9a6c66994949b57ba5e650361e98fc36b1ba");
+  }
+
+  #[test]
+  fn fnv1a_reference_vectors() {
+    assert_eq!(fnv1a(b""), 0x811c9dc5);
+    assert_eq!(fnv1a(b"a"), 0xe40c292c);
+    assert_eq!(fnv1a(b"foobar"), 0xbf9cf968);
+  }
+
+  #[test]
+  fn xxhash64_reference_vectors() {
+    assert_eq!(xxhash64(b"", 0), 0xef46db3751d8e999);
+    assert_eq!(xxhash64(b"a", 0), 0xd24ec4f1a98c6e5b);
+    assert_eq!(xxhash64(b"abc", 0), 0x44bc2cf5ad770999);
+    assert_eq!(xxhash64(b"Nobody inspects the spammish repetition", 0), 0xfbcea83c8a378bf1);
+    assert_eq!(xxhash64(b"The quick brown fox jumps over the lazy dog", 0), 0x0b242d361fda71bc);
+  }
+
+  #[test]
+  fn truncate_hex() {
+    assert_eq!(hash_hex("abc", &HashAlgorithm::Sha1, 8), "a9993e36");
+    assert_eq!(hash_hex("abc", &HashAlgorithm::Xxhash64, 20), "44bc2cf5ad770999");
+    assert_eq!(hash_hex("", &HashAlgorithm::Fnv1a, 4), "811c");
+  }
+}
@@ -0,0 +1,43 @@
+use std::cell::RefCell;
+
+use lightningcss::{properties::Property, stylesheet::PrinterOptions, traits::ToCss};
+
+use crate::style_propetries::{expr::Expr, style_value_type::StyleValueType, unit::PropertyTuple};
+
+// 属性转换钩子，参数为 css 属性名（如 --brand-elevation、margin-top）及声明，返回 Some 时替代内置的转换
+pub type PropertyHook = Box<dyn Fn(&str, &Property) -> Option<PropertyTuple>>;
+
+thread_local! {
+  static PROPERTY_HOOKS: RefCell<Vec<PropertyHook>> = RefCell::new(Vec::new());
+}
+
+// 注册属性转换钩子，在内置转换之前按注册顺序执行，第一个返回 Some 的钩子生效，如：
+// register_property_hook(|property_id, property| match property_id {
+//   "--brand-elevation" => Some(PropertyTuple::One("shadowRadius".to_string(), ...)),
+//   _ => None
+// });
+pub fn register_property_hook(hook: impl Fn(&str, &Property) -> Option<PropertyTuple> + 'static) {
+  PROPERTY_HOOKS.with(|cell| cell.borrow_mut().push(Box::new(hook)));
+}
+
+// 移除所有已注册的属性转换钩子
+pub fn clear_property_hooks() {
+  PROPERTY_HOOKS.with(|cell| cell.borrow_mut().clear());
+}
+
+// 执行属性转换钩子，转换结果按表达式输出
+pub fn run_property_hooks(property: &Property) -> Option<Vec<StyleValueType>> {
+  PROPERTY_HOOKS.with(|cell| {
+    let hooks = cell.borrow();
+    if hooks.is_empty() {
+      return None
+    }
+    let property_id = property.property_id().to_css_string(PrinterOptions::default()).ok()?;
+    let tuple = hooks.iter().find_map(|hook| hook(&property_id, property))?;
+    let style_values = match tuple {
+      PropertyTuple::One(id, expr) => vec![StyleValueType::Expr(Expr::new(id, expr))],
+      PropertyTuple::Array(props) => props.into_iter().map(|(id, expr)| StyleValueType::Expr(Expr::new(id, expr))).collect()
+    };
+    Some(style_values)
+  })
+}
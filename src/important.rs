@@ -0,0 +1,81 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use indexmap::IndexMap;
+use lightningcss::properties::Property;
+use swc_core::atoms::Atom;
+
+use crate::{
+  document::JSXDocument,
+  parse_style_properties::parse_style_properties,
+  scope::generate_scope_id,
+  style_parser::{GeneratedClassRecord, KeyFrameItem, StyleMap},
+  visitor::SpanKey,
+};
+
+// 节点 => 类样式中生效的 !important 声明 (属性名, 类名)，写入代码时从静态的内联样式中移除这些属性
+pub type ImportantRecord = IndexMap<SpanKey, Vec<(Atom, String)>>;
+
+// !important 的层叠：类样式在运行时按 className 依次合并、内联样式始终覆盖类样式，!important 无法生效，
+// 对静态 className 的节点在编译期处理：
+// 排在后面的类中的普通声明会覆盖 !important 声明时，将 !important 声明生成类名添加到节点的 className 末尾，如
+// .a { color: red !important } .b { color: blue } <View className="a b"> => className="a b __important_s1a2b3c4d"
+// 节点的 !important 属性记录到 ImportantRecord 中，由 ImportantInlineStyleVisitor 从内联样式中移除
+// important_properties: 选择器 => !important 声明的属性，properties: 选择器 => 合并后的声明
+pub fn resolve_important_styles<'i>(
+  document: &JSXDocument,
+  important_properties: &HashMap<String, Vec<Atom>>,
+  properties: &[(String, Vec<(Atom, Property<'i>)>)],
+  keyframes: Rc<RefCell<HashMap<String, Vec<KeyFrameItem>>>>,
+  all_style: &mut StyleMap,
+  generated_classes: &mut GeneratedClassRecord,
+) -> ImportantRecord {
+  let mut important_record = ImportantRecord::new();
+  if important_properties.values().all(|names| names.is_empty()) {
+    return important_record
+  }
+  let Some(jsx_record) = &document.jsx_record else {
+    return important_record
+  };
+  let get_properties = |class: &str| properties.iter().find(|(selector, _)| *selector == format!(".{}", class)).map(|(_, properties)| properties);
+  for (span, element) in jsx_record.iter() {
+    // 动态 className 记录为空字符串，无法确定样式
+    let Some(class_name) = element.attrs().find(|(name, _)| *name == "className").map(|(_, value)| value).filter(|value| !value.is_empty()) else {
+      continue
+    };
+    let classes = class_name.split_whitespace().collect::<Vec<_>>();
+    // 各属性生效的 !important 声明所在的类，同一属性取排在最后的类
+    let mut winners: IndexMap<Atom, usize> = IndexMap::new();
+    classes.iter().enumerate().for_each(|(index, class)| {
+      important_properties.get(&format!(".{}", class)).into_iter().flatten().for_each(|name| {
+        winners.insert(name.clone(), index);
+      });
+    });
+    if winners.is_empty() {
+      continue
+    }
+    let mut important_styles = vec![];
+    for (name, index) in winners.iter() {
+      let is_overridden = classes.iter().skip(index + 1).any(|class| {
+        get_properties(class).is_some_and(|properties| properties.iter().any(|(id, _)| id == name))
+      });
+      if !is_overridden {
+        continue
+      }
+      if let Some(property) = get_properties(classes[*index]).and_then(|properties| properties.iter().find(|(id, _)| id == name)) {
+        important_styles.push(property.clone());
+      }
+    }
+    important_record.insert(*span, winners.iter().map(|(name, index)| (name.clone(), classes[*index].to_string())).collect());
+    if important_styles.is_empty() {
+      continue
+    }
+    // !important 声明相同的节点共用同一个类名
+    let class = format!("__important_{}", generate_scope_id(&format!("{:?}", important_styles)));
+    all_style.entry(format!(".{}", class)).or_insert_with(|| parse_style_properties(&important_styles, Some(keyframes.clone())));
+    let classes = generated_classes.entry(*span).or_default();
+    if !classes.contains(&class) {
+      classes.push(class);
+    }
+  }
+  important_record
+}
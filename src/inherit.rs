@@ -0,0 +1,181 @@
+use ego_tree::NodeRef;
+
+use crate::{
+  diagnostics::{push_diagnostic, push_lint_diagnostic},
+  document::JSXDocument,
+  lint::get_lint_severity,
+  options::with_transform_options,
+  scope::generate_scope_id,
+  style_parser::{GeneratedClassRecord, StyleMap},
+  style_propetries::style_value_type::StyleValueType,
+  utils::to_camel_case,
+  visitor::JSXNode,
+};
+
+// 支持继承的文本属性，CSS 中这些属性由子元素继承，Harmony、ReactNative 中需要设置在 Text 组件上才能生效
+pub const INHERITABLE_PROPERTIES: [&str; 9] = [
+  "color",
+  "font-size",
+  "font-weight",
+  "font-style",
+  "font-family",
+  "line-height",
+  "letter-spacing",
+  "word-spacing",
+  "text-align",
+];
+
+// 承载文本的组件
+const TEXT_COMPONENTS: [&str; 1] = ["Text"];
+
+// 不承载文本的容器组件，设置在这些组件上的文本属性不会生效
+const NON_TEXT_COMPONENTS: [&str; 5] = ["View", "ScrollView", "Image", "Swiper", "SwiperItem"];
+
+fn get_style_value_id(value: &StyleValueType) -> Option<&str> {
+  match value {
+    StyleValueType::ColorProperty(value) => Some(&value.id),
+    StyleValueType::FontSize(value) => Some(&value.id),
+    StyleValueType::FontWeight(value) => Some(&value.id),
+    StyleValueType::FontStyle(value) => Some(&value.id),
+    StyleValueType::FontFamily(value) => Some(&value.id),
+    StyleValueType::LineHeight(value) => Some(&value.id),
+    StyleValueType::LetterSpacing(value) => Some(&value.id),
+    StyleValueType::TextAlign(value) => Some(&value.id),
+    // 使用 css 变量等运行时计算的值
+    StyleValueType::Expr(value) => Some(value.id()),
+    _ => None
+  }
+}
+
+// 节点的静态 className 对应的样式，按 className 中的顺序排列，动态 className 无法确定样式，返回 None
+fn get_node_styles(document: &JSXDocument, node: NodeRef<JSXNode>, all_style: &StyleMap) -> Option<Vec<StyleValueType>> {
+  let JSXNode::Element(span) = node.value() else {
+    return None
+  };
+  let element = document.jsx_record.as_ref()?.get(span)?;
+  let class_name = element.attrs().find(|(name, _)| *name == "className").map(|(_, value)| value);
+  match class_name {
+    // 动态 className 记录为空字符串
+    Some("") => None,
+    Some(class_name) => Some(
+      class_name
+        .split_whitespace()
+        .filter_map(|class| all_style.get(&format!(".{}", class)))
+        .flat_map(|values| values.iter().cloned())
+        .collect()
+    ),
+    None => Some(vec![])
+  }
+}
+
+// 文本样式继承：Text 组件未设置的可继承属性，取最近的设置了该属性的祖先节点的值，
+// 生成类名添加到 Text 组件上，如 <View className="title"><Text>...</Text></View> => <Text className="__inherit_s1a2b3c4d">
+pub fn inherit_text_styles(document: &JSXDocument, all_style: &mut StyleMap, generated_classes: &mut GeneratedClassRecord) {
+  let properties = with_transform_options(|options| options.inherit_properties.clone())
+    .into_iter()
+    .filter_map(|property| {
+      if INHERITABLE_PROPERTIES.contains(&property.as_str()) {
+        Some(to_camel_case(&property, false))
+      } else {
+        push_diagnostic("inherit", format!("{} is not an inheritable text property and will be ignored", property));
+        None
+      }
+    })
+    .collect::<Vec<String>>();
+  if properties.is_empty() {
+    return
+  }
+  let Some(jsx_record) = &document.jsx_record else {
+    return
+  };
+  for node in document.tree.nodes() {
+    let JSXNode::Element(span) = node.value() else {
+      continue
+    };
+    if !jsx_record.get(span).is_some_and(|element| TEXT_COMPONENTS.contains(&element.name())) {
+      continue
+    }
+    let Some(own_styles) = get_node_styles(document, node, all_style) else {
+      continue
+    };
+    let mut inherited_styles = vec![];
+    for property in properties.iter() {
+      if own_styles.iter().any(|value| get_style_value_id(value) == Some(property.as_str())) {
+        continue
+      }
+      // 由近及远查找设置了该属性的祖先节点，同一节点中后出现的类名覆盖先出现的
+      let inherited = node.ancestors().find_map(|ancestor| {
+        get_node_styles(document, ancestor, all_style)?
+          .into_iter()
+          .rev()
+          .find(|value| get_style_value_id(value) == Some(property.as_str()))
+      });
+      if let Some(value) = inherited {
+        inherited_styles.push(value);
+      }
+    }
+    if inherited_styles.is_empty() {
+      continue
+    }
+    // 继承的样式相同的节点共用同一个类名
+    let class = format!("__inherit_{}", generate_scope_id(&format!("{:?}", inherited_styles)));
+    all_style.entry(format!(".{}", class)).or_insert(inherited_styles);
+    let classes = generated_classes.entry(*span).or_default();
+    if !classes.contains(&class) {
+      classes.push(class);
+    }
+  }
+}
+
+// 样式检查 no-text-style-on-view：View 等非文本组件的静态 className 对应的类样式中设置了文本属性，如 <View className="title"> 与 .title { font-size: 16px }，
+// 属性已配置在 inheritProperties 中且节点下有 Text 组件时，由 inherit_text_styles 继承到 Text 组件上，不再提示
+pub fn lint_text_styles(document: &JSXDocument, all_style: &StyleMap) {
+  let Some(severity) = get_lint_severity("no-text-style-on-view") else {
+    return
+  };
+  let Some(jsx_record) = &document.jsx_record else {
+    return
+  };
+  let text_properties = INHERITABLE_PROPERTIES.map(|property| to_camel_case(property, false));
+  let inherit_properties = with_transform_options(|options| {
+    options.inherit_properties.iter().map(|property| to_camel_case(property, false)).collect::<Vec<String>>()
+  });
+  for node in document.tree.nodes() {
+    let JSXNode::Element(span) = node.value() else {
+      continue
+    };
+    let Some(element) = jsx_record.get(span).filter(|element| NON_TEXT_COMPONENTS.contains(&element.name())) else {
+      continue
+    };
+    // 动态 className 记录为空字符串，无法确定样式
+    let Some(class_name) = element.attrs().find(|(name, _)| *name == "className").map(|(_, value)| value) else {
+      continue
+    };
+    let has_text_child = node.descendants().any(|descendant| match descendant.value() {
+      JSXNode::Element(span) => jsx_record.get(span).is_some_and(|element| TEXT_COMPONENTS.contains(&element.name())),
+      _ => false
+    });
+    for class in class_name.split_whitespace() {
+      let Some(values) = all_style.get(&format!(".{}", class)) else {
+        continue
+      };
+      values
+        .iter()
+        .filter_map(get_style_value_id)
+        .filter(|id| text_properties.iter().any(|property| property == id))
+        .filter(|id| !(has_text_child && inherit_properties.iter().any(|property| property == id)))
+        .for_each(|id| {
+          let hint = match has_text_child {
+            true => "set it on the Text children or add it to inheritProperties",
+            false => "set it on a Text component",
+          };
+          push_lint_diagnostic(
+            "no-text-style-on-view",
+            severity.clone(),
+            id,
+            format!("{} of .{} has no effect on {}, text properties only apply to Text components, {} (no-text-style-on-view)", id, class, element.name(), hint)
+          );
+        });
+    }
+  }
+}
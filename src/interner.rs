@@ -0,0 +1,125 @@
+use std::{cell::RefCell, collections::HashMap, fmt};
+
+use lightningcss::{properties::Property, stylesheet::PrinterOptions, traits::ToCss};
+use swc_core::atoms::Atom;
+
+use crate::utils::to_camel_case;
+
+// 字符串驻留的统计信息，用于调试大型样式表的内存占用
+#[derive(Debug, Clone, Default)]
+pub struct InternerStats {
+  // 查找次数
+  pub lookups: usize,
+  // 命中已驻留字符串的次数
+  pub hits: usize,
+  // 本次转换新驻留的字符串数量
+  pub unique: usize,
+  // 命中时复用已有的 Atom 而不再分配 String 的字节数，长度小于 7 的 Atom 内联存储，不计入
+  pub saved_bytes: usize,
+}
+
+impl fmt::Display for InternerStats {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "interner: {} lookups, {} hits, {} unique strings, {} bytes saved",
+      self.lookups, self.hits, self.unique, self.saved_bytes
+    )
+  }
+}
+
+#[derive(Default)]
+struct Interner {
+  atoms: HashMap<String, Atom>,
+  // 无厂商前缀的属性名（中划线命名）=> 驼峰命名，避免每条声明都序列化 property_id 并转换驼峰
+  property_names: HashMap<String, Atom>,
+  stats: InternerStats,
+}
+
+impl Interner {
+  fn hit(&mut self, atom: &Atom) {
+    self.stats.lookups += 1;
+    self.stats.hits += 1;
+    if atom.len() >= MAX_INLINE_LEN {
+      self.stats.saved_bytes += atom.len();
+    }
+  }
+
+  fn miss(&mut self) {
+    self.stats.lookups += 1;
+    self.stats.unique += 1;
+  }
+}
+
+// Atom 内联存储的长度上限，不超过该长度的字符串不分配堆内存
+const MAX_INLINE_LEN: usize = 7;
+
+thread_local! {
+  static INTERNER: RefCell<Interner> = RefCell::new(Interner::default());
+}
+
+// 驻留字符串，相同内容的属性名共享同一个 Atom，clone 时只增加引用计数
+pub fn intern(value: &str) -> Atom {
+  INTERNER.with(|cell| {
+    let mut interner = cell.borrow_mut();
+    if let Some(atom) = interner.atoms.get(value).cloned() {
+      interner.hit(&atom);
+      return atom
+    }
+    let atom = Atom::from(value);
+    interner.atoms.insert(value.to_string(), atom.clone());
+    interner.miss();
+    atom
+  })
+}
+
+// 属性的驼峰命名，如 margin-top => marginTop，带厂商前缀的属性如 -webkit-line-clamp => WebkitLineClamp
+pub fn intern_property_name(property: &Property) -> Atom {
+  let property_id = property.property_id();
+  if !property_id.prefix().is_empty() {
+    return intern(&to_camel_case(&property_id.to_css_string(PrinterOptions::default()).unwrap(), false))
+  }
+  INTERNER.with(|cell| {
+    let mut interner = cell.borrow_mut();
+    if let Some(atom) = interner.property_names.get(property_id.name()).cloned() {
+      interner.hit(&atom);
+      return atom
+    }
+    let atom = Atom::from(to_camel_case(property_id.name(), false));
+    interner.property_names.insert(property_id.name().to_string(), atom.clone());
+    interner.miss();
+    atom
+  })
+}
+
+// 取出统计信息并清空驻留的字符串，避免长期运行的进程（如 bundler 的 watch 模式）中驻留表持续增长
+pub fn take_interner_stats() -> InternerStats {
+  INTERNER.with(|cell| std::mem::take(&mut *cell.borrow_mut()).stats)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{intern, take_interner_stats};
+
+  #[test]
+  fn count_saved_bytes_for_heap_atoms() {
+    take_interner_stats();
+    intern("color");
+    intern("color");
+    intern("backgroundColor");
+    intern("backgroundColor");
+    let stats = take_interner_stats();
+    assert_eq!((stats.lookups, stats.hits, stats.unique), (4, 2, 2));
+    assert_eq!(stats.saved_bytes, "backgroundColor".len());
+  }
+
+  #[test]
+  fn clear_atoms_when_taking_stats() {
+    take_interner_stats();
+    intern("marginTop");
+    take_interner_stats();
+    intern("marginTop");
+    let stats = take_interner_stats();
+    assert_eq!((stats.hits, stats.unique), (0, 1));
+  }
+}
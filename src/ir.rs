@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+
+use indexmap::IndexMap;
+use lightningcss::{stylesheet::PrinterOptions, traits::ToCss};
+use serde::{Deserialize, Serialize};
+use swc_core::common::SourceMap;
+
+use crate::{
+  document::JSXDocument,
+  report::{get_matched_elements, is_pseudo_selector},
+  style_parser::{KeyFrameItem, StyleData, StyleMap},
+  style_propetries::unit::Platform,
+  stylesheet::{generate_declarations, StyleSheetValue},
+};
+
+// 样式中间表示 JSON 的结构版本，结构发生不兼容变更时递增
+const STYLE_IR_VERSION: u32 = 1;
+
+// (属性, 值)，按转换后的声明顺序排列，值的格式与样式表 JSON 一致
+pub type IrProperties = Vec<(String, StyleSheetValue)>;
+
+// 计算后的样式中间表示，供外部工具在代码生成之外读取或二次处理样式
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StyleIr {
+  pub version: u32,
+  pub platform: String,
+  pub file: Option<String>,
+  // 选择器的样式，按层叠顺序排列
+  pub rules: Vec<IrRule>,
+  // 节点的样式，按节点在组件中的顺序排列
+  pub nodes: Vec<IrNode>,
+  // @keyframes 动画，按名称排序
+  pub keyframes: Vec<IrKeyframes>,
+  // 暗色模式、@container 等按条件生效的样式
+  pub variants: Vec<IrVariant>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IrRule {
+  pub selector: String,
+  pub properties: IrProperties,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IrNode {
+  pub tag: String,
+  // 节点在组件代码中的位置，行号从 1 开始，列号从 0 开始；上游插件生成的节点没有源码位置
+  pub line: Option<usize>,
+  pub column: Option<usize>,
+  pub span: [u32; 2],
+  // 节点的类名，含编译期生成的类名
+  pub class_names: Vec<String>,
+  // 匹配的规则，按层叠顺序排列，含伪类规则
+  pub rules: Vec<String>,
+  // 非伪类规则按层叠顺序合并后的样式，后声明的值覆盖先声明的值
+  pub properties: IrProperties,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IrKeyframes {
+  pub name: String,
+  pub frames: Vec<IrKeyframe>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IrKeyframe {
+  // 帧的位置，0 ~ 1
+  pub percentage: f32,
+  // 帧内声明的 animation-timing-function，如 "ease-in"
+  pub timing_function: Option<String>,
+  pub properties: IrProperties,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IrVariantKind {
+  // prefers-color-scheme: dark
+  Dark,
+  // 无法在编译期求值的 @container 规则
+  Container,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IrVariant {
+  pub kind: IrVariantKind,
+  // @container 的容器名
+  pub name: Option<String>,
+  pub condition: String,
+  // 条件成立时的样式，暗色模式下为亮色样式合并暗色样式后的完整样式
+  pub rules: Vec<IrRule>,
+}
+
+impl StyleIr {
+  pub fn new(
+    document: &JSXDocument,
+    style_data: &StyleData,
+    keyframes: &HashMap<String, Vec<KeyFrameItem>>,
+    cm: &SourceMap,
+    file: Option<String>,
+    platform: Platform,
+  ) -> Self {
+    let all_style = style_data.all_style.borrow();
+    let generated_classes = style_data.generated_classes.borrow();
+    let rules = generate_ir_rules(&all_style, platform.clone());
+    let matches = rules.iter().map(|rule| (rule, get_matched_elements(document, &generated_classes, &rule.selector))).collect::<Vec<_>>();
+
+    let nodes = document.jsx_record.iter().flat_map(|jsx_record| jsx_record.iter()).map(|(span, element)| {
+      let mut class_names = element.classes().map(|class| class.to_string()).collect::<Vec<_>>();
+      class_names.extend(generated_classes.get(span).cloned().unwrap_or_default());
+      let matched_rules = matches
+        .iter()
+        .filter(|(_, elements)| elements.contains(span))
+        .map(|(rule, _)| *rule)
+        .collect::<Vec<_>>();
+      let mut properties = IndexMap::new();
+      matched_rules
+        .iter()
+        .filter(|rule| !is_pseudo_selector(&rule.selector))
+        .for_each(|rule| properties.extend(rule.properties.iter().cloned()));
+      let position = match span.0.is_dummy() {
+        true => None,
+        false => Some(cm.lookup_char_pos(span.0.lo)),
+      };
+      IrNode {
+        tag: element.name().to_string(),
+        line: position.as_ref().map(|position| position.line),
+        column: position.as_ref().map(|position| position.col.0),
+        span: [span.0.lo.0, span.0.hi.0],
+        class_names,
+        rules: matched_rules.iter().map(|rule| rule.selector.clone()).collect(),
+        properties: properties.into_iter().collect(),
+      }
+    }).collect();
+
+    let mut keyframes = keyframes.iter().map(|(name, items)| IrKeyframes {
+      name: name.clone(),
+      frames: items.iter().map(|item| IrKeyframe {
+        percentage: item.percentage,
+        timing_function: item.timing_function.as_ref().map(|timing_function| timing_function.to_css_string(PrinterOptions::default()).unwrap_or_default()),
+        properties: generate_declarations(item.declarations.clone(), platform.clone()),
+      }).collect(),
+    }).collect::<Vec<_>>();
+    keyframes.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut variants = vec![];
+    let dark_style = style_data.dark_style.borrow();
+    if !dark_style.is_empty() {
+      variants.push(IrVariant {
+        kind: IrVariantKind::Dark,
+        name: None,
+        condition: "(prefers-color-scheme: dark)".to_string(),
+        rules: generate_ir_rules(&dark_style, platform.clone()),
+      });
+    }
+    variants.extend(style_data.container_style.borrow().iter().map(|(query, style)| IrVariant {
+      kind: IrVariantKind::Container,
+      name: query.name.clone(),
+      condition: query.condition.clone(),
+      rules: generate_ir_rules(style, platform.clone()),
+    }));
+
+    StyleIr {
+      version: STYLE_IR_VERSION,
+      platform: match platform {
+        Platform::Harmony => "Harmony".to_string(),
+        Platform::ReactNative => "ReactNative".to_string(),
+      },
+      file,
+      rules,
+      nodes,
+      keyframes,
+      variants,
+    }
+  }
+
+  pub fn to_json(&self) -> String {
+    serde_json::to_string_pretty(self).unwrap()
+  }
+
+  // 读取 ParseResult.ir 输出的 JSON
+  pub fn from_json(json: &str) -> serde_json::Result<Self> {
+    serde_json::from_str(json)
+  }
+}
+
+fn generate_ir_rules(style: &StyleMap, platform: Platform) -> Vec<IrRule> {
+  style.iter().map(|(selector, value)| IrRule {
+    selector: selector.to_string(),
+    properties: generate_declarations(value.to_vec(), platform.clone()),
+  }).collect()
+}
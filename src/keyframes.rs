@@ -0,0 +1,146 @@
+use std::convert::Infallible;
+
+use indexmap::IndexMap;
+use lightningcss::{
+  declaration::DeclarationBlock,
+  properties::{animation::AnimationName, Property},
+  rules::{keyframes::KeyframesName, CssRule},
+  stylesheet::StyleSheet,
+  visit_types,
+  visitor::{Visit, VisitTypes, Visitor},
+};
+
+use crate::{diagnostics::push_diagnostic, options::with_transform_options, style_parser::get_style_index};
+
+// 多个样式文件中定义了同名的 @keyframes 时，按 css 的规则后定义的覆盖先定义的，输出诊断信息；
+// 开启 rename_keyframes 时，除最后一个文件外，其他文件中的 @keyframes 重命名为 "名称_文件序号"，并改写同一文件中 animation、animation-name 的引用
+pub fn resolve_keyframes_collisions(stylesheet: &mut StyleSheet, style_starts: &[u32]) {
+  if style_starts.len() < 2 {
+    return
+  }
+  let mut collector = KeyframesCollector { style_starts, names: IndexMap::new() };
+  stylesheet.visit(&mut collector).unwrap();
+
+  let rename_keyframes = with_transform_options(|options| options.rename_keyframes);
+  // (文件序号, 原名称, 新名称)
+  let mut renames: Vec<(usize, String, String)> = vec![];
+  collector.names.iter().filter(|(_, indexes)| indexes.len() > 1).for_each(|(name, indexes)| {
+    let last = *indexes.last().unwrap();
+    if !rename_keyframes {
+      let files = indexes.iter().map(|index| format!("styles[{}]", index)).collect::<Vec<_>>().join(", ");
+      push_diagnostic("animationName", format!("@keyframes {} is defined in {}, the one in styles[{}] overrides the others, enable renameKeyframes to keep the definition of each file", name, files, last));
+      return
+    }
+    indexes[..indexes.len() - 1].iter().for_each(|index| {
+      let mut new_name = format!("{}_{}", name, index);
+      let mut suffix = 1;
+      while collector.names.contains_key(&new_name) || renames.iter().any(|(_, _, renamed)| *renamed == new_name) {
+        new_name = format!("{}_{}_{}", name, index, suffix);
+        suffix += 1;
+      }
+      push_diagnostic("animationName", format!("@keyframes {} of styles[{}] is renamed to {} as it is also defined in styles[{}]", name, index, new_name, last));
+      renames.push((*index, name.clone(), new_name));
+    });
+  });
+  if renames.is_empty() {
+    return
+  }
+  let mut renamer = KeyframesRenamer { style_starts, renames };
+  stylesheet.visit(&mut renamer).unwrap();
+}
+
+// @keyframes 的名称，@keyframes "fade in" 与 @keyframes fadeIn 均取引号内的原始名称
+pub fn get_keyframes_name<'a>(name: &'a KeyframesName) -> &'a str {
+  match name {
+    KeyframesName::Ident(ident) => ident.0.as_ref(),
+    KeyframesName::Custom(name) => name.as_ref()
+  }
+}
+
+// 收集 @keyframes 的名称及定义所在的样式文件
+struct KeyframesCollector<'a> {
+  style_starts: &'a [u32],
+  // 名称 => 定义了该名称的样式文件序号，按出现顺序排列
+  names: IndexMap<String, Vec<usize>>,
+}
+
+impl<'a, 'i> Visitor<'i> for KeyframesCollector<'a> {
+  type Error = Infallible;
+  const TYPES: VisitTypes = visit_types!(RULES);
+
+  fn visit_rule(&mut self, rule: &mut CssRule<'i>) -> Result<(), Self::Error> {
+    if let CssRule::Keyframes(keyframes_rule) = rule {
+      let index = get_style_index(keyframes_rule.loc.line, self.style_starts);
+      let indexes = self.names.entry(get_keyframes_name(&keyframes_rule.name).to_string()).or_default();
+      if !indexes.contains(&index) {
+        indexes.push(index);
+      }
+    }
+    // @media、@supports 等规则中的 @keyframes
+    rule.visit_children(self)
+  }
+}
+
+struct KeyframesRenamer<'a> {
+  style_starts: &'a [u32],
+  renames: Vec<(usize, String, String)>,
+}
+
+impl<'a, 'i> Visitor<'i> for KeyframesRenamer<'a> {
+  type Error = Infallible;
+  const TYPES: VisitTypes = visit_types!(RULES);
+
+  fn visit_rule(&mut self, rule: &mut CssRule<'i>) -> Result<(), Self::Error> {
+    match rule {
+      CssRule::Keyframes(keyframes_rule) => {
+        let index = get_style_index(keyframes_rule.loc.line, self.style_starts);
+        if let Some(new_name) = self.get_new_name(index, get_keyframes_name(&keyframes_rule.name)) {
+          match &mut keyframes_rule.name {
+            KeyframesName::Ident(ident) => ident.0 = new_name.into(),
+            KeyframesName::Custom(name) => *name = new_name.into()
+          }
+        }
+      },
+      CssRule::Style(style) => {
+        let index = get_style_index(style.loc.line, self.style_starts);
+        self.rename_references(index, &mut style.declarations);
+      },
+      _ => {}
+    }
+    // 嵌套规则及 @media、@supports 等规则中的样式
+    rule.visit_children(self)
+  }
+}
+
+impl<'a> KeyframesRenamer<'a> {
+  fn get_new_name(&self, index: usize, name: &str) -> Option<String> {
+    self.renames
+      .iter()
+      .find(|(rename_index, rename_name, _)| *rename_index == index && rename_name == name)
+      .map(|(_, _, new_name)| new_name.clone())
+  }
+
+  fn rename_animation_name(&self, index: usize, animation_name: &mut AnimationName) {
+    match animation_name {
+      AnimationName::Ident(ident) => {
+        if let Some(new_name) = self.get_new_name(index, ident.0.as_ref()) {
+          ident.0 = new_name.into();
+        }
+      },
+      AnimationName::String(name) => {
+        if let Some(new_name) = self.get_new_name(index, name.as_ref()) {
+          *name = new_name.into();
+        }
+      },
+      AnimationName::None => {}
+    }
+  }
+
+  fn rename_references(&self, index: usize, declarations: &mut DeclarationBlock) {
+    declarations.declarations.iter_mut().chain(declarations.important_declarations.iter_mut()).for_each(|property| match property {
+      Property::AnimationName(names, _) => names.iter_mut().for_each(|name| self.rename_animation_name(index, name)),
+      Property::Animation(animations, _) => animations.iter_mut().for_each(|animation| self.rename_animation_name(index, &mut animation.name)),
+      _ => {}
+    });
+  }
+}
@@ -0,0 +1,217 @@
+use std::{cmp::Reverse, collections::HashMap};
+
+use lightningcss::{
+  properties::Property,
+  rules::{layer::LayerBlockRule, CssRule, CssRuleList, Location},
+  stylesheet::StyleSheet,
+};
+use swc_core::atoms::Atom;
+
+use crate::{diagnostics::push_diagnostic, interner::intern_property_name, options::{with_transform_options, StylePrecedence}, shorthand::expand_shorthand, style_parser::{get_style_index, StyleRecord}, utils::{get_selector_classes, is_class_selector}};
+
+// @layer 层级，按首次声明的顺序记录完整的层级名，如 base、base.reset
+#[derive(Debug, Default)]
+pub struct LayerOrder {
+  names: Vec<String>,
+  // 匿名层级的数量，用于生成唯一的层级名
+  anonymous_count: usize,
+}
+
+impl LayerOrder {
+  // 登记层级，同时登记其父级层级，如 a.b.c => a、a.b、a.b.c
+  pub fn register(&mut self, name: &str) {
+    let mut full_name = String::new();
+    for part in name.split('.') {
+      if !full_name.is_empty() {
+        full_name.push('.');
+      }
+      full_name.push_str(part);
+      if !self.names.contains(&full_name) {
+        self.names.push(full_name.clone());
+      }
+    }
+  }
+
+  // 匿名层级 @layer { ... } 每次声明均为新的层级，# 不是合法的层级名字符，不会与具名层级冲突
+  pub fn register_anonymous(&mut self, parent: Option<&str>) -> String {
+    self.anonymous_count += 1;
+    let name = match parent {
+      Some(parent) => format!("{}.#{}", parent, self.anonymous_count),
+      None => format!("#{}", self.anonymous_count),
+    };
+    self.register(&name);
+    name
+  }
+
+  // 计算各层级的优先级，需在所有层级登记完成后调用
+  pub fn ranks(&self) -> LayerRanks {
+    let mut order = vec![];
+    self.collect_order(None, &mut order);
+    LayerRanks(order.into_iter().enumerate().map(|(index, name)| (name.to_string(), index)).collect())
+  }
+
+  // 后序遍历层级树，子层级排在父层级之前
+  fn collect_order<'a>(&'a self, parent: Option<&'a str>, order: &mut Vec<&'a str>) {
+    self.names
+      .iter()
+      .filter(|name| match (parent, name.rsplit_once('.')) {
+        (Some(parent), Some((name_parent, _))) => name_parent == parent,
+        (None, None) => true,
+        _ => false
+      })
+      .for_each(|name| self.collect_order(Some(name), order));
+    order.push(parent.unwrap_or_default());
+  }
+}
+
+// 层级名 => 优先级，未分层的样式对应空字符串
+pub struct LayerRanks(HashMap<String, usize>);
+
+impl LayerRanks {
+  // 层级的优先级，数值越大优先级越高：先声明的层级低于后声明的层级，子层级低于父层级中未分层的样式，
+  // 未分层的样式（None）优先级最高
+  pub fn rank(&self, layer: Option<&str>) -> usize {
+    self.0.get(layer.unwrap_or_default()).copied().unwrap_or_default()
+  }
+}
+
+// 不同选择器的声明在层叠中的优先级，先比较是否 !important，再比较层级，!important 声明的层级顺序相反
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum CascadeRank {
+  Normal(usize),
+  Important(Reverse<usize>),
+}
+
+// 嵌套选择器（后代选择器、复合选择器）的样式由运行时在类样式之后合并，不受 @layer 层级的影响，
+// 在编译期移除被层级更高的类选择器覆盖的声明，如 @layer base { .box.x { color: red } } .box { color: blue }
+// 中 .box.x 的 color 被移除。类选择器的类名均出现在嵌套选择器最后一个复合选择器中时，匹配嵌套选择器的节点必然匹配该类选择器
+pub fn remove_shadowed_layer_declarations(style_record: &StyleRecord, ranks: &LayerRanks, final_style: &mut Vec<(String, Vec<(Atom, Property)>)>) {
+  // 选择器 => 各属性生效的声明的优先级
+  let cascade_ranks = style_record.iter().map(|(selector, declarations)| {
+    let mut properties: HashMap<Atom, CascadeRank> = HashMap::new();
+    declarations.iter().for_each(|declaration| {
+      let rank = ranks.rank(declaration.layer.as_deref());
+      let block = &declaration.declaration;
+      let normal = block.declarations.iter().flat_map(expand_shorthand).map(|property| (property, CascadeRank::Normal(rank)));
+      let important = block.important_declarations.iter().flat_map(expand_shorthand).map(|property| (property, CascadeRank::Important(Reverse(rank))));
+      normal.chain(important).for_each(|(property, rank)| {
+        let entry = properties.entry(intern_property_name(&property)).or_insert(rank);
+        *entry = rank.max(*entry);
+      });
+    });
+    (selector.as_str(), properties)
+  }).collect::<HashMap<_, _>>();
+  // 仅由类名组成的选择器及其类名
+  let class_selectors = final_style.iter()
+    .filter(|(selector, _)| is_class_selector(selector))
+    .filter_map(|(selector, _)| Some((selector.clone(), get_selector_classes(selector)?)))
+    .collect::<Vec<_>>();
+  // 嵌套选择器及其最后一个复合选择器中的类名
+  let nesting_selectors = final_style.iter()
+    .filter_map(|(selector, _)| Some((selector.clone(), get_nesting_subject_classes(selector)?)))
+    .collect::<Vec<_>>();
+  let mut shadowed_selectors = vec![];
+  for (selector, properties) in final_style.iter_mut() {
+    let (Some(subject_classes), Some(selector_ranks)) = (get_nesting_subject_classes(selector), cascade_ranks.get(selector.as_str())) else {
+      continue
+    };
+    let is_covering = |classes: &[String]| classes.iter().all(|class| subject_classes.contains(class));
+    let covering_ranks = class_selectors.iter()
+      .filter(|(other, classes)| other.as_str() != selector.as_str() && is_covering(classes))
+      .filter_map(|(other, _)| cascade_ranks.get(other.as_str()))
+      .collect::<Vec<_>>();
+    properties.retain(|(name, _)| {
+      let Some(rank) = selector_ranks.get(name) else {
+        return true
+      };
+      !covering_ranks.iter().any(|other| other.get(name).is_some_and(|other| other > rank))
+    });
+    if properties.is_empty() {
+      shadowed_selectors.push(selector.clone());
+      continue
+    }
+    // 可能匹配同一节点的其他嵌套选择器，无法在编译期确定匹配关系，运行时按选择器的长度合并，提示层级可能不生效
+    nesting_selectors.iter()
+      .filter(|(other, classes)| other.as_str() != selector.as_str() && !(is_class_selector(other) && is_covering(classes)) && classes.iter().any(|class| subject_classes.contains(class)))
+      .for_each(|(other, _)| {
+        let Some(other_ranks) = cascade_ranks.get(other.as_str()) else {
+          return
+        };
+        let names = properties.iter()
+          .filter(|(name, _)| selector_ranks.get(name).is_some_and(|rank| other_ranks.get(name).is_some_and(|other| other > rank)))
+          .map(|(name, _)| name.to_string())
+          .collect::<Vec<_>>();
+        if !names.is_empty() {
+          push_diagnostic("@layer", format!("{} is in a lower cascade layer than {} but may override its {} at runtime, nesting selectors are merged by selector length", selector, other, names.join(", ")));
+        }
+      });
+  }
+  // 声明全部被覆盖的嵌套选择器不再输出
+  final_style.retain(|(selector, _)| !shadowed_selectors.contains(selector));
+}
+
+// 嵌套选择器（后代选择器、复合选择器）最后一个复合选择器中的类名，单个类选择器在合并类样式时已按层级排序，
+// 伪类、伪元素由运行时根据节点状态合并，均返回 None
+fn get_nesting_subject_classes(selector: &str) -> Option<Vec<String>> {
+  let subject = selector.rsplit(|c: char| c.is_whitespace() || ">+~".contains(c)).next()?;
+  let is_nesting = subject != selector || subject.matches('.').count() > 1;
+  if !is_nesting || !is_class_selector(subject) {
+    return None
+  }
+  get_selector_classes(subject)
+}
+
+// 页面样式优先时，全局样式（styles 中的前 global_style_count 个）中的规则放入最先声明的匿名层级，
+// 按 @layer 的层叠规则页面样式覆盖全局样式，与样式的顺序及 specificity 无关
+pub fn wrap_global_styles(stylesheet: &mut StyleSheet, style_starts: &[u32]) {
+  let global_style_count = with_transform_options(|options| match options.style_precedence {
+    StylePrecedence::Page => options.global_style_count,
+    StylePrecedence::Order => 0
+  });
+  if global_style_count == 0 {
+    return
+  }
+  // 没有位置信息的规则与其前一条规则属于同一个样式文件
+  let mut index = 0;
+  let (global_rules, page_rules): (Vec<_>, Vec<_>) = std::mem::take(&mut stylesheet.rules.0).into_iter().partition(|rule| {
+    if let Some(line) = get_rule_line(rule) {
+      index = get_style_index(line, style_starts);
+    }
+    index < global_style_count
+  });
+  stylesheet.rules.0 = page_rules;
+  if !global_rules.is_empty() {
+    stylesheet.rules.0.insert(0, CssRule::LayerBlock(LayerBlockRule {
+      name: None,
+      rules: CssRuleList(global_rules),
+      loc: Location { source_index: 0, line: 0, column: 1 },
+    }));
+  }
+}
+
+fn get_rule_line(rule: &CssRule) -> Option<u32> {
+  let loc = match rule {
+    CssRule::Media(rule) => rule.loc,
+    CssRule::Import(rule) => rule.loc,
+    CssRule::Style(rule) => rule.loc,
+    CssRule::Keyframes(rule) => rule.loc,
+    CssRule::FontFace(rule) => rule.loc,
+    CssRule::FontPaletteValues(rule) => rule.loc,
+    CssRule::Page(rule) => rule.loc,
+    CssRule::Supports(rule) => rule.loc,
+    CssRule::CounterStyle(rule) => rule.loc,
+    CssRule::Namespace(rule) => rule.loc,
+    CssRule::MozDocument(rule) => rule.loc,
+    CssRule::Nesting(rule) => rule.loc,
+    CssRule::Viewport(rule) => rule.loc,
+    CssRule::CustomMedia(rule) => rule.loc,
+    CssRule::LayerStatement(rule) => rule.loc,
+    CssRule::LayerBlock(rule) => rule.loc,
+    CssRule::Property(rule) => rule.loc,
+    CssRule::Container(rule) => rule.loc,
+    CssRule::StartingStyle(rule) => rule.loc,
+    CssRule::Unknown(rule) => rule.loc,
+    _ => return None
+  };
+  Some(loc.line)
+}
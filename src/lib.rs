@@ -1,15 +1,11 @@
 #![deny(clippy::all)]
 
-use std::{cell::RefCell, rc::Rc};
+use std::collections::HashMap;
 use serde::Deserialize;
 
-use style_parser::StyleParser;
-use style_propetries::unit::Platform;
+use swc_core::ecma::ast::{EsVersion, Program};
 
-use swc_core::{
-  common::{comments::SingleThreadedComments, sync::Lrc, SourceMap}, ecma::{ast::Bool, codegen::{text_writer::JsWriter, Emitter}}
-};
-use crate::{document::JSXDocument, style_write::StyleWrite};
+use crate::{session::{Artifact, Session}, strict::get_strict_error};
 
 #[macro_use]
 extern crate napi_derive;
@@ -23,80 +19,667 @@ mod constants;
 mod style_propetries;
 mod style_parser;
 mod parse_style_properties;
+mod options;
+mod diagnostics;
+mod logical_properties;
+mod current_color;
+mod shorthand;
+mod theme;
+mod supports;
+mod transformer;
+mod stylesheet;
+mod arkui;
+mod minify;
+mod session;
+mod interner;
+mod scope;
+mod important;
+mod inherit;
+mod hooks;
+mod tag;
+mod layer;
+mod container;
+mod declaration;
+mod report;
+mod trace;
+mod api_version;
+mod validate;
+mod stats;
+mod lint;
+mod strict;
+mod fallback;
+mod ir;
+mod hash;
+mod keyframes;
+mod location;
+#[cfg(feature = "plugin")]
+mod plugin;
+
+// 供 rust 调用方直接调用的转换入口，如 Transformer::new(component, styles).platform(Platform::Harmony).transform()
+pub use transformer::{Transformer, TransformResult};
+pub use options::{ColorFormat, CommentsMode, HashAlgorithm, LintSeverity, QuoteStyle, RnOs, RoundingMode, StylePrecedence, TransformOptions, WritingDirection};
+pub use document::SourceSyntax;
+pub use diagnostics::{Diagnostic, DiagnosticSeverity};
+pub use style_parser::{ClassUsage, FontFaceItem};
+pub use stats::TransformStats;
+pub use interner::InternerStats;
+pub use style_propetries::unit::Platform;
+// 供 rust 调用方注册自定义属性的转换
+pub use hooks::{register_property_hook, clear_property_hooks, PropertyHook};
+pub use style_propetries::unit::PropertyTuple;
+pub use stylesheet::StyleSheetValue;
+pub use ir::{IrKeyframe, IrKeyframes, IrNode, IrProperties, IrRule, IrVariant, IrVariantKind, StyleIr};
 
 // component: jsx的code string
 // styles: css的code string
 // platform_string: "ReactNative" | "Harmony"
+// root_font_size: 1rem 对应的 px 值，默认 16
+// em_font_size: 1em 对应的 px 值，默认与 root_font_size 一致
+// direction: "ltr" | "rtl"，逻辑属性的书写方向，默认 "ltr"
+// mirror_rtl: direction 为 "rtl" 时，是否将 margin-left、text-align: left 等物理方向的属性左右镜像
+// font_family_map: 字体映射表，key 为 css 中的字体名称，value 为平台字体名称
+// extract_theme: 是否将 :root 中的 css 变量提取为 export const theme = {...}，var() 编译为 theme.xxx
+// supports_features: @supports 特性支持表，key 为 "属性" 或 "属性: 值"，覆盖平台内置的特性表
+// passthrough_unrecognized: 无法转换的声明按原始字符串透传（key 为驼峰命名），默认丢弃
+// rn_style_sheet_create: React Native 下是否使用 StyleSheet.create 创建样式对象
+// tree_shaking: 是否移除未被使用的类样式，并提示没有对应样式的 className
+// minify: 是否对输出的样式进行优化，合并上下左右相同的 margin、padding，移除默认值，属性按 key 排序
+// scoped: 是否开启作用域模式，选择器与 className 中的类名添加作用域前缀（ParseResult.scoped_classes 为类名映射）
+// scope_id: 作用域 id，未指定时根据组件代码生成
+// scope_name_template: 作用域类名的模板，如 "[name]_[local]_[hash:6]"，与 css-loader 的 localIdentName 一致，未指定时为 "作用域 id_类名"
+// scope_hash_algorithm: "fnv1a" | "xxhash64" | "sha1"，作用域类名及作用域 id 中 hash 的算法，默认 "fnv1a"
+// scope_hash_length: hash 保留的位数，默认 8，模板中的 [hash:N] 优先
+// scope_path: 样式文件相对于项目根目录的路径，作为模板中的 [name] 并参与 hash 计算，与 css-loader 生成相同的类名，未指定时使用作用域 id
+// inherit_properties: 需要由容器节点继承到 Text 组件的文本属性，如 ["color", "font-size"]
+// tag_map: 组件与标签名的映射，如 { "View": "div" }，标签选择器 view、div 均匹配 View 组件
+// component_sources: 除 @tarojs/components 外，导出 Taro 组件的模块，如 ["@/components/base"]
+// color_format: "auto" | "hex" | "rgb" | "hex8"，颜色的输出格式，默认 "auto"，lab()、oklch() 等色彩空间均转换为 sRGB
+// container_queries: 是否支持 @container 容器查询，容器尺寸可静态确定时在编译期求值，否则输出由运行时判断的条件样式
+// precision: 长度数值（含 rem、em 换算后的 px 值）保留的小数位数，默认不取整
+// rounding: "round" | "floor" | "ceil"，长度数值的取整方式，默认 "round"，需同时指定 precision
+// rn_os: "android" | "ios"，React Native 的目标系统，box-shadow 在 android 下转为 elevation，在 ios 下转为 shadow* 属性
+// harmony_api_version: 鸿蒙的目标 API 版本，如 9，需要更高版本的属性会降级（如多个 text-shadow 只取第一个）或丢弃并输出诊断信息，默认不检查
+// style_key_map: 输出的样式属性名映射，key 为转换后的驼峰属性名，value 为运行时使用的属性名，如 { "animationTimeingFunction": "animationTimingFunction" }
+// lint_rules: 样式检查规则的级别，key 为规则名（no-float、no-position-fixed、no-display-inline、unsupported-selector、no-text-style-on-view），value 为 "off" | "warning" | "error"，未配置的规则按 "warning" 检查，no-text-style-on-view 默认关闭
+// fallback_rules: 值回退规则，key 为 "属性: 值"，value 为替换的声明，如 { "display: grid": "display: flex; flex-direction: column" }，目标平台不支持的属性值自动替换并记录到诊断信息中，优先于内置的回退规则
+// strict: 是否开启严格模式，属性值无法解析的声明、转换后被丢弃的声明（目标平台不支持的属性或属性值）及语法错误的声明输出为 severity 为 "error" 的诊断信息，存在错误时 parse、transformForBundler 抛出异常，异常信息中列出所有错误及源码位置
+// hoist_static_styles: 是否将类组件中静态 className 的样式对象提升到模块作用域，首次使用时计算并缓存，避免每次 render 重新创建，含嵌套样式的组件不做提升
+// comments: "all" | "license" | "none"，输出代码中保留的注释，"license" 仅保留 /*! */ 及包含 @license、@preserve 的注释，默认 "all"
+// ascii_only: 输出代码是否只包含 ascii 字符，非 ascii 字符转义为 \uXXXX
+// target: "es3" | "es5" | "es2015" ... "es2022" | "esnext"，输出代码的目标 ES 版本，默认 "esnext"，es5 及以下时字符串不再保留原始写法
+// quote_style: "double" | "single"，生成的字符串使用的引号，组件代码中原有的字符串保持原样
+// rename_keyframes: 多个样式文件中定义了同名的 @keyframes 时，除最后一个文件外的 @keyframes 重命名为 "名称_文件序号"，并改写同一文件中 animation、animation-name 的引用，默认按 css 的规则后定义的覆盖先定义的，并输出诊断信息
+// scrollable_hint: overflow: scroll、auto 时是否额外输出 __scrollable__: "vertical" | "horizontal" | "both"，供运行时将 View 替换为 Scroll，仅鸿蒙平台输出
+// render_props: 是否将属性中的 JSX（如 <List renderItem={() => <View className="row" />} />）作为节点的后代加入节点树，参与标签选择器、兄弟选择器的编译期匹配及文本样式继承
+// global_style_count: styles 中前 n 个为全局样式（如 app.css），Session 中登记的页面自动设置
+// style_precedence: "order" | "page"，全局样式与页面样式同时匹配时的优先级，"page" 时全局样式视为最先声明的 @layer 层级，页面样式覆盖全局样式，默认 "order" 按 specificity 及样式的顺序
+// platforms: parsePlatforms 输出的平台，如 ["Harmony", "ReactNative"]，默认输出两个平台
+// syntax: "tsx" | "jsx"，组件代码的语法，默认 "tsx"
+// stylesheet: 是否额外输出转换后的样式表 JSON（ParseResult.stylesheet）
+// arkui: 是否额外输出 ArkUI @Styles 代码（ParseResult.arkui），仅 Harmony 平台可用
+// dts: 是否额外输出样式对象的 TypeScript 类型声明（ParseResult.dts）
+// match_report: 是否额外输出节点与样式规则的匹配报告 JSON（ParseResult.match_report）
+// ir: 是否额外输出计算后的样式中间表示 JSON（ParseResult.ir），含各规则、各节点的样式，@keyframes 及暗色模式、@container 变体
+// filename: 组件的文件名，输出到匹配报告及样式中间表示中
 
 #[napi(object)]
 #[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ParseOptions {
   pub platform_string: String,
-  pub is_entry: bool
+  pub is_entry: bool,
+  pub root_font_size: Option<f64>,
+  pub em_font_size: Option<f64>,
+  pub direction: Option<String>,
+  pub mirror_rtl: Option<bool>,
+  pub font_family_map: Option<HashMap<String, String>>,
+  pub extract_theme: Option<bool>,
+  pub supports_features: Option<HashMap<String, bool>>,
+  pub passthrough_unrecognized: Option<bool>,
+  pub rn_style_sheet_create: Option<bool>,
+  pub tree_shaking: Option<bool>,
+  pub minify: Option<bool>,
+  pub scoped: Option<bool>,
+  pub scope_id: Option<String>,
+  pub scope_name_template: Option<String>,
+  pub scope_hash_algorithm: Option<String>,
+  pub scope_hash_length: Option<u32>,
+  pub scope_path: Option<String>,
+  pub inherit_properties: Option<Vec<String>>,
+  pub tag_map: Option<HashMap<String, String>>,
+  pub component_sources: Option<Vec<String>>,
+  pub color_format: Option<String>,
+  pub container_queries: Option<bool>,
+  pub precision: Option<u32>,
+  pub rounding: Option<String>,
+  pub rn_os: Option<String>,
+  pub harmony_api_version: Option<u32>,
+  pub style_key_map: Option<HashMap<String, String>>,
+  pub lint_rules: Option<HashMap<String, String>>,
+  pub fallback_rules: Option<HashMap<String, String>>,
+  pub strict: Option<bool>,
+  pub hoist_static_styles: Option<bool>,
+  pub comments: Option<String>,
+  pub ascii_only: Option<bool>,
+  pub target: Option<String>,
+  pub quote_style: Option<String>,
+  pub rename_keyframes: Option<bool>,
+  pub scrollable_hint: Option<bool>,
+  pub render_props: Option<bool>,
+  pub global_style_count: Option<u32>,
+  pub style_precedence: Option<String>,
+  pub platforms: Option<Vec<String>>,
+  pub syntax: Option<String>,
+  pub stylesheet: Option<bool>,
+  pub arkui: Option<bool>,
+  pub dts: Option<bool>,
+  pub match_report: Option<bool>,
+  pub ir: Option<bool>,
+  pub filename: Option<String>
+}
+
+#[napi(object)]
+pub struct ParseDiagnostic {
+  pub property: String,
+  pub message: String,
+  // "warning" | "error"
+  pub severity: String,
+  // 样式检查产生的诊断信息对应的规则名
+  pub rule: Option<String>
+}
+
+#[napi(object)]
+pub struct ParseFontFace {
+  pub family_name: String,
+  pub src: Vec<String>,
+  pub font_weight: Option<String>,
+  pub font_style: Option<String>
 }
 
 #[napi(object)]
 pub struct ParseResult {
-  pub code: String
+  pub code: String,
+  // @font-face 声明的字体清单，由运行时调用 registerFont 注册
+  pub fonts: Vec<ParseFontFace>,
+  // 转换过程中的诊断信息，如平台不支持的属性
+  pub diagnostics: Vec<ParseDiagnostic>,
+  // 转换后的样式表 JSON
+  pub stylesheet: Option<String>,
+  // ArkUI @Styles 代码
+  pub arkui: Option<String>,
+  // 样式对象的 TypeScript 类型声明
+  pub dts: Option<String>,
+  // 节点与样式规则的匹配报告 JSON
+  pub match_report: Option<String>,
+  // 计算后的样式中间表示 JSON
+  pub ir: Option<String>,
+  // 作用域模式下的类名映射，原始类名 => 添加作用域前缀后的类名
+  pub scoped_classes: Option<HashMap<String, String>>,
+  // 转换的性能统计，用于在构建流程中跟踪耗时变化
+  pub stats: ParseStats
 }
 
-#[napi]
-pub fn parse(component: String, styles: Vec<String>, options: ParseOptions) -> ParseResult {
+#[napi(object)]
+pub struct ParsePhase {
+  pub name: String,
+  pub duration_ms: f64
+}
+
+#[napi(object)]
+pub struct ParseStats {
+  // 解析的样式规则数量
+  pub rules_parsed: u32,
+  // 匹配到节点的选择器数量
+  pub rules_matched: u32,
+  // 组件中的 JSX 节点数量
+  pub nodes_visited: u32,
+  // 类名索引的命中率，0 ~ 1
+  pub selector_hit_rate: f64,
+  // 字符串驻留的命中率，0 ~ 1
+  pub interner_hit_rate: f64,
+  // 各阶段的耗时，按执行顺序排列
+  pub phases: Vec<ParsePhase>
+}
 
-  let platform = match options.platform_string.as_str() {
+#[napi(object)]
+pub struct BundlerMeta {
+  // className 中有对应样式的类名
+  pub used_classes: Vec<String>,
+  // className 中没有对应样式的类名，可能是拼写错误
+  pub unmatched_classes: Vec<String>,
+  // 样式表中未被组件引用的类名
+  pub unused_classes: Vec<String>,
+  pub diagnostics: Vec<ParseDiagnostic>
+}
+
+#[napi(object)]
+pub struct BundlerResult {
+  pub code: String,
+  pub map: Option<String>,
+  pub meta: BundlerMeta
+}
+
+fn get_platform(platform_string: &str) -> Platform {
+  match platform_string {
     "ReactNative" => Platform::ReactNative,
     "Harmony" => Platform::Harmony,
     _ => Platform::Harmony
-  };
+  }
+}
+
+fn get_syntax(syntax: Option<&str>) -> SourceSyntax {
+  match syntax {
+    Some("jsx") => SourceSyntax::Jsx,
+    _ => SourceSyntax::Tsx
+  }
+}
 
-  let is_entry = options.is_entry || false;
-
-  let mut is_enable_nesting = true;
-
-  // 解析组件文件
-  let cm: Lrc<SourceMap> = Default::default();
-  let comments = SingleThreadedComments::default();
-  let mut document = JSXDocument::new();
-  document.parse(component, cm.clone(), &comments);
-
-  // 解析样式文件
-  let css = styles.join("\n");
-  let mut style_parser = StyleParser::new(&document, platform.clone(), is_entry.clone());
-  style_parser.parse(&css);
-  let style_data = style_parser.calc();
-
-  // 判断计算的结果是否会含有嵌套选择器
-  if is_enable_nesting {
-    is_enable_nesting = style_data.has_nesting;
-  }
-
-  let program = Rc::new(RefCell::new(document.program.as_ref().unwrap().clone()));
-  let jsx_record = Rc::new(RefCell::new(document.jsx_record.as_ref().unwrap().clone()));
-  let mut style_write = StyleWrite::new(
-    program.clone(),
-    jsx_record.clone(),
-    style_data.pesudo_style_record.clone(),
-    style_data.all_style.clone(),
-    is_enable_nesting,
-    is_entry,
-  );
-  style_write.write(platform, document.taro_components.clone());
-
-  // ast 转代码
-  let mut buf = Vec::new();
-  {
-    let writer = Box::new(JsWriter::new(cm.clone(), "\n", &mut buf, None));
-    let mut emitter = Emitter {
-      cfg: Default::default(),
-      cm: cm.clone(),
-      wr: writer,
-      comments: Some(&comments),
+fn get_transform_options(options: &ParseOptions) -> TransformOptions {
+  let mut transform_options = TransformOptions::default();
+  if let Some(root_font_size) = options.root_font_size {
+    transform_options.root_font_size = root_font_size;
+    transform_options.em_font_size = root_font_size;
+  }
+  if let Some(em_font_size) = options.em_font_size {
+    transform_options.em_font_size = em_font_size;
+  }
+  if let Some(direction) = &options.direction {
+    transform_options.direction = match direction.as_str() {
+      "rtl" => WritingDirection::Rtl,
+      _ => WritingDirection::Ltr
+    };
+  }
+  if let Some(mirror_rtl) = options.mirror_rtl {
+    transform_options.mirror_rtl = mirror_rtl;
+  }
+  if let Some(font_family_map) = &options.font_family_map {
+    transform_options.font_family_map = font_family_map.clone();
+  }
+  if let Some(extract_theme) = options.extract_theme {
+    transform_options.extract_theme = extract_theme;
+  }
+  if let Some(supports_features) = &options.supports_features {
+    transform_options.supports_features = supports_features.clone();
+  }
+  if let Some(passthrough_unrecognized) = options.passthrough_unrecognized {
+    transform_options.passthrough_unrecognized = passthrough_unrecognized;
+  }
+  if let Some(rn_style_sheet_create) = options.rn_style_sheet_create {
+    transform_options.rn_style_sheet_create = rn_style_sheet_create;
+  }
+  if let Some(tree_shaking) = options.tree_shaking {
+    transform_options.tree_shaking = tree_shaking;
+  }
+  if let Some(minify) = options.minify {
+    transform_options.minify = minify;
+  }
+  if let Some(scoped) = options.scoped {
+    transform_options.scoped = scoped;
+  }
+  if let Some(scope_id) = &options.scope_id {
+    transform_options.scope_id = Some(scope_id.clone());
+  }
+  if let Some(scope_name_template) = &options.scope_name_template {
+    transform_options.scope_name_template = Some(scope_name_template.clone());
+  }
+  if let Some(scope_hash_algorithm) = &options.scope_hash_algorithm {
+    transform_options.scope_hash_algorithm = match scope_hash_algorithm.to_lowercase().as_str() {
+      "xxhash64" => HashAlgorithm::Xxhash64,
+      "sha1" => HashAlgorithm::Sha1,
+      _ => HashAlgorithm::Fnv1a
     };
-    emitter.emit_program(&program.borrow()).unwrap();
   }
-  let code = String::from_utf8(buf).unwrap().replace("\r\n", "\n");
+  if let Some(scope_hash_length) = options.scope_hash_length {
+    transform_options.scope_hash_length = scope_hash_length as usize;
+  }
+  if let Some(scope_path) = &options.scope_path {
+    transform_options.scope_path = Some(scope_path.clone());
+  }
+  if let Some(inherit_properties) = &options.inherit_properties {
+    transform_options.inherit_properties = inherit_properties.clone();
+  }
+  if let Some(tag_map) = &options.tag_map {
+    transform_options.tag_map = tag_map.clone();
+  }
+  if let Some(component_sources) = &options.component_sources {
+    transform_options.component_sources = component_sources.clone();
+  }
+  if let Some(color_format) = &options.color_format {
+    transform_options.color_format = match color_format.as_str() {
+      "hex" => ColorFormat::Hex,
+      "rgb" => ColorFormat::Rgb,
+      "hex8" => ColorFormat::Hex8,
+      _ => ColorFormat::Auto
+    };
+  }
+  if let Some(container_queries) = options.container_queries {
+    transform_options.container_queries = container_queries;
+  }
+  if let Some(precision) = options.precision {
+    transform_options.precision = Some(precision);
+  }
+  if let Some(rounding) = &options.rounding {
+    transform_options.rounding = match rounding.as_str() {
+      "floor" => RoundingMode::Floor,
+      "ceil" => RoundingMode::Ceil,
+      _ => RoundingMode::Round
+    };
+  }
+  if let Some(rn_os) = &options.rn_os {
+    transform_options.rn_os = match rn_os.as_str() {
+      "android" => Some(RnOs::Android),
+      "ios" => Some(RnOs::Ios),
+      _ => None
+    };
+  }
+  if let Some(harmony_api_version) = options.harmony_api_version {
+    transform_options.harmony_api_version = Some(harmony_api_version);
+  }
+  if let Some(style_key_map) = &options.style_key_map {
+    transform_options.style_key_map = style_key_map.clone();
+  }
+  if let Some(fallback_rules) = &options.fallback_rules {
+    transform_options.fallback_rules = fallback_rules.clone();
+  }
+  if let Some(strict) = options.strict {
+    transform_options.strict = strict;
+  }
+  if let Some(hoist_static_styles) = options.hoist_static_styles {
+    transform_options.hoist_static_styles = hoist_static_styles;
+  }
+  if let Some(comments) = &options.comments {
+    transform_options.comments = match comments.as_str() {
+      "license" => CommentsMode::License,
+      "none" => CommentsMode::None,
+      _ => CommentsMode::All
+    };
+  }
+  if let Some(ascii_only) = options.ascii_only {
+    transform_options.ascii_only = ascii_only;
+  }
+  if let Some(target) = &options.target {
+    transform_options.target = match target.to_lowercase().as_str() {
+      "es3" => EsVersion::Es3,
+      "es5" => EsVersion::Es5,
+      "es2015" | "es6" => EsVersion::Es2015,
+      "es2016" => EsVersion::Es2016,
+      "es2017" => EsVersion::Es2017,
+      "es2018" => EsVersion::Es2018,
+      "es2019" => EsVersion::Es2019,
+      "es2020" => EsVersion::Es2020,
+      "es2021" => EsVersion::Es2021,
+      "es2022" => EsVersion::Es2022,
+      _ => EsVersion::EsNext
+    };
+  }
+  if let Some(quote_style) = &options.quote_style {
+    transform_options.quote_style = match quote_style.as_str() {
+      "single" => Some(QuoteStyle::Single),
+      "double" => Some(QuoteStyle::Double),
+      _ => None
+    };
+  }
+  if let Some(rename_keyframes) = options.rename_keyframes {
+    transform_options.rename_keyframes = rename_keyframes;
+  }
+  if let Some(scrollable_hint) = options.scrollable_hint {
+    transform_options.scrollable_hint = scrollable_hint;
+  }
+  if let Some(render_props) = options.render_props {
+    transform_options.render_props = render_props;
+  }
+  if let Some(global_style_count) = options.global_style_count {
+    transform_options.global_style_count = global_style_count as usize;
+  }
+  if let Some(style_precedence) = &options.style_precedence {
+    transform_options.style_precedence = match style_precedence.to_lowercase().as_str() {
+      "page" => StylePrecedence::Page,
+      _ => StylePrecedence::Order
+    };
+  }
+  if let Some(lint_rules) = &options.lint_rules {
+    transform_options.lint_rules = lint_rules.iter().map(|(rule, severity)| {
+      (rule.clone(), match severity.as_str() {
+        "off" => LintSeverity::Off,
+        "error" => LintSeverity::Error,
+        _ => LintSeverity::Warning
+      })
+    }).collect();
+  }
+  transform_options
+}
+
+fn get_parse_diagnostics(diagnostics: Vec<Diagnostic>) -> Vec<ParseDiagnostic> {
+  diagnostics.into_iter().map(|diagnostic| ParseDiagnostic {
+    property: diagnostic.property,
+    message: diagnostic.message,
+    severity: diagnostic.severity.as_str().to_string(),
+    rule: diagnostic.rule
+  }).collect()
+}
+
+fn get_parse_result(result: TransformResult) -> ParseResult {
+  let diagnostics = get_parse_diagnostics(result.diagnostics);
+
+  let fonts = result.font_faces.into_iter().map(|font_face| ParseFontFace {
+    family_name: font_face.family_name,
+    src: font_face.src,
+    font_weight: font_face.font_weight,
+    font_style: font_face.font_style
+  }).collect();
+
+  let stats = ParseStats {
+    rules_parsed: result.stats.rules_parsed as u32,
+    rules_matched: result.stats.rules_matched as u32,
+    nodes_visited: result.stats.nodes_visited as u32,
+    selector_hit_rate: result.stats.selector_hit_rate(),
+    interner_hit_rate: result.stats.interner_hit_rate(),
+    phases: result.stats.phases.iter().map(|(name, duration)| ParsePhase {
+      name: name.clone(),
+      duration_ms: duration.as_secs_f64() * 1000.0
+    }).collect()
+  };
 
   ParseResult {
-    code
+    code: result.code,
+    fonts,
+    diagnostics,
+    stylesheet: result.stylesheet,
+    arkui: result.arkui,
+    dts: result.dts,
+    match_report: result.match_report,
+    ir: result.ir.map(|ir| ir.to_json()),
+    scoped_classes: match result.scoped_classes.is_empty() {
+      true => None,
+      false => Some(result.scoped_classes.into_iter().collect())
+    },
+    stats
   }
-}
\ No newline at end of file
+}
+
+// 严格模式下存在错误级别的诊断信息时转换失败
+fn check_strict(diagnostics: &[Diagnostic]) -> napi::Result<()> {
+  match get_strict_error(diagnostics) {
+    Some(message) => Err(napi::Error::from_reason(message)),
+    None => Ok(())
+  }
+}
+
+fn create_transformer(component: String, styles: Vec<String>, options: &ParseOptions) -> Transformer {
+  let transformer = Transformer::new(component, styles)
+    .platform(get_platform(&options.platform_string))
+    .options(get_transform_options(options))
+    .entry(options.is_entry)
+    .syntax(get_syntax(options.syntax.as_deref()))
+    .stylesheet(options.stylesheet.unwrap_or(false))
+    .arkui(options.arkui.unwrap_or(false))
+    .dts(options.dts.unwrap_or(false))
+    .match_report(options.match_report.unwrap_or(false))
+    .ir(options.ir.unwrap_or(false));
+  match &options.filename {
+    Some(filename) => transformer.filename(filename.clone()),
+    None => transformer
+  }
+}
+
+#[napi]
+pub fn parse(component: String, styles: Vec<String>, options: ParseOptions) -> napi::Result<ParseResult> {
+  let result = create_transformer(component, styles, &options).transform();
+  if options.strict == Some(true) {
+    check_strict(&result.diagnostics)?;
+  }
+  Ok(get_parse_result(result))
+}
+
+// 组件代码只解析一次，同时输出多个平台的转换结果，key 为平台名，如 { Harmony: {...}, ReactNative: {...} }
+// 平台由 options.platforms 指定，未指定时输出 Harmony、ReactNative 两个平台，options.platform_string 不生效
+#[napi]
+pub fn parse_platforms(component: String, styles: Vec<String>, options: ParseOptions) -> napi::Result<HashMap<String, ParseResult>> {
+  let platforms = options.platforms.clone().unwrap_or(vec!["Harmony".to_string(), "ReactNative".to_string()]);
+  let results = create_transformer(component, styles, &options)
+    .transform_platforms(platforms.iter().map(|platform| get_platform(platform)).collect());
+  platforms.into_iter().zip(results).map(|(platform, (_, result))| {
+    if options.strict == Some(true) {
+      check_strict(&result.diagnostics).map_err(|err| napi::Error::from_reason(format!("{}: {}", platform, err.reason)))?;
+    }
+    Ok((platform, get_parse_result(result)))
+  }).collect()
+}
+
+// 转换上游 swc 插件传入的 AST，用于在 swc 插件中调用，返回的 ParseResult.code 为空
+pub fn transform_program(program: Program, styles: Vec<String>, options: ParseOptions) -> (Program, ParseResult) {
+  let mut result = Transformer::from_program(program, styles)
+    .platform(get_platform(&options.platform_string))
+    .options(get_transform_options(&options))
+    .entry(options.is_entry)
+    .stylesheet(options.stylesheet.unwrap_or(false))
+    .arkui(options.arkui.unwrap_or(false))
+    .dts(options.dts.unwrap_or(false))
+    .transform();
+
+  let program = result.program.take().unwrap();
+  (program, get_parse_result(result))
+}
+
+// 供 Vite、Rollup 插件在 transform 钩子中调用
+// id: 模块路径，作为 source map 的源文件名，未指定 syntax 时 .js、.jsx 按 jsx 解析
+// styles: 组件依赖的样式，按层叠顺序排列，@import 需由调用方展开
+#[napi]
+pub fn transform_for_bundler(code: String, id: String, styles: Vec<String>, options: ParseOptions) -> napi::Result<BundlerResult> {
+  let syntax = match options.syntax.as_deref() {
+    Some(syntax) => get_syntax(Some(syntax)),
+    None if id.ends_with(".js") || id.ends_with(".jsx") => SourceSyntax::Jsx,
+    None => SourceSyntax::Tsx
+  };
+  let result = Transformer::new(code, styles)
+    .platform(get_platform(&options.platform_string))
+    .options(get_transform_options(&options))
+    .entry(options.is_entry)
+    .syntax(syntax)
+    .filename(id)
+    .source_map(true)
+    .transform();
+
+  if options.strict == Some(true) {
+    check_strict(&result.diagnostics)?;
+  }
+
+  Ok(BundlerResult {
+    code: result.code,
+    map: result.map,
+    meta: BundlerMeta {
+      used_classes: result.class_usage.used,
+      unmatched_classes: result.class_usage.unmatched,
+      unused_classes: result.class_usage.unused,
+      diagnostics: get_parse_diagnostics(result.diagnostics)
+    }
+  })
+}
+
+// 转换结果的快照：输出的代码及诊断信息，用于快照测试固定转换结果，如：
+// insta::assert_snapshot!(transform_snapshot(jsx, css, "Harmony"));
+pub fn transform_snapshot(component: &str, styles: &str, platform_string: &str) -> String {
+  let result = Transformer::new(component.to_string(), vec![styles.to_string()])
+    .platform(get_platform(platform_string))
+    .transform();
+  let mut snapshot = result.code;
+  for diagnostic in result.diagnostics {
+    snapshot.push_str(&format!("// [{}] {}\n", diagnostic.property, diagnostic.message));
+  }
+  snapshot
+}
+
+#[napi(object)]
+pub struct SessionArtifact {
+  // 组件文件路径
+  pub path: String,
+  pub result: ParseResult
+}
+
+// 增量转换会话，供开发服务器在文件变更时调用，仅返回需要重新输出的组件
+#[napi(js_name = "Session")]
+pub struct ParseSession {
+  session: Session
+}
+
+#[napi]
+impl ParseSession {
+  #[napi(constructor)]
+  pub fn new(options: ParseOptions) -> Self {
+    let session = Session::new(get_platform(&options.platform_string))
+      .options(get_transform_options(&options))
+      .entry(options.is_entry)
+      .syntax(get_syntax(options.syntax.as_deref()))
+      .stylesheet(options.stylesheet.unwrap_or(false))
+      .arkui(options.arkui.unwrap_or(false))
+      .dts(options.dts.unwrap_or(false))
+      .match_report(options.match_report.unwrap_or(false))
+      .ir(options.ir.unwrap_or(false));
+    ParseSession { session }
+  }
+
+  // 登记组件及其依赖的样式文件路径
+  #[napi]
+  pub fn add_component(&mut self, path: String, component: String, style_paths: Vec<String>) -> Vec<SessionArtifact> {
+    get_session_artifacts(self.session.add_component(&path, component, style_paths))
+  }
+
+  // 登记应用入口及其样式文件路径，其样式为各页面共享的全局样式
+  #[napi]
+  pub fn add_app(&mut self, path: String, component: String, style_paths: Vec<String>) -> Vec<SessionArtifact> {
+    get_session_artifacts(self.session.add_app(&path, component, style_paths))
+  }
+
+  // 登记页面及其自身的样式文件路径，转换时全局样式在页面样式之前合并
+  #[napi]
+  pub fn add_page(&mut self, path: String, component: String, style_paths: Vec<String>) -> Vec<SessionArtifact> {
+    get_session_artifacts(self.session.add_page(&path, component, style_paths))
+  }
+
+  #[napi]
+  pub fn remove_component(&mut self, path: String) -> Vec<SessionArtifact> {
+    get_session_artifacts(self.session.remove_component(&path))
+  }
+
+  #[napi]
+  pub fn jsx_changed(&mut self, path: String, new_content: String) -> Vec<SessionArtifact> {
+    get_session_artifacts(self.session.jsx_changed(&path, new_content))
+  }
+
+  #[napi]
+  pub fn css_changed(&mut self, path: String, new_content: String) -> Vec<SessionArtifact> {
+    get_session_artifacts(self.session.css_changed(&path, new_content))
+  }
+
+  #[napi]
+  pub fn css_removed(&mut self, path: String) -> Vec<SessionArtifact> {
+    get_session_artifacts(self.session.css_removed(&path))
+  }
+}
+
+fn get_session_artifacts(artifacts: Vec<Artifact>) -> Vec<SessionArtifact> {
+  artifacts.into_iter().map(|artifact| SessionArtifact {
+    path: artifact.path,
+    result: get_parse_result(artifact.result)
+  }).collect()
+}
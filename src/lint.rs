@@ -0,0 +1,125 @@
+use std::convert::Infallible;
+
+use lightningcss::{
+  declaration::DeclarationBlock,
+  properties::{position::Position, Property},
+  rules::{CssRule, Location},
+  selector::{Component, PseudoElement, Selector},
+  stylesheet::{PrinterOptions, StyleSheet},
+  traits::ToCss,
+  visit_types,
+  visitor::{Visit, VisitTypes, Visitor},
+};
+
+use crate::{
+  diagnostics::{push_diagnostic, push_lint_diagnostic, DiagnosticSeverity},
+  options::{with_transform_options, LintSeverity},
+  style_parser::format_source_position,
+  style_propetries::unit::Platform,
+  utils::normalize_style_key,
+};
+
+// 样式检查规则，检查目标平台上无法生效的写法
+// no-float: float 布局
+// no-position-fixed: React Native 下的 position: fixed
+// no-display-inline: display: inline、inline-block
+// unsupported-selector: id、属性、通配符选择器及 ::before、::after 以外的伪元素
+// no-text-style-on-view: View 等非文本组件上的文本属性，由 inherit::lint_text_styles 按组件树检查
+pub const LINT_RULES: [&str; 5] = ["no-float", "no-position-fixed", "no-display-inline", "unsupported-selector", "no-text-style-on-view"];
+
+// 默认关闭的规则，容器上设置 color、font-size 等文本属性的写法很常见，需显式配置后检查
+const DEFAULT_OFF_RULES: [&str; 1] = ["no-text-style-on-view"];
+
+// 规则配置的级别，未配置的规则按 warning 检查，关闭时返回 None
+pub fn get_lint_severity(rule: &str) -> Option<DiagnosticSeverity> {
+  with_transform_options(|options| match options.lint_rules.get(rule) {
+    Some(LintSeverity::Off) => None,
+    Some(LintSeverity::Error) => Some(DiagnosticSeverity::Error),
+    Some(LintSeverity::Warning) => Some(DiagnosticSeverity::Warning),
+    None if DEFAULT_OFF_RULES.contains(&rule) => None,
+    None => Some(DiagnosticSeverity::Warning)
+  })
+}
+
+// 按配置的规则级别检查样式表，结果输出为诊断信息
+pub fn lint_stylesheet(stylesheet: &mut StyleSheet, style_starts: &[u32], platform: &Platform) {
+  with_transform_options(|options| {
+    options.lint_rules.keys().filter(|rule| !LINT_RULES.contains(&rule.as_str())).for_each(|rule| {
+      push_diagnostic("lint", format!("unknown lint rule {}, available rules are {}", rule, LINT_RULES.join(", ")));
+    });
+  });
+  let severities = LINT_RULES.map(get_lint_severity);
+  let mut visitor = LintVisitor { style_starts, platform, severities };
+  stylesheet.visit(&mut visitor).unwrap();
+}
+
+struct LintVisitor<'a> {
+  style_starts: &'a [u32],
+  platform: &'a Platform,
+  // 与 LINT_RULES 一一对应，None 为关闭
+  severities: [Option<DiagnosticSeverity>; LINT_RULES.len()],
+}
+
+impl<'a, 'i> Visitor<'i> for LintVisitor<'a> {
+  type Error = Infallible;
+  const TYPES: VisitTypes = visit_types!(RULES);
+
+  fn visit_rule(&mut self, rule: &mut CssRule<'i>) -> Result<(), Self::Error> {
+    if let CssRule::Style(style) = rule {
+      style.selectors.0.iter().for_each(|selector| self.lint_selector(selector, &style.loc));
+      self.lint_declarations(&style.declarations, &style.loc);
+    }
+    // 嵌套规则及 @media、@supports 等规则中的样式
+    rule.visit_children(self)
+  }
+}
+
+impl<'a> LintVisitor<'a> {
+  fn report(&self, rule: &str, property: &str, message: String, loc: &Location) {
+    let Some(index) = LINT_RULES.iter().position(|name| *name == rule) else {
+      return
+    };
+    if let Some(severity) = &self.severities[index] {
+      push_lint_diagnostic(
+        rule,
+        severity.clone(),
+        property,
+        format!("{} at {} ({})", message, format_source_position(loc.line, loc.column, self.style_starts), rule)
+      );
+    }
+  }
+
+  fn lint_declarations(&self, declarations: &DeclarationBlock, loc: &Location) {
+    declarations.declarations.iter().chain(declarations.important_declarations.iter()).for_each(|property| {
+      let name = property.property_id().name().to_string();
+      let value = property.value_to_css_string(PrinterOptions::default()).unwrap_or_default();
+      if name == "float" {
+        self.report("no-float", "float", format!("float: {} is not supported on {:?}, use flex layout instead", value, self.platform), loc);
+      }
+      match property {
+        Property::Position(Position::Fixed) if *self.platform == Platform::ReactNative => {
+          self.report("no-position-fixed", "position", "position: fixed is not supported on ReactNative, use absolute positioning in a root view instead".to_string(), loc);
+        },
+        Property::Display(_) if value == "inline" || value == "inline-block" => {
+          self.report("no-display-inline", &normalize_style_key(&name), format!("display: {} is not supported on {:?}, elements are laid out as flex items", value, self.platform), loc);
+        },
+        _ => {}
+      }
+    });
+  }
+
+  fn lint_selector(&self, selector: &Selector, loc: &Location) {
+    let unsupported = selector.iter_raw_match_order().find_map(|component| match component {
+      Component::ID(_) => Some("id selectors"),
+      Component::AttributeInNoNamespaceExists { .. } | Component::AttributeInNoNamespace { .. } | Component::AttributeOther(_) => Some("attribute selectors"),
+      Component::ExplicitUniversalType => Some("universal selectors"),
+      Component::PseudoElement(PseudoElement::Before | PseudoElement::After) => None,
+      Component::PseudoElement(_) => Some("pseudo-elements other than ::before and ::after"),
+      _ => None
+    });
+    if let Some(unsupported) = unsupported {
+      let selector_str = selector.to_css_string(PrinterOptions::default()).unwrap_or_default();
+      self.report("unsupported-selector", "selector", format!("{} is not supported: {} can not be matched on {:?}", selector_str, unsupported, self.platform), loc);
+    }
+  }
+}
@@ -0,0 +1,125 @@
+use lightningcss::{properties::Property, rules::Location, stylesheet::PrinterOptions, traits::ToCss};
+
+// 规则中各声明的源码位置，lightningcss 只记录规则的位置，声明的位置从源码中查找
+pub struct DeclarationLocator {
+  // (中划线命名的属性名, 位置, 是否已匹配)，按声明顺序排列
+  locations: Vec<(String, Location, bool)>,
+}
+
+impl DeclarationLocator {
+  // css: 拼接后的样式源码，loc: 规则的位置，depth: 声明所在的块的层级，普通规则为 1，@keyframes 中的帧为 2
+  pub fn new(css: &str, loc: &Location, depth: usize) -> Self {
+    DeclarationLocator {
+      locations: find_declarations(css, loc, depth).into_iter().map(|(name, loc)| (name, loc, false)).collect()
+    }
+  }
+
+  // 声明的位置，同名的声明按顺序依次匹配，源码中找不到时返回 None
+  pub fn locate(&mut self, property: &Property) -> Option<Location> {
+    let name = property.property_id().to_css_string(PrinterOptions::default()).ok()?;
+    let (_, loc, used) = self.locations.iter_mut().find(|(other, _, used)| !*used && other.eq_ignore_ascii_case(&name))?;
+    *used = true;
+    Some(*loc)
+  }
+}
+
+// 行号从 0 开始、列号从 1 开始的位置在源码中的字节偏移
+fn get_offset(css: &str, loc: &Location) -> Option<usize> {
+  let mut offset = 0;
+  for _ in 0..loc.line {
+    offset += css[offset..].find('\n')? + 1;
+  }
+  let line = &css[offset..];
+  let column = line.char_indices().nth(loc.column.saturating_sub(1) as usize).map_or(line.len(), |(index, _)| index);
+  Some(offset + column)
+}
+
+// 从规则的位置开始扫描规则的块，收集第 depth 层块中的声明名称及位置，跳过注释、字符串及括号中的内容
+fn find_declarations(css: &str, loc: &Location, depth: usize) -> Vec<(String, Location)> {
+  let mut declarations = vec![];
+  let Some(start) = get_offset(css, loc) else {
+    return declarations
+  };
+  let (mut line, mut column) = (loc.line, loc.column);
+  let mut chars = css[start..].chars().peekable();
+  let mut block_depth = 0;
+  let mut paren_depth = 0;
+  let mut quote = None;
+  let mut in_comment = false;
+  // 是否在等待声明的第一个字符，及正在收集的声明名称
+  let mut expect_name = false;
+  let mut name: Option<(String, Location)> = None;
+  while let Some(char) = chars.next() {
+    let current = Location { source_index: loc.source_index, line, column };
+    match char {
+      '\n' => {
+        line += 1;
+        column = 1;
+      },
+      _ => column += 1
+    }
+    if in_comment {
+      if char == '*' && chars.peek() == Some(&'/') {
+        chars.next();
+        column += 1;
+        in_comment = false;
+      }
+      continue
+    }
+    if let Some(quote_char) = quote {
+      if char == '\\' {
+        chars.next();
+        column += 1;
+      } else if char == quote_char {
+        quote = None;
+      }
+      continue
+    }
+    match char {
+      '/' if chars.peek() == Some(&'*') => {
+        chars.next();
+        column += 1;
+        in_comment = true;
+      },
+      '"' | '\'' => {
+        quote = Some(char);
+        expect_name = false;
+      },
+      '(' => paren_depth += 1,
+      ')' => paren_depth -= 1,
+      '{' if paren_depth == 0 => {
+        block_depth += 1;
+        name = None;
+        expect_name = block_depth == depth;
+      },
+      '}' if paren_depth == 0 => {
+        // 规则的位置不在块之前时（如嵌套规则），扫描到块的结尾为止
+        if block_depth <= 1 {
+          break
+        }
+        block_depth -= 1;
+        name = None;
+        expect_name = block_depth == depth;
+      },
+      ';' if paren_depth == 0 && block_depth == depth => {
+        name = None;
+        expect_name = true;
+      },
+      ':' if block_depth == depth => {
+        if let Some((name, loc)) = name.take() {
+          declarations.push((name.trim().to_string(), loc));
+        }
+      },
+      _ if expect_name && !char.is_whitespace() => {
+        expect_name = false;
+        name = Some((char.to_string(), current));
+      },
+      _ => {
+        if let Some((name, _)) = &mut name {
+          name.push(char);
+        }
+      }
+    }
+  }
+  declarations
+}
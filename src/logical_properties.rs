@@ -0,0 +1,289 @@
+use lightningcss::{
+  properties::{Property, border::{BorderSideWidth, GenericBorder, LineStyle}, text::TextAlign},
+  values::{color::CssColor, length::{LengthPercentage, LengthPercentageOrAuto}, size::Size2D},
+  vendor_prefix::VendorPrefix
+};
+use swc_core::atoms::Atom;
+
+use crate::{interner::intern, options::{with_transform_options, WritingDirection}};
+
+#[derive(Debug, Clone, Copy)]
+enum Side {
+  Top,
+  Right,
+  Bottom,
+  Left
+}
+
+fn side_name(side: Side) -> &'static str {
+  match side {
+    Side::Top => "Top",
+    Side::Right => "Right",
+    Side::Bottom => "Bottom",
+    Side::Left => "Left",
+  }
+}
+
+fn margin_property<'i>(side: Side, value: &LengthPercentageOrAuto) -> (Atom, Property<'i>) {
+  let value = value.clone();
+  (intern(&format!("margin{}", side_name(side))), match side {
+    Side::Top => Property::MarginTop(value),
+    Side::Right => Property::MarginRight(value),
+    Side::Bottom => Property::MarginBottom(value),
+    Side::Left => Property::MarginLeft(value),
+  })
+}
+
+fn padding_property<'i>(side: Side, value: &LengthPercentageOrAuto) -> (Atom, Property<'i>) {
+  let value = value.clone();
+  (intern(&format!("padding{}", side_name(side))), match side {
+    Side::Top => Property::PaddingTop(value),
+    Side::Right => Property::PaddingRight(value),
+    Side::Bottom => Property::PaddingBottom(value),
+    Side::Left => Property::PaddingLeft(value),
+  })
+}
+
+fn inset_property<'i>(side: Side, value: &LengthPercentageOrAuto) -> (Atom, Property<'i>) {
+  let value = value.clone();
+  (intern(&side_name(side).to_lowercase()), match side {
+    Side::Top => Property::Top(value),
+    Side::Right => Property::Right(value),
+    Side::Bottom => Property::Bottom(value),
+    Side::Left => Property::Left(value),
+  })
+}
+
+fn border_width_property<'i>(side: Side, value: &BorderSideWidth) -> (Atom, Property<'i>) {
+  let value = value.clone();
+  (intern(&format!("border{}Width", side_name(side))), match side {
+    Side::Top => Property::BorderTopWidth(value),
+    Side::Right => Property::BorderRightWidth(value),
+    Side::Bottom => Property::BorderBottomWidth(value),
+    Side::Left => Property::BorderLeftWidth(value),
+  })
+}
+
+fn border_style_property<'i>(side: Side, value: &LineStyle) -> (Atom, Property<'i>) {
+  let value = *value;
+  (intern(&format!("border{}Style", side_name(side))), match side {
+    Side::Top => Property::BorderTopStyle(value),
+    Side::Right => Property::BorderRightStyle(value),
+    Side::Bottom => Property::BorderBottomStyle(value),
+    Side::Left => Property::BorderLeftStyle(value),
+  })
+}
+
+fn border_color_property<'i>(side: Side, value: &CssColor) -> (Atom, Property<'i>) {
+  let value = value.clone();
+  (intern(&format!("border{}Color", side_name(side))), match side {
+    Side::Top => Property::BorderTopColor(value),
+    Side::Right => Property::BorderRightColor(value),
+    Side::Bottom => Property::BorderBottomColor(value),
+    Side::Left => Property::BorderLeftColor(value),
+  })
+}
+
+fn border_property<'i, const P: u8>(side: Side, value: &GenericBorder<LineStyle, P>) -> (Atom, Property<'i>) {
+  let width = value.width.clone();
+  let style = value.style;
+  let color = value.color.clone();
+  (intern(&format!("border{}", side_name(side))), match side {
+    Side::Top => Property::BorderTop(GenericBorder { width, style, color }),
+    Side::Right => Property::BorderRight(GenericBorder { width, style, color }),
+    Side::Bottom => Property::BorderBottom(GenericBorder { width, style, color }),
+    Side::Left => Property::BorderLeft(GenericBorder { width, style, color }),
+  })
+}
+
+// 圆角按 (block 边, inline 边) 定位到物理角
+fn border_radius_property<'i>(block: Side, inline: Side, value: &Size2D<LengthPercentage>) -> (Atom, Property<'i>) {
+  let value = value.clone();
+  (intern(&format!("border{}{}Radius", side_name(block), side_name(inline))), match (block, inline) {
+    (Side::Top, Side::Left) => Property::BorderTopLeftRadius(value, VendorPrefix::None),
+    (Side::Top, _) => Property::BorderTopRightRadius(value, VendorPrefix::None),
+    (_, Side::Left) => Property::BorderBottomLeftRadius(value, VendorPrefix::None),
+    _ => Property::BorderBottomRightRadius(value, VendorPrefix::None),
+  })
+}
+
+// 物理属性左右镜像，如 margin-left => margin-right、text-align: left => right，用于 ltr 编写的样式在 rtl 下复用
+fn mirror_property<'i>(id: &Atom, property: &Property<'i>) -> (Atom, Property<'i>) {
+  match property {
+    Property::MarginLeft(value) => margin_property(Side::Right, value),
+    Property::MarginRight(value) => margin_property(Side::Left, value),
+    Property::PaddingLeft(value) => padding_property(Side::Right, value),
+    Property::PaddingRight(value) => padding_property(Side::Left, value),
+    Property::Left(value) => inset_property(Side::Right, value),
+    Property::Right(value) => inset_property(Side::Left, value),
+    Property::BorderLeft(value) => border_property(Side::Right, value),
+    Property::BorderRight(value) => border_property(Side::Left, value),
+    Property::BorderLeftWidth(value) => border_width_property(Side::Right, value),
+    Property::BorderRightWidth(value) => border_width_property(Side::Left, value),
+    Property::BorderLeftStyle(value) => border_style_property(Side::Right, value),
+    Property::BorderRightStyle(value) => border_style_property(Side::Left, value),
+    Property::BorderLeftColor(value) => border_color_property(Side::Right, value),
+    Property::BorderRightColor(value) => border_color_property(Side::Left, value),
+    Property::BorderTopLeftRadius(value, _) => border_radius_property(Side::Top, Side::Right, value),
+    Property::BorderTopRightRadius(value, _) => border_radius_property(Side::Top, Side::Left, value),
+    Property::BorderBottomLeftRadius(value, _) => border_radius_property(Side::Bottom, Side::Right, value),
+    Property::BorderBottomRightRadius(value, _) => border_radius_property(Side::Bottom, Side::Left, value),
+    // 简写属性交换左右两个方向的值
+    Property::Margin(value) => {
+      let mut value = value.clone();
+      std::mem::swap(&mut value.left, &mut value.right);
+      (id.clone(), Property::Margin(value))
+    }
+    Property::Padding(value) => {
+      let mut value = value.clone();
+      std::mem::swap(&mut value.left, &mut value.right);
+      (id.clone(), Property::Padding(value))
+    }
+    Property::Inset(value) => {
+      let mut value = value.clone();
+      std::mem::swap(&mut value.left, &mut value.right);
+      (id.clone(), Property::Inset(value))
+    }
+    Property::BorderWidth(value) => {
+      let mut value = value.clone();
+      std::mem::swap(&mut value.left, &mut value.right);
+      (id.clone(), Property::BorderWidth(value))
+    }
+    Property::BorderStyle(value) => {
+      let mut value = value.clone();
+      std::mem::swap(&mut value.left, &mut value.right);
+      (id.clone(), Property::BorderStyle(value))
+    }
+    Property::BorderColor(value) => {
+      let mut value = value.clone();
+      std::mem::swap(&mut value.left, &mut value.right);
+      (id.clone(), Property::BorderColor(value))
+    }
+    Property::BorderRadius(value, prefix) => {
+      let mut value = value.clone();
+      std::mem::swap(&mut value.top_left, &mut value.top_right);
+      std::mem::swap(&mut value.bottom_left, &mut value.bottom_right);
+      (id.clone(), Property::BorderRadius(value, *prefix))
+    }
+    Property::TextAlign(TextAlign::Left) => (id.clone(), Property::TextAlign(TextAlign::Right)),
+    Property::TextAlign(TextAlign::Right) => (id.clone(), Property::TextAlign(TextAlign::Left)),
+    _ => (id.clone(), property.clone())
+  }
+}
+
+// 逻辑属性转换为物理属性，如 margin-inline-start => margin-left
+// 按横向书写模式处理：block 方向对应 top/bottom，inline 方向对应 left/right，rtl 时 inline 方向左右互换
+// rtl 且开启 mirror_rtl 时，物理属性同样左右镜像
+pub fn normalize_logical_properties<'i>(properties: &[(Atom, Property<'i>)]) -> Vec<(Atom, Property<'i>)> {
+  let (is_rtl, mirror_rtl) = with_transform_options(|options| (options.direction == WritingDirection::Rtl, options.mirror_rtl));
+  let (inline_start, inline_end) = if is_rtl { (Side::Right, Side::Left) } else { (Side::Left, Side::Right) };
+
+  let mut final_properties = vec![];
+  for (id, property) in properties.iter() {
+    match property {
+      // 外边距
+      Property::MarginBlockStart(value) => final_properties.push(margin_property(Side::Top, value)),
+      Property::MarginBlockEnd(value) => final_properties.push(margin_property(Side::Bottom, value)),
+      Property::MarginInlineStart(value) => final_properties.push(margin_property(inline_start, value)),
+      Property::MarginInlineEnd(value) => final_properties.push(margin_property(inline_end, value)),
+      Property::MarginBlock(value) => {
+        final_properties.push(margin_property(Side::Top, &value.block_start));
+        final_properties.push(margin_property(Side::Bottom, &value.block_end));
+      }
+      Property::MarginInline(value) => {
+        final_properties.push(margin_property(inline_start, &value.inline_start));
+        final_properties.push(margin_property(inline_end, &value.inline_end));
+      }
+      // 内边距
+      Property::PaddingBlockStart(value) => final_properties.push(padding_property(Side::Top, value)),
+      Property::PaddingBlockEnd(value) => final_properties.push(padding_property(Side::Bottom, value)),
+      Property::PaddingInlineStart(value) => final_properties.push(padding_property(inline_start, value)),
+      Property::PaddingInlineEnd(value) => final_properties.push(padding_property(inline_end, value)),
+      Property::PaddingBlock(value) => {
+        final_properties.push(padding_property(Side::Top, &value.block_start));
+        final_properties.push(padding_property(Side::Bottom, &value.block_end));
+      }
+      Property::PaddingInline(value) => {
+        final_properties.push(padding_property(inline_start, &value.inline_start));
+        final_properties.push(padding_property(inline_end, &value.inline_end));
+      }
+      // 定位
+      Property::InsetBlockStart(value) => final_properties.push(inset_property(Side::Top, value)),
+      Property::InsetBlockEnd(value) => final_properties.push(inset_property(Side::Bottom, value)),
+      Property::InsetInlineStart(value) => final_properties.push(inset_property(inline_start, value)),
+      Property::InsetInlineEnd(value) => final_properties.push(inset_property(inline_end, value)),
+      Property::InsetBlock(value) => {
+        final_properties.push(inset_property(Side::Top, &value.block_start));
+        final_properties.push(inset_property(Side::Bottom, &value.block_end));
+      }
+      Property::InsetInline(value) => {
+        final_properties.push(inset_property(inline_start, &value.inline_start));
+        final_properties.push(inset_property(inline_end, &value.inline_end));
+      }
+      // 尺寸
+      Property::InlineSize(value) => final_properties.push((intern("width"), Property::Width(value.clone()))),
+      Property::BlockSize(value) => final_properties.push((intern("height"), Property::Height(value.clone()))),
+      Property::MinInlineSize(value) => final_properties.push((intern("minWidth"), Property::MinWidth(value.clone()))),
+      Property::MinBlockSize(value) => final_properties.push((intern("minHeight"), Property::MinHeight(value.clone()))),
+      Property::MaxInlineSize(value) => final_properties.push((intern("maxWidth"), Property::MaxWidth(value.clone()))),
+      Property::MaxBlockSize(value) => final_properties.push((intern("maxHeight"), Property::MaxHeight(value.clone()))),
+      // 边框
+      Property::BorderBlockStart(value) => final_properties.push(border_property(Side::Top, value)),
+      Property::BorderBlockEnd(value) => final_properties.push(border_property(Side::Bottom, value)),
+      Property::BorderInlineStart(value) => final_properties.push(border_property(inline_start, value)),
+      Property::BorderInlineEnd(value) => final_properties.push(border_property(inline_end, value)),
+      Property::BorderBlock(value) => {
+        final_properties.push(border_property(Side::Top, value));
+        final_properties.push(border_property(Side::Bottom, value));
+      }
+      Property::BorderInline(value) => {
+        final_properties.push(border_property(inline_start, value));
+        final_properties.push(border_property(inline_end, value));
+      }
+      Property::BorderBlockStartWidth(value) => final_properties.push(border_width_property(Side::Top, value)),
+      Property::BorderBlockEndWidth(value) => final_properties.push(border_width_property(Side::Bottom, value)),
+      Property::BorderInlineStartWidth(value) => final_properties.push(border_width_property(inline_start, value)),
+      Property::BorderInlineEndWidth(value) => final_properties.push(border_width_property(inline_end, value)),
+      Property::BorderBlockWidth(value) => {
+        final_properties.push(border_width_property(Side::Top, &value.start));
+        final_properties.push(border_width_property(Side::Bottom, &value.end));
+      }
+      Property::BorderInlineWidth(value) => {
+        final_properties.push(border_width_property(inline_start, &value.start));
+        final_properties.push(border_width_property(inline_end, &value.end));
+      }
+      Property::BorderBlockStartStyle(value) => final_properties.push(border_style_property(Side::Top, value)),
+      Property::BorderBlockEndStyle(value) => final_properties.push(border_style_property(Side::Bottom, value)),
+      Property::BorderInlineStartStyle(value) => final_properties.push(border_style_property(inline_start, value)),
+      Property::BorderInlineEndStyle(value) => final_properties.push(border_style_property(inline_end, value)),
+      Property::BorderBlockStyle(value) => {
+        final_properties.push(border_style_property(Side::Top, &value.start));
+        final_properties.push(border_style_property(Side::Bottom, &value.end));
+      }
+      Property::BorderInlineStyle(value) => {
+        final_properties.push(border_style_property(inline_start, &value.start));
+        final_properties.push(border_style_property(inline_end, &value.end));
+      }
+      Property::BorderBlockStartColor(value) => final_properties.push(border_color_property(Side::Top, value)),
+      Property::BorderBlockEndColor(value) => final_properties.push(border_color_property(Side::Bottom, value)),
+      Property::BorderInlineStartColor(value) => final_properties.push(border_color_property(inline_start, value)),
+      Property::BorderInlineEndColor(value) => final_properties.push(border_color_property(inline_end, value)),
+      Property::BorderBlockColor(value) => {
+        final_properties.push(border_color_property(Side::Top, &value.start));
+        final_properties.push(border_color_property(Side::Bottom, &value.end));
+      }
+      Property::BorderInlineColor(value) => {
+        final_properties.push(border_color_property(inline_start, &value.start));
+        final_properties.push(border_color_property(inline_end, &value.end));
+      }
+      // 圆角
+      Property::BorderStartStartRadius(value) => final_properties.push(border_radius_property(Side::Top, inline_start, value)),
+      Property::BorderStartEndRadius(value) => final_properties.push(border_radius_property(Side::Top, inline_end, value)),
+      Property::BorderEndStartRadius(value) => final_properties.push(border_radius_property(Side::Bottom, inline_start, value)),
+      Property::BorderEndEndRadius(value) => final_properties.push(border_radius_property(Side::Bottom, inline_end, value)),
+      _ if is_rtl && mirror_rtl => final_properties.push(mirror_property(id, property)),
+      _ => final_properties.push((id.clone(), property.clone()))
+    }
+  }
+  final_properties
+}
@@ -1,12 +1,8 @@
-use std::{cell::RefCell, fs, rc::Rc};
-use style_parser::StyleParser;
+use std::{collections::HashMap, env, fs, path::{Path, PathBuf}, process};
+use serde::Serialize;
 use style_propetries::unit::Platform;
 
-use swc_core::{
-  ecma::codegen::{text_writer::JsWriter, Emitter},
-  common::{comments::SingleThreadedComments, sync::Lrc, SourceMap}
-};
-use crate::{document::JSXDocument, style_write::StyleWrite};
+use crate::{document::SourceSyntax, options::TransformOptions, strict::get_strict_error, style_parser::ClassUsage, transformer::{TransformResult, Transformer}};
 
 
 mod document;
@@ -18,62 +14,309 @@ mod constants;
 mod style_propetries;
 mod style_parser;
 mod parse_style_properties;
+mod options;
+mod diagnostics;
+mod logical_properties;
+mod current_color;
+mod shorthand;
+mod theme;
+mod supports;
+mod transformer;
+mod stylesheet;
+mod arkui;
+mod minify;
+mod session;
+mod interner;
+mod scope;
+mod important;
+mod inherit;
+mod hooks;
+mod tag;
+mod layer;
+mod container;
+mod declaration;
+mod report;
+mod trace;
+mod api_version;
+mod validate;
+mod stats;
+mod lint;
+mod strict;
+mod fallback;
+mod ir;
+mod hash;
+mod keyframes;
+mod location;
 
-// component: jsx的code string
-// styles: css的code string
-// platform_string: "ReactNative" | "Harmony"
+const USAGE: &str = "用法:
+  parse-css-to-stylesheet transform <file.jsx> --css <file.css> [--css <file.css>] [--platform harmony|rn] [--entry] [--syntax tsx|jsx] [--stylesheet] [--arkui] [--dts] [--match-report] [--ir] [--class-report] [--debug-report] [--strict] [--out <file>]
+  parse-css-to-stylesheet batch <glob> --out-dir <dir> [--css <file.css>] [--platform harmony|rn] [--syntax tsx|jsx] [--stylesheet] [--arkui] [--dts] [--match-report] [--ir] [--class-report] [--debug-report] [--strict]
 
+batch 模式下，每个组件文件使用同目录下同名的 .css 文件（如 index.jsx => index.css）以及 --css 指定的公共样式，
+转换结果写入 --out-dir，保留组件相对于 glob 中第一个通配符之前的目录的路径（如 src/pages/**/*.jsx 中的 src/pages），--out-dir 中的文件不参与转换，
+开启 --stylesheet、--arkui、--dts、--match-report 时，样式表 JSON、ArkUI @Styles 代码、TypeScript 类型声明、匹配报告分别写入输出文件同目录下的同名 .json、.ets、.d.ts、.report.json 文件，transform 未指定 --out 时输出到 stdout，
+开启 --ir 时，计算后的样式中间表示（各规则、各节点的样式及 @keyframes、暗色模式、@container 变体）写入同名的 .ir.json 文件，
+开启 --class-report 时，样式表中未被组件引用的类名、className 中没有对应样式的类名写入同名的 .classes.json 文件，可用于在 CI 中检查，
+开启 --debug-report 时，转换过程的统计信息（如字符串驻留的内存复用情况、类名与样式的对应情况）输出到 stderr，
+开启 --strict 时，存在目标平台不支持的声明或语法错误时转换失败";
 
-pub fn main() {
+// 命令行参数
+struct CliArgs {
+  command: String,
+  input: String,
+  css: Vec<String>,
+  platform: Platform,
+  is_entry: bool,
+  syntax: SourceSyntax,
+  stylesheet: bool,
+  arkui: bool,
+  dts: bool,
+  match_report: bool,
+  ir: bool,
+  class_report: bool,
+  debug_report: bool,
+  strict: bool,
+  out: Option<String>,
+  out_dir: Option<String>,
+}
+
+fn parse_args(args: Vec<String>) -> Result<CliArgs, String> {
+  let mut args = args.into_iter();
+  let command = args.next().ok_or("缺少子命令")?;
+  let input = args.next().ok_or("缺少输入文件")?;
+  let mut cli_args = CliArgs {
+    command,
+    input,
+    css: vec![],
+    platform: Platform::Harmony,
+    is_entry: false,
+    syntax: SourceSyntax::Tsx,
+    stylesheet: false,
+    arkui: false,
+    dts: false,
+    match_report: false,
+    ir: false,
+    class_report: false,
+    debug_report: false,
+    strict: false,
+    out: None,
+    out_dir: None,
+  };
+  while let Some(arg) = args.next() {
+    match arg.as_str() {
+      "--css" => cli_args.css.push(args.next().ok_or("--css 缺少文件路径")?),
+      "--platform" => {
+        cli_args.platform = match args.next().ok_or("--platform 缺少平台")?.to_lowercase().as_str() {
+          "harmony" => Platform::Harmony,
+          "rn" | "reactnative" => Platform::ReactNative,
+          platform => return Err(format!("不支持的平台: {}", platform))
+        }
+      },
+      "--entry" => cli_args.is_entry = true,
+      "--syntax" => {
+        cli_args.syntax = match args.next().ok_or("--syntax 缺少语法")?.as_str() {
+          "jsx" => SourceSyntax::Jsx,
+          _ => SourceSyntax::Tsx
+        }
+      },
+      "--stylesheet" => cli_args.stylesheet = true,
+      "--arkui" => cli_args.arkui = true,
+      "--dts" => cli_args.dts = true,
+      "--match-report" => cli_args.match_report = true,
+      "--ir" => cli_args.ir = true,
+      "--class-report" => cli_args.class_report = true,
+      "--debug-report" => cli_args.debug_report = true,
+      "--strict" => cli_args.strict = true,
+      "--out" => cli_args.out = Some(args.next().ok_or("--out 缺少文件路径")?),
+      "--out-dir" => cli_args.out_dir = Some(args.next().ok_or("--out-dir 缺少目录")?),
+      _ => return Err(format!("未知参数: {}", arg))
+    }
+  }
+  Ok(cli_args)
+}
 
-  let component = fs::read_to_string("__test__/fixure/pesudo.jsx").unwrap();
-  let css = fs::read_to_string("__test__/fixure/pesudo.scss").unwrap();
+fn read_file(path: &Path) -> Result<String, String> {
+  fs::read_to_string(path).map_err(|err| format!("读取 {} 失败: {}", path.display(), err))
+}
+
+fn write_file(path: &Path, content: &str) -> Result<(), String> {
+  if let Some(parent) = path.parent() {
+    fs::create_dir_all(parent).map_err(|err| format!("创建目录 {} 失败: {}", parent.display(), err))?;
+  }
+  fs::write(path, content).map_err(|err| format!("写入 {} 失败: {}", path.display(), err))
+}
 
-  let platform = Platform::Harmony;
-  let is_entry = false; // 是否是入口文件
-  let mut is_enable_nesting = true;
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ClassReport<'a> {
+  file: Option<String>,
+  // 样式表中未被组件引用的类名
+  unused_classes: &'a [String],
+  // className 中没有对应样式的类名
+  unmatched_classes: &'a [String],
+}
 
-  // 解析组件文件
-  let cm: Lrc<SourceMap> = Default::default();
-  let comments = SingleThreadedComments::default();
-  let mut document = JSXDocument::new();
-  document.parse(component, cm.clone(), &comments);
+// 生成类名的使用报告，用于在 CI 中检查未使用的样式及没有样式的类名
+fn generate_class_report(class_usage: &ClassUsage, file: Option<String>) -> String {
+  let report = ClassReport {
+    file,
+    unused_classes: &class_usage.unused,
+    unmatched_classes: &class_usage.unmatched,
+  };
+  serde_json::to_string_pretty(&report).unwrap()
+}
 
-  // 解析样式文件
-  let mut style_parser = StyleParser::new(&document, platform.clone(), is_entry.clone());
-  style_parser.parse(&css);
-  let style_data = style_parser.calc();
+// 转换单个组件文件，诊断信息输出到 stderr
+fn transform_file(path: &Path, styles: Vec<String>, cli_args: &CliArgs) -> Result<TransformResult, String> {
+  let component = read_file(path)?;
+  let result = Transformer::new(component, styles)
+    .platform(cli_args.platform.clone())
+    .entry(cli_args.is_entry)
+    .syntax(cli_args.syntax.clone())
+    .stylesheet(cli_args.stylesheet)
+    .arkui(cli_args.arkui)
+    .dts(cli_args.dts)
+    .match_report(cli_args.match_report)
+    .ir(cli_args.ir)
+    .filename(path.display().to_string())
+    .options(TransformOptions {
+      strict: cli_args.strict,
+      ..TransformOptions::default()
+    })
+    .transform();
+  result.font_faces.iter().for_each(|font_face| {
+    eprintln!("{}: @font-face {:?}", path.display(), font_face);
+  });
+  result.diagnostics.iter().for_each(|diagnostic| {
+    eprintln!("{}: {}: [{}] {}", path.display(), diagnostic.severity.as_str(), diagnostic.property, diagnostic.message);
+  });
+  if cli_args.debug_report {
+    eprintln!("{}: {}", path.display(), result.interner_stats);
+    eprintln!("{}: {}", path.display(), result.stats);
+    eprintln!("{}: 有样式的类名 {:?}，没有样式的类名 {:?}，未被引用的类名 {:?}", path.display(), result.class_usage.used, result.class_usage.unmatched, result.class_usage.unused);
+  }
+  if cli_args.strict {
+    if let Some(message) = get_strict_error(&result.diagnostics) {
+      return Err(format!("{}: {}", path.display(), message))
+    }
+  }
+  Ok(result)
+}
+
+// 写入转换结果，样式表 JSON、ArkUI 代码、类型声明、匹配报告、样式中间表示、类名报告分别写入同名的 .json、.ets、.d.ts、.report.json、.ir.json、.classes.json 文件
+fn write_output(out_path: &Path, result: &TransformResult, class_report: Option<String>) -> Result<(), String> {
+  write_file(out_path, &result.code)?;
+  if let Some(stylesheet) = &result.stylesheet {
+    write_file(&out_path.with_extension("json"), stylesheet)?;
+  }
+  if let Some(arkui) = &result.arkui {
+    write_file(&out_path.with_extension("ets"), arkui)?;
+  }
+  if let Some(dts) = &result.dts {
+    write_file(&out_path.with_extension("d.ts"), dts)?;
+  }
+  if let Some(match_report) = &result.match_report {
+    write_file(&out_path.with_extension("report.json"), match_report)?;
+  }
+  if let Some(ir) = &result.ir {
+    write_file(&out_path.with_extension("ir.json"), &ir.to_json())?;
+  }
+  if let Some(class_report) = &class_report {
+    write_file(&out_path.with_extension("classes.json"), class_report)?;
+  }
+  Ok(())
+}
 
-  // 判断计算的结果是否会含有嵌套选择器
-  if is_enable_nesting {
-    is_enable_nesting = style_data.has_nesting;
+fn run_transform(cli_args: &CliArgs) -> Result<(), String> {
+  let styles = cli_args.css.iter().map(|css| read_file(Path::new(css))).collect::<Result<Vec<String>, String>>()?;
+  let result = transform_file(Path::new(&cli_args.input), styles, cli_args)?;
+  let class_report = cli_args.class_report.then(|| generate_class_report(&result.class_usage, Some(cli_args.input.clone())));
+  match &cli_args.out {
+    Some(out) => write_output(Path::new(out), &result, class_report),
+    None => {
+      println!("{}", result.code);
+      if let Some(stylesheet) = &result.stylesheet {
+        println!("{}", stylesheet);
+      }
+      if let Some(arkui) = &result.arkui {
+        println!("{}", arkui);
+      }
+      if let Some(dts) = &result.dts {
+        println!("{}", dts);
+      }
+      if let Some(match_report) = &result.match_report {
+        println!("{}", match_report);
+      }
+      if let Some(ir) = &result.ir {
+        println!("{}", ir.to_json());
+      }
+      if let Some(class_report) = &class_report {
+        println!("{}", class_report);
+      }
+      Ok(())
+    }
   }
+}
 
-  let program = Rc::new(RefCell::new(document.program.as_ref().unwrap().clone()));
-  let jsx_record = Rc::new(RefCell::new(document.jsx_record.as_ref().unwrap().clone()));
-  let mut style_write = StyleWrite::new(
-    program.clone(),
-    jsx_record.clone(),
-    style_data.pesudo_style_record.clone(),
-    style_data.all_style.clone(),
-    is_enable_nesting,
-    is_entry
-  );
-  style_write.write(platform, document.taro_components.clone());
-
-  // ast 转代码
-  let mut buf = Vec::new();
-  {
-    let writer = Box::new(JsWriter::new(cm.clone(), "\n", &mut buf, None));
-    let mut emitter = Emitter {
-      cfg: Default::default(),
-      cm: cm.clone(),
-      wr: writer,
-      comments: Some(&comments),
+// glob 中第一个含通配符的路径片段之前的目录，如 src/pages/**/*.jsx => src/pages
+fn get_glob_base(pattern: &str) -> PathBuf {
+  Path::new(pattern)
+    .components()
+    .take_while(|component| !component.as_os_str().to_string_lossy().contains(['*', '?', '[', '{']))
+    .collect()
+}
+
+fn run_batch(cli_args: &CliArgs) -> Result<(), String> {
+  // 输出到组件同目录时，生成的文件会在下次执行时再次匹配，且可能覆盖 index.rn.jsx 等平台专属的组件文件
+  let out_dir = PathBuf::from(cli_args.out_dir.as_ref().ok_or("batch 缺少 --out-dir")?);
+  let shared_styles = cli_args.css.iter().map(|css| read_file(Path::new(css))).collect::<Result<Vec<String>, String>>()?;
+  let glob_base = get_glob_base(&cli_args.input);
+  let canonical_out_dir = fs::canonicalize(&out_dir).ok();
+  let paths = glob::glob(&cli_args.input).map_err(|err| format!("glob 无效: {}", err))?;
+  let mut out_paths = HashMap::new();
+  let mut count = 0;
+  for path in paths {
+    let path = path.map_err(|err| err.to_string())?;
+    // 跳过上次输出到 --out-dir 中的文件
+    if canonical_out_dir.as_ref().is_some_and(|out_dir| fs::canonicalize(&path).is_ok_and(|path| path.starts_with(out_dir))) {
+      continue
+    }
+    // 保留相对于 glob 目录的路径，避免 pages/a/index.jsx 与 pages/b/index.jsx 写入同一个文件
+    let relative_path = match path.strip_prefix(&glob_base) {
+      Ok(relative_path) if !relative_path.as_os_str().is_empty() => relative_path.to_path_buf(),
+      _ => PathBuf::from(path.file_name().unwrap())
     };
-    emitter.emit_program(&program.borrow()).unwrap();
+    let out_path = out_dir.join(relative_path);
+    if let Some(other) = out_paths.insert(out_path.clone(), path.clone()) {
+      return Err(format!("{} 与 {} 的输出路径 {} 冲突", other.display(), path.display(), out_path.display()))
+    }
+    // 组件同名的样式文件，如 index.jsx => index.css
+    let mut styles = shared_styles.clone();
+    let css_path = path.with_extension("css");
+    if css_path.exists() {
+      styles.push(read_file(&css_path)?);
+    }
+    let result = transform_file(&path, styles, cli_args)?;
+    let class_report = cli_args.class_report.then(|| generate_class_report(&result.class_usage, Some(path.display().to_string())));
+    write_output(&out_path, &result, class_report)?;
+    eprintln!("{} => {}", path.display(), out_path.display());
+    count += 1;
+  }
+  eprintln!("共转换 {} 个文件", count);
+  Ok(())
+}
+
+pub fn main() {
+  let args = env::args().skip(1).collect::<Vec<String>>();
+  let result = parse_args(args).and_then(|cli_args| {
+    match cli_args.command.as_str() {
+      "transform" => run_transform(&cli_args),
+      "batch" => run_batch(&cli_args),
+      command => Err(format!("未知子命令: {}", command))
+    }
+  });
+  if let Err(err) = result {
+    eprintln!("{}\n\n{}", err, USAGE);
+    process::exit(1);
   }
-  let code = String::from_utf8(buf).unwrap().replace("\r\n", "\n");
-  
-  println!("{}", code);
-}
\ No newline at end of file
+}
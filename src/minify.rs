@@ -0,0 +1,126 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use swc_core::{
+  common::{EqIgnoreSpan, DUMMY_SP},
+  ecma::ast::*,
+};
+
+use crate::utils::TSelector;
+
+// 可合并为简写的属性组，(简写属性, [上, 右, 下, 左])
+const MERGEABLE_GROUPS: [(&str, [&str; 4]); 2] = [
+  ("margin", ["marginTop", "marginRight", "marginBottom", "marginLeft"]),
+  ("padding", ["paddingTop", "paddingRight", "paddingBottom", "paddingLeft"]),
+];
+
+// 与平台默认值相同的属性值，(属性, 默认值)
+const DEFAULT_VALUES: [(&str, f64); 2] = [
+  ("opacity", 1.0),
+  ("flexGrow", 0.0),
+];
+
+pub fn get_prop_key(prop: &PropOrSpread) -> Option<String> {
+  match prop {
+    PropOrSpread::Prop(prop) => match &**prop {
+      Prop::KeyValue(KeyValueProp { key: PropName::Ident(ident), .. }) => Some(ident.sym.to_string()),
+      _ => None
+    },
+    _ => None
+  }
+}
+
+fn get_prop_value(prop: &PropOrSpread) -> Option<&Expr> {
+  match prop {
+    PropOrSpread::Prop(prop) => match &**prop {
+      Prop::KeyValue(KeyValueProp { value, .. }) => Some(value),
+      _ => None
+    },
+    _ => None
+  }
+}
+
+// 多个选择器中出现的属性，类样式在运行时按 className 依次合并，这些属性的写法会影响覆盖结果，不能优化
+fn get_contested_keys<'a>(props_list: impl Iterator<Item = &'a Vec<PropOrSpread>>) -> Vec<String> {
+  let mut key_counts: HashMap<String, usize> = HashMap::new();
+  props_list.for_each(|props| {
+    let keys = props.iter().filter_map(get_prop_key).map(|key| {
+      // 简写与其展开的属性视为同一组
+      MERGEABLE_GROUPS
+        .iter()
+        .find(|(_, longhands)| longhands.contains(&key.as_str()))
+        .map(|(shorthand, _)| shorthand.to_string())
+        .unwrap_or(key)
+    }).collect::<HashSet<String>>();
+    keys.into_iter().for_each(|key| {
+      *key_counts.entry(key).or_insert(0) += 1;
+    });
+  });
+  key_counts.into_iter().filter(|(_, count)| *count > 1).map(|(key, _)| key).collect()
+}
+
+// 上下左右的值相同时合并为简写，如 marginTop: 10, marginRight: 10, ... => margin: 10
+fn merge_longhands(props: &mut Vec<PropOrSpread>, contested_keys: &[String]) {
+  for (shorthand, longhands) in MERGEABLE_GROUPS.iter() {
+    if contested_keys.iter().any(|key| key == shorthand) {
+      continue
+    }
+    let values = longhands.iter().map(|longhand| {
+      props.iter().find(|prop| get_prop_key(prop).as_deref() == Some(*longhand)).and_then(get_prop_value)
+    }).collect::<Option<Vec<&Expr>>>();
+    let Some(values) = values else {
+      continue
+    };
+    if !values.iter().all(|value| (*value).eq_ignore_span(values[0])) {
+      continue
+    }
+    let value = values[0].clone();
+    let index = props.iter().position(|prop| get_prop_key(prop).is_some_and(|key| longhands.contains(&key.as_str()))).unwrap();
+    props.retain(|prop| !get_prop_key(prop).is_some_and(|key| longhands.contains(&key.as_str())));
+    props.insert(index, PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+      key: PropName::Ident(Ident::new((*shorthand).into(), DUMMY_SP)),
+      value: Box::new(value),
+    }))));
+  }
+}
+
+// 移除与默认值相同的属性，如 opacity: 1
+fn drop_default_values(props: &mut Vec<PropOrSpread>, contested_keys: &[String]) {
+  props.retain(|prop| {
+    let Some(key) = get_prop_key(prop) else {
+      return true
+    };
+    if contested_keys.contains(&key) {
+      return true
+    }
+    match (DEFAULT_VALUES.iter().find(|(name, _)| *name == key), get_prop_value(prop)) {
+      (Some((_, default_value)), Some(Expr::Lit(Lit::Num(num)))) => num.value != *default_value,
+      _ => true
+    }
+  });
+}
+
+// 属性按 key 排序，保证输出稳定，伪类等计算属性保持原有顺序排在最后
+fn sort_keys(props: &mut [PropOrSpread]) {
+  props.sort_by_key(|prop| match get_prop_key(prop) {
+    Some(key) => (0, key),
+    None => (1, String::new())
+  });
+}
+
+// 样式输出优化：合并简写、移除默认值、属性排序，减小生成代码的体积
+pub fn minify_style_entries(
+  style_entries: &mut BTreeMap<String, Vec<PropOrSpread>>,
+  nesting_style_entries: &mut BTreeMap<String, (Vec<TSelector>, Vec<PropOrSpread>)>,
+) {
+  let contested_keys = get_contested_keys(
+    style_entries.values().chain(nesting_style_entries.values().map(|(_, props)| props))
+  );
+  style_entries
+    .values_mut()
+    .chain(nesting_style_entries.values_mut().map(|(_, props)| props))
+    .for_each(|props| {
+      merge_longhands(props, &contested_keys);
+      drop_default_values(props, &contested_keys);
+      sort_keys(props);
+    });
+}
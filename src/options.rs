@@ -0,0 +1,239 @@
+use std::{cell::RefCell, collections::HashMap};
+
+use swc_core::ecma::ast::EsVersion;
+
+use crate::style_propetries::unit::Platform;
+
+// 书写方向，决定逻辑属性 inline 方向的 start/end 对应 left 还是 right
+#[derive(Debug, Clone, PartialEq)]
+pub enum WritingDirection {
+  Ltr,
+  Rtl
+}
+
+// 颜色的输出格式
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColorFormat {
+  // 保持最短写法，如 #fff、red => #FF0000、rgba(0, 0, 0, 0.5)
+  Auto,
+  // 不透明的颜色统一为 #RRGGBB，半透明的颜色为 rgba()
+  Hex,
+  // 统一为 rgb()、rgba()
+  Rgb,
+  // 统一为 8 位 hex，鸿蒙为 #AARRGGBB，RN 为 #RRGGBBAA
+  Hex8
+}
+
+// 长度数值的取整方式
+#[derive(Debug, Clone, PartialEq)]
+pub enum RoundingMode {
+  // 四舍五入
+  Round,
+  // 向下取整
+  Floor,
+  // 向上取整
+  Ceil
+}
+
+// 样式检查规则的级别
+#[derive(Debug, Clone, PartialEq)]
+pub enum LintSeverity {
+  // 关闭该规则
+  Off,
+  Warning,
+  Error
+}
+
+// 输出代码中保留的注释
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommentsMode {
+  // 保留所有注释
+  All,
+  // 仅保留版权注释，如 /*! ... */ 及包含 @license、@preserve 的注释
+  License,
+  // 移除所有注释
+  None
+}
+
+// 输出代码中生成的字符串使用的引号，组件代码中原有的字符串保持原样
+#[derive(Debug, Clone, PartialEq)]
+pub enum QuoteStyle {
+  Double,
+  Single
+}
+
+// 作用域类名中 hash 的算法，与 css-loader 的 localIdentHashFunction 对应
+#[derive(Debug, Clone, PartialEq)]
+pub enum HashAlgorithm {
+  // 32 位 FNV-1a，最多 8 位 hex
+  Fnv1a,
+  // 最多 16 位 hex
+  Xxhash64,
+  // 最多 40 位 hex
+  Sha1
+}
+
+// 全局样式（如 app.css）与页面样式同时匹配时的优先级
+#[derive(Debug, Clone, PartialEq)]
+pub enum StylePrecedence {
+  // 按 css 的层叠规则，先比较 specificity，再比较样式的顺序
+  Order,
+  // 页面样式优先，全局样式视为最先声明的 @layer 层级，与样式的顺序及 specificity 无关
+  Page,
+}
+
+// React Native 的目标系统，决定 box-shadow 等系统间存在差异的属性的输出
+#[derive(Debug, Clone, PartialEq)]
+pub enum RnOs {
+  // 输出 elevation 及 shadowColor
+  Android,
+  // 输出 shadowColor、shadowOffset、shadowOpacity、shadowRadius
+  Ios
+}
+
+// 样式转换配置，由 ParseOptions 解析而来，在一次 parse 过程中对所有属性转换生效
+#[derive(Debug, Clone)]
+pub struct TransformOptions {
+  // 1rem 对应的 px 值
+  pub root_font_size: f64,
+  // 1em 对应的 px 值，编译期无法得知元素的实际字号，默认与 root_font_size 一致
+  pub em_font_size: f64,
+  pub direction: WritingDirection,
+  // rtl 时是否将物理方向的属性左右镜像，如 margin-left => margin-right、text-align: left => right
+  pub mirror_rtl: bool,
+  // 字体映射表，将 web 字体名称映射为平台字体名称，如 { "PingFang SC": "HarmonyOS Sans" }
+  pub font_family_map: HashMap<String, String>,
+  // 是否将 :root 中的 css 变量提取为 theme 对象
+  pub extract_theme: bool,
+  // @supports 特性支持表，如 { "display: grid": false }，覆盖平台内置的特性表
+  pub supports_features: HashMap<String, bool>,
+  // 是否将无法转换的声明按原始字符串透传，而不是丢弃
+  pub passthrough_unrecognized: bool,
+  // React Native 下是否使用 StyleSheet.create 创建样式对象
+  pub rn_style_sheet_create: bool,
+  // 是否移除未被任何节点使用的类样式，并提示没有对应样式的 className
+  pub tree_shaking: bool,
+  // 是否对输出的样式进行优化：合并简写、移除默认值、属性排序
+  pub minify: bool,
+  // 是否开启作用域模式，类名添加组件的作用域前缀，避免样式泄漏到其他组件
+  pub scoped: bool,
+  // 作用域 id，未指定时根据组件代码生成
+  pub scope_id: Option<String>,
+  // 作用域类名的模板，如 "[name]_[local]_[hash:6]"，[name] 为 scope_path 的文件名（不含扩展名），[local] 为原始类名，
+  // [hash] 为 "scope_path\0原始类名" 的 hash，None 时为 "作用域 id_原始类名"
+  pub scope_name_template: Option<String>,
+  // 作用域类名及生成的作用域 id 中 hash 的算法
+  pub scope_hash_algorithm: HashAlgorithm,
+  // hash 保留的位数，模板中的 [hash:N] 优先
+  pub scope_hash_length: usize,
+  // 参与 hash 计算的样式文件路径，与 css-loader 中相对于 context 的资源路径一致时生成相同的类名，None 时使用作用域 id
+  pub scope_path: Option<String>,
+  // 需要由容器节点继承到 Text 组件的文本属性，如 ["color", "font-size"]，为空时不做继承
+  pub inherit_properties: Vec<String>,
+  // 组件与标签名的映射，如 { "View": "div", "Text": "span" }，用于标签选择器的匹配
+  pub tag_map: HashMap<String, String>,
+  // 除 @tarojs/components 外，导出 Taro 组件的模块，如 ["@/components/base"]
+  pub component_sources: Vec<String>,
+  // 颜色的输出格式，hsl()、hwb()、lab()、lch()、oklab()、oklch()、color() 等均转换为 sRGB 后输出
+  pub color_format: ColorFormat,
+  // 是否支持 @container 容器查询，容器尺寸可静态确定时在编译期求值，否则由运行时根据容器尺寸判断
+  pub container_queries: bool,
+  // 长度数值保留的小数位数，如 10.126px、0.5rem 换算的 8.004px，None 时不做取整
+  pub precision: Option<u32>,
+  // 长度数值的取整方式，precision 为 None 时不生效
+  pub rounding: RoundingMode,
+  // React Native 的目标系统，None 时输出与系统无关的通用属性
+  pub rn_os: Option<RnOs>,
+  // 鸿蒙的目标 API 版本，高版本才支持的属性会降级或丢弃，None 时不做检查
+  pub harmony_api_version: Option<u32>,
+  // 输出的样式属性名映射，如 { "animationTimeingFunction": "animationTimingFunction" }，用于适配不同的运行时
+  pub style_key_map: HashMap<String, String>,
+  // 样式检查规则的级别，key 为规则名，未配置的规则按 Warning 检查
+  pub lint_rules: HashMap<String, LintSeverity>,
+  // 值回退规则，key 为 "属性: 值"，value 为替换的声明，如 { "display: grid": "display: flex; flex-direction: column" }，优先于内置的回退规则
+  pub fallback_rules: HashMap<String, String>,
+  // 严格模式，转换后被丢弃的声明及语法错误输出为错误级别的诊断信息，存在错误时转换失败
+  pub strict: bool,
+  // 是否将类组件中静态 className 的样式对象提升到模块作用域，避免每次 render 重新创建
+  pub hoist_static_styles: bool,
+  // 输出代码中保留的注释
+  pub comments: CommentsMode,
+  // 输出代码是否只包含 ascii 字符，非 ascii 字符转义为 \uXXXX
+  pub ascii_only: bool,
+  // 输出代码的目标 ES 版本，影响字符串等字面量的输出，es5 及以下时字符串不再保留原始写法
+  pub target: EsVersion,
+  // 生成的字符串使用的引号，None 时由 swc 决定（默认双引号）
+  pub quote_style: Option<QuoteStyle>,
+  // 多个样式文件中定义了同名的 @keyframes 时，是否按文件重命名并改写同一文件中的 animation-name 引用，关闭时后定义的覆盖先定义的
+  pub rename_keyframes: bool,
+  // overflow: scroll、auto 时是否额外输出 __scrollable__（"vertical" | "horizontal" | "both"），仅鸿蒙平台输出
+  pub scrollable_hint: bool,
+  // 是否将属性中的 JSX（如 render props）加入节点树，参与标签、兄弟选择器的编译期匹配及文本样式继承
+  pub render_props: bool,
+  // styles 中前 global_style_count 个样式为全局样式，Session 转换页面时自动设置
+  pub global_style_count: usize,
+  // 全局样式与页面样式的优先级
+  pub style_precedence: StylePrecedence,
+  // 当前转换的目标平台，由 Transformer 设置，用于与平台相关的属性值格式，如 8 位 hex 颜色中透明度的位置
+  pub platform: Platform,
+}
+
+impl Default for TransformOptions {
+  fn default() -> Self {
+    TransformOptions {
+      root_font_size: 16.0,
+      em_font_size: 16.0,
+      direction: WritingDirection::Ltr,
+      mirror_rtl: false,
+      font_family_map: HashMap::new(),
+      extract_theme: false,
+      supports_features: HashMap::new(),
+      passthrough_unrecognized: false,
+      rn_style_sheet_create: false,
+      tree_shaking: false,
+      minify: false,
+      scoped: false,
+      scope_id: None,
+      scope_name_template: None,
+      scope_hash_algorithm: HashAlgorithm::Fnv1a,
+      scope_hash_length: 8,
+      scope_path: None,
+      inherit_properties: vec![],
+      tag_map: HashMap::new(),
+      component_sources: vec![],
+      color_format: ColorFormat::Auto,
+      container_queries: false,
+      precision: None,
+      rounding: RoundingMode::Round,
+      rn_os: None,
+      harmony_api_version: None,
+      style_key_map: HashMap::new(),
+      lint_rules: HashMap::new(),
+      fallback_rules: HashMap::new(),
+      strict: false,
+      hoist_static_styles: false,
+      comments: CommentsMode::All,
+      ascii_only: false,
+      target: EsVersion::latest(),
+      quote_style: None,
+      rename_keyframes: false,
+      scrollable_hint: false,
+      render_props: false,
+      global_style_count: 0,
+      style_precedence: StylePrecedence::Order,
+      platform: Platform::Harmony,
+    }
+  }
+}
+
+thread_local! {
+  static TRANSFORM_OPTIONS: RefCell<TransformOptions> = RefCell::new(TransformOptions::default());
+}
+
+pub fn set_transform_options(options: TransformOptions) {
+  TRANSFORM_OPTIONS.with(|cell| *cell.borrow_mut() = options);
+}
+
+pub fn with_transform_options<R>(f: impl FnOnce(&TransformOptions) -> R) -> R {
+  TRANSFORM_OPTIONS.with(|cell| f(&cell.borrow()))
+}
@@ -1,14 +1,45 @@
 use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
 use lightningcss::{properties::{custom::TokenOrValue, Property}, stylesheet::PrinterOptions, traits::ToCss};
-use swc_core::{common::DUMMY_SP, ecma::{ast::{self}, utils::quote_ident}};
+use swc_core::{atoms::Atom, common::DUMMY_SP, ecma::{ast::{self}, utils::quote_ident}};
 use swc_core::ecma::ast::*;
 
-use crate::{constants::ENV_FUN, constants::VAR_FUN, style_parser::KeyFrameItem, style_propetries::{animation::Animation, aspect_ratio::AspactRatio, background::Background, background_image::BackgroundImage, background_position::BackgroundPosition, background_repeat::BackgroundRepeat, background_size::BackgroundSize, border::Border, border_color::BorderColor, border_radius::BorderRadius, border_style::BorderStyle, border_width::BorderWidth, box_shadow::BoxShadow, color::ColorProperty, display::Display, expr::Expr, flex::Flex, flex_align::FlexAlign, flex_basis::FlexBasis, flex_direction::FlexDirection, flex_wrap::FlexWrap, font_size::FontSize, font_style::FontStyle, font_weight::FontWeight, gap::Gap, item_align::ItemAlign, length_value::LengthValueProperty, letter_spacing::LetterSpacing, line_height::LineHeight, marin_padding::MarginPadding, max_size::MaxSizeProperty, normal::Normal, number::NumberProperty, overflow::Overflow, size::SizeProperty, style_value_type::StyleValueType, text_align::TextAlign, text_decoration::TextDecoration, text_overflow::TextOverflow, text_shadow::TextShadow, text_transform::TextTransform, transform::Transform, transform_origin::TransformOrigin, unit::{generate_expr_by_length_value, Platform}, vertical_align::VerticalAlign}};
+use crate::{constants::ENV_FUN, generate_invalid_expr, validate::is_invalid_value, current_color::resolve_current_color, constants::VAR_FUN, hooks::run_property_hooks, logical_properties::normalize_logical_properties, options::with_transform_options, theme::get_theme_member_expr, style_parser::KeyFrameItem, style_propetries::{animation::Animation, aspect_ratio::AspectRatio, background::Background, background_image::BackgroundImage, background_position::BackgroundPosition, background_repeat::BackgroundRepeat, background_size::BackgroundSize, border::Border, border_color::BorderColor, border_radius::BorderRadius, border_style::BorderStyle, border_width::BorderWidth, box_shadow::BoxShadow, filter::Filter, mask::Mask, scroll::Scroll, direction::Direction, outline::Outline, content::{parse_counter_list, Content}, passthrough::Passthrough, color::ColorProperty, display::Display, opacity::Opacity, visibility::Visibility, pointer_events::PointerEvents, expr::Expr, flex::Flex, flex_align::FlexAlign, flex_basis::FlexBasis, flex_direction::FlexDirection, flex_wrap::FlexWrap, font_size::FontSize, font_family::FontFamily, font_style::FontStyle, font_weight::FontWeight, gap::{get_px_size, Gap}, item_align::ItemAlign, length_value::LengthValueProperty, letter_spacing::LetterSpacing, line_height::LineHeight, marin_padding::MarginPadding, max_size::MaxSizeProperty, normal::Normal, number::NumberProperty, overflow::{Overflow, Scrollable}, size::SizeProperty, style_value_type::StyleValueType, text_align::TextAlign, text_decoration::TextDecoration, text_overflow::TextOverflow, text_shadow::TextShadow, text_stroke::TextStroke, text_transform::TextTransform, white_space::WhiteSpace, line_clamp::LineClamp, word_break::WordBreak, transform::Transform, transform_origin::TransformOrigin, transition::TransitionConfig, unit::{generate_expr_by_length_value, Platform}, vertical_align::VerticalAlign}};
 
-pub fn parse_style_properties(properties: &Vec<(String, Property)>, keyframes_map: Option<Rc<RefCell<HashMap<String, Vec<KeyFrameItem>>>>>) -> Vec<StyleValueType> {
+pub fn parse_style_properties(properties: &Vec<(Atom, Property)>, keyframes_map: Option<Rc<RefCell<HashMap<String, Vec<KeyFrameItem>>>>>) -> Vec<StyleValueType> {
   let mut final_properties = vec![];
+  // 逻辑属性先转换为物理属性
+  let properties = normalize_logical_properties(properties);
+  let properties = resolve_current_color(properties);
+  let passthrough_unrecognized = with_transform_options(|options| options.passthrough_unrecognized);
+  // 收集同一条规则中声明的计数器，供 content 中的 counter() 静态求值
+  let mut counters: HashMap<String, i32> = HashMap::new();
+  properties.iter().for_each(|(id, value)| {
+    match id.as_str() {
+      "counterReset" => parse_counter_list(value, 0).into_iter().for_each(|(name, num)| { counters.insert(name, num); }),
+      "counterIncrement" => parse_counter_list(value, 1).into_iter().for_each(|(name, num)| { *counters.entry(name).or_insert(0) += num; }),
+      _ => {}
+    }
+  });
+  // 收集同一条规则中以 px 声明的宽高，供百分比的 gap 静态求值
+  let mut container_size: (Option<f32>, Option<f32>) = (None, None);
+  properties.iter().for_each(|(id, value)| {
+    match id.as_str() {
+      "width" => container_size.0 = get_px_size(value),
+      "height" => container_size.1 = get_px_size(value),
+      _ => {}
+    }
+  });
+  // 同一条规则中声明的字号，供无单位、百分比的 line-height 及 em 的字间距计算
+  let font_size = properties.iter().rev().find(|(id, _)| id == "fontSize").map(|(_, value)| value);
+  // 同一条规则中声明的行高，供百分比的 vertical-align 计算
+  let line_height = properties.iter().rev().find(|(id, _)| id == "lineHeight").map(|(_, value)| value);
   for (id, value)  in properties.iter() {
+    // 自定义的属性转换钩子优先于内置转换
+    if let Some(style_values) = run_property_hooks(value) {
+      final_properties.extend(style_values);
+      continue;
+    }
 
     let mut is_env: bool = false;
     match value {
@@ -46,6 +77,11 @@ pub fn parse_style_properties(properties: &Vec<(String, Property)>, keyframes_ma
             },
             TokenOrValue::Var(env) => {
               is_env = true;
+              // 开启主题提取时，:root 中声明的变量直接编译为 theme.xxx
+              if let Some(theme_expr) = get_theme_member_expr(&env.name.to_css_string(PrinterOptions::default()).unwrap()) {
+                final_properties.push(StyleValueType::Expr(Expr::new(id.to_string(), theme_expr)));
+                return;
+              }
               let mut args = vec![
                 ExprOrSpread {
                   spread: None,
@@ -82,8 +118,17 @@ pub fn parse_style_properties(properties: &Vec<(String, Property)>, keyframes_ma
     if is_env {
       continue;
     }
+    // 无法解析的属性值（如 width: foo）不经过各属性的转换，直接丢弃，开启透传时按原始字符串输出
+    if is_invalid_value(value) {
+      if passthrough_unrecognized {
+        let raw = value.value_to_css_string(PrinterOptions::default()).unwrap_or_default();
+        final_properties.push(StyleValueType::Passthrough(Passthrough::new(id.to_string(), raw, StyleValueType::Expr(Expr::new(id.to_string(), generate_invalid_expr!())))));
+      }
+      continue;
+    }
 
     
+    let start_index = final_properties.len();
     let property_name = id.as_str();
     match property_name {
           // 基础样式
@@ -118,13 +163,22 @@ pub fn parse_style_properties(properties: &Vec<(String, Property)>, keyframes_ma
             final_properties.push(StyleValueType::FlexWrap(FlexWrap::from((id.to_string(), value))));
           }
           "aspectRatio" => {
-            final_properties.push(StyleValueType::AspectRatio(AspactRatio::from((id.to_string(), value))));
+            final_properties.push(StyleValueType::AspectRatio(AspectRatio::from((id.to_string(), value))));
           }
           "display" => {
             final_properties.push(StyleValueType::Display(Display::from((id.to_string(), value))));
           }
+          "opacity" => {
+            final_properties.push(StyleValueType::Opacity(Opacity::from((id.to_string(), value))));
+          }
+          "visibility" => {
+            final_properties.push(StyleValueType::Visibility(Visibility::from((id.to_string(), value))));
+          }
+          "pointerEvents" => {
+            final_properties.push(StyleValueType::PointerEvents(PointerEvents::from((id.to_string(), value))));
+          }
           "gap" | "columnGap" | "rowGap" => {
-            final_properties.push(StyleValueType::Gap(Gap::from((id.to_string(), value))));
+            final_properties.push(StyleValueType::Gap(Gap::from((id.to_string(), value, container_size))));
           }
           "margin" | "padding"  => {
             final_properties.push(StyleValueType::MarginPadding(MarginPadding::from((id.to_string(), value))));
@@ -138,9 +192,15 @@ pub fn parse_style_properties(properties: &Vec<(String, Property)>, keyframes_ma
           "height" | "width" | "minHeight" | "minWidth" => {
             final_properties.push(StyleValueType::SizeProperty(SizeProperty::from((id.to_string(), value))));
           }
-          "overflow" => {
+          "overflow" | "overflowX" | "overflowY" => {
             final_properties.push(StyleValueType::Overflow(Overflow::from((id.to_string(), value))));
           }
+          "scrollBehavior" | "overscrollBehavior" | "overscrollBehaviorX" | "overscrollBehaviorY" => {
+            final_properties.push(StyleValueType::Scroll(Scroll::from((id.to_string(), value))));
+          }
+          "direction" | "writingMode" => {
+            final_properties.push(StyleValueType::Direction(Direction::from((id.to_string(), value))));
+          }
           "color" | "backgroundColor" => {
             final_properties.push(StyleValueType::ColorProperty(ColorProperty::from((id.to_string(), value))));
           }
@@ -148,6 +208,9 @@ pub fn parse_style_properties(properties: &Vec<(String, Property)>, keyframes_ma
           "fontSize" => {
             final_properties.push(StyleValueType::FontSize(FontSize::from((id.to_string(), value))));
           }
+          "fontFamily" => {
+            final_properties.push(StyleValueType::FontFamily(FontFamily::from((id.to_string(), value))));
+          }
           "fontStyle" => {
             final_properties.push(StyleValueType::FontStyle(FontStyle::from((id.to_string(), value))));
           }
@@ -155,38 +218,64 @@ pub fn parse_style_properties(properties: &Vec<(String, Property)>, keyframes_ma
             final_properties.push(StyleValueType::FontWeight(FontWeight::from((id.to_string(), value))));
           }
           "lineHeight" => {
-            final_properties.push(StyleValueType::LineHeight(LineHeight::from((id.to_string(), value))));
+            final_properties.push(StyleValueType::LineHeight(LineHeight::from((id.to_string(), value, font_size))));
           }
           "textAlign" => {
             final_properties.push(StyleValueType::TextAlign(TextAlign::from((id.to_string(), value))));
           }
-          "textDecoration" => {
-            // textDecorationLine、textDecorationColor、textDecorationStyle
-            final_properties.push(StyleValueType::TextDecoration(TextDecoration::from((id.to_string(), value))));
-          }
-          "textDecorationLine" => {
-            final_properties.push(StyleValueType::TextDecoration(TextDecoration::from((id.to_string(), value))));
-          }
-          "textDecorationColor" => {
-            final_properties.push(StyleValueType::TextDecoration(TextDecoration::from((id.to_string(), value))));
-          }
-          "textDecorationStyle" => {
-            final_properties.push(StyleValueType::TextDecoration(TextDecoration::from((id.to_string(), value))));
+          "textDecoration" | "textDecorationLine" | "textDecorationColor" | "textDecorationStyle" | "textDecorationThickness" => {
+            // 同一条规则中的简写与子属性合并为一个 textDecoration
+            let text_decoration = TextDecoration::from((id.to_string(), value));
+            let existing = final_properties.iter_mut().find_map(|style_value| match style_value {
+              StyleValueType::TextDecoration(existing) => Some(existing),
+              _ => None
+            });
+            match existing {
+              Some(existing) => existing.merge(text_decoration),
+              None => final_properties.push(StyleValueType::TextDecoration(text_decoration))
+            }
           }
           "textShadow" => {
             final_properties.push(StyleValueType::TextShadow(TextShadow::from((id.to_string(), value))));
           }
+          "WebkitTextStroke" | "WebkitTextStrokeWidth" | "WebkitTextStrokeColor" => {
+            // 同一条规则中的简写与子属性合并为一个 textStroke
+            let text_stroke = TextStroke::from((id.to_string(), value));
+            let existing = final_properties.iter_mut().find_map(|style_value| match style_value {
+              StyleValueType::TextStroke(existing) => Some(existing),
+              _ => None
+            });
+            match existing {
+              Some(existing) => existing.merge(text_stroke),
+              None => final_properties.push(StyleValueType::TextStroke(text_stroke))
+            }
+          }
           "textTransform" => {
             final_properties.push(StyleValueType::TextTransform(TextTransform::from((id.to_string(), value))));
           }
           "textOverflow" => {
             final_properties.push(StyleValueType::TextOverflow(TextOverflow::from((id.to_string(), value))));
           }
-          "letterSpacing" => {
-            final_properties.push(StyleValueType::LetterSpacing(LetterSpacing::from((id.to_string(), value))));
+          "whiteSpace" => {
+            final_properties.push(StyleValueType::WhiteSpace(WhiteSpace::from((id.to_string(), value))));
+          }
+          "WebkitLineClamp" | "lineClamp" => {
+            final_properties.push(StyleValueType::LineClamp(LineClamp::from((id.to_string(), value))));
+          }
+          "wordBreak" | "hyphens" | "WebkitHyphens" => {
+            final_properties.push(StyleValueType::WordBreak(WordBreak::from((id.to_string(), value))));
+          }
+          "overflowWrap" | "wordWrap" => {
+            // 同时声明了 word-break 时以 word-break 为准，避免生成的 wordBreak 被覆盖
+            if !properties.iter().any(|(id, _)| id == "wordBreak") {
+              final_properties.push(StyleValueType::WordBreak(WordBreak::from((id.to_string(), value))));
+            }
+          }
+          "letterSpacing" | "wordSpacing" => {
+            final_properties.push(StyleValueType::LetterSpacing(LetterSpacing::from((id.to_string(), value, font_size))));
           }
           "verticalAlign" => {
-            final_properties.push(StyleValueType::VerticalAlign(VerticalAlign::from((id.to_string(), value))));
+            final_properties.push(StyleValueType::VerticalAlign(VerticalAlign::from((id.to_string(), value, line_height))));
           }
           // 边框
           "borderColor" => {
@@ -219,6 +308,9 @@ pub fn parse_style_properties(properties: &Vec<(String, Property)>, keyframes_ma
           "borderTop" | "borderBottom" | "borderLeft" | "borderRight" => {
             final_properties.push(StyleValueType::Border(Border::from((id.to_string(), value))));
           }
+          "outline" | "outlineWidth" | "outlineStyle" | "outlineColor" => {
+            final_properties.push(StyleValueType::Outline(Outline::from((id.to_string(), value))));
+          }
           // 变换
           "transform" => {
             final_properties.push(StyleValueType::Transform(Transform::from((id.to_string(), value))));
@@ -245,21 +337,26 @@ pub fn parse_style_properties(properties: &Vec<(String, Property)>, keyframes_ma
           "boxShadow" => {
             final_properties.push(StyleValueType::BoxShadow(BoxShadow::from((id.to_string(), value))));
           }
+          "filter" | "backdropFilter" | "WebkitBackdropFilter" => {
+            final_properties.push(StyleValueType::Filter(Filter::from((id.to_string(), value))));
+          }
+          // 遮罩
+          "maskImage" | "maskSize" | "maskPosition" | "maskRepeat" | "maskMode" | "maskClip" | "maskOrigin" | "maskComposite" | "maskType"
+          | "WebkitMaskImage" | "WebkitMaskSize" | "WebkitMaskPosition" | "WebkitMaskRepeat" | "WebkitMaskClip" | "WebkitMaskOrigin" | "WebkitMaskComposite" => {
+            final_properties.push(StyleValueType::Mask(Mask::from((id.to_string(), value))));
+          }
           "content" => {
-            // 判断content内容是否是空字符串
-            let content_value = value.value_to_css_string(PrinterOptions::default()).unwrap().trim().to_string();
-            if content_value != "\"\"" {
-              // 替换字符串，将左右两边的"干掉
-              let content_value = content_value.trim_matches('"');
-              final_properties.push(StyleValueType::Normal(Normal::new(id.to_string(), content_value.to_string())));
-            }
+            final_properties.push(StyleValueType::Content(Content::from((id.to_string(), value, counters.clone()))));
+          }
+          "counterReset" | "counterIncrement" => {
+            // 计数器仅用于 content 求值，不输出
           }
           "animation" | "animationName" => {
             if let Some(ref keyframes_map) = keyframes_map {
               final_properties.push(StyleValueType::Animation(Animation::from((id.to_string(), value, Some(keyframes_map.clone())))))
             }
           }
-          "animationDelay" | "animationDuration" | "animationIterationCount" | "animationTimingFunction" => {
+          "animationDelay" | "animationDuration" | "animationIterationCount" | "animationTimingFunction" | "animationFillMode" | "animationDirection" | "animationPlayState" => {
             final_properties.push(StyleValueType::Animation(Animation::from((id.to_string(), value, None))))
           }
           _ => {
@@ -267,6 +364,43 @@ pub fn parse_style_properties(properties: &Vec<(String, Property)>, keyframes_ma
             final_properties.push(StyleValueType::Normal(Normal::new(id.to_string(), value.value_to_css_string(PrinterOptions::default()).unwrap())));
           }
         }
+    // 开启透传时，转换结果为 Invalid 的声明按原始字符串输出
+    if passthrough_unrecognized {
+      let raw = value.value_to_css_string(PrinterOptions::default()).unwrap_or_default();
+      let style_values = final_properties.split_off(start_index);
+      final_properties.extend(style_values.into_iter().map(|style_value| {
+        StyleValueType::Passthrough(Passthrough::new(id.to_string(), raw.clone(), style_value))
+      }));
+    }
 }
+  // transition 中 transform、opacity 的过渡参数额外输出为 transitionConfig，原始声明仍按字符串输出
+  let transition_config = properties.iter().filter(|(id, _)| {
+    matches!(id.as_str(), "transition" | "transitionProperty" | "transitionDuration" | "transitionDelay" | "transitionTimingFunction")
+  }).map(|(id, value)| TransitionConfig::from((id.to_string(), value))).reduce(|mut transition_config, other| {
+    transition_config.merge(other);
+    transition_config
+  });
+  if let Some(transition_config) = transition_config {
+    final_properties.push(StyleValueType::TransitionConfig(transition_config));
+  }
+  // overflow: scroll、auto 额外输出可滚动的方向，供运行时将容器替换为 Scroll 组件
+  if with_transform_options(|options| options.scrollable_hint) {
+    let scrollable = Scrollable::from_overflows(final_properties.iter().filter_map(|style_value| match style_value {
+      StyleValueType::Overflow(overflow) => Some(overflow),
+      _ => None
+    }));
+    if let Some(scrollable) = scrollable {
+      final_properties.push(StyleValueType::Scrollable(scrollable));
+    }
+  }
+  // 关键帧缺少 0%、100% 帧时，以同一条规则中的静态样式补齐起止帧
+  if final_properties.iter().any(|style_value| matches!(style_value, StyleValueType::Animation(animation) if animation.keyframes.is_some())) {
+    let base_styles = final_properties.iter().filter(|style_value| !matches!(style_value, StyleValueType::Animation(_))).cloned().collect::<Vec<_>>();
+    final_properties.iter_mut().for_each(|style_value| {
+      if let StyleValueType::Animation(animation) = style_value {
+        animation.base_styles = base_styles.clone();
+      }
+    });
+  }
   final_properties
 }
\ No newline at end of file
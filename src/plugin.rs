@@ -0,0 +1,67 @@
+use std::{fs, path::Path};
+
+use serde::Deserialize;
+use swc_core::{
+  ecma::ast::Program,
+  plugin::{
+    errors::HANDLER,
+    metadata::{TransformPluginMetadataContextKind, TransformPluginProgramMetadata},
+    plugin_transform,
+  },
+};
+
+use crate::{get_transform_options, scope::generate_scope_id_with_hash, transform_program, ParseOptions};
+
+// swc 将项目根目录挂载到 Wasm 插件的 /cwd 下
+const PLUGIN_CWD: &str = "/cwd";
+
+// 插件配置，如 ["parse-css-to-stylesheet", { "styles": ["src/app.css"], "platformString": "Harmony", "isEntry": false }]
+// 除 styles 外的字段与 ParseOptions 相同
+#[derive(Deserialize)]
+struct PluginConfig {
+  // 样式文件路径，相对路径基于项目根目录
+  styles: Vec<String>,
+  #[serde(flatten)]
+  options: ParseOptions,
+}
+
+fn read_style(path: &str) -> Option<String> {
+  let path = match Path::new(path).is_absolute() {
+    true => Path::new(path).to_path_buf(),
+    false => Path::new(PLUGIN_CWD).join(path),
+  };
+  fs::read_to_string(&path).ok()
+}
+
+#[plugin_transform]
+pub fn process_transform(program: Program, metadata: TransformPluginProgramMetadata) -> Program {
+  let config = metadata.get_transform_plugin_config().unwrap_or_default();
+  let mut config: PluginConfig = serde_json::from_str(&config).expect("parse-css-to-stylesheet 插件配置格式错误");
+
+  let styles = config.styles.iter().filter_map(|path| {
+    let style = read_style(path);
+    if style.is_none() {
+      HANDLER.with(|handler| handler.warn(&format!("[parse-css-to-stylesheet] failed to read style file {}", path)));
+    }
+    style
+  }).collect();
+
+  // 作用域模式下未指定 scopeId 时根据文件名生成，AST 输入没有可用于计算的源码
+  if config.options.scoped == Some(true) && config.options.scope_id.is_none() {
+    let options = get_transform_options(&config.options);
+    config.options.scope_id = metadata
+      .get_context(&TransformPluginMetadataContextKind::Filename)
+      .map(|filename| generate_scope_id_with_hash(&filename, &options.scope_hash_algorithm, options.scope_hash_length));
+  }
+
+  let (program, result) = transform_program(program, styles, config.options);
+  for diagnostic in result.diagnostics {
+    let message = format!("[parse-css-to-stylesheet] [{}] {}", diagnostic.property, diagnostic.message);
+    // 样式检查规则配置为 error 时报告为错误
+    match diagnostic.severity.as_str() {
+      "error" => HANDLER.with(|handler| handler.err(&message)),
+      _ => HANDLER.with(|handler| handler.warn(&message))
+    }
+  }
+  program
+}
@@ -0,0 +1,123 @@
+use std::collections::{BTreeMap, HashSet};
+
+use serde::Serialize;
+use swc_core::common::SourceMap;
+
+use crate::{
+  constants::SUPPORT_PSEUDO_KEYS,
+  document::JSXDocument,
+  style_parser::{GeneratedClassRecord, StyleMap},
+  style_propetries::unit::Platform,
+  stylesheet::{generate_rules, StyleSheetValue},
+  utils::{is_class_selector, is_tailwind_arbitrary},
+  visitor::SpanKey,
+};
+
+// 匹配报告 JSON 的结构版本，结构发生不兼容变更时递增
+const MATCH_REPORT_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchReport {
+  pub version: u32,
+  pub file: Option<String>,
+  pub elements: Vec<ElementMatch>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ElementMatch {
+  pub tag: String,
+  // 节点在组件代码中的位置，行号从 1 开始，列号从 0 开始；上游插件生成的节点没有源码位置
+  pub line: Option<usize>,
+  pub column: Option<usize>,
+  pub span: [u32; 2],
+  // 节点的类名，含编译期生成的类名
+  pub class_names: Vec<String>,
+  // 匹配的规则，按层叠顺序排列，含伪类规则
+  pub rules: Vec<String>,
+  // 非伪类规则按顺序合并后的样式，与输出代码中的值一致
+  pub declarations: BTreeMap<String, StyleSheetValue>,
+}
+
+// 生成节点与样式规则的匹配报告，用于排查样式未生效的原因
+// 后代、兄弟等组合选择器在静态节点树上匹配，无法在编译期确定是否匹配的节点（如 list.map 渲染的节点）不计入
+pub fn generate_match_report(
+  document: &JSXDocument,
+  generated_classes: &GeneratedClassRecord,
+  all_style: &StyleMap,
+  cm: &SourceMap,
+  file: Option<String>,
+  platform: Platform,
+) -> String {
+  let Some(jsx_record) = &document.jsx_record else {
+    return String::new()
+  };
+  let rules = generate_rules(all_style, platform);
+  let matches = all_style.keys().map(|selector| (selector, get_matched_elements(document, generated_classes, selector))).collect::<Vec<_>>();
+
+  let elements = jsx_record.iter().map(|(span, element)| {
+    let mut class_names = element.classes().map(|class| class.to_string()).collect::<Vec<_>>();
+    class_names.extend(generated_classes.get(span).cloned().unwrap_or_default());
+    let selectors = matches
+      .iter()
+      .filter(|(_, elements)| elements.contains(span))
+      .map(|(selector, _)| selector.to_string())
+      .collect::<Vec<_>>();
+    let mut declarations = BTreeMap::new();
+    selectors
+      .iter()
+      .filter(|selector| !is_pseudo_selector(selector))
+      .filter_map(|selector| rules.get(selector))
+      .for_each(|rule| declarations.extend(rule.iter().map(|(key, value)| (key.clone(), value.clone()))));
+    let position = match span.0.is_dummy() {
+      true => None,
+      false => Some(cm.lookup_char_pos(span.0.lo)),
+    };
+    ElementMatch {
+      tag: element.name().to_string(),
+      line: position.as_ref().map(|position| position.line),
+      column: position.as_ref().map(|position| position.col.0),
+      span: [span.0.lo.0, span.0.hi.0],
+      class_names,
+      rules: selectors,
+      declarations,
+    }
+  }).collect();
+
+  let report = MatchReport {
+    version: MATCH_REPORT_VERSION,
+    file,
+    elements,
+  };
+  serde_json::to_string_pretty(&report).unwrap()
+}
+
+pub fn is_pseudo_selector(selector: &str) -> bool {
+  SUPPORT_PSEUDO_KEYS.into_iter().any(|key| selector.contains(key))
+}
+
+// 选择器匹配的节点，伪类规则按去掉伪类后的选择器匹配
+pub fn get_matched_elements(document: &JSXDocument, generated_classes: &GeneratedClassRecord, selector: &str) -> HashSet<SpanKey> {
+  let selector = match is_pseudo_selector(selector) {
+    true => selector.split(':').next().unwrap_or_default(),
+    false => selector
+  };
+  let classes = match (is_class_selector(selector), is_tailwind_arbitrary(selector) && selector.starts_with('.')) {
+    (true, _) => selector.split('.').filter(|class| !class.is_empty()).collect::<Vec<_>>(),
+    (_, true) => vec![&selector[1..]],
+    _ => {
+      return document
+        .select_static(selector)
+        .map(|result| result.elements.into_iter().collect())
+        .unwrap_or_default()
+    }
+  };
+  document.jsx_record.iter().flat_map(|jsx_record| jsx_record.iter()).filter(|(span, element)| {
+    let generated = generated_classes.get(*span);
+    classes.iter().all(|class| {
+      element.classes().any(|element_class| element_class == *class)
+        || generated.is_some_and(|generated| generated.iter().any(|generated_class| generated_class == class))
+    })
+  }).map(|(span, _)| *span).collect()
+}
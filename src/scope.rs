@@ -0,0 +1,163 @@
+use std::cell::RefCell;
+
+use indexmap::IndexMap;
+
+use crate::{hash::hash_hex, options::{with_transform_options, HashAlgorithm, TransformOptions}};
+
+thread_local! {
+  // 原始类名 => 添加作用域前缀后的类名
+  static SCOPED_CLASSES: RefCell<IndexMap<String, String>> = RefCell::new(IndexMap::new());
+}
+
+// 根据组件代码生成作用域 id，相同的代码得到相同的 id，保证多次构建的输出一致
+pub fn generate_scope_id(source: &str) -> String {
+  generate_scope_id_with_hash(source, &HashAlgorithm::Fnv1a, 8)
+}
+
+// 按指定的 hash 算法及位数生成作用域 id，如 s1a2b3c4d
+pub fn generate_scope_id_with_hash(source: &str, algorithm: &HashAlgorithm, length: usize) -> String {
+  format!("s{}", hash_hex(source, algorithm, length))
+}
+
+fn get_scope_id() -> Option<String> {
+  with_transform_options(|options| match options.scoped {
+    true => options.scope_id.clone(),
+    false => None
+  })
+}
+
+pub fn is_scoped() -> bool {
+  get_scope_id().is_some()
+}
+
+// 类名添加作用域前缀，如 title => s1a2b3c4d_title，配置了模板时按模板生成，如 [name]_[local]_[hash:6] => index_title_3f2a1b
+pub fn scope_class(class: &str) -> String {
+  if let Some(scoped_class) = SCOPED_CLASSES.with(|cell| cell.borrow().get(class).cloned()) {
+    return scoped_class
+  }
+  let scoped_class = with_transform_options(|options| {
+    let scope_id = options.scope_id.as_ref().filter(|_| options.scoped)?;
+    Some(match &options.scope_name_template {
+      Some(template) => interpolate_scope_name(template, class, scope_id, options),
+      None => format!("{}_{}", scope_id, class)
+    })
+  });
+  let Some(scoped_class) = scoped_class else {
+    return class.to_string()
+  };
+  SCOPED_CLASSES.with(|cell| {
+    cell.borrow_mut().insert(class.to_string(), scoped_class.clone());
+  });
+  scoped_class
+}
+
+// 按模板生成作用域类名，与 css-loader 的 localIdentName 一致：[hash] 为 "路径\0原始类名" 的 hex hash，
+// 类名中不合法的字符替换为 -，以数字或 -- 开头时添加 _ 前缀
+fn interpolate_scope_name(template: &str, class: &str, scope_id: &str, options: &TransformOptions) -> String {
+  let path = options.scope_path.as_deref().unwrap_or(scope_id);
+  let file = path.rsplit(['/', '\\']).next().unwrap_or(path);
+  let name = match file.rfind('.') {
+    Some(index) if index > 0 => &file[..index],
+    _ => file
+  };
+  let content = format!("{}\0{}", path, class);
+  let mut result = String::new();
+  let mut rest = template;
+  while let Some(start) = rest.find('[') {
+    let Some(end) = rest[start..].find(']').map(|end| start + end) else {
+      break
+    };
+    result.push_str(&rest[..start]);
+    let placeholder = &rest[start + 1..end];
+    match placeholder {
+      "name" => result.push_str(name),
+      "local" => result.push_str(class),
+      "hash" => result.push_str(&hash_hex(&content, &options.scope_hash_algorithm, options.scope_hash_length)),
+      _ => match placeholder.strip_prefix("hash:").and_then(|length| length.parse::<usize>().ok()) {
+        Some(length) => result.push_str(&hash_hex(&content, &options.scope_hash_algorithm, length)),
+        // 不支持的占位符保持原样
+        None => result.push_str(&rest[start..=end])
+      }
+    }
+    rest = &rest[end + 1..];
+  }
+  result.push_str(rest);
+  let mut result = result.chars().map(|c| match c.is_ascii_alphanumeric() || c == '-' || c == '_' || c as u32 >= 0xA0 {
+    true => c,
+    false => '-'
+  }).collect::<String>();
+  let mut chars = result.chars();
+  let needs_prefix = match (chars.next(), chars.next()) {
+    (Some(first), _) if first.is_ascii_digit() => true,
+    (Some('-'), Some(second)) => second.is_ascii_digit() || second == '-',
+    _ => false
+  };
+  if needs_prefix {
+    result.insert(0, '_');
+  }
+  result
+}
+
+// 空格分隔的类名列表添加作用域前缀，保留原有的空白字符
+// scope_start、scope_end 为 false 时，首尾与模板字符串表达式相连的类名不完整，如 `item-${index}`，保持不变
+pub fn scope_class_list(value: &str, scope_start: bool, scope_end: bool) -> String {
+  // 按空白字符切分为片段，保留空白字符
+  let mut segments: Vec<String> = vec![];
+  for c in value.chars() {
+    match segments.last_mut() {
+      Some(segment) if segment.starts_with(char::is_whitespace) == c.is_whitespace() => segment.push(c),
+      _ => segments.push(c.to_string())
+    }
+  }
+  let count = segments.len();
+  segments
+    .into_iter()
+    .enumerate()
+    .map(|(index, segment)| {
+      let is_partial = (index == 0 && !scope_start) || (index == count - 1 && !scope_end);
+      if segment.starts_with(char::is_whitespace) || is_partial {
+        segment
+      } else {
+        scope_class(&segment)
+      }
+    })
+    .collect()
+}
+
+// 选择器中的类名添加作用域前缀，如 .title > .icon:hover => .s1a2b3c4d_title > .s1a2b3c4d_icon:hover
+// tailwind 任意值类名中 [] 内的字符均属于类名，如 .w-[0.5px]
+pub fn scope_selector(selector: &str) -> String {
+  if !is_scoped() {
+    return selector.to_string()
+  }
+  let mut result = String::new();
+  let mut chars = selector.chars().peekable();
+  while let Some(c) = chars.next() {
+    result.push(c);
+    if c != '.' {
+      continue
+    }
+    let mut class = String::new();
+    let mut depth = 0;
+    while let Some(&next) = chars.peek() {
+      match next {
+        '[' => depth += 1,
+        ']' => depth -= 1,
+        _ if depth == 0 && (next.is_whitespace() || ".:>+~#,*()".contains(next)) => break,
+        _ => {}
+      }
+      class.push(next);
+      chars.next();
+    }
+    if class.is_empty() {
+      continue
+    }
+    result.push_str(&scope_class(&class));
+  }
+  result
+}
+
+// 取出本次转换的类名映射
+pub fn take_scoped_classes() -> IndexMap<String, String> {
+  SCOPED_CLASSES.with(|cell| std::mem::take(&mut *cell.borrow_mut()))
+}
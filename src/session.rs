@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+
+use indexmap::IndexMap;
+
+use crate::{
+  document::SourceSyntax,
+  options::TransformOptions,
+  style_propetries::unit::Platform,
+  transformer::{TransformResult, Transformer},
+};
+
+// 组件的类型，对应 Taro 的应用入口、页面及普通组件
+#[derive(Clone, PartialEq)]
+enum ComponentKind {
+  Component,
+  // 应用入口，如 app.tsx，其样式（如 app.css）为全局样式
+  App,
+  // 页面，如 pages/index/index.tsx，全局样式在页面样式之前合并
+  Page,
+}
+
+// 会话中登记的组件
+struct Component {
+  kind: ComponentKind,
+  code: String,
+  // 组件依赖的样式文件路径，按引入顺序合并
+  style_paths: Vec<String>,
+  // 上一次输出的代码，用于判断是否需要重新输出
+  last_code: Option<String>,
+}
+
+// 需要重新输出的组件及其转换结果
+pub struct Artifact {
+  pub path: String,
+  pub result: TransformResult,
+}
+
+// 增量转换会话，供 webpack、vite 等开发服务器在文件变更时调用，仅重新转换受影响的组件，
+// 并且只返回输出发生变化的组件，如：
+// let mut session = Session::new(Platform::Harmony);
+// session.add_component("index.tsx", code, vec!["index.css".to_string()]);
+// session.css_changed("index.css", css);
+// 应用入口的样式为全局样式，在各页面的样式之前合并，页面之间的选择器互不影响，如：
+// session.add_app("app.tsx", code, vec!["app.css".to_string()]);
+// session.add_page("pages/index/index.tsx", code, vec!["pages/index/index.css".to_string()]);
+pub struct Session {
+  platform: Platform,
+  options: TransformOptions,
+  syntax: SourceSyntax,
+  is_entry: bool,
+  stylesheet: bool,
+  arkui: bool,
+  dts: bool,
+  match_report: bool,
+  ir: bool,
+  components: IndexMap<String, Component>,
+  styles: HashMap<String, String>,
+}
+
+impl Session {
+  pub fn new(platform: Platform) -> Self {
+    Session {
+      platform,
+      options: TransformOptions::default(),
+      syntax: SourceSyntax::Tsx,
+      is_entry: false,
+      stylesheet: false,
+      arkui: false,
+      dts: false,
+      match_report: false,
+      ir: false,
+      components: IndexMap::new(),
+      styles: HashMap::new(),
+    }
+  }
+
+  pub fn options(mut self, options: TransformOptions) -> Self {
+    self.options = options;
+    self
+  }
+
+  pub fn syntax(mut self, syntax: SourceSyntax) -> Self {
+    self.syntax = syntax;
+    self
+  }
+
+  // 是否是入口文件
+  pub fn entry(mut self, is_entry: bool) -> Self {
+    self.is_entry = is_entry;
+    self
+  }
+
+  pub fn stylesheet(mut self, stylesheet: bool) -> Self {
+    self.stylesheet = stylesheet;
+    self
+  }
+
+  pub fn arkui(mut self, arkui: bool) -> Self {
+    self.arkui = arkui;
+    self
+  }
+
+  pub fn dts(mut self, dts: bool) -> Self {
+    self.dts = dts;
+    self
+  }
+
+  pub fn match_report(mut self, match_report: bool) -> Self {
+    self.match_report = match_report;
+    self
+  }
+
+  pub fn ir(mut self, ir: bool) -> Self {
+    self.ir = ir;
+    self
+  }
+
+  // 登记组件及其依赖的样式文件，已登记的组件会被覆盖，返回组件的转换结果
+  pub fn add_component(&mut self, path: &str, code: String, style_paths: Vec<String>) -> Vec<Artifact> {
+    self.insert_component(path, ComponentKind::Component, code, style_paths);
+    self.emit(vec![path.to_string()])
+  }
+
+  // 登记应用入口，其样式文件为各页面共享的全局样式，已登记的应用入口会被替换，
+  // 全局样式变化时页面需要重新转换，返回应用入口及输出发生变化的页面
+  pub fn add_app(&mut self, path: &str, code: String, style_paths: Vec<String>) -> Vec<Artifact> {
+    self.components.retain(|app_path, component| component.kind != ComponentKind::App || app_path == path);
+    self.insert_component(path, ComponentKind::App, code, style_paths);
+    let mut paths = vec![path.to_string()];
+    paths.extend(self.get_page_paths());
+    self.emit(paths)
+  }
+
+  // 登记页面及其自身的样式文件，转换时全局样式在页面样式之前合并，选择器仅与本页面的节点匹配
+  pub fn add_page(&mut self, path: &str, code: String, style_paths: Vec<String>) -> Vec<Artifact> {
+    self.insert_component(path, ComponentKind::Page, code, style_paths);
+    self.emit(vec![path.to_string()])
+  }
+
+  fn insert_component(&mut self, path: &str, kind: ComponentKind, code: String, style_paths: Vec<String>) {
+    self.components.insert(path.to_string(), Component {
+      kind,
+      code,
+      style_paths,
+      last_code: None,
+    });
+  }
+
+  fn get_page_paths(&self) -> Vec<String> {
+    self.components
+      .iter()
+      .filter(|(_, component)| component.kind == ComponentKind::Page)
+      .map(|(path, _)| path.clone())
+      .collect()
+  }
+
+  // 全局样式文件，即应用入口的样式文件
+  fn get_global_style_paths(&self) -> Vec<String> {
+    self.components
+      .values()
+      .find(|component| component.kind == ComponentKind::App)
+      .map(|component| component.style_paths.clone())
+      .unwrap_or_default()
+  }
+
+  // 组件转换时合并的样式文件，页面为全局样式及页面自身的样式，页面中重复引入的全局样式只合并一次
+  fn get_style_paths(&self, component: &Component) -> Vec<String> {
+    if component.kind != ComponentKind::Page {
+      return component.style_paths.clone()
+    }
+    let mut style_paths = self.get_global_style_paths();
+    component.style_paths.iter().for_each(|style_path| {
+      if !style_paths.contains(style_path) {
+        style_paths.push(style_path.clone());
+      }
+    });
+    style_paths
+  }
+
+  // 依赖样式文件的组件，全局样式文件被所有页面依赖
+  fn get_dependents(&self, path: &str) -> Vec<String> {
+    self.components
+      .iter()
+      .filter(|(_, component)| self.get_style_paths(component).iter().any(|style_path| style_path == path))
+      .map(|(path, _)| path.clone())
+      .collect()
+  }
+
+  // 移除应用入口时全局样式随之移除，页面需要重新转换
+  pub fn remove_component(&mut self, path: &str) -> Vec<Artifact> {
+    match self.components.shift_remove(path) {
+      Some(component) if component.kind == ComponentKind::App => self.emit(self.get_page_paths()),
+      _ => vec![]
+    }
+  }
+
+  // 组件文件变更，内容未变化或未登记的组件不需要重新输出
+  pub fn jsx_changed(&mut self, path: &str, new_content: String) -> Vec<Artifact> {
+    match self.components.get_mut(path) {
+      Some(component) if component.code != new_content => {
+        component.code = new_content;
+        self.emit(vec![path.to_string()])
+      },
+      _ => vec![]
+    }
+  }
+
+  // 样式文件变更，重新转换所有依赖该样式文件的组件
+  pub fn css_changed(&mut self, path: &str, new_content: String) -> Vec<Artifact> {
+    if self.styles.get(path) == Some(&new_content) {
+      return vec![]
+    }
+    self.styles.insert(path.to_string(), new_content);
+    self.emit(self.get_dependents(path))
+  }
+
+  // 样式文件删除，依赖该样式文件的组件按缺少该文件重新转换
+  pub fn css_removed(&mut self, path: &str) -> Vec<Artifact> {
+    match self.styles.remove(path) {
+      Some(_) => self.emit(self.get_dependents(path)),
+      None => vec![]
+    }
+  }
+
+  // 重新转换组件，仅返回输出代码发生变化的组件
+  fn emit(&mut self, paths: Vec<String>) -> Vec<Artifact> {
+    let mut artifacts = vec![];
+    for path in paths {
+      let Some(component) = self.components.get(&path) else {
+        continue
+      };
+      // 尚未读取到内容的样式文件按空文件处理
+      let styles = self.get_style_paths(component)
+        .iter()
+        .filter_map(|style_path| self.styles.get(style_path).cloned())
+        .collect::<Vec<String>>();
+      // 页面的样式中全局样式在前，供 stylePrecedence 区分全局样式与页面样式
+      let mut options = self.options.clone();
+      if component.kind == ComponentKind::Page {
+        options.global_style_count = self.get_global_style_paths().iter().filter(|style_path| self.styles.contains_key(*style_path)).count();
+      }
+      let result = Transformer::new(component.code.clone(), styles)
+        .platform(self.platform.clone())
+        .options(options)
+        .entry(self.is_entry || component.kind == ComponentKind::App)
+        .syntax(self.syntax.clone())
+        .stylesheet(self.stylesheet)
+        .arkui(self.arkui)
+        .dts(self.dts)
+        .match_report(self.match_report)
+        .ir(self.ir)
+        .filename(path.clone())
+        .transform();
+      let component = self.components.get_mut(&path).unwrap();
+      if component.last_code.as_ref() == Some(&result.code) {
+        continue
+      }
+      component.last_code = Some(result.code.clone());
+      artifacts.push(Artifact { path, result });
+    }
+    artifacts
+  }
+}
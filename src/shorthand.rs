@@ -0,0 +1,59 @@
+use lightningcss::properties::Property;
+
+// 简写属性展开为对应的普通属性，如 margin: 10px 20px => margin-top、margin-right、margin-bottom、margin-left
+// 在样式合并之前展开，保证简写属性与普通属性之间按声明顺序正确覆盖
+pub fn expand_shorthand<'i>(property: &Property<'i>) -> Vec<Property<'i>> {
+  match property {
+    Property::Margin(value) => vec![
+      Property::MarginTop(value.top.clone()),
+      Property::MarginRight(value.right.clone()),
+      Property::MarginBottom(value.bottom.clone()),
+      Property::MarginLeft(value.left.clone()),
+    ],
+    Property::Padding(value) => vec![
+      Property::PaddingTop(value.top.clone()),
+      Property::PaddingRight(value.right.clone()),
+      Property::PaddingBottom(value.bottom.clone()),
+      Property::PaddingLeft(value.left.clone()),
+    ],
+    Property::Inset(value) => vec![
+      Property::Top(value.top.clone()),
+      Property::Right(value.right.clone()),
+      Property::Bottom(value.bottom.clone()),
+      Property::Left(value.left.clone()),
+    ],
+    Property::BorderWidth(value) => vec![
+      Property::BorderTopWidth(value.top.clone()),
+      Property::BorderRightWidth(value.right.clone()),
+      Property::BorderBottomWidth(value.bottom.clone()),
+      Property::BorderLeftWidth(value.left.clone()),
+    ],
+    Property::BorderColor(value) => vec![
+      Property::BorderTopColor(value.top.clone()),
+      Property::BorderRightColor(value.right.clone()),
+      Property::BorderBottomColor(value.bottom.clone()),
+      Property::BorderLeftColor(value.left.clone()),
+    ],
+    Property::BorderStyle(value) => vec![
+      Property::BorderTopStyle(value.top),
+      Property::BorderRightStyle(value.right),
+      Property::BorderBottomStyle(value.bottom),
+      Property::BorderLeftStyle(value.left),
+    ],
+    // font-stretch、font-variant-caps 暂不支持，不展开
+    Property::Font(value) => vec![
+      Property::FontFamily(value.family.clone()),
+      Property::FontSize(value.size.clone()),
+      Property::FontStyle(value.style.clone()),
+      Property::FontWeight(value.weight.clone()),
+      Property::LineHeight(value.line_height.clone()),
+    ],
+    // mask 仅展开鸿蒙支持的 mask-image、mask-position、mask-size
+    Property::Mask(masks, prefix) => vec![
+      Property::MaskImage(masks.iter().map(|mask| mask.image.clone()).collect(), *prefix),
+      Property::MaskPosition(masks.iter().map(|mask| mask.position.clone()).collect(), *prefix),
+      Property::MaskSize(masks.iter().map(|mask| mask.size.clone()).collect(), *prefix),
+    ],
+    _ => vec![property.clone()]
+  }
+}
@@ -0,0 +1,101 @@
+use std::{cell::RefCell, fmt, time::{Duration, Instant}};
+
+// 一次转换的性能统计，用于在大型项目中跟踪转换耗时及选择器匹配的变化
+#[derive(Debug, Clone, Default)]
+pub struct TransformStats {
+  // 解析的样式规则数量，嵌套规则及 @media、@supports 等规则中的样式规则均计入
+  pub rules_parsed: usize,
+  // 匹配到节点的选择器数量
+  pub rules_matched: usize,
+  // 组件中解析出的 JSX 节点数量
+  pub nodes_visited: usize,
+  // 按类名索引查找节点的次数
+  pub selector_lookups: usize,
+  // 类名索引中存在候选节点的次数
+  pub selector_hits: usize,
+  // 字符串驻留的查找次数
+  pub interner_lookups: usize,
+  // 命中已驻留字符串的次数
+  pub interner_hits: usize,
+  // 各阶段的耗时，按执行顺序排列，如 parse_component、parse_style、calc_style、write_style、codegen
+  pub phases: Vec<(String, Duration)>,
+}
+
+impl TransformStats {
+  pub fn selector_hit_rate(&self) -> f64 {
+    hit_rate(self.selector_hits, self.selector_lookups)
+  }
+
+  pub fn interner_hit_rate(&self) -> f64 {
+    hit_rate(self.interner_hits, self.interner_lookups)
+  }
+
+  pub fn total_duration(&self) -> Duration {
+    self.phases.iter().map(|(_, duration)| *duration).sum()
+  }
+}
+
+fn hit_rate(hits: usize, lookups: usize) -> f64 {
+  match lookups {
+    0 => 0.0,
+    _ => hits as f64 / lookups as f64
+  }
+}
+
+impl fmt::Display for TransformStats {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "stats: {} rules parsed, {} rules matched, {} nodes, selector index hit rate {:.1}%, interner hit rate {:.1}%, total {:.2}ms",
+      self.rules_parsed,
+      self.rules_matched,
+      self.nodes_visited,
+      self.selector_hit_rate() * 100.0,
+      self.interner_hit_rate() * 100.0,
+      self.total_duration().as_secs_f64() * 1000.0
+    )?;
+    self.phases.iter().try_for_each(|(name, duration)| write!(f, ", {} {:.2}ms", name, duration.as_secs_f64() * 1000.0))
+  }
+}
+
+thread_local! {
+  static STATS: RefCell<TransformStats> = RefCell::new(TransformStats::default());
+}
+
+fn update_stats(f: impl FnOnce(&mut TransformStats)) {
+  STATS.with(|cell| f(&mut cell.borrow_mut()));
+}
+
+pub fn record_rule_parsed() {
+  update_stats(|stats| stats.rules_parsed += 1);
+}
+
+pub fn record_rule_matched() {
+  update_stats(|stats| stats.rules_matched += 1);
+}
+
+pub fn record_selector_lookup(hit: bool) {
+  update_stats(|stats| {
+    stats.selector_lookups += 1;
+    if hit {
+      stats.selector_hits += 1;
+    }
+  });
+}
+
+pub fn record_phase(name: &str, duration: Duration) {
+  update_stats(|stats| stats.phases.push((name.to_string(), duration)));
+}
+
+// 执行并记录一个阶段的耗时
+pub fn time_phase<R>(name: &str, f: impl FnOnce() -> R) -> R {
+  let start = Instant::now();
+  let result = f();
+  record_phase(name, start.elapsed());
+  result
+}
+
+// 取出统计信息并清零
+pub fn take_transform_stats() -> TransformStats {
+  STATS.with(|cell| std::mem::take(&mut *cell.borrow_mut()))
+}
@@ -0,0 +1,110 @@
+use std::convert::Infallible;
+
+use lightningcss::{
+  declaration::DeclarationBlock,
+  properties::Property,
+  rules::{CssRule, Location},
+  stylesheet::{PrinterOptions, StyleSheet},
+  traits::ToCss,
+  visit_types,
+  visitor::{Visit, VisitTypes, Visitor},
+};
+use swc_core::atoms::Atom;
+
+use crate::{
+  diagnostics::{push_error, Diagnostic, DiagnosticSeverity},
+  interner::intern_property_name,
+  location::DeclarationLocator,
+  parse_style_properties::parse_style_properties,
+  style_parser::format_source_position,
+  style_propetries::unit::Platform,
+  supports::evaluate_supports_condition,
+  utils::normalize_style_key,
+  validate::is_invalid_value,
+  visitor::parse_raw_style_values,
+};
+
+// 同一条规则中为其他属性提供计算依据的属性，如百分比的 vertical-align 依赖 line-height
+const CONTEXT_PROPERTIES: [&str; 6] = ["fontSize", "lineHeight", "width", "height", "counterReset", "counterIncrement"];
+
+// 严格模式：检查转换后被丢弃（Invalid）的声明，逐条输出为带声明位置的错误
+pub fn check_stylesheet(stylesheet: &mut StyleSheet, css: &str, style_starts: &[u32], platform: &Platform) {
+  let mut visitor = StrictVisitor { css, style_starts, platform };
+  stylesheet.visit(&mut visitor).unwrap();
+}
+
+// 严格模式下转换失败的错误信息，包含所有错误级别的诊断信息，没有错误时返回 None
+pub fn get_strict_error(diagnostics: &[Diagnostic]) -> Option<String> {
+  let errors = diagnostics.iter().filter(|diagnostic| diagnostic.severity == DiagnosticSeverity::Error).collect::<Vec<_>>();
+  if errors.is_empty() {
+    return None
+  }
+  let mut message = format!("strict mode: {} unsupported declaration(s) found", errors.len());
+  errors.iter().for_each(|diagnostic| message.push_str(&format!("\n  [{}] {}", diagnostic.property, diagnostic.message)));
+  Some(message)
+}
+
+struct StrictVisitor<'a> {
+  css: &'a str,
+  style_starts: &'a [u32],
+  platform: &'a Platform,
+}
+
+impl<'a, 'i> Visitor<'i> for StrictVisitor<'a> {
+  type Error = Infallible;
+  const TYPES: VisitTypes = visit_types!(RULES);
+
+  fn visit_rule(&mut self, rule: &mut CssRule<'i>) -> Result<(), Self::Error> {
+    match rule {
+      CssRule::Style(style) => {
+        let selector = style.selectors.to_css_string(PrinterOptions::default()).unwrap_or_default();
+        let mut locator = DeclarationLocator::new(self.css, &style.loc, 1);
+        self.check_declarations(&selector, &style.declarations, &style.loc, &mut locator);
+      },
+      // 条件不成立的 @supports 中的规则不会输出，通常是为其他平台准备的样式
+      CssRule::Supports(supports_rule) if !evaluate_supports_condition(&supports_rule.condition, self.platform) => {
+        return Ok(())
+      },
+      _ => {}
+    }
+    rule.visit_children(self)
+  }
+}
+
+impl<'a> StrictVisitor<'a> {
+  fn check_declarations(&self, selector: &str, declarations: &DeclarationBlock, rule_loc: &Location, locator: &mut DeclarationLocator) {
+    let properties = declarations.declarations.iter().chain(declarations.important_declarations.iter()).map(|property| {
+      (intern_property_name(property), property.clone())
+    }).collect::<Vec<(Atom, Property)>>();
+    properties.iter().enumerate().for_each(|(index, (id, property))| {
+      // 未在源码中找到声明时使用规则的位置
+      let loc = locator.locate(property).unwrap_or(*rule_loc);
+      // 无法解析的值已在检查属性值时输出错误
+      if is_invalid_value(property) {
+        return
+      }
+      // 转换依赖的属性放在前面，只取当前声明转换出的样式
+      let mut context = properties.iter().enumerate().filter(|(other_index, (other_id, _))| {
+        *other_index != index && CONTEXT_PROPERTIES.contains(&other_id.as_str())
+      }).map(|(_, property)| property.clone()).collect::<Vec<_>>();
+      let context_len = parse_style_properties(&context, None).len();
+      context.push((id.clone(), property.clone()));
+      let style_values = parse_style_properties(&context, None).split_off(context_len);
+      // 没有转换结果的声明（如 counter-reset）仅用于计算，不视为丢弃
+      if style_values.is_empty() || !parse_raw_style_values(style_values, self.platform.clone()).is_empty() {
+        return
+      }
+      push_error(
+        &normalize_style_key(id),
+        format!(
+          "{} {{ {}: {} }} is not supported on {:?} at {}",
+          selector,
+          property.property_id().to_css_string(PrinterOptions::default()).unwrap_or_default(),
+          property.value_to_css_string(PrinterOptions::default()).unwrap_or_default(),
+          self.platform,
+          format_source_position(loc.line, loc.column, self.style_starts)
+        )
+      );
+    });
+  }
+}
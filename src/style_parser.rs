@@ -1,19 +1,62 @@
-use std::{rc::Rc, cell::RefCell, convert::Infallible, collections::HashMap, hash::Hash};
+use std::{rc::Rc, cell::RefCell, cmp::Reverse, sync::{Arc, RwLock}, convert::Infallible, collections::{HashMap, HashSet}, hash::Hash};
 
-use lightningcss::{declaration::DeclarationBlock, properties::Property, rules::{keyframes::KeyframeSelector, CssRule}, stylesheet::{ParserOptions, PrinterOptions, StyleSheet}, traits::ToCss, visit_types, visitor::{Visit, VisitTypes, Visitor}};
+use lightningcss::{declaration::DeclarationBlock, error::ErrorLocation, media_query::{MediaCondition, MediaFeatureId, MediaFeatureName, MediaFeatureValue, MediaList, MediaType, Qualifier, QueryFeature}, properties::Property, properties::font::FontFamily, rules::{font_face::{FontFaceProperty, Source}, keyframes::KeyframeSelector, style::StyleRule, CssRule, CssRuleList}, values::easing::EasingFunction, stylesheet::{ParserFlags, ParserOptions, PrinterOptions, StyleSheet}, traits::ToCss, visit_types, visitor::{Visit, VisitTypes, Visitor}};
 
-use crate::{constants::SUPPORT_PSEUDO_KEYS, document::JSXDocument, style_propetries::{style_value_type::StyleValueType, unit::Platform}, utils::{is_tailwind_arbitrary, to_camel_case}, visitor::SpanKey};
+use indexmap::IndexMap;
+use swc_core::{atoms::Atom, ecma::visit::VisitWith};
+use tracing::trace;
+
+use crate::{constants::SUPPORT_PSEUDO_KEYS, container::{collect_query_containers, evaluate_container_rule, ContainerMatch, ContainerQuery}, diagnostics::{push_diagnostic, push_error}, document::{JSXDocument, NegationMatch}, fallback::apply_fallback_rules, options::with_transform_options, shorthand::expand_shorthand, supports::evaluate_supports_condition, theme::{clear_theme_variables, push_theme_variable}, style_propetries::{style_value_type::StyleValueType, unit::Platform}, important::{resolve_important_styles, ImportantRecord}, inherit::{inherit_text_styles, lint_text_styles}, interner::intern_property_name, keyframes::{get_keyframes_name, resolve_keyframes_collisions}, layer::{remove_shadowed_layer_declarations, wrap_global_styles, LayerOrder}, lint::lint_stylesheet, scope::{generate_scope_id, scope_selector}, stats::{record_rule_matched, record_rule_parsed}, strict::check_stylesheet, tag::{has_tag_selector, normalize_tag_selector}, utils::{expand_functional_pseudo, get_selector_classes, is_tailwind_arbitrary, normalize_compound_selector, split_top_level}, validate::validate_stylesheet, visitor::{ClassNameLiteralVisitor, SpanKey, StringLiteralVisitor}};
 
 use super::parse_style_properties::parse_style_properties;
 
 pub type StyleValue = Vec<StyleValueType>;
+// 选择器 => 样式，使用有序表保证多次构建的输出一致
+pub type StyleMap = IndexMap<String, StyleValue>;
+// 节点 => 编译期生成的类名，如兄弟选择器、文本样式继承
+pub type GeneratedClassRecord = IndexMap<SpanKey, Vec<String>>;
+// 按选择器收集的样式声明 (selector, vec[declaration1, declaration2, ...])
+pub type StyleRecord<'i> = Vec<(String, Vec<StyleDeclaration<'i>>)>;
+// @container 规则 => 该规则中的样式，按规则的声明顺序排列
+pub type ContainerStyle = Vec<(ContainerQuery, StyleMap)>;
+type ContainerStyleRecord<'i> = Vec<(ContainerQuery, StyleRecord<'i>)>;
+// (优先级, 选择器)
+type SelectorItem = (u32, String);
 
-pub struct StyleData<'i> {
-  pub pesudo_style_record: Rc<RefCell<HashMap<SpanKey, Vec<(String, Vec<(String, Property<'i>)>)>>>>,
-  pub all_style: Rc<RefCell<HashMap<String, StyleValue>>>,
+pub struct StyleData {
+  pub all_style: Rc<RefCell<StyleMap>>,
+  // 暗色模式下的完整样式（亮色样式合并暗色样式），key 与 all_style 一致
+  pub dark_style: Rc<RefCell<StyleMap>>,
+  // 运行时根据容器尺寸判断是否生效的样式
+  pub container_style: Rc<RefCell<ContainerStyle>>,
+  pub font_faces: Vec<FontFaceItem>,
+  pub generated_classes: Rc<RefCell<GeneratedClassRecord>>,
+  // 节点 => 类样式中生效的 !important 声明
+  pub important_record: ImportantRecord,
+  pub class_usage: ClassUsage,
   pub has_nesting: bool
 }
 
+// 组件中 className 的类名与样式的对应情况，均按字母序排列
+#[derive(Debug, Default, Clone)]
+pub struct ClassUsage {
+  // 有对应样式的类名
+  pub used: Vec<String>,
+  // 没有对应样式的类名，可能是拼写错误，动态 className 中的字符串、模板字符串、对象的 key 也计入
+  pub unmatched: Vec<String>,
+  // 样式表中未被组件引用的类名，类名未出现在 className 及任何字符串字面量中时视为未引用
+  pub unused: Vec<String>,
+}
+
+// 解析后的样式，多平台转换时共用
+pub struct ParsedStyles<'i> {
+  pub css: &'i str,
+  // 各样式文件在拼接后的样式中的起始行
+  pub style_starts: Vec<u32>,
+  // 解析失败时为 None
+  pub stylesheet: Option<StyleSheet<'i, 'i>>,
+}
+
 pub struct KeyFramesData {
   pub name: String,
   pub keyframes: Vec<KeyFrameItem>
@@ -22,32 +65,343 @@ pub struct KeyFramesData {
 #[derive(Debug)]
 pub struct KeyFrameItem {
   pub percentage: f32,
+  // 帧内声明的 animation-timing-function，作用于当前帧到下一帧的过渡
+  pub timing_function: Option<EasingFunction>,
   pub declarations: Vec<StyleValueType>
 }
 
+// @font-face 声明的字体，供运行时调用 registerFont 注册
+#[derive(Debug, Clone)]
+pub struct FontFaceItem {
+  pub family_name: String,
+  // 仅保留 url() 字体资源，local() 为系统字体无需注册
+  pub src: Vec<String>,
+  pub font_weight: Option<String>,
+  pub font_style: Option<String>
+}
+
 #[derive(Debug, Clone)]
 pub struct StyleDeclaration<'i> {
   pub specificity: u32,
+  // 所属的 @layer 层级，未分层时为 None
+  pub layer: Option<String>,
   pub declaration: DeclarationBlock<'i>,
 }
 
 struct StyleVisitor<'i> {
-  all_style: Rc<RefCell<Vec<(String, Vec<StyleDeclaration<'i>>)>>>,
+  platform: Platform,
+  document: &'i JSXDocument,
+  all_style: Rc<RefCell<StyleRecord<'i>>>,
+  dark_style: Rc<RefCell<StyleRecord<'i>>>,
+  container_style: Rc<RefCell<ContainerStyleRecord<'i>>>,
   keyframes: Rc<RefCell<HashMap<String, Vec<KeyFrameItem>>>>,
+  font_faces: Rc<RefCell<Vec<FontFaceItem>>>,
+  generated_classes: Rc<RefCell<GeneratedClassRecord>>,
+  layers: Rc<RefCell<LayerOrder>>,
+  // 当前所在的 @layer 层级
+  current_layer: Option<String>,
 }
 
 impl<'i> StyleVisitor<'i> {
   pub fn new(
-    all_style: Rc<RefCell<Vec<(String, Vec<StyleDeclaration<'i>>)>>>,
+    platform: Platform,
+    document: &'i JSXDocument,
+    all_style: Rc<RefCell<StyleRecord<'i>>>,
+    dark_style: Rc<RefCell<StyleRecord<'i>>>,
+    container_style: Rc<RefCell<ContainerStyleRecord<'i>>>,
     keyframes: Rc<RefCell<HashMap<String, Vec<KeyFrameItem>>>>,
+    font_faces: Rc<RefCell<Vec<FontFaceItem>>>,
+    generated_classes: Rc<RefCell<GeneratedClassRecord>>,
+    layers: Rc<RefCell<LayerOrder>>,
   ) -> Self {
     StyleVisitor {
+      platform,
+      document,
       all_style,
-      keyframes
+      dark_style,
+      container_style,
+      keyframes,
+      font_faces,
+      generated_classes,
+      layers,
+      current_layer: None
+    }
+  }
+
+  // 含 +、~ 或标签选择器的选择器在编译期匹配节点，匹配的节点添加生成的类名，选择器替换为该类名，
+  // 如 .item + .item => .__sibling_s1a2b3c4d、view > .item => .__tag_s1a2b3c4d，没有匹配的节点时返回 None
+  fn resolve_static_selector(&self, selector: &str) -> Option<String> {
+    let is_sibling = selector.contains(" + ") || selector.contains(" ~ ");
+    if !is_sibling && !has_tag_selector(selector) {
+      return Some(selector.to_string())
+    }
+    let (diagnostic_property, prefix) = if is_sibling { ("sibling", "__sibling") } else { ("tag", "__tag") };
+    let Some(static_match) = self.document.select_static(selector) else {
+      push_diagnostic(diagnostic_property, format!("{} is not supported, only class selectors and tag selectors are allowed, the rule is ignored", selector));
+      return None
+    };
+    if static_match.uncertain {
+      push_diagnostic(diagnostic_property, format!("{} can not be fully resolved at compile time, elements rendered in expressions (such as list.map, conditional rendering or render props) are not matched", selector));
+    }
+    if static_match.elements.is_empty() {
+      return None
+    }
+    // 标签名按 tagMap 统一，view、div 等写法生成相同的类名
+    let class = format!("{}_{}", prefix, generate_scope_id(&normalize_tag_selector(selector)));
+    let mut generated_classes = self.generated_classes.borrow_mut();
+    for span in static_match.elements {
+      let classes = generated_classes.entry(span).or_default();
+      if !classes.contains(&class) {
+        classes.push(class.clone());
+      }
+    }
+    Some(format!(".{}", class))
+  }
+
+  // 将样式规则按选择器收集到 all_style 中，is_dark 为 true 时 all_style 为暗色模式的样式
+  fn collect_style_rule(&self, style: &StyleRule<'i>, all_style: &mut StyleRecord<'i>, is_dark: bool) {
+    let selectors = get_selector_items(style);
+    self.collect_declarations(&selectors, &style.declarations, all_style);
+    self.collect_nested_rules(&selectors, &style.rules, all_style, is_dark);
+  }
+
+  // CSS 嵌套规则按父选择器展开后收集，如 .a { &:hover {} & .b {} } => .a:hover、.a .b
+  // 嵌套的 @media (prefers-color-scheme: dark) 在亮色样式中由 collect_nested_dark_rules 单独收集
+  fn collect_nested_rules(&self, parents: &[SelectorItem], rules: &CssRuleList<'i>, all_style: &mut StyleRecord<'i>, is_dark: bool) {
+    for rule in rules.0.iter() {
+      match rule {
+        CssRule::Style(style) => {
+          let selectors = resolve_nesting_selectors(parents, style);
+          self.collect_declarations(&selectors, &style.declarations, all_style);
+          self.collect_nested_rules(&selectors, &style.rules, all_style, is_dark);
+        }
+        CssRule::Media(media_rule) if is_dark && is_dark_color_scheme_query(&media_rule.query) => {
+          self.collect_nested_rules(parents, &media_rule.rules, all_style, is_dark);
+        }
+        CssRule::Supports(supports_rule) if evaluate_supports_condition(&supports_rule.condition, &self.platform) => {
+          self.collect_nested_rules(parents, &supports_rule.rules, all_style, is_dark);
+        }
+        _ => {}
+      }
+    }
+  }
+
+  // 收集亮色样式规则中嵌套的 @media (prefers-color-scheme: dark)，如 .a { @media (prefers-color-scheme: dark) { color: white } }
+  fn collect_nested_dark_rules(&self, parents: &[SelectorItem], rules: &CssRuleList<'i>) {
+    for rule in rules.0.iter() {
+      match rule {
+        CssRule::Style(style) => {
+          self.collect_nested_dark_rules(&resolve_nesting_selectors(parents, style), &style.rules);
+        }
+        CssRule::Media(media_rule) if is_dark_color_scheme_query(&media_rule.query) => {
+          let mut dark_style = self.dark_style.borrow_mut();
+          self.collect_nested_rules(parents, &media_rule.rules, &mut dark_style, true);
+          add_placeholders(&mut self.all_style.borrow_mut(), &dark_style);
+        }
+        CssRule::Media(media_rule) => push_ignored_media_diagnostic(&media_rule.query),
+        CssRule::Supports(supports_rule) if evaluate_supports_condition(&supports_rule.condition, &self.platform) => {
+          self.collect_nested_dark_rules(parents, &supports_rule.rules);
+        }
+        _ => {}
+      }
+    }
+  }
+
+  fn collect_declarations(&self, selectors: &[SelectorItem], declaration_block: &DeclarationBlock<'i>, all_style: &mut StyleRecord<'i>) {
+    for (specificity, selector) in selectors.iter() {
+      // :is()、:where() 展开后的选择器均沿用原选择器的优先级
+      let specificity = *specificity;
+      let Some(expanded_selectors) = expand_functional_pseudo(selector) else {
+        push_diagnostic(":is", format!("{} is not supported, only class selectors are allowed in :is() and :where(), the rule is ignored", selector));
+        continue
+      };
+      for selector in expanded_selectors {
+        // 作用域模式下类名添加作用域前缀
+        let selector = scope_selector(&selector);
+        let Some(selector) = resolve_negation_selector(&selector, self.document) else {
+          continue
+        };
+        let Some(selector) = self.resolve_static_selector(&selector) else {
+          continue
+        };
+        // 复合类选择器统一类名顺序，保证 .a.b 与 .b.a 按声明顺序合并
+        let selector = normalize_compound_selector(&selector);
+        let decorations = all_style.iter_mut().find(|(id, _)| id == &selector);
+        if let Some((_, declarations)) = decorations {
+          declarations.push(StyleDeclaration {
+            specificity,
+            layer: self.current_layer.clone(),
+            declaration: declaration_block.clone(),
+          });
+        } else {
+          all_style.push((
+            selector.clone(),
+            vec![StyleDeclaration {
+              specificity,
+              layer: self.current_layer.clone(),
+              declaration: declaration_block.clone(),
+            }],
+          ));
+        }
+      }
+    }
+  }
+}
+
+fn get_selector_items(style: &StyleRule) -> Vec<SelectorItem> {
+  style.selectors.0.iter().map(|selector| {
+    // FEATURE: 优化 key 的生成 移除 key 中的 \\ 转义，以支持 tailwind.css 动态类名匹配
+    (selector.specificity(), selector.to_css_string(PrinterOptions::default()).unwrap().replace("\\", ""))
+  }).collect()
+}
+
+// 嵌套规则的选择器中 & 替换为父选择器，父选择器有多个时逐一展开，如 .a, .b { & .c {} } => .a .c、.b .c
+// & 的优先级为 0，展开后的优先级为父选择器与嵌套选择器的优先级之和
+fn resolve_nesting_selectors(parents: &[SelectorItem], style: &StyleRule) -> Vec<SelectorItem> {
+  let selectors = get_selector_items(style);
+  parents.iter().flat_map(|(parent_specificity, parent)| {
+    selectors.iter().map(move |(specificity, selector)| {
+      (parent_specificity + specificity, selector.replace('&', parent))
+    })
+  }).collect()
+}
+
+// 仅在暗色模式或 @container 中声明的选择器，在普通样式中添加空样式，保证节点能匹配到该选择器
+fn add_placeholders(all_style: &mut StyleRecord, style: &StyleRecord) {
+  style.iter().for_each(|(selector, _)| {
+    if !all_style.iter().any(|(id, _)| id == selector) {
+      all_style.push((selector.clone(), vec![]));
+    }
+  });
+}
+
+// 求值选择器中的 :not()，无法在编译期求值或不会生效时返回 None
+fn resolve_negation_selector(selector: &str, document: &JSXDocument) -> Option<String> {
+  if !selector.contains(":not(") {
+    return Some(selector.to_string())
+  }
+  let mut compounds = vec![];
+  for compound in split_top_level(selector, ' ') {
+    match document.resolve_negation(&compound) {
+      NegationMatch::Unaffected(compound) => compounds.push(compound),
+      NegationMatch::Excluded => return None,
+      NegationMatch::Partial => {
+        push_diagnostic(":not", format!("{} matches only part of the elements with the same className, which can not be distinguished at runtime, the rule is ignored", selector));
+        return None
+      }
+      NegationMatch::Unsupported => {
+        push_diagnostic(":not", format!("{} is not supported, only class selectors are allowed in :not(), the rule is ignored", selector));
+        return None
+      }
+    }
+  }
+  Some(compounds.join(" "))
+}
+
+// 解析样式并校验声明，与平台无关，多平台转换时只解析一次
+// css 为多个样式文件按换行拼接的结果，style_starts 为各样式文件在 css 中的起始行，用于定位语法错误
+pub fn parse_styles(css: &str, style_starts: Vec<u32>) -> ParsedStyles<'_> {
+  ParsedStyles {
+    css,
+    stylesheet: parse_stylesheet(css, &style_starts),
+    style_starts,
+  }
+}
+
+fn parse_stylesheet<'i>(css: &'i str, style_starts: &[u32]) -> Option<StyleSheet<'i, 'i>> {
+  // 开启 CSS 嵌套语法，嵌套规则在收集时按父选择器展开
+  // 开启错误恢复，忽略语法错误的规则及声明并记录错误位置，继续转换其他规则
+  let warnings = Arc::new(RwLock::new(vec![]));
+  let stylesheet = StyleSheet::parse(css, ParserOptions {
+    flags: ParserFlags::NESTING,
+    error_recovery: true,
+    warnings: Some(Arc::clone(&warnings)),
+    ..ParserOptions::default()
+  });
+  // 严格模式下被忽略的规则及声明视为错误
+  let strict = with_transform_options(|options| options.strict);
+  warnings.read().unwrap().iter().for_each(|warning| {
+    let message = format!("{} at {}, the invalid rule or declaration is ignored", warning.kind, get_source_position(warning.loc.as_ref(), style_starts));
+    match strict {
+      true => push_error("css", message),
+      false => push_diagnostic("css", message)
+    }
+  });
+  let mut stylesheet = match stylesheet {
+    Ok(stylesheet) => stylesheet,
+    Err(error) => {
+      let message = format!("{} at {}, failed to parse the styles", error.kind, get_source_position(error.loc.as_ref(), style_starts));
+      match strict {
+        true => push_error("css", message),
+        false => push_diagnostic("css", message)
+      }
+      return None
     }
+  };
+  validate_stylesheet(&mut stylesheet, css, style_starts);
+  resolve_keyframes_collisions(&mut stylesheet, style_starts);
+  Some(stylesheet)
+}
+
+fn get_layer_name(parent: Option<&str>, name: &str) -> String {
+  match parent {
+    Some(parent) => format!("{}.{}", parent, name),
+    None => name.to_string()
   }
 }
 
+// 语法错误的位置，多个样式文件时标明所在的文件序号，如 line 3, column 5 of styles[1]
+fn get_source_position(loc: Option<&ErrorLocation>, style_starts: &[u32]) -> String {
+  let Some(loc) = loc else {
+    return "unknown position".to_string()
+  };
+  format_source_position(loc.line, loc.column, style_starts)
+}
+
+// 行号所在的样式文件序号，line 为拼接后的 css 中从 0 开始的行号
+pub fn get_style_index(line: u32, style_starts: &[u32]) -> usize {
+  style_starts.iter().rposition(|start| *start <= line).unwrap_or(0)
+}
+
+// line 为拼接后的 css 中从 0 开始的行号
+pub fn format_source_position(line: u32, column: u32, style_starts: &[u32]) -> String {
+  let index = get_style_index(line, style_starts);
+  let line = line - style_starts.get(index).copied().unwrap_or(0) + 1;
+  match style_starts.len() > 1 {
+    true => format!("line {}, column {} of styles[{}]", line, column, index),
+    false => format!("line {}, column {}", line, column)
+  }
+}
+
+// 是否为仅以暗色模式为条件的 @media，如 (prefers-color-scheme: dark)、only screen and (prefers-color-scheme: dark)
+// 其他媒体特性无法在编译期求值，含有其他特性的查询不视为暗色模式
+fn is_dark_color_scheme_query(query: &MediaList) -> bool {
+  !query.media_queries.is_empty() && query.media_queries.iter().all(|query| {
+    query.qualifier != Some(Qualifier::Not)
+      && matches!(query.media_type, MediaType::All | MediaType::Screen)
+      && query.condition.as_ref().is_some_and(is_dark_color_scheme_condition)
+  })
+}
+
+fn is_dark_color_scheme_condition(condition: &MediaCondition) -> bool {
+  match condition {
+    MediaCondition::Feature(QueryFeature::Plain {
+      name: MediaFeatureName::Standard(MediaFeatureId::PrefersColorScheme),
+      value: MediaFeatureValue::Ident(ident)
+    }) => ident.0.eq_ignore_ascii_case("dark"),
+    MediaCondition::Operation { conditions, .. } => conditions.iter().all(is_dark_color_scheme_condition),
+    _ => false
+  }
+}
+
+// 暗色模式以外的 @media 无法在编译期求值，其中的规则被丢弃
+fn push_ignored_media_diagnostic(query: &MediaList) {
+  push_diagnostic("@media", format!(
+    "@media {} is not supported, only prefers-color-scheme: dark can be resolved, the rule is ignored",
+    query.to_css_string(PrinterOptions::default()).unwrap_or_default()
+  ));
+}
+
 // 收集所有的样式到 all_style 中，以元祖的形式存在 (selector, vec[declaration1, declaration2, ...])
 impl<'i> Visitor<'i> for StyleVisitor<'i> {
   type Error = Infallible;
@@ -57,65 +411,82 @@ impl<'i> Visitor<'i> for StyleVisitor<'i> {
     match rule {
       // 属性规则收集
       CssRule::Style(style) => {
+        record_rule_parsed();
         let selectors_str = style.selectors.to_string();
-        // FEATURE: 按照,分割选择器，且保证,前面不是转义字符，以支持 tailwind.css 动态类名
-        let selectors: Vec<&str> = selectors_str.split("(?<!\\\\),").collect::<Vec<&str>>();
-        for index in 0..selectors.len() {
-          // FEATURE: 优化 key 的生成 移除 key 中的 \\ 转义，以支持 tailwind.css 动态类名匹配
-          let selector = selectors[index].trim().to_string().replace("\\", "");
-          let mut all_style = self.all_style.borrow_mut();
-          let decorations = all_style.iter_mut().find(|(id, _)| id == &selector);
-          if let Some((_, declarations)) = decorations {
-            declarations.push(StyleDeclaration {
-              specificity: style.selectors.0.get(index).unwrap().specificity(),
-              declaration: style.declarations.clone(),
-            });
-          } else {
-            all_style.push((
-              selector.clone(),
-              vec![StyleDeclaration {
-                specificity: style.selectors.0.get(index).unwrap().specificity(),
-                declaration: style.declarations.clone(),
-              }],
-            ));
-          }
+        // :root 中的 css 变量记录为主题变量，需在解析其他规则（如 @keyframes）前收集
+        if selectors_str == ":root" {
+          style.declarations.declarations.iter().chain(style.declarations.important_declarations.iter()).for_each(|property| {
+            if let Property::Custom(_) = property {
+              push_theme_variable(
+                property.property_id().to_css_string(PrinterOptions::default()).unwrap(),
+                property.value_to_css_string(PrinterOptions::default()).unwrap_or_default()
+              );
+            }
+          });
         }
+        self.collect_style_rule(style, &mut self.all_style.borrow_mut(), false);
+        self.collect_nested_dark_rules(&get_selector_items(style), &style.rules);
       }
+      // 暗色模式样式收集，@media (prefers-color-scheme: dark) { ... }
+      CssRule::Media(media_rule) if is_dark_color_scheme_query(&media_rule.query) => {
+        let mut dark_style = self.dark_style.borrow_mut();
+        let mut all_style = self.all_style.borrow_mut();
+        media_rule.rules.0.iter().for_each(|rule| {
+          if let CssRule::Style(style) = rule {
+            self.collect_style_rule(style, &mut dark_style, true);
+          }
+        });
+        add_placeholders(&mut all_style, &dark_style);
+      }
+      CssRule::Media(media_rule) => push_ignored_media_diagnostic(&media_rule.query),
       // 动画收集
       CssRule::Keyframes(keyframes_rule) => {
         let mut keyframe_data = KeyFramesData {
-          name: keyframes_rule.name.to_css_string(PrinterOptions::default()).unwrap(),
+          name: get_keyframes_name(&keyframes_rule.name).to_string(),
           keyframes: vec![]
         };
         keyframes_rule.keyframes.clone().into_iter().for_each(|keyframe| {
+          let mut timing_function = None;
+          let properties = keyframe.declarations.declarations.iter().flat_map(expand_shorthand).filter_map(|property| {
+            // 帧内的 animation-timing-function 不作为样式，单独记录为当前帧的缓动
+            if let Property::AnimationTimingFunction(easing_functions, _) = &property {
+              timing_function = easing_functions.first().cloned();
+              return None
+            }
+            Some((
+              intern_property_name(&property),
+              property,
+            ))
+          })
+          .collect::<Vec<(_, _)>>();
+          // 0%, 100% { ... } 多个选择器共用同一份声明
           keyframe.selectors.into_iter().for_each(|selector| {
-            let properties = keyframe.declarations.iter().map(|property| {
-              (
-                to_camel_case(
-                  property.0
-                    .property_id()
-                    .to_css_string(PrinterOptions::default())
-                    .unwrap()
-                    .as_str(),
-                  false,
-                ),
-                property.0.clone(),
-              )
-            })
-            .collect::<Vec<(_, _)>>(); // Speci
-            let keyframe_item = KeyFrameItem {
-              percentage: match selector {
-                KeyframeSelector::Percentage(percentage) => {
-                  percentage.0
-                }
-                KeyframeSelector::From => 0.0,
-                KeyframeSelector::To => 1.0,
-              },
-              declarations: parse_style_properties(&properties, None)
+            let percentage = match selector {
+              KeyframeSelector::Percentage(percentage) => {
+                percentage.0
+              }
+              KeyframeSelector::From => 0.0,
+              KeyframeSelector::To => 1.0,
             };
-
-            keyframe_data.keyframes.push(keyframe_item)
-            
+            // 超出 0% ~ 100% 的帧无效
+            if !(0.0..=1.0).contains(&percentage) {
+              push_diagnostic("animationName", format!("keyframe selector {}% of @keyframes {} is out of range and will be ignored", percentage * 100.0, keyframe_data.name));
+              return
+            }
+            let declarations = parse_style_properties(&properties, None);
+            // 相同百分比的帧合并，后声明的覆盖先声明的
+            if let Some(keyframe_item) = keyframe_data.keyframes.iter_mut().find(|item| item.percentage == percentage) {
+              keyframe_item.declarations.extend(declarations);
+              if timing_function.is_some() {
+                keyframe_item.timing_function = timing_function.clone();
+              }
+            } else {
+              keyframe_data.keyframes.push(KeyFrameItem {
+                percentage,
+                timing_function: timing_function.clone(),
+                declarations
+              })
+            }
           });
         });
         // 更具percentage排序
@@ -124,6 +495,106 @@ impl<'i> Visitor<'i> for StyleVisitor<'i> {
         let mut keyframes = self.keyframes.borrow_mut();
         keyframes.insert(keyframe_data.name, keyframe_data.keyframes);
       }
+      // @supports 按平台特性表在编译期求值，条件成立时收集其中的规则
+      CssRule::Supports(supports_rule) if evaluate_supports_condition(&supports_rule.condition, &self.platform) => {
+        for rule in supports_rule.rules.0.iter_mut() {
+          self.visit_rule(rule)?;
+        }
+      }
+      // @layer a, b; 仅声明层级的顺序
+      CssRule::LayerStatement(layer_rule) => {
+        let mut layers = self.layers.borrow_mut();
+        layer_rule.names.iter().for_each(|name| {
+          layers.register(&get_layer_name(self.current_layer.as_deref(), &name.to_css_string(PrinterOptions::default()).unwrap()));
+        });
+      }
+      // @layer a { ... } 中的规则记录所属层级，嵌套的层级名为 父层级.子层级
+      CssRule::LayerBlock(layer_rule) => {
+        let name = match &layer_rule.name {
+          Some(name) => {
+            let name = get_layer_name(self.current_layer.as_deref(), &name.to_css_string(PrinterOptions::default()).unwrap());
+            self.layers.borrow_mut().register(&name);
+            name
+          }
+          None => self.layers.borrow_mut().register_anonymous(self.current_layer.as_deref())
+        };
+        let parent_layer = self.current_layer.replace(name);
+        for rule in layer_rule.rules.0.iter_mut() {
+          self.visit_rule(rule)?;
+        }
+        self.current_layer = parent_layer;
+      }
+      // @container 容器查询，容器尺寸已在之前的规则中静态声明时在编译期求值，否则收集为由运行时判断的条件样式
+      CssRule::Container(container_rule) if with_transform_options(|options| options.container_queries) => {
+        let name = container_rule.name.as_ref().map(|name| name.0.0.to_string());
+        let containers = collect_query_containers(&self.all_style.borrow());
+        match evaluate_container_rule(name.as_deref(), &container_rule.condition, &containers) {
+          ContainerMatch::Matched => {
+            for rule in container_rule.rules.0.iter_mut() {
+              self.visit_rule(rule)?;
+            }
+          }
+          ContainerMatch::Unmatched => {}
+          ContainerMatch::Unresolved(unresolved_containers) => {
+            let query = ContainerQuery {
+              name,
+              condition: container_rule.condition.to_css_string(PrinterOptions::default()).unwrap()
+            };
+            push_diagnostic("@container", format!(
+              "@container {}{} can not be resolved at compile time, unresolved containers: {}, the rule is evaluated at runtime",
+              query.name.as_ref().map(|name| format!("{} ", name)).unwrap_or_default(),
+              query.condition,
+              unresolved_containers.join(", ")
+            ));
+            let mut container_style = vec![];
+            container_rule.rules.0.iter().for_each(|rule| {
+              if let CssRule::Style(style) = rule {
+                self.collect_style_rule(style, &mut container_style, false);
+              }
+            });
+            add_placeholders(&mut self.all_style.borrow_mut(), &container_style);
+            self.container_style.borrow_mut().push((query, container_style));
+          }
+        }
+      }
+      // 字体收集
+      CssRule::FontFace(font_face_rule) => {
+        let mut font_face = FontFaceItem {
+          family_name: String::new(),
+          src: vec![],
+          font_weight: None,
+          font_style: None
+        };
+        font_face_rule.properties.iter().for_each(|property| {
+          match property {
+            FontFaceProperty::FontFamily(family) => {
+              font_face.family_name = match family {
+                FontFamily::FamilyName(name) => name.to_string(),
+                FontFamily::Generic(generic) => generic.to_css_string(PrinterOptions::default()).unwrap()
+              };
+            }
+            FontFaceProperty::Source(sources) => {
+              font_face.src = sources.iter().filter_map(|source| {
+                match source {
+                  Source::Url(url_source) => Some(url_source.url.url.to_string()),
+                  Source::Local(_) => None
+                }
+              }).collect();
+            }
+            FontFaceProperty::FontWeight(weight) => {
+              font_face.font_weight = weight.to_css_string(PrinterOptions::default()).ok();
+            }
+            FontFaceProperty::FontStyle(style) => {
+              font_face.font_style = style.to_css_string(PrinterOptions::default()).ok();
+            }
+            _ => {}
+          }
+        });
+        // 缺少字体名称或字体资源时无法注册
+        if !font_face.family_name.is_empty() && !font_face.src.is_empty() {
+          self.font_faces.borrow_mut().push(font_face);
+        }
+      }
       _ => {}
     }
     Ok(())
@@ -131,8 +602,13 @@ impl<'i> Visitor<'i> for StyleVisitor<'i> {
 }
 
 pub struct StyleParser<'i> {
-  pub all_style: Rc<RefCell<Vec<(String, Vec<StyleDeclaration<'i>>)>>>,
+  pub all_style: Rc<RefCell<StyleRecord<'i>>>,
+  pub dark_style: Rc<RefCell<StyleRecord<'i>>>,
+  pub container_style: Rc<RefCell<ContainerStyleRecord<'i>>>,
   pub keyframes: Rc<RefCell<HashMap<String, Vec<KeyFrameItem>>>>,
+  pub font_faces: Rc<RefCell<Vec<FontFaceItem>>>,
+  pub generated_classes: Rc<RefCell<GeneratedClassRecord>>,
+  pub layers: Rc<RefCell<LayerOrder>>,
   pub document: &'i JSXDocument,
   pub platform: Platform,
   pub is_entry: bool
@@ -142,28 +618,52 @@ impl<'i> StyleParser<'i> {
   pub fn new(document: &'i JSXDocument, platform:Platform, is_entry: bool) -> Self {
     StyleParser {
       all_style: Rc::new(RefCell::new(vec![])),
+      dark_style: Rc::new(RefCell::new(vec![])),
+      container_style: Rc::new(RefCell::new(vec![])),
       keyframes: Rc::new(RefCell::new(HashMap::new())),
+      font_faces: Rc::new(RefCell::new(vec![])),
+      generated_classes: Rc::new(RefCell::new(IndexMap::new())),
+      layers: Rc::new(RefCell::new(LayerOrder::default())),
       document,
       platform,
       is_entry
     }
   }
 
-  pub fn parse(&mut self, css: &'i str) {
-    let mut stylesheet = StyleSheet::parse(css, ParserOptions::default()).expect("解析样式失败");
-    let mut style_visitor = StyleVisitor::new(Rc::clone(&self.all_style), Rc::clone(&self.keyframes));
+  // styles 为 parse_styles 解析的样式，多平台转换时共用，各平台复制规则后分别处理降级规则、检查及 @supports 条件并收集样式
+  pub fn parse(&mut self, styles: &ParsedStyles<'i>) {
+    clear_theme_variables();
+    let Some(stylesheet) = &styles.stylesheet else {
+      return
+    };
+    let (css, style_starts) = (styles.css, styles.style_starts.as_slice());
+    let mut stylesheet = StyleSheet::new(stylesheet.sources.clone(), stylesheet.rules.clone(), ParserOptions::default());
+    apply_fallback_rules(&mut stylesheet, &self.platform);
+    lint_stylesheet(&mut stylesheet, style_starts, &self.platform);
+    if with_transform_options(|options| options.strict) {
+      check_stylesheet(&mut stylesheet, css, style_starts, &self.platform);
+    }
+    wrap_global_styles(&mut stylesheet, style_starts);
+    let mut style_visitor = StyleVisitor::new(self.platform.clone(), self.document, Rc::clone(&self.all_style), Rc::clone(&self.dark_style), Rc::clone(&self.container_style), Rc::clone(&self.keyframes), Rc::clone(&self.font_faces), Rc::clone(&self.generated_classes), Rc::clone(&self.layers));
     stylesheet.visit(&mut style_visitor).unwrap();
   }
 
-  pub fn calc(&self) -> StyleData<'i> {
+  pub fn calc(&self) -> StyleData {
     // 遍历 style_record，计算每个节点的最终样式
     let mut all_style = self.all_style.borrow_mut();
-    let mut style_record = HashMap::new();
-    let mut pesudo_style_record = HashMap::new();
+    let mut style_record = IndexMap::new();
     let mut css_variables = vec![];
     // 是否含有嵌套选择器
     let mut has_nesting = false;
 
+    // 选择器 => !important 声明的属性
+    let mut important_properties: HashMap<String, Vec<Atom>> = HashMap::new();
+    all_style.iter().for_each(|(selector, declarations)| {
+      important_properties.entry(selector.clone()).or_default().extend(declarations.iter().flat_map(|declaration| {
+        declaration.declaration.important_declarations.iter().flat_map(expand_shorthand).map(|property| intern_property_name(&property))
+      }));
+    });
+
     // final_all_style 转换为驼峰命名
     let mut final_all_style = vec![];
     self.calc_style_record(&mut all_style).iter_mut().for_each(|(selector, style_value)| {
@@ -182,14 +682,7 @@ impl<'i> StyleParser<'i> {
       }
       let properties = style_value.declaration.declarations.iter().map(|property| {
         (
-          to_camel_case(
-            property
-              .property_id()
-              .to_css_string(PrinterOptions::default())
-              .unwrap()
-              .as_str(),
-            false,
-          ),
+          intern_property_name(property),
           property.clone(),
         )
       })
@@ -202,36 +695,45 @@ impl<'i> StyleParser<'i> {
       final_all_style.push((selector.to_owned(), properties));
     });
 
-    let mut pesudo_selector = None;
+    let selectors = final_all_style.iter().map(|(selector, _)| selector.clone()).collect::<Vec<_>>();
+    let class_usage = self.get_class_usage(&selectors);
+    // 移除未使用的类样式
+    let unused_selectors = match with_transform_options(|options| options.tree_shaking) {
+      true => self.tree_shake(&selectors, &class_usage),
+      false => HashSet::new()
+    };
+    final_all_style.retain(|(selector, _)| !unused_selectors.contains(selector));
+    // 嵌套选择器的样式在运行时覆盖类样式，移除其中被层级更高的类样式覆盖的声明
+    remove_shadowed_layer_declarations(&all_style, &self.layers.borrow().ranks(), &mut final_all_style);
+
     for (selector, style_value) in final_all_style.iter_mut() {
       // 用于查询的选择器
       let mut element_selector = selector.clone();
-      // 判断是否伪类(暂时支持鸿蒙)
-      if (SUPPORT_PSEUDO_KEYS.into_iter().any(|s| selector.contains(s))) && self.platform == Platform::Harmony {
-        let _selectors = selector.split(":").collect::<Vec<&str>>();
-        pesudo_selector = _selectors[1].parse::<String>().ok();
+      // 伪类的样式由运行时根据节点状态合并，不记录到节点的样式中(暂时支持鸿蒙)
+      let is_pesudo = SUPPORT_PSEUDO_KEYS.into_iter().any(|s| selector.contains(s)) && self.platform == Platform::Harmony;
+      if is_pesudo {
         // 伪类需要把 : 之后的选择器去掉，只保留 : 之前的选择器，用于查询所属的element
-        element_selector = _selectors[0].to_string();
+        element_selector = selector.split(":").next().unwrap_or_default().to_string();
       }
 
       let elements = self.document.select(element_selector.as_str());
+      trace!(selector = selector.as_str(), elements = elements.len(), "rule matched");
+      if !elements.is_empty() {
+        record_rule_matched();
+      }
+      if is_pesudo {
+        continue
+      }
       for element in elements {
-        match pesudo_selector {
-          Some(ref selector) => {
-            let declarations= pesudo_style_record.entry(element.span).or_insert(vec![]);
-            declarations.push((selector.clone(), style_value.clone()));
-          }
-          None => {
-            let declarations: &mut Vec<Vec<(String, Property<'_>)>> = style_record.entry(element.span).or_insert(vec![]);
-            declarations.push(style_value.clone());
-          }
-        }
+        let declarations: &mut Vec<Vec<(Atom, Property<'_>)>> = style_record.entry(element.span).or_insert(vec![]);
+        declarations.push(style_value.clone());
       }
     }             
 
     // 进行样式解析优化，提前解析 ArkUI 的样式，减少运行时的计算
-    let final_all_style = final_all_style
-    .iter_mut()
+    let final_properties = final_all_style;
+    let mut final_all_style = final_properties
+    .iter()
     .map(|(selector, properties)| {
       (
         selector.to_owned(),
@@ -244,54 +746,173 @@ impl<'i> StyleParser<'i> {
         ),
       )
     })
-    .collect::<HashMap<_, _>>();
+    .collect::<StyleMap>();
 
-    let final_pesudo_style_record = pesudo_style_record;
+    // 暗色模式样式：亮色样式在前，暗色样式在后，合并后暗色样式覆盖亮色样式
+    let mut dark_style_record = self.dark_style.borrow().iter().filter(|(selector, _)| !unused_selectors.contains(selector)).map(|(selector, dark_declarations)| {
+      let mut declarations = all_style
+        .iter()
+        .find(|(id, _)| id == selector)
+        .map(|(_, declarations)| declarations.clone())
+        .unwrap_or_default();
+      declarations.extend(dark_declarations.iter().cloned());
+      (selector.clone(), declarations)
+    }).collect::<Vec<_>>();
+    let final_dark_style = self.parse_style_record(&mut dark_style_record);
+
+    // 容器查询的条件样式，每条 @container 规则中的样式单独合并
+    let final_container_style = self.container_style.borrow_mut().iter_mut().map(|(query, container_style)| {
+      container_style.retain(|(selector, _)| !unused_selectors.contains(selector));
+      (query.clone(), self.parse_style_record(container_style))
+    }).collect::<ContainerStyle>();
+
+    // !important 声明在运行时无法覆盖后面的类及内联样式，在编译期处理
+    let important_record = resolve_important_styles(self.document, &important_properties, &final_properties, self.keyframes.clone(), &mut final_all_style, &mut self.generated_classes.borrow_mut());
+
+    // 文本属性的检查及继承，需在类样式解析完成后进行
+    lint_text_styles(self.document, &final_all_style);
+    inherit_text_styles(self.document, &mut final_all_style, &mut self.generated_classes.borrow_mut());
 
     StyleData {
-      pesudo_style_record: Rc::new(RefCell::new(final_pesudo_style_record)),
       all_style: Rc::new(RefCell::new(final_all_style)),
+      dark_style: Rc::new(RefCell::new(final_dark_style)),
+      container_style: Rc::new(RefCell::new(final_container_style)),
+      font_faces: self.font_faces.borrow().clone(),
+      generated_classes: Rc::clone(&self.generated_classes),
+      important_record,
+      class_usage,
       has_nesting
     }
   }
 
+  // 合并样式记录并解析为平台样式
+  fn parse_style_record(&self, style_record: &mut StyleRecord<'i>) -> StyleMap {
+    self.calc_style_record(style_record)
+    .iter()
+    .map(|(selector, style_value)| {
+      let properties = style_value.declaration.declarations.iter().map(|property| {
+        (
+          intern_property_name(property),
+          property.clone(),
+        )
+      })
+      .collect::<Vec<(_, _)>>();
+      (selector.to_owned(), parse_style_properties(&properties, Some(self.keyframes.clone())))
+    })
+    .collect::<StyleMap>()
+  }
+
+  // 节点上静态 className 的类名
+  fn get_jsx_classes(&self) -> HashSet<String> {
+    let mut jsx_classes = HashSet::new();
+    if let Some(jsx_record) = &self.document.jsx_record {
+      jsx_record.values().for_each(|element| {
+        jsx_classes.extend(element.classes().map(|class| class.to_string()));
+      });
+    }
+    jsx_classes
+  }
+
+  // 按选择器中出现的类名，区分 className 中有样式和没有样式的类名，并找出样式表中未被组件引用的类名
+  fn get_class_usage(&self, selectors: &[String]) -> ClassUsage {
+    let mut style_classes = HashSet::new();
+    // 无法解析类名的选择器不计入未引用的类名
+    let mut parsed_classes = HashSet::new();
+    selectors.iter().for_each(|selector| match get_selector_classes(selector) {
+      Some(classes) => {
+        style_classes.extend(classes.iter().cloned());
+        parsed_classes.extend(classes);
+      },
+      // 无法解析类名的选择器，其中的类名也视为有样式
+      None => style_classes.extend(selector.split('.').map(|class| class.to_string()))
+    });
+    let mut class_name_visitor = ClassNameLiteralVisitor::new();
+    let mut string_visitor = StringLiteralVisitor::new();
+    if let Some(program) = &self.document.program {
+      program.visit_with(&mut class_name_visitor);
+      program.visit_with(&mut string_visitor);
+    }
+    let mut jsx_classes = self.get_jsx_classes();
+    jsx_classes.extend(class_name_visitor.classes);
+    let mut referenced_classes = string_visitor.words;
+    referenced_classes.extend(jsx_classes.iter().cloned());
+    self.generated_classes.borrow().values().for_each(|classes| referenced_classes.extend(classes.iter().cloned()));
+
+    let mut jsx_classes = jsx_classes.into_iter().collect::<Vec<_>>();
+    jsx_classes.sort();
+    let (used, unmatched) = jsx_classes.into_iter().partition(|class| style_classes.contains(class));
+    let mut unused = parsed_classes.into_iter().filter(|class| !referenced_classes.contains(class)).collect::<Vec<_>>();
+    unused.sort();
+    ClassUsage { used, unmatched, unused }
+  }
+
+  // 返回未被使用的选择器，选择器中的类名均出现在 className 或字符串字面量中时视为已使用
+  // 同时提示没有对应样式的静态 className，可能是拼写错误
+  fn tree_shake(&self, selectors: &[String], class_usage: &ClassUsage) -> HashSet<String> {
+    let mut visitor = StringLiteralVisitor::new();
+    if let Some(program) = &self.document.program {
+      program.visit_with(&mut visitor);
+    }
+    let mut used_classes = visitor.words;
+    used_classes.extend(self.get_jsx_classes());
+    // 编译期生成的类名在输出时添加到节点上
+    self.generated_classes.borrow().values().for_each(|classes| used_classes.extend(classes.iter().cloned()));
+
+    // 无法解析类名的选择器保留
+    let unused_selectors = selectors.iter().filter(|selector| {
+      get_selector_classes(selector).is_some_and(|classes| !classes.is_empty() && classes.iter().any(|class| !used_classes.contains(class)))
+    }).cloned().collect::<HashSet<_>>();
+    selectors.iter().filter(|selector| unused_selectors.contains(*selector)).for_each(|selector| {
+      push_diagnostic("tree-shaking", format!("{} is not used by any element and has been removed", selector));
+    });
+
+    class_usage.unmatched.iter().for_each(|class| {
+      push_diagnostic("className", format!("className {} has no matching style, it may be a typo", class));
+    });
+    unused_selectors
+  }
+
   // 合并相同类型的 style，比如 .a { color: red } .a { color: blue } => .a { color: blue }，并且 !important 的优先级高于普通的
+  // 层叠顺序先比较 @layer 层级再比较 specificity，!important 声明的层级顺序相反，先声明的层级优先
   fn calc_style_record<T: Hash + Eq + Clone>(
     &self,
     style_record: &mut Vec<(T, Vec<StyleDeclaration<'i>>)>,
   ) -> Vec<(T, StyleDeclaration<'i>)> {
+    let layer_ranks = self.layers.borrow().ranks();
     // 创建一个新的向量 final_style_record，用于存储最终的样式记录
     let mut final_style_record = vec![];
     // 对输入的 style_record 中的每个元素进行迭代
     for (id, declarations) in style_record.iter_mut() {
-       // 对每个 declarations 中的 StyleDeclaration 进行按 specificity 排序
-      declarations.sort_by(|a: &StyleDeclaration<'_>, b| a.specificity.cmp(&b.specificity));
+       // 对每个 declarations 中的 StyleDeclaration 进行按层级、specificity 排序
+      declarations.sort_by_key(|declaration| (layer_ranks.rank(declaration.layer.as_deref()), declaration.specificity));
       let mut final_properties: Vec<Property<'i>> = Vec::new();
       for declaration in declarations.iter() {
         let declaration = &declaration.declaration;
         let declarations = &declaration.declarations;
-        for declaration in declarations.iter() {
+        for declaration in declarations.iter().flat_map(expand_shorthand) {
           let has_property_index = final_properties
             .iter()
             .position(|property| property.property_id() == declaration.property_id());
           if let Some(index) = has_property_index {
-            final_properties[index] = declaration.clone();
+            final_properties[index] = declaration;
           } else {
-            final_properties.push(declaration.clone());
+            final_properties.push(declaration);
           }
         }
       }
-      for declaration in declarations.iter() {
+      let mut important_declarations = declarations.iter().collect::<Vec<_>>();
+      important_declarations.sort_by_key(|declaration| (Reverse(layer_ranks.rank(declaration.layer.as_deref())), declaration.specificity));
+      for declaration in important_declarations {
         let declaration = &declaration.declaration;
         let important_declarations = &declaration.important_declarations;
-        for declaration in important_declarations.iter() {
+        for declaration in important_declarations.iter().flat_map(expand_shorthand) {
           let has_property_index = final_properties
             .iter()
             .position(|property| property.property_id() == declaration.property_id());
           if let Some(index) = has_property_index {
-            final_properties[index] = declaration.clone();
+            final_properties[index] = declaration;
           } else {
-            final_properties.push(declaration.clone());
+            final_properties.push(declaration);
           }
         }
       }
@@ -299,6 +920,7 @@ impl<'i> StyleParser<'i> {
         (*id).clone(),
         StyleDeclaration {
           specificity: 0,
+          layer: None,
           declaration: DeclarationBlock {
             declarations: final_properties,
             important_declarations: vec![],
@@ -2,21 +2,27 @@
 
 use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
-use lightningcss::{printer::PrinterOptions, properties::{animation, Property}, traits::ToCss, values::{easing::EasingFunction, time}};
+use lightningcss::{printer::PrinterOptions, properties::{animation, Property}, traits::ToCss, values::easing::EasingFunction};
 
-use crate::{generate_expr_lit_num, generate_expr_lit_str, generate_invalid_expr, style_parser::KeyFrameItem, visitor::parse_style_values};
+use crate::{diagnostics::push_diagnostic, generate_expr_lit_num, generate_expr_lit_str, generate_invalid_expr, minify::get_prop_key, style_parser::KeyFrameItem, visitor::parse_style_values};
 use swc_core::{common::DUMMY_SP, ecma::ast::*};
-use super::{traits::ToExpr, unit::{Platform, PropertyTuple}};
+use super::{style_value_type::StyleValueType, traits::ToExpr, unit::{convert_time_to_ms, generate_expr_by_easing_function, Platform, PropertyTuple}};
 
 #[derive(Debug, Clone)]
 pub struct Animation {
   pub id: String,
   pub keyframes: Option<Rc<RefCell<HashMap<String, Vec<KeyFrameItem>>>>>,
   pub animation_name: Option<String>,
+  // 单位：毫秒
   pub animation_duration: Option<f32>,
   pub animation_delay: Option<f32>,
   pub animation_iteration: Option<f32>,
-  pub animation_timeing_function: Option<EasingFunction>
+  pub animation_timeing_function: Option<EasingFunction>,
+  pub animation_fill_mode: Option<animation::AnimationFillMode>,
+  pub animation_direction: Option<animation::AnimationDirection>,
+  pub animation_play_state: Option<animation::AnimationPlayState>,
+  // 同一条规则中的静态样式，关键帧缺少 0%、100% 时用于补齐起止帧
+  pub base_styles: Vec<StyleValueType>
 }
 
 impl From<(String, &Property<'_>, Option<Rc<RefCell<HashMap<String, Vec<KeyFrameItem>>>>>)> for Animation {
@@ -27,58 +33,67 @@ impl From<(String, &Property<'_>, Option<Rc<RefCell<HashMap<String, Vec<KeyFrame
     let mut animation_delay =  None; // 0.0
     let mut animation_iteration =  None; // 1.0
     let mut animation_timeing_function = None; // EasingFunction::Ease
+    let mut animation_fill_mode = None; // AnimationFillMode::None
+    let mut animation_direction = None; // AnimationDirection::Normal
+    let mut animation_play_state = None; // AnimationPlayState::Running
     
     match value.1 {
-      // Property::AnimationName(_, _) => todo!(),
-      // Property::AnimationDuration(_, _) => todo!(),
-      // Property::AnimationTimingFunction(_, _) => todo!(),
-      // Property::AnimationIterationCount(_, _) => todo!(),
-      // Property::AnimationDirection(_, _) => todo!(),
-      // Property::AnimationPlayState(_, _) => todo!(),
-      // Property::AnimationDelay(_, _) => todo!(),
-      // Property::AnimationFillMode(_, _) => todo!(),
+      // lightningcss 已按类型区分简写中的各值：第一个时间为时长、第二个为延迟，缓动、次数、方向、填充模式、播放状态的关键字，其余的标识符为名称
       Property::Animation(animation_list, _) => {
-        animation_list.into_iter().for_each(|animation| {
-          animation_name = Some(animation.name.to_css_string(PrinterOptions::default()).unwrap());
-          animation_duration = Some(match animation.duration {
-            time::Time::Seconds(s) => s,
-            time::Time::Milliseconds(m) => m * 60.0,
-          });
-          animation_delay = Some(match animation.delay {
-            time::Time::Seconds(s) => s,
-            time::Time::Milliseconds(m) => m * 60.0,
-          });
+        // 运行时一个节点只能执行一个动画，多个动画时取第一个
+        if animation_list.len() > 1 {
+          push_diagnostic("animation", format!(
+            "multiple animations are not supported, only the first one {} is used",
+            animation_list.first().map(|animation| get_animation_name(&animation.name).unwrap_or("none".to_string())).unwrap_or_default()
+          ));
+        }
+        if let Some(animation) = animation_list.first() {
+          animation_name = get_animation_name(&animation.name);
+          animation_duration = Some(convert_time_to_ms(&animation.duration));
+          animation_delay = Some(convert_time_to_ms(&animation.delay));
           animation_iteration = Some(match animation.iteration_count {
             animation::AnimationIterationCount::Number(num) => num,
             animation::AnimationIterationCount::Infinite => -1.0,
           });
 
           animation_timeing_function = Some(animation.timing_function.clone());
-        });
+          animation_fill_mode = Some(animation.fill_mode);
+          animation_direction = Some(animation.direction);
+          animation_play_state = Some(animation.play_state);
+        }
       },
       Property::AnimationDelay(delay, _) => {
-        animation_delay = Some(match delay.get(0).unwrap() {
-          time::Time::Seconds(s) => *s,
-          time::Time::Milliseconds(m) => m * 60.0,
-        });
+        animation_delay = Some(convert_time_to_ms(delay.first().unwrap()));
       },
       Property::AnimationDuration(duration, _) => {
-        animation_duration = Some(match duration.get(0).unwrap() {
-          time::Time::Seconds(s) => *s,
-          time::Time::Milliseconds(m) => m * 60.0,
-        })
+        animation_duration = Some(convert_time_to_ms(duration.first().unwrap()))
       },
       Property::AnimationIterationCount(iteration, _) => {
-        animation_iteration = Some(match iteration.get(0).unwrap() {
+        animation_iteration = Some(match iteration.first().unwrap() {
           animation::AnimationIterationCount::Number(num) => *num,
           animation::AnimationIterationCount::Infinite => -1.0,
         })
       },
-      Property::AnimationName(name, _) => {
-        animation_name = Some(name.to_css_string(PrinterOptions::default()).unwrap())
+      Property::AnimationName(names, _) => {
+        if names.len() > 1 {
+          push_diagnostic("animationName", format!(
+            "multiple animation names are not supported, only the first one {} is used",
+            names.first().and_then(get_animation_name).unwrap_or("none".to_string())
+          ));
+        }
+        animation_name = names.first().and_then(get_animation_name)
       },
       Property::AnimationTimingFunction(timing_function, _) => {
-        animation_timeing_function = Some(timing_function.get(0).unwrap().clone());
+        animation_timeing_function = Some(timing_function.first().unwrap().clone());
+      },
+      Property::AnimationFillMode(fill_mode, _) => {
+        animation_fill_mode = fill_mode.first().copied();
+      },
+      Property::AnimationDirection(direction, _) => {
+        animation_direction = direction.first().copied();
+      },
+      Property::AnimationPlayState(play_state, _) => {
+        animation_play_state = play_state.first().copied();
       },
       _ => {}
     }
@@ -90,55 +105,141 @@ impl From<(String, &Property<'_>, Option<Rc<RefCell<HashMap<String, Vec<KeyFrame
       animation_duration,
       animation_delay,
       animation_iteration,
-      animation_timeing_function
+      animation_timeing_function,
+      animation_fill_mode,
+      animation_direction,
+      animation_play_state,
+      base_styles: vec![]
     }
 
   }
 }
 
 
+impl Animation {
+  // 关键帧的 (百分比, 缓动, 样式)，缺少 0%、100% 帧时，按 css 的规则以元素自身的样式作为隐式的起止帧，避免动画开始、结束时跳变
+  fn get_keyframe_frames<'a>(&self, name: &str, keyframe_items: &'a [KeyFrameItem]) -> Vec<(f32, Option<&'a EasingFunction>, Vec<PropOrSpread>)> {
+    let mut frames = keyframe_items.iter().map(|item| {
+      (item.percentage, item.timing_function.as_ref(), parse_style_values(item.declarations.clone(), Platform::Harmony))
+    }).collect::<Vec<_>>();
+    let missing_start = frames.first().is_some_and(|(percentage, _, _)| *percentage > 0.0);
+    let missing_end = frames.last().is_some_and(|(percentage, _, _)| *percentage < 1.0);
+    if !missing_start && !missing_end {
+      return frames
+    }
+
+    // 关键帧中出现的属性
+    let mut animated_keys = vec![];
+    frames.iter().flat_map(|(_, _, event)| event.iter().filter_map(get_prop_key)).for_each(|key| {
+      if !animated_keys.contains(&key) {
+        animated_keys.push(key);
+      }
+    });
+    let base_props = parse_style_values(self.base_styles.clone(), Platform::Harmony);
+    let base_keys = base_props.iter().filter_map(get_prop_key).collect::<Vec<_>>();
+    let missing_keys = animated_keys.iter().filter(|key| !base_keys.contains(key)).cloned().collect::<Vec<_>>();
+    if !missing_keys.is_empty() {
+      push_diagnostic(
+        "animationName",
+        format!(
+          "@keyframes {} has no {} frame and {} has no static value in the same rule, the animation may jump",
+          name,
+          match (missing_start, missing_end) {
+            (true, true) => "0% and 100%",
+            (true, false) => "0%",
+            _ => "100%"
+          },
+          missing_keys.join(", ")
+        )
+      );
+    }
+    let implicit_frame = base_props.into_iter().filter(|prop| get_prop_key(prop).is_some_and(|key| animated_keys.contains(&key))).collect::<Vec<_>>();
+    if missing_start {
+      frames.insert(0, (0.0, None, implicit_frame.clone()));
+    }
+    if missing_end {
+      frames.push((1.0, None, implicit_frame));
+    }
+    frames
+  }
+}
+
+// animation-name 对应的 @keyframes 名称，none 时返回 None，字符串形式的名称取引号内的值
+fn get_animation_name(name: &animation::AnimationName) -> Option<String> {
+  match name {
+    animation::AnimationName::None => None,
+    animation::AnimationName::Ident(ident) => Some(ident.0.to_string()),
+    animation::AnimationName::String(name) => Some(name.to_string())
+  }
+}
+
+// 百分比以 f32 存储，如 30% 为 0.3f32，转为 f64 后保留 4 位小数，避免输出 0.30000001192092896
+fn normalize_percentage(percentage: f32) -> f64 {
+  (percentage as f64 * 10000.0).round() / 10000.0
+}
+
 impl ToExpr for Animation {
   fn to_expr(&self) -> PropertyTuple {
 
     let mut exprs = vec![];
     if let Some(delay) = self.animation_delay {
-      exprs.push(("animationDelay".to_string(), generate_expr_lit_num!((delay * 1000.0) as f64)))
+      exprs.push(("animationDelay".to_string(), generate_expr_lit_num!(delay as f64)))
     }
     if let Some(iteration) = self.animation_iteration {
       exprs.push(("animationIterationCount".to_string(), generate_expr_lit_num!(iteration as f64)))
     }
     if let Some(duration) = self.animation_duration {
-      exprs.push(("animationDuration".to_string(), generate_expr_lit_num!((duration * 1000.0) as f64)))
+      exprs.push(("animationDuration".to_string(), generate_expr_lit_num!(duration as f64)))
     }
     if let Some(timeing_function) = &self.animation_timeing_function {
-      exprs.push(("animationTimeingFunction".to_string(), generate_expr_lit_str!(timeing_function.to_css_string(PrinterOptions::default()).unwrap())))
+      exprs.push(("animationTimeingFunction".to_string(), generate_expr_by_easing_function(timeing_function)))
+    }
+    if let Some(fill_mode) = &self.animation_fill_mode {
+      exprs.push(("animationFillMode".to_string(), generate_expr_lit_str!(fill_mode.to_css_string(PrinterOptions::default()).unwrap())))
+    }
+    if let Some(direction) = &self.animation_direction {
+      exprs.push(("animationDirection".to_string(), generate_expr_lit_str!(direction.to_css_string(PrinterOptions::default()).unwrap())))
+    }
+    if let Some(play_state) = &self.animation_play_state {
+      exprs.push(("animationPlayState".to_string(), generate_expr_lit_str!(play_state.to_css_string(PrinterOptions::default()).unwrap())))
     }
     if let Some(name) = &self.animation_name {
       if let Some(keframes) = &self.keyframes {
 
       let keyframe_map = keframes.borrow();
+      if !keyframe_map.contains_key(name) {
+        push_diagnostic("animationName", format!("@keyframes {} is not defined, the animation name is ignored", name));
+      }
       if let Some(keyframe_items) = keyframe_map.get(name) {
         // animation-name: keyframes
         exprs.push(("animationName".to_string(), Expr::Array(ArrayLit {
           span: DUMMY_SP,
-          elems: keyframe_items.into_iter().map(|item| {
-            return Some(ExprOrSpread {
+          elems: self.get_keyframe_frames(name, keyframe_items).into_iter().map(|(percentage, curve, event)| {
+            let mut props = vec![
+              PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                key: PropName::Str("percentage".into()),
+                value: Box::new(generate_expr_lit_num!(normalize_percentage(percentage)))
+              }))),
+              PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                key: PropName::Str("event".into()),
+                value: Box::new(Expr::Object(ObjectLit {
+                  span: DUMMY_SP,
+                  props: event
+                }))
+              })))
+            ];
+            // 帧内单独声明的缓动函数
+            if let Some(timing_function) = curve {
+              props.push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                key: PropName::Str("curve".into()),
+                value: Box::new(generate_expr_by_easing_function(timing_function))
+              }))));
+            }
+            Some(ExprOrSpread {
               spread: None,
               expr: Box::new(Expr::Object(ObjectLit {
                 span: DUMMY_SP,
-                props: vec![
-                  PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
-                    key: PropName::Str("percentage".into()),
-                    value: Box::new(generate_expr_lit_num!(item.percentage as f64))
-                  }))),                   
-                  PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
-                    key: PropName::Str("event".into()),
-                    value: Box::new(Expr::Object(ObjectLit {
-                      span: DUMMY_SP,
-                      props: parse_style_values(item.declarations.clone(), Platform::Harmony)
-                    }))
-                  })))
-                ]
+                props
               }))
             })
           }).collect::<Vec<Option<ExprOrSpread>>>()
@@ -1,50 +1,52 @@
-
-
 use lightningcss::properties::Property;
 
-use crate::{generate_expr_lit_num, generate_expr_ident};
+use crate::{generate_expr_lit_num, generate_invalid_expr};
 
 use super::{traits::ToExpr, unit::PropertyTuple};
 
 #[derive(Debug, Clone)]
-pub struct AspactRatio {
+pub struct AspectRatio {
   pub id: String,
-  pub value: EAspactRatio
+  pub value: EAspectRatio
 }
 
 #[derive(Debug, Clone)]
-pub enum EAspactRatio {
+pub enum EAspectRatio {
   Auto,
   Ratio(f64, f64),
 }
 
-impl From<(String, &Property<'_>)> for AspactRatio {
+impl EAspectRatio {
+  // 宽高比的数值，如 16 / 9 => 1.777...，auto 或比值为 0 时无数值
+  pub fn to_number(&self) -> Option<f64> {
+    match self {
+      EAspectRatio::Ratio(first, second) if *first > 0.0 && *second > 0.0 => Some(first / second),
+      _ => None
+    }
+  }
+}
+
+impl From<(String, &Property<'_>)> for AspectRatio {
   fn from(prop: (String, &Property<'_>)) -> Self {
     match prop.1 {
-      Property::AspectRatio(value) => {
-        match value.auto {
-          true => AspactRatio { id: prop.0, value: EAspactRatio::Auto },
-          false => {
-            if let Some(ratio) = &value.ratio {
-              AspactRatio { id: prop.0, value: EAspactRatio::Ratio(ratio.0.into(), ratio.1.into()) }
-            } else {
-              AspactRatio { id: prop.0, value: EAspactRatio::Auto }
-            }
-          }
-        }
+      // auto 16 / 9 中的 auto 表示优先使用元素的固有宽高比，没有固有宽高比时使用后面的比值，两端均不支持固有宽高比，直接使用比值
+      Property::AspectRatio(value) => match &value.ratio {
+        Some(ratio) => AspectRatio { id: prop.0, value: EAspectRatio::Ratio(ratio.0.into(), ratio.1.into()) },
+        None => AspectRatio { id: prop.0, value: EAspectRatio::Auto }
       },
-      _ => AspactRatio { id: prop.0, value: EAspactRatio::Auto },
+      _ => AspectRatio { id: prop.0, value: EAspectRatio::Auto },
     }
   }
 }
 
-impl ToExpr for AspactRatio {
+impl ToExpr for AspectRatio {
+  // auto 及比值为 0 的退化情况等同于未设置宽高比，不输出
   fn to_expr(&self) -> PropertyTuple {
     PropertyTuple::One(
       "aspectRatio".to_string(),
-      match self.value {
-        EAspactRatio::Ratio(first, second) => generate_expr_lit_num!(first / second),
-        _ => generate_expr_lit_num!(1.0),
+      match self.value.to_number() {
+        Some(ratio) => generate_expr_lit_num!(ratio),
+        None => generate_invalid_expr!(),
       }
     )
   }
@@ -52,10 +54,10 @@ impl ToExpr for AspactRatio {
   fn to_rn_expr(&self) -> PropertyTuple {
     PropertyTuple::One(
       "aspectRatio".to_string(),
-      match self.value {
-        EAspactRatio::Ratio(first, second) => generate_expr_lit_num!(first / second),
-        _ => generate_expr_ident!("auto"),
+      match self.value.to_number() {
+        Some(ratio) => generate_expr_lit_num!(ratio),
+        None => generate_invalid_expr!(),
       }
     )
   }
-}
\ No newline at end of file
+}
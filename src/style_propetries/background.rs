@@ -1,8 +1,5 @@
 use lightningcss::{
   properties::{background::Background as LNBackground, Property},
-  stylesheet::PrinterOptions,
-  targets::{Features, Targets},
-  traits::ToCss,
   values::color::CssColor,
 };
 use smallvec::SmallVec;
@@ -10,7 +7,7 @@ use smallvec::SmallVec;
 use crate::{generate_expr_lit_str, generate_invalid_expr};
 
 use super::{
-  background_image::{parse_background_image_item, BackgroundImage}, background_position::{parse_background_position_item, BackgroundPosition}, background_repeat::{parse_background_repeat_item, BackgroundRepeat}, background_size::{parse_background_size_item, BackgroundSize}, traits::ToExpr, unit::{convert_color_keywords_to_hex, PropertyTuple}
+  background_image::{parse_background_image_item, BackgroundImage}, background_position::{parse_background_position_item, BackgroundPosition}, background_repeat::{parse_background_repeat_item, BackgroundRepeat}, background_size::{parse_background_size_item, BackgroundSize}, traits::ToExpr, unit::{convert_css_color, PropertyTuple}
 };
 
 fn parse_background(background: &SmallVec<[LNBackground<'_>; 1]>) -> Background {
@@ -30,21 +27,7 @@ fn parse_background(background: &SmallVec<[LNBackground<'_>; 1]>) -> Background
     }
     background_repeat.push(parse_background_repeat_item(&item.repeat));
     if item.color != CssColor::default() {
-      background_color = Some(
-        convert_color_keywords_to_hex(
-          item
-            .color
-            .to_css_string(PrinterOptions {
-              minify: false,
-              targets: Targets {
-                include: Features::HexAlphaColors,
-                ..Targets::default()
-              },
-              ..PrinterOptions::default()
-            })
-            .unwrap()
-        ),
-      );
+      background_color = Some(convert_css_color(&item.color));
     }
   }
   let mut bg = Background::new();
@@ -1,9 +1,10 @@
 use lightningcss::{
-  properties::Property, stylesheet::PrinterOptions, targets::{Features, Targets}, traits::ToCss, values::{
+  properties::Property, values::{
     angle::Angle,
-    gradient::{ Gradient, GradientItem, LineDirection},
+    gradient::{ConicGradient, Gradient, GradientItem, LineDirection, LinearGradient, RadialGradient},
     image::Image,
-    percentage::{DimensionPercentage, Percentage},
+    length::LengthValue,
+    percentage::DimensionPercentage,
     position::{HorizontalPositionKeyword, VerticalPositionKeyword},
   }
 };
@@ -14,7 +15,104 @@ use swc_core::common::DUMMY_SP;
 
 use crate::{generate_expr_lit_num, generate_expr_lit_str, generate_invalid_expr};
 
-use super::{graident_properties::{linear_gradient::{LinearGradientDirection, LinearGradientItem}, radial_gradient::{RadialGradientItem, RadialGradientPoint}}, traits::ToExpr, unit::{convert_color_keywords_to_hex, PropertyTuple}};
+use super::{graident_properties::{linear_gradient::{LinearGradientDirection, LinearGradientItem}, radial_gradient::{RadialGradientItem, RadialGradientPoint}, sweep_gradient::SweepGradientItem}, traits::ToExpr, unit::{convert_css_color, PropertyTuple}};
+
+// 渐变的颜色断点，(颜色, 0~1 的位置)，未指定位置的断点按剩余空间均分，无法计算比例的位置（如 px）按 0 处理
+fn parse_color_stops<D: Clone>(items: &[GradientItem<D>], get_offset: impl Fn(&D) -> Option<f32>) -> Vec<(Expr, Expr)> {
+  let mut color_stops = vec![];
+  let mut now_percentage = 0.0;
+  let colors_len = items.len() - 1;
+  for (index, item) in items.iter().enumerate() {
+    if let GradientItem::ColorStop(color_stop) = item {
+      let item_pecentage = now_percentage + (((1.0 - now_percentage) / colors_len as f32) * index as f32);
+      let offset = match &color_stop.position {
+        Some(position) => get_offset(position),
+        None => Some(item_pecentage)
+      };
+      color_stops.push((
+        generate_expr_lit_str!(convert_css_color(&color_stop.color)),
+        match offset {
+          Some(offset) => {
+            now_percentage = offset;
+            generate_expr_lit_num!(offset as f64)
+          },
+          None => generate_expr_lit_num!(0.0),
+        },
+      ));
+    }
+  }
+  color_stops
+}
+
+fn get_length_offset(position: &DimensionPercentage<LengthValue>) -> Option<f32> {
+  match position {
+    DimensionPercentage::Percentage(percentage) => Some(percentage.0),
+    _ => None
+  }
+}
+
+fn get_angle_offset(position: &DimensionPercentage<Angle>) -> Option<f32> {
+  match position {
+    DimensionPercentage::Percentage(percentage) => Some(percentage.0),
+    DimensionPercentage::Dimension(angle) => Some(get_angle_degrees(angle) / 360.0),
+    DimensionPercentage::Calc(_) => None,
+  }
+}
+
+fn get_angle_degrees(angle: &Angle) -> f32 {
+  match angle {
+    Angle::Deg(deg) => *deg,
+    Angle::Rad(rad) => rad.to_degrees(),
+    Angle::Turn(turn) => turn * 360.0,
+    Angle::Grad(grad) => grad * 0.9,
+  }
+}
+
+fn parse_linear_gradient(gradient: &LinearGradient, repeating: bool) -> BackgroundImageKind {
+  let color_stops = parse_color_stops(&gradient.items, get_length_offset);
+  // 关键字方向转换为角度，如 to right => 90，与 ArkUI 的 angle 一致：0 为向上，顺时针为正
+  // 对角方向的角度取决于元素的宽高比，保留为 GradientDirection
+  let (angle, derection) = match &gradient.direction {
+    LineDirection::Angle(angle) => (Some(get_angle_degrees(angle)), None),
+    LineDirection::Vertical(VerticalPositionKeyword::Top) => (Some(0.0), None),
+    LineDirection::Horizontal(HorizontalPositionKeyword::Right) => (Some(90.0), None),
+    LineDirection::Vertical(VerticalPositionKeyword::Bottom) => (Some(180.0), None),
+    LineDirection::Horizontal(HorizontalPositionKeyword::Left) => (Some(270.0), None),
+    LineDirection::Corner { horizontal, vertical } => (None, Some(match (horizontal, vertical) {
+      (HorizontalPositionKeyword::Left, VerticalPositionKeyword::Top) => LinearGradientDirection::LeftTop,
+      (HorizontalPositionKeyword::Left, VerticalPositionKeyword::Bottom) => LinearGradientDirection::LeftBottom,
+      (HorizontalPositionKeyword::Right, VerticalPositionKeyword::Top) => LinearGradientDirection::RightTop,
+      (HorizontalPositionKeyword::Right, VerticalPositionKeyword::Bottom) => LinearGradientDirection::RightBottom,
+    })),
+  };
+  BackgroundImageKind::LinearGradient(LinearGradientItem {
+    angle,
+    color_stops,
+    derection,
+    repeating,
+  })
+}
+
+fn parse_radial_gradient(gradient: &RadialGradient, repeating: bool) -> BackgroundImageKind {
+  // Radial 华为的半径需要具体的单位
+  BackgroundImageKind::RadialGradient(RadialGradientItem {
+    color_stops: parse_color_stops(&gradient.items, get_length_offset),
+    point: RadialGradientPoint { x: gradient.position.x.clone(), y: gradient.position.y.clone() },
+    shape: gradient.shape.clone(),
+    repeating,
+  })
+}
+
+// conic-gradient 对应 ArkUI 的 sweepGradient，起始角度 from 对应 rotation
+fn parse_conic_gradient(gradient: &ConicGradient, repeating: bool) -> BackgroundImageKind {
+  let rotation = get_angle_degrees(&gradient.angle);
+  BackgroundImageKind::SweepGradient(SweepGradientItem {
+    color_stops: parse_color_stops(&gradient.items, get_angle_offset),
+    point: RadialGradientPoint { x: gradient.position.x.clone(), y: gradient.position.y.clone() },
+    rotation: if rotation == 0.0 { None } else { Some(rotation) },
+    repeating,
+  })
+}
 
 pub fn parse_background_image_item(image: &Image) -> Option<BackgroundImageKind> {
   match image {
@@ -23,150 +121,12 @@ pub fn parse_background_image_item(image: &Image) -> Option<BackgroundImageKind>
     ),
     Image::Gradient(gradient) => {
       match &**gradient {
-        Gradient::Linear(gradient) => {
-          let mut color_stops = vec![];
-          let mut now_percentage = 0.0;
-          let colors_len = gradient.items.len() - 1;
-          for (index, item) in gradient.items.clone().into_iter().enumerate()  {
-            match item {
-              GradientItem::ColorStop(color_stop) => {
-                let item_pecentage = now_percentage + (((1.0 - now_percentage) / colors_len as f32) * index as f32);
-                let color_stop_position = color_stop
-                  .position
-                  .clone()
-                  .unwrap_or(DimensionPercentage::Percentage(Percentage(item_pecentage)));
-                color_stops.push((
-                  generate_expr_lit_str!(convert_color_keywords_to_hex(color_stop
-                    .color
-                    .to_css_string(PrinterOptions {
-                      minify: false,
-                      targets: Targets {
-                        include: Features::HexAlphaColors,
-                        ..Targets::default()
-                      },
-                      ..PrinterOptions::default()
-                    })
-                    .unwrap())),
-                  match &color_stop_position {
-                    DimensionPercentage::Dimension(_) => generate_expr_lit_num!(0.0),
-                    DimensionPercentage::Percentage(percentage) => {
-                      now_percentage = percentage.0;
-                      generate_expr_lit_num!(percentage.0 as f64)
-                    },
-                    DimensionPercentage::Calc(_) => generate_expr_lit_num!(0.0),
-                  },
-                ));
-              }
-              _ => {}
-            };
-          }
-          let direction = &gradient.direction;
-          match direction {
-            LineDirection::Angle(angle) => {
-              let angle = match angle {
-                Angle::Deg(deg) => Some(*deg),
-                Angle::Rad(rad) => Some(rad.to_degrees()),
-                Angle::Turn(turn) => Some(turn * 360.0),
-                Angle::Grad(grad) => Some(grad * 0.9),
-              };
-              Some(
-                BackgroundImageKind::LinearGradient(LinearGradientItem {
-                  angle,
-                  color_stops,
-                  derection: None,
-                })
-              )
-            }
-            LineDirection::Horizontal(horizontal) => Some(BackgroundImageKind::LinearGradient(LinearGradientItem {
-              angle: None,
-              color_stops,
-              derection: Some(match horizontal {
-                HorizontalPositionKeyword::Left => LinearGradientDirection::Left,
-                HorizontalPositionKeyword::Right => LinearGradientDirection::Right,
-              })
-            })),
-            LineDirection::Vertical(vertical) => Some(BackgroundImageKind::LinearGradient(LinearGradientItem {
-              angle: None,
-              color_stops,
-              derection: Some(match vertical {
-                VerticalPositionKeyword::Top => LinearGradientDirection::Top,
-                VerticalPositionKeyword::Bottom => LinearGradientDirection::Bottom,
-              }),
-            })),
-            LineDirection::Corner {
-              horizontal,
-              vertical,
-            } => Some(BackgroundImageKind::LinearGradient(LinearGradientItem {
-              angle: None,
-              color_stops,
-              derection: Some(match (horizontal, vertical) {
-                (HorizontalPositionKeyword::Left, VerticalPositionKeyword::Top) => {
-                  LinearGradientDirection::LeftTop
-                }
-                (HorizontalPositionKeyword::Left, VerticalPositionKeyword::Bottom) => {
-                  LinearGradientDirection::LeftBottom
-                }
-                (HorizontalPositionKeyword::Right, VerticalPositionKeyword::Top) => {
-                  LinearGradientDirection::RightTop
-                }
-                (HorizontalPositionKeyword::Right, VerticalPositionKeyword::Bottom) => {
-                  LinearGradientDirection::RightBottom
-                }
-              }),
-            })),
-          }
-        },
-        Gradient::RepeatingLinear(_) => None,
-        Gradient::Radial(gradient) => { 
-          // Radial 华为的半径需要具体的单位
-          let mut color_stops = vec![];
-          let mut now_percentage = 0.0;
-          let colors_len = gradient.items.len() - 1;
-          for (index, item) in gradient.items.clone().into_iter().enumerate()  {
-            match item {
-              GradientItem::ColorStop(color_stop) => {
-                let item_pecentage = now_percentage + (((1.0 - now_percentage) / colors_len as f32) * index as f32);
-                let color_stop_position = color_stop
-                  .position
-                  .clone()
-                  .unwrap_or(DimensionPercentage::Percentage(Percentage(item_pecentage)));
-                color_stops.push((
-                  generate_expr_lit_str!(convert_color_keywords_to_hex(color_stop
-                    .color
-                    .to_css_string(PrinterOptions {
-                      minify: false,
-                      targets: Targets {
-                        include: Features::HexAlphaColors,
-                        ..Targets::default()
-                      },
-                      ..PrinterOptions::default()
-                    })
-                    .unwrap())),
-                  match &color_stop_position {
-                    DimensionPercentage::Dimension(_) => generate_expr_lit_num!(0.0),
-                    DimensionPercentage::Percentage(percentage) => {
-                      now_percentage = percentage.0;
-                      generate_expr_lit_num!(percentage.0 as f64)
-                    },
-                    DimensionPercentage::Calc(_) => generate_expr_lit_num!(0.0),
-                  },
-                ));
-              }
-              _ => {}
-            };
-          }
-          
-         
-
-          Some(BackgroundImageKind::RadialGradient(RadialGradientItem {
-            color_stops,
-            point: RadialGradientPoint { x: gradient.position.x.clone(), y: gradient.position.y.clone() },
-            shape: gradient.shape.clone(),
-          }))
-        },
-        Gradient::RepeatingRadial(_) => None,
-        Gradient::Conic(_) => None,
-        Gradient::RepeatingConic(_) => None,
+        Gradient::Linear(gradient) => Some(parse_linear_gradient(gradient, false)),
+        Gradient::RepeatingLinear(gradient) => Some(parse_linear_gradient(gradient, true)),
+        Gradient::Radial(gradient) => Some(parse_radial_gradient(gradient, false)),
+        Gradient::RepeatingRadial(gradient) => Some(parse_radial_gradient(gradient, true)),
+        Gradient::Conic(gradient) => Some(parse_conic_gradient(gradient, false)),
+        Gradient::RepeatingConic(gradient) => Some(parse_conic_gradient(gradient, true)),
         Gradient::WebKitGradient(_) => None,
       }
     }
@@ -189,6 +149,7 @@ pub enum BackgroundImageKind {
   String(String),
   LinearGradient(LinearGradientItem),
   RadialGradient(RadialGradientItem),
+  SweepGradient(SweepGradientItem),
 }
 
 #[derive(Debug, Clone)]
@@ -218,6 +179,9 @@ impl ToExpr for BackgroundImage {
       Some(BackgroundImageKind::RadialGradient(radial_gradient)) => {
         radial_gradient.to_expr()
       },
+      Some(BackgroundImageKind::SweepGradient(sweep_gradient)) => {
+        sweep_gradient.to_expr()
+      },
       _ => generate_invalid_expr!()
     };
     PropertyTuple::One(
@@ -1,7 +1,4 @@
-use lightningcss::{
-  properties::Property,
-  traits::ToCss
-};
+use lightningcss::properties::Property;
 
 use swc_core::common::DUMMY_SP;
 use swc_core::ecma::ast::*;
@@ -1,6 +1,6 @@
 use lightningcss::{
   properties::Property,
-  traits::ToCss, values::color::CssColor
+  values::color::CssColor
 };
 
 use swc_core::ecma::ast::Expr;
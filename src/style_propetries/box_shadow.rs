@@ -1,10 +1,10 @@
-use lightningcss::{properties::Property, values::{length::Length, color::CssColor}, traits::ToCss};
+use lightningcss::{properties::Property, values::{length::Length, color::CssColor}};
 
 use swc_core::ecma::ast::*;
 use swc_core::common::DUMMY_SP;
-use crate::{generate_expr_by_length, generate_expr_lit_bool, generate_prop_name, generate_string_by_css_color, style_propetries::traits::ToExpr};
+use crate::{diagnostics::push_diagnostic, generate_expr_by_length, generate_expr_lit_bool, generate_expr_lit_num, generate_invalid_expr, generate_prop_name, generate_string_by_css_color, options::{with_transform_options, RnOs}, style_propetries::traits::ToExpr};
 
-use super::unit::PropertyTuple;
+use super::unit::{generate_expr_by_length_value, Platform, PropertyTuple};
 
 
 #[derive(Debug, Clone)]
@@ -92,6 +92,9 @@ impl ToExpr for BoxShadow {
     }
 
     fn to_rn_expr(&self) -> PropertyTuple {
+      if let Some(os) = with_transform_options(|options| options.rn_os.clone()) {
+        return self.to_rn_os_expr(os);
+      }
       PropertyTuple::Array(
         vec![
           ("BoxShadowOffset".to_string(), Expr::Object(ObjectLit {
@@ -118,6 +121,57 @@ impl ToExpr for BoxShadow {
     }
 }
 
+impl BoxShadow {
+  // 区分系统输出：android 的阴影由 elevation 决定，ios 使用 shadow* 属性
+  fn to_rn_os_expr(&self, os: RnOs) -> PropertyTuple {
+    if self.inset == Some(true) {
+      push_diagnostic(&self.id, "inset box-shadow is not supported on ReactNative".to_string());
+      return PropertyTuple::One("boxShadow".to_string(), generate_invalid_expr!());
+    }
+    let mut props = vec![];
+    if let Some(color) = &self.color {
+      props.push(("shadowColor".to_string(), generate_string_by_css_color!(color)));
+    }
+    match os {
+      RnOs::Android => {
+        // elevation 为阴影的高度，取模糊半径的一半
+        match &self.blur_radius {
+          Some(Length::Value(blur_radius)) => props.push(("elevation".to_string(), generate_expr_by_length_value(&(blur_radius.clone() * 0.5), Platform::ReactNative))),
+          Some(Length::Calc(_)) => push_diagnostic(&self.id, "calc() blur radius can not be converted to elevation".to_string()),
+          None => {}
+        }
+      },
+      RnOs::Ios => {
+        if let (Some(offset_x), Some(offset_y)) = (&self.offset_x, &self.offset_y) {
+          props.push(("shadowOffset".to_string(), Expr::Object(ObjectLit {
+            span: DUMMY_SP,
+            props: vec![
+              PropOrSpread::Prop(Box::new(Prop::KeyValue(
+                KeyValueProp {
+                  key: generate_prop_name!("width"),
+                  value: Box::new(generate_expr_by_length!(offset_x, Platform::ReactNative)),
+                }
+              ))),
+              PropOrSpread::Prop(Box::new(Prop::KeyValue(
+                KeyValueProp {
+                  key: generate_prop_name!("height"),
+                  value: Box::new(generate_expr_by_length!(offset_y, Platform::ReactNative)),
+                }
+              ))),
+            ],
+          })));
+        }
+        // 透明度由颜色的 alpha 决定
+        props.push(("shadowOpacity".to_string(), generate_expr_lit_num!(1.0)));
+        if let Some(blur_radius) = &self.blur_radius {
+          props.push(("shadowRadius".to_string(), generate_expr_by_length!(blur_radius, Platform::ReactNative)));
+        }
+      }
+    }
+    PropertyTuple::Array(props)
+  }
+}
+
 impl From<(String, &Property<'_>)> for BoxShadow {
   fn from(prop: (String, &Property<'_>)) -> Self {
     let box_shadow = BoxShadow::new(prop.0);
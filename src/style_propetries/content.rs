@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+
+use lightningcss::properties::{custom::{Token, TokenList, TokenOrValue}, Property};
+
+use crate::{diagnostics::push_diagnostic, generate_expr_lit_str, generate_invalid_expr};
+
+use super::{traits::ToExpr, unit::PropertyTuple};
+
+
+// content 中的一段内容
+#[derive(Debug, Clone)]
+pub enum ContentItem {
+  // 字符串字面量，转义字符（如 "\2022"）已由 lightningcss 解码
+  Text(String),
+  // counter(name)，在同一条规则中声明了 counter-reset / counter-increment 时可静态求值
+  Counter(String),
+  // 无法静态求值的内容，如 attr()、counters()、open-quote
+  Unsupported(String),
+}
+
+// ::before / ::after 的 content 属性，生成伪元素中的文本
+#[derive(Debug, Clone)]
+pub struct Content {
+  pub value: Vec<ContentItem>,
+  // 同一条规则中声明的计数器最终值
+  pub counters: HashMap<String, i32>
+}
+
+fn get_token_list<'a, 'i>(property: &'a Property<'i>) -> Option<&'a TokenList<'i>> {
+  match property {
+    Property::Unparsed(unparsed) => Some(&unparsed.value),
+    Property::Custom(custom) => Some(&custom.value),
+    _ => None
+  }
+}
+
+// 解析 counter-reset / counter-increment 的值，如 `item 2 other`，未指定数值时取 default_value
+pub fn parse_counter_list(property: &Property<'_>, default_value: i32) -> Vec<(String, i32)> {
+  let mut counters: Vec<(String, i32)> = vec![];
+  if let Some(token_list) = get_token_list(property) {
+    token_list.0.iter().for_each(|item| {
+      match item {
+        // none 表示不设置计数器
+        TokenOrValue::Token(Token::Ident(name)) if name.as_ref() != "none" => {
+          counters.push((name.to_string(), default_value));
+        },
+        TokenOrValue::Token(Token::Number { int_value: Some(num), .. }) => {
+          if let Some(last) = counters.last_mut() {
+            last.1 = *num;
+          }
+        },
+        _ => {}
+      }
+    });
+  }
+  counters
+}
+
+impl From<(String, &Property<'_>, HashMap<String, i32>)> for Content {
+  fn from(value: (String, &Property<'_>, HashMap<String, i32>)) -> Self {
+    let mut content_items = vec![];
+    if let Some(token_list) = get_token_list(value.1) {
+      token_list.0.iter().for_each(|item| {
+        match item {
+          TokenOrValue::Token(Token::String(text)) => content_items.push(ContentItem::Text(text.to_string())),
+          // content: none / normal 不生成内容
+          TokenOrValue::Token(Token::Ident(ident)) if ident.as_ref() == "none" || ident.as_ref() == "normal" => {},
+          TokenOrValue::Token(Token::WhiteSpace(_)) => {},
+          TokenOrValue::Function(function) if function.name.0.as_ref() == "counter" => {
+            // 仅支持 counter(name) 及 counter(name, decimal)
+            let mut args = function.arguments.0.iter().filter(|token| !matches!(token, TokenOrValue::Token(Token::WhiteSpace(_))));
+            match (args.next(), args.next(), args.next()) {
+              (Some(TokenOrValue::Token(Token::Ident(name))), None, None) => content_items.push(ContentItem::Counter(name.to_string())),
+              (Some(TokenOrValue::Token(Token::Ident(name))), Some(TokenOrValue::Token(Token::Comma)), Some(TokenOrValue::Token(Token::Ident(style)))) if style.as_ref() == "decimal" => {
+                content_items.push(ContentItem::Counter(name.to_string()))
+              },
+              _ => content_items.push(ContentItem::Unsupported("counter()".to_string()))
+            }
+          },
+          TokenOrValue::Function(function) => content_items.push(ContentItem::Unsupported(format!("{}()", function.name.0))),
+          TokenOrValue::Token(Token::Ident(ident)) => content_items.push(ContentItem::Unsupported(ident.to_string())),
+          _ => content_items.push(ContentItem::Unsupported("content value".to_string()))
+        }
+      });
+    }
+    Content {
+      value: content_items,
+      counters: value.2
+    }
+  }
+}
+
+impl Content {
+  // 拼接出伪元素的文本，无法求值的部分会被忽略
+  fn get_text(&self) -> String {
+    let mut text = String::new();
+    self.value.iter().for_each(|item| {
+      match item {
+        ContentItem::Text(value) => text.push_str(value),
+        ContentItem::Counter(name) => {
+          match self.counters.get(name) {
+            Some(num) => text.push_str(&num.to_string()),
+            None => push_diagnostic("content", format!("counter({}) must be declared with counter-reset or counter-increment in the same rule and will be ignored", name))
+          }
+        },
+        ContentItem::Unsupported(name) => push_diagnostic("content", format!("{} is not supported in content and will be ignored", name))
+      }
+    });
+    text
+  }
+}
+
+impl ToExpr for Content {
+  fn to_expr(&self) -> PropertyTuple {
+    // 空字符串不生成伪元素
+    let text = self.get_text();
+    PropertyTuple::One(
+      "content".to_string(),
+      if text.is_empty() {
+        generate_invalid_expr!()
+      } else {
+        generate_expr_lit_str!(text)
+      }
+    )
+  }
+
+  fn to_rn_expr(&self) -> PropertyTuple {
+    push_diagnostic("content", "::before and ::after are not supported on ReactNative".to_string());
+    PropertyTuple::One(
+      "content".to_string(),
+      generate_invalid_expr!()
+    )
+  }
+}
@@ -0,0 +1,87 @@
+use lightningcss::{properties::Property, stylesheet::PrinterOptions};
+
+use crate::{diagnostics::push_diagnostic, generate_expr_enum, generate_expr_lit_str, generate_invalid_expr};
+
+use super::{traits::ToExpr, unit::PropertyTuple};
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EnumValue {
+  // direction
+  Ltr,
+  Rtl,
+  Inherit,
+  // writing-mode
+  HorizontalTb,
+  Vertical,
+  Invalid
+}
+
+// direction、writing-mode
+#[derive(Debug, Clone)]
+pub struct Direction {
+  pub id: String,
+  pub value: EnumValue
+}
+
+impl From<(String, &Property<'_>)> for Direction {
+  fn from(value: (String, &Property<'_>)) -> Self {
+    // lightningcss 不识别 direction、writing-mode，按字符串解析
+    let value_str = value.1.value_to_css_string(PrinterOptions::default()).unwrap_or_default();
+    let is_writing_mode = value.0 == "writingMode";
+    Direction {
+      id: value.0,
+      value: match (value_str.trim(), is_writing_mode) {
+        ("ltr", false) => EnumValue::Ltr,
+        ("rtl", false) => EnumValue::Rtl,
+        ("inherit", false) => EnumValue::Inherit,
+        ("horizontal-tb", true) => EnumValue::HorizontalTb,
+        ("vertical-rl" | "vertical-lr" | "sideways-rl" | "sideways-lr", true) => EnumValue::Vertical,
+        _ => EnumValue::Invalid
+      }
+    }
+  }
+}
+
+impl Direction {
+  // 两端均只支持横向书写，horizontal-tb 为默认值无需输出，纵向书写输出诊断信息
+  fn to_writing_mode_expr(&self) -> PropertyTuple {
+    if self.value == EnumValue::Vertical {
+      push_diagnostic(&self.id, "vertical writing mode is not supported".to_string());
+    }
+    PropertyTuple::One(self.id.to_string(), generate_invalid_expr!())
+  }
+}
+
+impl ToExpr for Direction {
+  fn to_expr(&self) -> PropertyTuple {
+    if self.id == "writingMode" {
+      return self.to_writing_mode_expr()
+    }
+    // 鸿蒙通过通用属性 direction 设置布局方向，inherit 对应跟随系统语言方向的 Auto
+    PropertyTuple::One(
+      self.id.to_string(),
+      match self.value {
+        EnumValue::Ltr => generate_expr_enum!("Direction", "Ltr"),
+        EnumValue::Rtl => generate_expr_enum!("Direction", "Rtl"),
+        EnumValue::Inherit => generate_expr_enum!("Direction", "Auto"),
+        _ => generate_invalid_expr!()
+      }
+    )
+  }
+
+  fn to_rn_expr(&self) -> PropertyTuple {
+    if self.id == "writingMode" {
+      return self.to_writing_mode_expr()
+    }
+    PropertyTuple::One(
+      self.id.to_string(),
+      match self.value {
+        EnumValue::Ltr => generate_expr_lit_str!("ltr"),
+        EnumValue::Rtl => generate_expr_lit_str!("rtl"),
+        EnumValue::Inherit => generate_expr_lit_str!("inherit"),
+        _ => generate_invalid_expr!()
+      }
+    )
+  }
+}
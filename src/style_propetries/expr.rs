@@ -11,6 +11,10 @@ impl Expr {
   pub fn new(id: String, value: ast::Expr) -> Self {
     Self(id, value)
   }
+
+  pub fn id(&self) -> &str {
+    &self.0
+  }
 }
 
 
@@ -0,0 +1,123 @@
+use lightningcss::{properties::{Property, effects::{self, FilterList}}, stylesheet::PrinterOptions, traits::ToCss, values::{length::Length, percentage::NumberOrPercentage}};
+
+use crate::{diagnostics::push_diagnostic, generate_expr_by_length, generate_expr_lit_num, generate_invalid_expr};
+
+use super::{traits::ToExpr, unit::{convert_f32_to_f64, PropertyTuple}};
+
+
+#[derive(Debug, Clone)]
+pub enum FilterItem {
+  Blur(Length),
+  Brightness(f32),
+  Contrast(f32),
+  Grayscale(f32),
+  // 单位：deg
+  HueRotate(f32),
+  Invert(f32),
+  Opacity(f32),
+  Saturate(f32),
+  Sepia(f32),
+  // 暂不支持的函数，如 drop-shadow()、url()
+  Unsupported(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct Filter {
+  pub id: String,
+  pub value: Vec<FilterItem>
+}
+
+fn number_or_percentage_to_f32(value: &NumberOrPercentage) -> f32 {
+  match value {
+    NumberOrPercentage::Number(num) => *num,
+    NumberOrPercentage::Percentage(percentage) => percentage.0,
+  }
+}
+
+impl From<(String, &Property<'_>)> for Filter {
+  fn from(value: (String, &Property<'_>)) -> Self {
+    let mut filter_items = vec![];
+    match value.1 {
+      Property::Filter(filter_list, _) | Property::BackdropFilter(filter_list, _) => {
+        if let FilterList::Filters(filters) = filter_list {
+          filters.iter().for_each(|filter| {
+            filter_items.push(match filter {
+              effects::Filter::Blur(length) => FilterItem::Blur(length.clone()),
+              effects::Filter::Brightness(value) => FilterItem::Brightness(number_or_percentage_to_f32(value)),
+              effects::Filter::Contrast(value) => FilterItem::Contrast(number_or_percentage_to_f32(value)),
+              effects::Filter::Grayscale(value) => FilterItem::Grayscale(number_or_percentage_to_f32(value)),
+              effects::Filter::HueRotate(angle) => FilterItem::HueRotate(angle.to_degrees()),
+              effects::Filter::Invert(value) => FilterItem::Invert(number_or_percentage_to_f32(value)),
+              effects::Filter::Opacity(value) => FilterItem::Opacity(number_or_percentage_to_f32(value)),
+              effects::Filter::Saturate(value) => FilterItem::Saturate(number_or_percentage_to_f32(value)),
+              effects::Filter::Sepia(value) => FilterItem::Sepia(number_or_percentage_to_f32(value)),
+              _ => FilterItem::Unsupported(filter.to_css_string(PrinterOptions::default()).unwrap()),
+            })
+          });
+        }
+      }
+      _ => {}
+    }
+    Filter {
+      id: value.0,
+      value: filter_items
+    }
+  }
+}
+
+impl ToExpr for Filter {
+  fn to_expr(&self) -> PropertyTuple {
+    let is_backdrop = self.id != "filter";
+    let mut props = vec![];
+    // 每个滤镜函数对应鸿蒙的一个同名属性，如 blur(4px) => blur: convertNumber2VP(4)
+    self.value.iter().for_each(|item| {
+      let prop = match item {
+        FilterItem::Blur(length) => {
+          let key = if is_backdrop { "backdropBlur" } else { "blur" };
+          Some((key, generate_expr_by_length!(length, Platform::Harmony)))
+        },
+        // backdrop-filter 在鸿蒙上仅支持 blur
+        _ if is_backdrop => None,
+        FilterItem::Brightness(num) => Some(("brightness", generate_expr_lit_num!(convert_f32_to_f64(*num)))),
+        FilterItem::Contrast(num) => Some(("contrast", generate_expr_lit_num!(convert_f32_to_f64(*num)))),
+        FilterItem::Grayscale(num) => Some(("grayscale", generate_expr_lit_num!(convert_f32_to_f64(*num)))),
+        FilterItem::HueRotate(deg) => Some(("hueRotate", generate_expr_lit_num!(convert_f32_to_f64(*deg)))),
+        FilterItem::Invert(num) => Some(("invert", generate_expr_lit_num!(convert_f32_to_f64(*num)))),
+        FilterItem::Opacity(num) => Some(("opacity", generate_expr_lit_num!(convert_f32_to_f64(*num)))),
+        FilterItem::Saturate(num) => Some(("saturate", generate_expr_lit_num!(convert_f32_to_f64(*num)))),
+        FilterItem::Sepia(num) => Some(("sepia", generate_expr_lit_num!(convert_f32_to_f64(*num)))),
+        FilterItem::Unsupported(_) => None,
+      };
+      match prop {
+        Some((key, expr)) => props.push((key.to_string(), expr)),
+        None => push_diagnostic(&self.id, format!("{} is not supported on Harmony", filter_item_name(item)))
+      }
+    });
+    PropertyTuple::Array(props)
+  }
+
+  fn to_rn_expr(&self) -> PropertyTuple {
+    self.value.iter().for_each(|item| {
+      push_diagnostic(&self.id, format!("{} is not supported on ReactNative", filter_item_name(item)))
+    });
+    PropertyTuple::One(
+      self.id.to_string(),
+      generate_invalid_expr!()
+    )
+  }
+}
+
+fn filter_item_name(item: &FilterItem) -> String {
+  match item {
+    FilterItem::Blur(_) => "blur()".to_string(),
+    FilterItem::Brightness(_) => "brightness()".to_string(),
+    FilterItem::Contrast(_) => "contrast()".to_string(),
+    FilterItem::Grayscale(_) => "grayscale()".to_string(),
+    FilterItem::HueRotate(_) => "hue-rotate()".to_string(),
+    FilterItem::Invert(_) => "invert()".to_string(),
+    FilterItem::Opacity(_) => "opacity()".to_string(),
+    FilterItem::Saturate(_) => "saturate()".to_string(),
+    FilterItem::Sepia(_) => "sepia()".to_string(),
+    FilterItem::Unsupported(value) => value.to_string(),
+  }
+}
@@ -1,6 +1,6 @@
 use lightningcss::properties::Property;
 
-use crate::{generate_invalid_expr, generate_tpl_expr};
+use crate::generate_invalid_expr;
 
 use super::{flex_basis::FlexBasis, number::NumberProperty, traits::ToExpr, unit::PropertyTuple};
 
@@ -21,21 +21,15 @@ pub struct FlexValue {
 
 impl ToExpr for Flex {
   fn to_expr(&self) -> PropertyTuple {
-    let mut props = vec![];
     if let Some(value) = &self.value {
-      match value.basis.to_expr() {
-        PropertyTuple::One(_, val) => props.push(("flexBasis".to_string(), val)),
-        _ => {}
-      };
-      match value.grow.to_expr() {
-        PropertyTuple::One(_, val) => props.push(("flexGrow".to_string(), val)),
-        _ => {}
-      };
-      match value.shrink.to_expr() {
-        PropertyTuple::One(_, val) => props.push(("flexShrink".to_string(), val)),
-        _ => {}
-      };
-      PropertyTuple::Array(props)
+      PropertyTuple::Array(vec![
+        value.grow.to_expr(),
+        value.shrink.to_expr(),
+        value.basis.to_expr()
+      ].into_iter().filter_map(|prop| match prop {
+        PropertyTuple::One(id, val) => Some((id, val)),
+        _ => None
+      }).collect())
     } else {
       PropertyTuple::One(
         "flex".to_string(),
@@ -45,25 +39,16 @@ impl ToExpr for Flex {
   }
 
   fn to_rn_expr(&self) -> PropertyTuple {
-    let mut props = vec![];
-
+    // RN 的 flex 仅支持单个数字，统一展开为 flexGrow、flexShrink、flexBasis
     if let Some(value) = &self.value {
-      match value.grow.to_rn_expr() {
-        PropertyTuple::One(_, val) => props.push(val),
-        _ => {}
-      };
-      match value.shrink.to_rn_expr() {
-        PropertyTuple::One(_, val) => props.push(val),
-        _ => {}
-      };
-      match value.basis.to_rn_expr() {
-        PropertyTuple::One(_, val) => props.push(val),
-        _ => {}
-      };
-      PropertyTuple::One(
-        "flex".to_string(),
-        generate_tpl_expr!(props)
-      )
+      PropertyTuple::Array(vec![
+        value.grow.to_rn_expr(),
+        value.shrink.to_rn_expr(),
+        value.basis.to_rn_expr()
+      ].into_iter().filter_map(|prop| match prop {
+        PropertyTuple::One(id, val) => Some((id, val)),
+        _ => None
+      }).collect())
     } else {
       PropertyTuple::One(
         "flex".to_string(),
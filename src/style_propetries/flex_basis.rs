@@ -18,7 +18,8 @@ pub struct FlexBasis {
 pub enum EnumValue {
   LengthValue(LengthValue),
   Percentage(Percentage),
-  String(String)
+  String(String),
+  Auto
 }
 
 impl ToExpr for FlexBasis {
@@ -29,6 +30,7 @@ impl ToExpr for FlexBasis {
         EnumValue::String(value) => generate_expr_lit_calc!(value, Platform::Harmony),
         EnumValue::LengthValue(length_value) => generate_expr_by_length_value(length_value, Platform::Harmony),
         EnumValue::Percentage(value) => generate_expr_lit_str!((value.0 * 100.0).to_string() + "%"),
+        EnumValue::Auto => generate_expr_lit_str!("auto"),
       }
     )
   }
@@ -37,9 +39,10 @@ impl ToExpr for FlexBasis {
     PropertyTuple::One(
       self.id.to_string(),
       match &self.value {
-        EnumValue::String(value) => generate_expr_lit_calc!(value, Platform::Harmony),
+        EnumValue::String(value) => generate_expr_lit_calc!(value, Platform::ReactNative),
         EnumValue::LengthValue(length_value) => generate_expr_by_length_value(length_value, Platform::ReactNative),
         EnumValue::Percentage(value) => generate_expr_lit_str!((value.0 * 100.0).to_string() + "%"),
+        EnumValue::Auto => generate_expr_lit_str!("auto"),
       }
     )
   }
@@ -52,11 +55,11 @@ impl From<(String, &Property<'_>)> for FlexBasis {
       value: match prop.1 {
         Property::FlexBasis(value, _) => {
           match &value {
-            LengthPercentageOrAuto::Auto => EnumValue::String("auto".to_string()),
+            LengthPercentageOrAuto::Auto => EnumValue::Auto,
             LengthPercentageOrAuto::LengthPercentage(value) => generate_dimension_percentage!(EnumValue, value),
           }
         }
-        _ => EnumValue::String("auto".to_string())
+        _ => EnumValue::Auto
       }
     }
   }
@@ -67,7 +70,7 @@ impl FlexBasis {
     FlexBasis {
       id: prop.0,
       value: match prop.1 {
-        LengthPercentageOrAuto::Auto => EnumValue::String("auto".to_string()),
+        LengthPercentageOrAuto::Auto => EnumValue::Auto,
         LengthPercentageOrAuto::LengthPercentage(value) => generate_dimension_percentage!(EnumValue, value),
       }
     }
@@ -0,0 +1,59 @@
+use lightningcss::{properties::{Property, font}, stylesheet::PrinterOptions, traits::ToCss};
+
+use crate::{generate_expr_lit_str, generate_invalid_expr, options::with_transform_options};
+
+use super::{traits::ToExpr, unit::PropertyTuple};
+
+
+#[derive(Debug, Clone)]
+pub struct FontFamily {
+  pub id: String,
+  // 去掉引号后的字体名称列表，如 "PingFang SC", sans-serif => [PingFang SC, sans-serif]
+  pub value: Vec<String>
+}
+
+impl From<(String, &Property<'_>)> for FontFamily {
+  fn from(prop: (String, &Property<'_>)) -> Self {
+    FontFamily {
+      id: prop.0,
+      value: match prop.1 {
+        Property::FontFamily(families) => {
+          families.iter().map(|family| {
+            match family {
+              font::FontFamily::FamilyName(name) => name.to_string(),
+              font::FontFamily::Generic(generic) => generic.to_css_string(PrinterOptions::default()).unwrap()
+            }
+          }).collect()
+        }
+        _ => vec![]
+      }
+    }
+  }
+}
+
+impl FontFamily {
+  // 平台只支持单个字体名称：优先取字体映射表中第一个命中的字体，否则取第一个字体
+  fn get_family(&self) -> Option<String> {
+    with_transform_options(|options| {
+      self.value.iter()
+        .find_map(|family| options.font_family_map.get(family).cloned())
+        .or_else(|| self.value.first().cloned())
+    })
+  }
+}
+
+impl ToExpr for FontFamily {
+  fn to_expr(&self) -> PropertyTuple {
+    PropertyTuple::One(
+      self.id.to_string(),
+      match self.get_family() {
+        Some(family) => generate_expr_lit_str!(family),
+        None => generate_invalid_expr!()
+      }
+    )
+  }
+
+  fn to_rn_expr(&self) -> PropertyTuple {
+    self.to_expr()
+  }
+}
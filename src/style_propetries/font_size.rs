@@ -1,11 +1,13 @@
 use lightningcss::{
   properties::{Property, font},
-  values::{length::LengthValue, percentage::Percentage}, traits::ToCss,
+  values::{length::LengthValue, percentage::{DimensionPercentage, Percentage}}, traits::{Map, ToCss},
 };
 
-use crate::{generate_dimension_percentage, generate_expr_lit_calc, generate_expr_lit_str, generate_invalid_expr};
+use swc_core::ecma::ast::Expr;
 
-use super::{traits::ToExpr, unit::{generate_expr_by_length_value, Platform, PropertyTuple}};
+use crate::{diagnostics::push_diagnostic, options::with_transform_options, generate_dimension_percentage, generate_expr_lit_calc, generate_expr_lit_str, generate_invalid_expr};
+
+use super::{traits::ToExpr, unit::{convert_f32_to_f64, generate_expr_by_length_value, Platform, PropertyTuple}};
 
 
 #[derive(Debug, Clone)]
@@ -64,3 +66,42 @@ impl From<(String, &Property<'_>)> for FontSize {
     }
   }
 }
+
+// 相对于节点字号的长度，如 line-height: 1.5、letter-spacing: 0.1em
+pub enum FontRelativeLength {
+  LengthValue(LengthValue),
+  // 字号为 calc() 时，在运行时计算 字号 * 倍数：(字号的 calc 字符串, 倍数)
+  FontSizeMultiple(String, f32),
+}
+
+// 同一条规则中声明了字号时按该字号计算，否则按 em_font_size 计算（与 em 一致）并输出诊断信息
+// (属性名, 属性值, 倍数, 同一条规则中声明的字号)
+pub fn resolve_font_relative_length(id: &str, value: &Property<'_>, multiple: f32, font_size: Option<&Property<'_>>) -> FontRelativeLength {
+  match font_size {
+    Some(Property::FontSize(font::FontSize::Length(font_size))) => match font_size {
+      DimensionPercentage::Dimension(length_value) => FontRelativeLength::LengthValue(length_value.map(|num| multiply_f32(num, multiple))),
+      // 百分比的字号相对于父节点字号，同样按 em_font_size 计算
+      DimensionPercentage::Percentage(percentage) => FontRelativeLength::LengthValue(LengthValue::Em(multiply_f32(percentage.0, multiple))),
+      DimensionPercentage::Calc(calc) => FontRelativeLength::FontSizeMultiple(calc.to_css_string(Default::default()).unwrap(), multiple),
+    },
+    _ => {
+      // 节点的字号只在运行时确定，编译期无法得知，按 em_font_size 换算的结果可能与实际不符
+      push_diagnostic(id, format!(
+        "{} is relative to the font-size of the element, which is not declared as a length in the same rule, it is resolved against emFontSize ({}px)",
+        value.value_to_css_string(Default::default()).unwrap(),
+        with_transform_options(|options| options.em_font_size)
+      ));
+      FontRelativeLength::LengthValue(LengthValue::Em(multiple))
+    },
+  }
+}
+
+// 在 f64 中相乘，避免 f32 的精度误差，如 12 * 1.83 => 21.960001
+fn multiply_f32(num: f32, multiple: f32) -> f32 {
+  (convert_f32_to_f64(num) * convert_f32_to_f64(multiple)) as f32
+}
+
+// 字号 * 倍数合并为一个 calc 字符串，如 calc(10px + 1vw) * 1.2 => calc(12px + 1.2vw)，其中的长度逐个换算
+pub fn generate_font_size_multiple_expr(font_size: &str, multiple: f32, platform: Platform) -> Expr {
+  generate_expr_lit_calc!(format!("calc({} * {})", font_size, multiple), platform)
+}
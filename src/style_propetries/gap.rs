@@ -1,4 +1,4 @@
-use lightningcss::properties::{Property, align::GapValue};
+use lightningcss::{properties::{Property, align::GapValue, size::Size}, values::{calc::{Calc, MathFunction}, length::{LengthPercentage, LengthValue}, percentage::DimensionPercentage}};
 
 use crate::{generate_expr_by_length_percentage, generate_expr_lit_num, generate_expr_lit_str};
 
@@ -69,8 +69,76 @@ impl ToExpr for Gap {
 }
 
 
-impl From<(String, &Property<'_>)> for Gap {
-  fn from(prop: (String, &Property<'_>)) -> Self {
+// 同一条规则中以 px 声明的宽高，如 width: 200px => Some(200.0)
+pub fn get_px_size(value: &Property<'_>) -> Option<f32> {
+  match value {
+    Property::Width(Size::LengthPercentage(DimensionPercentage::Dimension(LengthValue::Px(px))))
+    | Property::Height(Size::LengthPercentage(DimensionPercentage::Dimension(LengthValue::Px(px)))) => Some(*px),
+    _ => None
+  }
+}
+
+// 按容器尺寸计算百分比的 px 值，包含 px 以外单位的 calc 无法静态计算
+fn resolve_length_percentage(value: &LengthPercentage, base: f32) -> Option<f32> {
+  match value {
+    DimensionPercentage::Dimension(LengthValue::Px(px)) => Some(*px),
+    DimensionPercentage::Percentage(percentage) => Some(percentage.0 * base),
+    DimensionPercentage::Calc(calc) => resolve_calc(calc, base),
+    _ => None
+  }
+}
+
+fn resolve_calc(calc: &Calc<LengthPercentage>, base: f32) -> Option<f32> {
+  match calc {
+    Calc::Value(value) => resolve_length_percentage(value, base),
+    Calc::Sum(a, b) => Some(resolve_calc(a, base)? + resolve_calc(b, base)?),
+    Calc::Product(num, value) => Some(num * resolve_calc(value, base)?),
+    Calc::Function(function) => match &**function {
+      MathFunction::Calc(calc) => resolve_calc(calc, base),
+      _ => None
+    },
+    _ => None
+  }
+}
+
+fn has_percentage(value: &LengthPercentage) -> bool {
+  match value {
+    DimensionPercentage::Percentage(_) => true,
+    DimensionPercentage::Calc(calc) => has_calc_percentage(calc),
+    _ => false
+  }
+}
+
+fn has_calc_percentage(calc: &Calc<LengthPercentage>) -> bool {
+  match calc {
+    Calc::Value(value) => has_percentage(value),
+    Calc::Sum(a, b) => has_calc_percentage(a) || has_calc_percentage(b),
+    Calc::Product(_, value) => has_calc_percentage(value),
+    Calc::Function(function) => match &**function {
+      MathFunction::Calc(calc) => has_calc_percentage(calc),
+      _ => false
+    },
+    _ => false
+  }
+}
+
+// 百分比的间距相对于容器的宽（column-gap）、高（row-gap），容器尺寸已知时转换为 px，否则原样输出
+fn resolve_gap(value: GapValue, base: Option<f32>) -> GapValue {
+  match (&value, base) {
+    (GapValue::LengthPercentage(val), Some(base)) if has_percentage(val) => {
+      match resolve_length_percentage(val, base) {
+        Some(px) => GapValue::LengthPercentage(DimensionPercentage::Dimension(LengthValue::Px(px))),
+        None => value
+      }
+    }
+    _ => value
+  }
+}
+
+// (属性名, 属性值, 同一条规则中以 px 声明的容器宽高)
+impl From<(String, &Property<'_>, (Option<f32>, Option<f32>))> for Gap {
+  fn from(prop: (String, &Property<'_>, (Option<f32>, Option<f32>))) -> Self {
+    let (width, height) = prop.2;
     let mut gap = Gap {
       id: prop.0,
       row: None,
@@ -78,14 +146,14 @@ impl From<(String, &Property<'_>)> for Gap {
     };
     match prop.1 {
       Property::Gap(value) => {
-        gap.row = Some(value.row.clone());
-        gap.column = Some(value.column.clone());
+        gap.row = Some(resolve_gap(value.row.clone(), height));
+        gap.column = Some(resolve_gap(value.column.clone(), width));
       }
       Property::RowGap(value) => {
-        gap.row = Some(value.clone())
+        gap.row = Some(resolve_gap(value.clone(), height))
       }
       Property::ColumnGap(value) => {
-        gap.column = Some(value.clone())
+        gap.column = Some(resolve_gap(value.clone(), width))
       }
       _ => {}
     };
@@ -5,12 +5,9 @@ use swc_core::common::DUMMY_SP;
 use crate::generate_expr_lit_num;
 
 
+// 对角方向，上下左右方向转换为角度
 #[derive(Debug, Clone)]
 pub enum LinearGradientDirection {
-  Left,
-  Right,
-  Top,
-  Bottom,
   LeftTop,
   LeftBottom,
   RightTop,
@@ -21,7 +18,8 @@ pub enum LinearGradientDirection {
 pub struct LinearGradientItem {
   pub angle: Option<f32>,
   pub color_stops: Vec<(Expr, Expr)>,
-  pub derection: Option<LinearGradientDirection>
+  pub derection: Option<LinearGradientDirection>,
+  pub repeating: bool,
 }
 
 impl LinearGradientItem {
@@ -75,10 +73,6 @@ impl LinearGradientItem {
           prop: MemberProp::Computed(ComputedPropName {
             span: DUMMY_SP,
             expr: Expr::Lit(Lit::Str(Str::from(match derection {
-              LinearGradientDirection::Left => "Left",
-              LinearGradientDirection::Right => "Right",
-              LinearGradientDirection::Top => "Top",
-              LinearGradientDirection::Bottom => "Bottom",
               LinearGradientDirection::LeftTop => "LeftTop",
               LinearGradientDirection::LeftBottom => "LeftBottom",
               LinearGradientDirection::RightTop => "RightTop",
@@ -90,6 +84,15 @@ impl LinearGradientItem {
         .into(),
       }))));
     }
+    if self.repeating {
+      props.push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+        key: PropName::Ident(Ident::new("repeating".into(), DUMMY_SP)),
+        value: Box::new(Expr::Lit(Lit::Bool(Bool {
+          span: DUMMY_SP,
+          value: true,
+        }))),
+      }))));
+    }
     Expr::Object(ObjectLit {
       span: DUMMY_SP,
       props,
@@ -1,2 +1,3 @@
 pub mod linear_gradient;
-pub mod radial_gradient;
\ No newline at end of file
+pub mod radial_gradient;
+pub mod sweep_gradient;
\ No newline at end of file
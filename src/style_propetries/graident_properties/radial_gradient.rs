@@ -12,11 +12,49 @@ pub struct RadialGradientPoint {
   pub y: PositionComponent<VerticalPositionKeyword>,
 }
 
+impl RadialGradientPoint {
+  // 渐变的中心点，如 ["50%", "50%"]
+  pub fn to_expr(&self) -> Expr {
+    Expr::Array(ArrayLit {
+      span: DUMMY_SP,
+      elems: vec![
+        Some(ExprOrSpread {
+          spread: None,
+          expr: Box::new(match &self.x {
+            PositionComponent::Center => generate_expr_lit_str!("50%"),
+            PositionComponent::Length(length_value) => generate_expr_by_length_percentage!(length_value, Platform::Harmony),
+            PositionComponent::Side { side, offset: _ } => {
+              match side {
+                HorizontalPositionKeyword::Left => generate_expr_lit_num!(0),
+                HorizontalPositionKeyword::Right => generate_expr_lit_str!("100%")
+              }
+            },
+          }),
+        }),
+        Some(ExprOrSpread {
+          spread: None,
+          expr: Box::new(match &self.y {
+            PositionComponent::Center => generate_expr_lit_str!("50%"),
+            PositionComponent::Length(length_value) => generate_expr_by_length_percentage!(length_value, Platform::Harmony),
+            PositionComponent::Side { side, offset: _ } => {
+              match side {
+                VerticalPositionKeyword::Top => generate_expr_lit_num!(0),
+                VerticalPositionKeyword::Bottom => generate_expr_lit_str!("100%")
+              }
+            },
+          }),
+        }),
+      ]
+    })
+  }
+}
+
 #[derive(Debug, Clone)]
 pub struct RadialGradientItem {
   pub color_stops: Vec<(Expr, Expr)>,
   pub point: RadialGradientPoint,
   pub shape: EndingShape,
+  pub repeating: bool,
 }
 
 impl RadialGradientItem {
@@ -55,37 +93,7 @@ impl RadialGradientItem {
     
     props.push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
       key: PropName::Ident(Ident::new("center".into(), DUMMY_SP)),
-      value: Box::new(Expr::Array(ArrayLit {
-        span: DUMMY_SP,
-        elems: vec![
-          Some(ExprOrSpread {
-            spread: None,
-            expr: Box::new(match &self.point.x {
-              PositionComponent::Center => generate_expr_lit_str!("50%"),
-              PositionComponent::Length(length_value) => generate_expr_by_length_percentage!(length_value, Platform::Harmony),
-              PositionComponent::Side { side, offset: _ } => {
-                match side {
-                  HorizontalPositionKeyword::Left => generate_expr_lit_num!(0),
-                  HorizontalPositionKeyword::Right => generate_expr_lit_str!("100%")
-                }
-              },
-            }),
-          }),
-          Some(ExprOrSpread {
-            spread: None,
-            expr: Box::new(match &self.point.y {
-              PositionComponent::Center => generate_expr_lit_str!("50%"),
-              PositionComponent::Length(length_value) => generate_expr_by_length_percentage!(length_value, Platform::Harmony),
-              PositionComponent::Side { side, offset: _ } => {
-                match side {
-                  VerticalPositionKeyword::Top => generate_expr_lit_num!(0),
-                  VerticalPositionKeyword::Bottom => generate_expr_lit_str!("100%")
-                }
-              },
-            }),
-          }),
-        ]
-      }))
+      value: Box::new(self.point.to_expr())
     }))));
 
     match &self.shape {
@@ -123,7 +131,7 @@ impl RadialGradientItem {
       key: PropName::Ident(Ident::new("repeating".into(), DUMMY_SP)),
       value: Box::new(Expr::Lit(Lit::Bool(Bool {
         span: DUMMY_SP,
-        value: false,
+        value: self.repeating,
       }))),
     }))));
 
@@ -0,0 +1,86 @@
+use swc_core::ecma::ast::*;
+use swc_core::common::DUMMY_SP;
+
+use crate::generate_expr_lit_num;
+
+use super::radial_gradient::RadialGradientPoint;
+
+// 角度渐变，对应 ArkUI 的 sweepGradient
+#[derive(Debug, Clone)]
+pub struct SweepGradientItem {
+  pub color_stops: Vec<(Expr, Expr)>,
+  pub point: RadialGradientPoint,
+  pub rotation: Option<f32>,
+  pub repeating: bool,
+}
+
+impl SweepGradientItem {
+  pub fn to_expr(&self) -> Expr {
+    let mut props = vec![];
+    props.push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+      key: PropName::Ident(Ident::new("colors".into(), DUMMY_SP)),
+      value: Expr::Array(ArrayLit {
+        span: DUMMY_SP,
+        elems: self
+          .color_stops
+          .iter()
+          .map(|item| {
+            Some(ExprOrSpread {
+              spread: None,
+              expr: Expr::Array(ArrayLit {
+                span: DUMMY_SP,
+                elems: vec![
+                  Some(ExprOrSpread {
+                    spread: None,
+                    expr: Box::new(item.0.clone())
+                  }),
+                  Some(ExprOrSpread {
+                    spread: None,
+                    expr: Box::new(item.1.clone())
+                  }),
+                ],
+              })
+              .into(),
+            })
+          })
+          .collect::<Vec<_>>(),
+      })
+      .into(),
+    }))));
+
+    props.push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+      key: PropName::Ident(Ident::new("center".into(), DUMMY_SP)),
+      value: Box::new(self.point.to_expr())
+    }))));
+
+    // conic-gradient 的颜色断点覆盖一整圈
+    props.push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+      key: PropName::Ident(Ident::new("start".into(), DUMMY_SP)),
+      value: Box::new(generate_expr_lit_num!(0.0)),
+    }))));
+    props.push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+      key: PropName::Ident(Ident::new("end".into(), DUMMY_SP)),
+      value: Box::new(generate_expr_lit_num!(360.0)),
+    }))));
+
+    if let Some(rotation) = self.rotation {
+      props.push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+        key: PropName::Ident(Ident::new("rotation".into(), DUMMY_SP)),
+        value: Box::new(generate_expr_lit_num!(rotation as f64)),
+      }))));
+    }
+
+    props.push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+      key: PropName::Ident(Ident::new("repeating".into(), DUMMY_SP)),
+      value: Box::new(Expr::Lit(Lit::Bool(Bool {
+        span: DUMMY_SP,
+        value: self.repeating,
+      }))),
+    }))));
+
+    Expr::Object(ObjectLit {
+      span: DUMMY_SP,
+      props,
+    })
+  }
+}
@@ -3,9 +3,9 @@ use lightningcss::{
   values::{self, length::LengthValue}, stylesheet::PrinterOptions, traits::ToCss
 };
 
-use crate::generate_invalid_expr;
+use crate::{generate_expr_lit_calc, generate_invalid_expr};
 
-use super::{unit::{PropertyTuple, generate_expr_by_length_value, Platform}, traits::ToExpr};
+use super::{font_size::{generate_font_size_multiple_expr, resolve_font_relative_length, FontRelativeLength}, unit::{PropertyTuple, generate_expr_by_length_value, Platform}, traits::ToExpr};
 
 
 #[derive(Debug, Clone)]
@@ -19,6 +19,8 @@ pub struct LetterSpacing {
 pub enum EnumValue {
   LengthValue(LengthValue),
   String(String),
+  // em 所相对的字号为 calc() 时，在运行时计算 字号 * 倍数：(字号的 calc 字符串, 倍数)
+  FontSizeMultiple(String, f32),
   Invalid
 }
 
@@ -27,8 +29,9 @@ impl ToExpr for LetterSpacing {
     PropertyTuple::One(
       self.id.to_string(),
       match &self.value {
-        EnumValue::String(_) => generate_invalid_expr!(),
+        EnumValue::String(value) => generate_expr_lit_calc!(value, Platform::Harmony),
         EnumValue::LengthValue(length_value) => generate_expr_by_length_value(&length_value, Platform::Harmony),
+        EnumValue::FontSizeMultiple(font_size, multiple) => generate_font_size_multiple_expr(font_size, *multiple, Platform::Harmony),
         EnumValue::Invalid => generate_invalid_expr!(),
       }
     )
@@ -38,28 +41,36 @@ impl ToExpr for LetterSpacing {
     PropertyTuple::One(
       self.id.to_string(),
       match &self.value {
-        EnumValue::String(_) => generate_invalid_expr!(),
+        EnumValue::String(value) => generate_expr_lit_calc!(value, Platform::ReactNative),
         EnumValue::LengthValue(length_value) => generate_expr_by_length_value(&length_value, Platform::ReactNative),
+        EnumValue::FontSizeMultiple(font_size, multiple) => generate_font_size_multiple_expr(font_size, *multiple, Platform::ReactNative),
         EnumValue::Invalid => generate_invalid_expr!(),
       }
     )
   }
 }
 
-impl From<(String, &Property<'_>)> for LetterSpacing {
-  fn from(prop: (String, &Property<'_>)) -> Self {
+// letter-spacing、word-spacing 共用，负值原样输出
+// em 相对于节点自身的字号，normal 等同于 0，用于覆盖继承或其他类名设置的间距
+// (属性名, 属性值, 同一条规则中声明的字号)
+impl From<(String, &Property<'_>, Option<&Property<'_>>)> for LetterSpacing {
+  fn from(prop: (String, &Property<'_>, Option<&Property<'_>>)) -> Self {
     LetterSpacing {
-      id: prop.0,
+      id: prop.0.clone(),
       value: match prop.1 {
-        Property::LetterSpacing(value) => {
+        Property::LetterSpacing(value) | Property::WordSpacing(value) => {
           match value {
             Spacing::Length(val) => {
               match val {
+                values::length::Length::Value(LengthValue::Em(num)) => match resolve_font_relative_length(&prop.0, prop.1, *num, prop.2) {
+                  FontRelativeLength::LengthValue(length_value) => EnumValue::LengthValue(length_value),
+                  FontRelativeLength::FontSizeMultiple(font_size, multiple) => EnumValue::FontSizeMultiple(font_size, multiple),
+                },
                 values::length::Length::Value(value) =>  EnumValue::LengthValue(value.to_owned()),
                 values::length::Length::Calc(calc) => EnumValue::String(calc.to_css_string(PrinterOptions::default()).unwrap())
               }
             }
-            Spacing::Normal => EnumValue::Invalid,
+            Spacing::Normal => EnumValue::LengthValue(LengthValue::Px(0.0)),
           }
         }
         _ => EnumValue::Invalid
@@ -0,0 +1,44 @@
+use lightningcss::{properties::Property, stylesheet::PrinterOptions};
+
+use crate::{generate_expr_lit_num, generate_invalid_expr};
+
+use super::{traits::ToExpr, unit::PropertyTuple};
+
+
+// -webkit-line-clamp：多行文本截断的行数
+#[derive(Debug, Clone)]
+pub struct LineClamp {
+  pub value: Option<u32>
+}
+
+impl From<(String, &Property<'_>)> for LineClamp {
+  fn from(value: (String, &Property<'_>)) -> Self {
+    // lightningcss 不识别 -webkit-line-clamp，按字符串解析，none 或非法值视为不限制
+    let value_str = value.1.value_to_css_string(PrinterOptions::default()).unwrap_or_default();
+    LineClamp {
+      value: value_str.trim().parse::<u32>().ok().filter(|lines| *lines > 0)
+    }
+  }
+}
+
+impl ToExpr for LineClamp {
+  fn to_expr(&self) -> PropertyTuple {
+    PropertyTuple::One(
+      "maxLines".to_string(),
+      match self.value {
+        Some(lines) => generate_expr_lit_num!(lines as f64),
+        None => generate_invalid_expr!()
+      }
+    )
+  }
+
+  fn to_rn_expr(&self) -> PropertyTuple {
+    PropertyTuple::One(
+      "numberOfLines".to_string(),
+      match self.value {
+        Some(lines) => generate_expr_lit_num!(lines as f64),
+        None => generate_invalid_expr!()
+      }
+    )
+  }
+}
@@ -1,12 +1,12 @@
 use lightningcss::{
   properties::{Property, font},
-  values::{length::LengthValue, percentage::Percentage},
+  values::{length::LengthValue, percentage::{DimensionPercentage, Percentage}},
   traits::ToCss,
 };
 
 use crate::{style_propetries::traits::ToExpr, generate_dimension_percentage, generate_invalid_expr};
 
-use super::unit::{generate_expr_by_length_value, Platform, PropertyTuple};
+use super::{font_size::{generate_font_size_multiple_expr, resolve_font_relative_length, FontRelativeLength}, unit::{generate_expr_by_length_value, Platform, PropertyTuple}};
 
 
 #[derive(Debug, Clone)]
@@ -21,6 +21,8 @@ pub enum EnumValue {
   LengthValue(LengthValue),
   Percentage(Percentage),
   String(String),
+  // 字号为 calc() 时，在运行时计算 字号 * 倍数：(字号的 calc 字符串, 倍数)
+  FontSizeMultiple(String, f32),
   Invalid
 }
 
@@ -32,6 +34,7 @@ impl ToExpr for LineHeight {
         EnumValue::String(_) => generate_invalid_expr!(),
         EnumValue::LengthValue(length_value) => generate_expr_by_length_value(&length_value, Platform::Harmony),
         EnumValue::Percentage(_) => generate_invalid_expr!(),
+        EnumValue::FontSizeMultiple(font_size, multiple) => generate_font_size_multiple_expr(font_size, *multiple, Platform::Harmony),
         EnumValue::Invalid => generate_invalid_expr!(),
       }
     )
@@ -44,25 +47,42 @@ impl ToExpr for LineHeight {
         EnumValue::String(_) => generate_invalid_expr!(),
         EnumValue::LengthValue(length_value) => generate_expr_by_length_value(&length_value, Platform::ReactNative),
         EnumValue::Percentage(_) => generate_invalid_expr!(),
+        EnumValue::FontSizeMultiple(font_size, multiple) => generate_font_size_multiple_expr(font_size, *multiple, Platform::ReactNative),
         EnumValue::Invalid => generate_invalid_expr!(),
       }
     )
   }
 }
 
-impl From<(String, &Property<'_>)> for LineHeight {
-  fn from(prop: (String, &Property<'_>)) -> Self {
+// RN和鸿蒙的行高都只支持长度：https://github.com/NervJS/taro/issues/11620
+// 无单位的倍数、百分比按 CSS 的语义相对于字号计算
+fn resolve_line_height_multiple(id: &str, value: &Property<'_>, multiple: f32, font_size: Option<&Property<'_>>) -> EnumValue {
+  match resolve_font_relative_length(id, value, multiple, font_size) {
+    FontRelativeLength::LengthValue(length_value) => EnumValue::LengthValue(length_value),
+    FontRelativeLength::FontSizeMultiple(font_size, multiple) => EnumValue::FontSizeMultiple(font_size, multiple),
+  }
+}
+
+// (属性名, 属性值, 同一条规则中声明的字号)
+impl From<(String, &Property<'_>, Option<&Property<'_>>)> for LineHeight {
+  fn from(prop: (String, &Property<'_>, Option<&Property<'_>>)) -> Self {
     LineHeight {
-      id: prop.0,
+      id: prop.0.clone(),
       value: match prop.1 {
         Property::LineHeight(value) => {
           match value {
+            font::LineHeight::Length(DimensionPercentage::Percentage(percentage)) => {
+              resolve_line_height_multiple(&prop.0, prop.1, percentage.0, prop.2)
+            },
+            // 行高的 em 相对于节点自身的字号，与无单位的倍数相同
+            font::LineHeight::Length(DimensionPercentage::Dimension(LengthValue::Em(num))) => {
+              resolve_line_height_multiple(&prop.0, prop.1, *num, prop.2)
+            },
             font::LineHeight::Length(val) => {
               generate_dimension_percentage!(EnumValue, val)
             },
-            // RN和鸿蒙都不支持数值类型：https://github.com/NervJS/taro/issues/11620
-            font::LineHeight::Number(_) => {
-              EnumValue::Invalid
+            font::LineHeight::Number(num) => {
+              resolve_line_height_multiple(&prop.0, prop.1, *num, prop.2)
             },
             font::LineHeight::Normal => {
               EnumValue::Invalid
@@ -36,47 +36,21 @@ macro_rules! generate_expr_lit_bool {
 #[macro_export]
 macro_rules! generate_expr_lit_calc {
   ($var:expr, $platform:expr) => {{
+    use $crate::style_propetries::unit::generate_expr_by_calc_string;
+    generate_expr_by_calc_string($var.as_str(), $platform)
+  }};
+}
 
+// 生成枚举成员表达式，如 generate_expr_enum!("Visibility", "Hidden") => Visibility.Hidden
+#[macro_export]
+macro_rules! generate_expr_enum {
+  ($enum_name:expr, $member:expr) => {{
     use swc_core::ecma::ast::*;
-    use swc_core::{
-      common::DUMMY_SP,
-      atoms::Atom
-    };
-
-    use $crate::constants::{CONVERT_STYLE_PX_FN, RN_CONVERT_STYLE_PX_FN, RN_CONVERT_STYLE_VU_FN};
-
-    let re = regex::Regex::new(r#"(\d+(?:px|vw|vh))"#).unwrap();
-    let result = re.replace_all($var.as_str(), |caps: &regex::Captures| {
-        let value = &caps[1];
-        let unit = &value[value.len() - 2..];
-        let parsed_value: i32 = value[..value.len() - 2].parse().unwrap();
-        if $platform == Platform::Harmony {
-          if unit == "px" {
-            // return format!("{}lpx", parsed_value);
-            return format!("${{{}({}, 'px')}}", CONVERT_STYLE_PX_FN, parsed_value);
-          } else {
-            return format!("${{{}({}, '{}')}}", CONVERT_STYLE_PX_FN, parsed_value, unit);
-          }
-        } else {
-          if unit == "px" {
-            return format!("${{{}({}, 'px')}}", RN_CONVERT_STYLE_PX_FN, parsed_value);
-          } else {
-            return format!("${{{}({}, '{}')}}", RN_CONVERT_STYLE_VU_FN, parsed_value, unit);
-          }
-        }
-    });
-    
-    Expr::Tpl(Tpl {
+    use swc_core::common::DUMMY_SP;
+    Expr::Member(MemberExpr {
       span: DUMMY_SP,
-      exprs: vec![],
-      quasis: vec![
-        TplElement {
-          span: DUMMY_SP,
-          tail: false,
-          cooked: None,
-          raw: Atom::from(result).into(),
-        }
-      ],
+      obj: Box::new(Expr::Ident(Ident::new($enum_name.into(), DUMMY_SP))),
+      prop: MemberProp::Ident(Ident::new($member.into(), DUMMY_SP)),
     })
   }};
 }
@@ -93,15 +67,8 @@ macro_rules! generate_expr_ident {
 #[macro_export]
 macro_rules! generate_string_by_css_color {
   ($color:expr) => {{
-    use $crate::style_propetries::unit::convert_color_keywords_to_hex;
-    convert_color_keywords_to_hex($color.to_css_string(lightningcss::stylesheet::PrinterOptions {
-      minify: false,
-      targets: lightningcss::targets::Targets {
-        include: lightningcss::targets::Features::HexAlphaColors,
-        ..lightningcss::targets::Targets::default()
-      },
-      ..lightningcss::stylesheet::PrinterOptions::default()
-    }).unwrap()).into()
+    use $crate::style_propetries::unit::convert_css_color;
+    convert_css_color(&$color).into()
   }};
 }
 
@@ -139,16 +106,12 @@ macro_rules! generate_expr_by_length_percentage_or_auto {
 #[macro_export]
 macro_rules! generate_expr_by_length_percentage {
   ($var:expr, $platform:expr) => {{
-    use $crate::{generate_expr_lit_str, generate_expr_lit_calc, style_propetries::unit::{generate_expr_by_length_value} };
-    use lightningcss::traits::ToCss;
-    
+    use $crate::{generate_expr_lit_str, style_propetries::unit::{generate_expr_by_length_value, generate_expr_by_calc} };
+
     match $var {
       lightningcss::values::percentage::DimensionPercentage::Dimension(dimension) => generate_expr_by_length_value(&dimension, $platform),
       lightningcss::values::percentage::DimensionPercentage::Percentage(percentage) => generate_expr_lit_str!((percentage.0 * 100.0).to_string() + "%"),
-      lightningcss::values::percentage::DimensionPercentage::Calc(calc) => {
-        let calc_string = calc.to_css_string(lightningcss::stylesheet::PrinterOptions::default()).unwrap();
-        generate_expr_lit_calc!(calc_string, $platform)
-      },
+      lightningcss::values::percentage::DimensionPercentage::Calc(calc) => generate_expr_by_calc(&calc, $platform),
     }
   }};
 }
@@ -191,27 +154,32 @@ macro_rules! generate_expr_based_on_platform {
 #[macro_export]
 macro_rules! generate_color_property {
   ($class:ident, $( $property_name:ident ), *) => {
-    use $crate::utils::fix_rgba;
-
     use swc_core::ecma::ast::*;
 
     #[derive(Debug, Clone)]
     pub struct $class {
       pub id: String,
-      pub value: String
+      // 无法解析的颜色为 None，转换为 Invalid
+      pub value: Option<String>
     }
 
     impl ToExpr for $class {
       fn to_expr(&self) -> PropertyTuple {
         PropertyTuple::One(
           self.id.clone(),
-          Expr::Lit(Lit::Str(fix_rgba(self.value.clone()).into())).into()
+          match &self.value {
+            Some(value) => Expr::Lit(Lit::Str(value.clone().into())),
+            None => $crate::generate_invalid_expr!()
+          }
         )
       }
       fn to_rn_expr(&self) -> PropertyTuple {
         PropertyTuple::One(
           self.id.clone(),
-          Expr::Lit(Lit::Str(self.value.clone().into())).into()
+          match &self.value {
+            Some(value) => Expr::Lit(Lit::Str(value.clone().into())),
+            None => $crate::generate_invalid_expr!()
+          }
         )
       }
     }
@@ -222,19 +190,12 @@ macro_rules! generate_color_property {
           id: prop.0,
           value: match prop.1 {
             $(
-              lightningcss::properties::Property::$property_name(_) => {
-                use $crate::style_propetries::unit::convert_color_keywords_to_hex;
-                convert_color_keywords_to_hex(prop.1.value_to_css_string(lightningcss::stylesheet::PrinterOptions {
-                  minify: false,
-                  targets: lightningcss::targets::Targets {
-                    include: lightningcss::targets::Features::HexAlphaColors,
-                    ..lightningcss::targets::Targets::default()
-                  },
-                  ..lightningcss::stylesheet::PrinterOptions::default()
-                }).unwrap())
+              lightningcss::properties::Property::$property_name(color) => {
+                use $crate::style_propetries::unit::convert_css_color;
+                Some(convert_css_color(color))
               }
             )*
-            _ => "".to_string()
+            _ => None
           }
         }
       }
@@ -332,7 +293,9 @@ macro_rules! generate_length_value_property {
       LengthValue(lightningcss::values::length::LengthValue),
       Percentage(lightningcss::values::percentage::Percentage),
       String(String),
-      Auto
+      Auto,
+      // 无法解析的属性值，转换为 Invalid
+      Invalid
     }
 
     impl ToExpr for $class {
@@ -343,7 +306,8 @@ macro_rules! generate_length_value_property {
             EnumValue::String(value) => generate_expr_lit_calc!(value, Platform::Harmony),
             EnumValue::LengthValue(length_value) => generate_expr_by_length_value(length_value, Platform::Harmony),
             EnumValue::Percentage(value) => generate_expr_lit_str!((value.0 * 100.0).to_string() + "%"),
-            EnumValue::Auto => generate_invalid_expr!()   // harmony 是个非法制，固不会生效
+            EnumValue::Auto => generate_invalid_expr!(),   // harmony 是个非法制，固不会生效
+            EnumValue::Invalid => generate_invalid_expr!()
           }
         )
       }
@@ -355,7 +319,8 @@ macro_rules! generate_length_value_property {
             EnumValue::String(value) => generate_expr_lit_calc!(value, Platform::ReactNative),
             EnumValue::LengthValue(length_value) => generate_expr_by_length_value(length_value, Platform::ReactNative),
             EnumValue::Percentage(value) => generate_expr_lit_str!((value.0 * 100.0).to_string() + "%"),
-            EnumValue::Auto => generate_expr_lit_str!("auto")
+            EnumValue::Auto => generate_expr_lit_str!("auto"),
+            EnumValue::Invalid => generate_invalid_expr!()
           }
         )
       }
@@ -379,7 +344,7 @@ macro_rules! generate_length_value_property {
           )*
           _ => $class {
             id: prop.0,
-            value: EnumValue::String("auto".to_string())
+            value: EnumValue::Invalid
           }
         }
       }
@@ -406,7 +371,13 @@ macro_rules! generate_size_property {
       LengthValue(lightningcss::values::length::LengthValue),
       Percentage(lightningcss::values::percentage::Percentage),
       String(String),
-      Auto
+      Auto,
+      // max-width、max-height 的 none
+      None,
+      // min-content、max-content、fit-content 等内容尺寸关键字，两端均无对应的值
+      Keyword(String),
+      // 无法解析的属性值，转换为 Invalid
+      Invalid
     }
 
     impl ToExpr for $class {
@@ -417,7 +388,13 @@ macro_rules! generate_size_property {
             EnumValue::String(value) => generate_expr_lit_calc!(value, Platform::Harmony),
             EnumValue::LengthValue(length_value) => generate_expr_by_length_value(length_value, Platform::Harmony),
             EnumValue::Percentage(value) => generate_expr_lit_str!((value.0 * 100.0).to_string() + "%"),
-            EnumValue::Auto => generate_expr_lit_str!("auto")   // harmony 是个非法制，固不会生效
+            EnumValue::Auto => generate_expr_lit_str!("auto"),   // harmony 是个非法制，固不会生效
+            EnumValue::None => $crate::generate_invalid_expr!(),
+            EnumValue::Keyword(keyword) => {
+              $crate::diagnostics::push_diagnostic(&self.id, format!("{} is not supported on Harmony and will be ignored", keyword));
+              $crate::generate_invalid_expr!()
+            },
+            EnumValue::Invalid => $crate::generate_invalid_expr!()
           }
         )
       }
@@ -429,7 +406,13 @@ macro_rules! generate_size_property {
             EnumValue::String(value) => generate_expr_lit_calc!(value, Platform::ReactNative),
             EnumValue::LengthValue(length_value) => generate_expr_by_length_value(length_value, Platform::ReactNative),
             EnumValue::Percentage(value) => generate_expr_lit_str!((value.0 * 100.0).to_string() + "%"),
-            EnumValue::Auto => generate_expr_lit_str!("auto")
+            EnumValue::Auto => generate_expr_lit_str!("auto"),
+            EnumValue::None => $crate::generate_invalid_expr!(),
+            EnumValue::Keyword(keyword) => {
+              $crate::diagnostics::push_diagnostic(&self.id, format!("{} is not supported on ReactNative and will be ignored", keyword));
+              $crate::generate_invalid_expr!()
+            },
+            EnumValue::Invalid => $crate::generate_invalid_expr!()
           }
         )
       }
@@ -450,14 +433,21 @@ macro_rules! generate_size_property {
                         lightningcss::values::percentage::DimensionPercentage::Calc(calc) => EnumValue::String(calc.to_css_string(lightningcss::stylesheet::PrinterOptions::default()).unwrap())
                       }
                   },
-                  _ => EnumValue::Auto
+                  _ => {
+                    let keyword = value.to_css_string(lightningcss::stylesheet::PrinterOptions::default()).unwrap();
+                    match keyword.as_str() {
+                      "auto" => EnumValue::Auto,
+                      "none" => EnumValue::None,
+                      _ => EnumValue::Keyword(keyword)
+                    }
+                  }
                 }
               }
             }
           )*
           _ =>  $class {
             id: prop.0,
-            value: EnumValue::String("auto".to_string())
+            value: EnumValue::Invalid
           }
         }
       }
@@ -0,0 +1,64 @@
+use lightningcss::properties::Property;
+
+use crate::{diagnostics::push_diagnostic, generate_invalid_expr};
+
+use super::{background_image::{parse_background_image, BackgroundImage, BackgroundImageKind}, background_position::{parse_background_position, BackgroundPosition, ImagePosition}, background_size::{parse_background_size, BackgroundSize, ImageSize}, traits::ToExpr, unit::PropertyTuple};
+
+#[derive(Debug, Clone)]
+pub enum MaskValue {
+  Image(Vec<BackgroundImageKind>),
+  Size(Vec<ImageSize>),
+  Position(Vec<ImagePosition>),
+  // 鸿蒙不支持的子属性，如 mask-repeat、mask-mode
+  Unsupported,
+}
+
+// mask-image、mask-size、mask-position 转换为鸿蒙的遮罩属性，取值与对应的 background-* 相同
+#[derive(Debug, Clone)]
+pub struct Mask {
+  pub id: String,
+  pub value: MaskValue
+}
+
+impl From<(String, &Property<'_>)> for Mask {
+  fn from(value: (String, &Property<'_>)) -> Self {
+    let mask_value = match value.1 {
+      Property::MaskImage(images, _) => MaskValue::Image(parse_background_image(images)),
+      Property::MaskSize(sizes, _) => MaskValue::Size(parse_background_size(sizes)),
+      Property::MaskPosition(positions, _) => MaskValue::Position(parse_background_position(&positions.iter().map(|position| position.clone().into()).collect())),
+      _ => MaskValue::Unsupported
+    };
+    Mask {
+      id: value.0,
+      value: mask_value
+    }
+  }
+}
+
+impl ToExpr for Mask {
+  fn to_expr(&self) -> PropertyTuple {
+    // 按 background-* 转换后替换为遮罩的属性名，-webkit-mask-image 与 mask-image 输出相同的属性
+    let (key, background) = match &self.value {
+      MaskValue::Image(images) if !images.is_empty() => ("maskImage", BackgroundImage { id: self.id.to_string(), value: images.clone() }.to_expr()),
+      MaskValue::Size(sizes) if !sizes.is_empty() => ("maskSize", BackgroundSize { id: self.id.to_string(), value: sizes.clone() }.to_expr()),
+      MaskValue::Position(positions) if !positions.is_empty() => ("maskPosition", BackgroundPosition { id: self.id.to_string(), value: positions.clone() }.to_expr()),
+      MaskValue::Unsupported => {
+        push_diagnostic(&self.id, format!("{} is not supported on Harmony, only mask-image, mask-size and mask-position are converted", self.id));
+        return PropertyTuple::One(self.id.to_string(), generate_invalid_expr!())
+      },
+      _ => return PropertyTuple::One(self.id.to_string(), generate_invalid_expr!())
+    };
+    match background {
+      PropertyTuple::One(_, expr) => PropertyTuple::One(key.to_string(), expr),
+      PropertyTuple::Array(props) => PropertyTuple::Array(props),
+    }
+  }
+
+  fn to_rn_expr(&self) -> PropertyTuple {
+    push_diagnostic(&self.id, format!("{} is not supported on ReactNative", self.id));
+    PropertyTuple::One(
+      self.id.to_string(),
+      generate_invalid_expr!()
+    )
+  }
+}
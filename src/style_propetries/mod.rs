@@ -11,6 +11,9 @@ pub mod number;
 pub mod color;
 pub mod marin_padding;
 pub mod display;
+pub mod opacity;
+pub mod visibility;
+pub mod pointer_events;
 pub mod aspect_ratio;
 pub mod flex;
 pub mod flex_align;
@@ -21,14 +24,19 @@ pub mod item_align;
 pub mod gap;
 pub mod overflow;
 pub mod font_size;
+pub mod font_family;
 pub mod font_style;
 pub mod font_weight;
 pub mod line_height;
 pub mod text_align;
 pub mod text_decoration;
 pub mod text_shadow;
+pub mod text_stroke;
 pub mod text_transform;
 pub mod text_overflow;
+pub mod white_space;
+pub mod line_clamp;
+pub mod word_break;
 pub mod letter_spacing;
 pub mod vertical_align;
 pub mod border_color;
@@ -36,9 +44,11 @@ pub mod border_width;
 pub mod border_radius;
 pub mod border_style;
 pub mod border;
+pub mod outline;
 pub mod transform;
 pub mod transform_properties;
 pub mod transform_origin;
+pub mod transition;
 pub mod background_repeat;
 pub mod background_position;
 pub mod background_size;
@@ -46,4 +56,10 @@ pub mod background_image;
 pub mod background;
 pub mod graident_properties;
 pub mod animation;
-pub mod box_shadow;
\ No newline at end of file
+pub mod box_shadow;
+pub mod filter;
+pub mod mask;
+pub mod scroll;
+pub mod direction;
+pub mod content;
+pub mod passthrough;
\ No newline at end of file
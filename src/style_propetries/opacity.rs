@@ -0,0 +1,41 @@
+use lightningcss::properties::Property;
+
+use crate::{generate_expr_lit_num, generate_invalid_expr};
+
+use super::{traits::ToExpr, unit::{convert_f32_to_f64, PropertyTuple}};
+
+
+#[derive(Debug, Clone)]
+pub struct Opacity {
+  pub id: String,
+  pub value: Option<f32>
+}
+
+impl From<(String, &Property<'_>)> for Opacity {
+  fn from(value: (String, &Property<'_>)) -> Self {
+    Opacity {
+      id: value.0,
+      value: match value.1 {
+        // 百分比已被解析为 0~1 的小数，超出范围的值按规范截断
+        Property::Opacity(alpha) => Some(alpha.0.clamp(0.0, 1.0)),
+        _ => None
+      }
+    }
+  }
+}
+
+impl ToExpr for Opacity {
+  fn to_expr(&self) -> PropertyTuple {
+    PropertyTuple::One(
+      self.id.to_string(),
+      match self.value {
+        Some(value) => generate_expr_lit_num!(convert_f32_to_f64(value)),
+        None => generate_invalid_expr!()
+      }
+    )
+  }
+
+  fn to_rn_expr(&self) -> PropertyTuple {
+    self.to_expr()
+  }
+}
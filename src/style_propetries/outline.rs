@@ -0,0 +1,99 @@
+use lightningcss::{
+  properties::{Property, border::{BorderSideWidth, LineStyle}, outline::OutlineStyle},
+  values::color::CssColor
+};
+
+use swc_core::common::DUMMY_SP;
+use swc_core::ecma::ast::*;
+use crate::{diagnostics::push_diagnostic, generate_expr_by_border_side_width, generate_expr_by_line_style, generate_invalid_expr, generate_string_by_css_color, style_propetries::unit::Platform};
+
+use super::{traits::ToExpr, unit::PropertyTuple, border_style::get_expr_by_val};
+
+
+#[derive(Debug, Clone)]
+pub struct Outline {
+  pub id: String,
+  pub width: Option<BorderSideWidth>,
+  pub style: Option<OutlineStyle>,
+  pub color: Option<CssColor>,
+}
+
+impl From<(String, &Property<'_>)> for Outline {
+  fn from(prop: (String, &Property<'_>)) -> Self {
+    let mut outline = Outline {
+      id: prop.0,
+      width: None,
+      style: None,
+      color: None
+    };
+    match prop.1 {
+      Property::Outline(value) => {
+        outline.width = Some(value.width.clone());
+        outline.style = Some(value.style.clone());
+        outline.color = Some(value.color.clone());
+      }
+      Property::OutlineWidth(value) => {
+        outline.width = Some(value.clone());
+      }
+      Property::OutlineStyle(value) => {
+        outline.style = Some(value.clone());
+      }
+      Property::OutlineColor(value) => {
+        outline.color = Some(value.clone());
+      }
+      _ => {}
+    }
+    outline
+  }
+}
+
+// 鸿蒙 OutlineStyle 枚举：SOLID、DASHED、DOTTED
+fn get_outline_style_expr(style: &OutlineStyle) -> Expr {
+  let val = match style {
+    OutlineStyle::LineStyle(LineStyle::Solid) => "SOLID",
+    OutlineStyle::LineStyle(LineStyle::Dashed) => "DASHED",
+    OutlineStyle::LineStyle(LineStyle::Dotted) => "DOTTED",
+    _ => return generate_invalid_expr!()
+  };
+  Expr::Member(MemberExpr {
+    span: DUMMY_SP,
+    obj: Box::new(Expr::Ident(Ident::new("OutlineStyle".into(), DUMMY_SP))),
+    prop: MemberProp::Ident(Ident {
+      span: DUMMY_SP,
+      sym: val.into(),
+      optional: false,
+    }),
+  })
+}
+
+impl ToExpr for Outline {
+  fn to_expr(&self) -> PropertyTuple {
+    let mut props: Vec<(String, Expr)> = vec![];
+    if let Some(width) = &self.width {
+      props.push(("outlineWidth".to_string(), generate_expr_by_border_side_width!(width, Platform::Harmony)));
+    }
+    if let Some(style) = &self.style {
+      props.push(("outlineStyle".to_string(), get_outline_style_expr(style)));
+    }
+    if let Some(color) = &self.color {
+      props.push(("outlineColor".to_string(), generate_string_by_css_color!(color)));
+    }
+    PropertyTuple::Array(props)
+  }
+
+  fn to_rn_expr(&self) -> PropertyTuple {
+    // RN 不支持 outline，降级为 border
+    push_diagnostic(&self.id, "outline is not supported on ReactNative, fallback to border".to_string());
+    let mut props: Vec<(String, Expr)> = vec![];
+    if let Some(width) = &self.width {
+      props.push(("borderWidth".to_string(), generate_expr_by_border_side_width!(width, Platform::ReactNative)));
+    }
+    if let Some(OutlineStyle::LineStyle(style)) = &self.style {
+      props.push(("borderStyle".to_string(), generate_expr_by_line_style!(style, Platform::ReactNative)));
+    }
+    if let Some(color) = &self.color {
+      props.push(("borderColor".to_string(), generate_string_by_css_color!(color)));
+    }
+    PropertyTuple::Array(props)
+  }
+}
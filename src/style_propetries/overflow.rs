@@ -1,8 +1,9 @@
 use lightningcss::properties::{
   Property, overflow::OverflowKeyword
 };
+use swc_core::ecma::ast::Expr;
 
-use crate::{generate_expr_lit_str, generate_invalid_expr};
+use crate::{constants::SCROLLABLE, diagnostics::push_diagnostic, generate_expr_lit_str, generate_invalid_expr};
 
 use super::{traits::ToExpr, unit::PropertyTuple};
 
@@ -10,7 +11,10 @@ use super::{traits::ToExpr, unit::PropertyTuple};
 #[derive(Debug, Clone)]
 pub struct Overflow {
   pub id: String,
-  pub value: EnumValue
+  // 水平方向，overflow-x、overflow-y 时与 y 相同
+  pub x: EnumValue,
+  // 垂直方向
+  pub y: EnumValue,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -21,49 +25,120 @@ pub enum EnumValue {
   Invalid,
 }
 
+fn parse_overflow_keyword(keyword: &OverflowKeyword) -> EnumValue {
+  match keyword {
+    OverflowKeyword::Hidden => EnumValue::Hidden,
+    OverflowKeyword::Visible => EnumValue::Visible,
+    OverflowKeyword::Clip => EnumValue::Invalid,
+    OverflowKeyword::Scroll => EnumValue::Scroll,
+    OverflowKeyword::Auto => EnumValue::Scroll
+  }
+}
+
+fn generate_overflow_expr(value: &EnumValue) -> Expr {
+  match value {
+    EnumValue::Hidden => generate_expr_lit_str!("hidden"),
+    EnumValue::Visible => generate_expr_lit_str!("visible"),
+    EnumValue::Scroll => generate_expr_lit_str!("scroll"),
+    EnumValue::Invalid => generate_invalid_expr!(),
+  }
+}
+
 impl From<(String, &Property<'_>)> for Overflow {
   fn from(value: (String, &Property<'_>)) -> Self {
+    let (x, y) = match value.1 {
+      Property::Overflow(value) => (parse_overflow_keyword(&value.x), parse_overflow_keyword(&value.y)),
+      Property::OverflowX(keyword) | Property::OverflowY(keyword) => (parse_overflow_keyword(keyword), parse_overflow_keyword(keyword)),
+      _ => (EnumValue::Invalid, EnumValue::Invalid)
+    };
     Overflow {
       id: value.0,
-      value: match value.1 {
-        Property::Overflow(value) => {
-          match value.x {
-            OverflowKeyword::Hidden => EnumValue::Hidden,
-            OverflowKeyword::Visible => EnumValue::Visible,
-            OverflowKeyword::Clip => EnumValue::Invalid,
-            OverflowKeyword::Scroll => EnumValue::Scroll,
-            OverflowKeyword::Auto => EnumValue::Scroll
-          }
-        },
-        _ => EnumValue::Invalid
-      }
+      x,
+      y
     }
   }
 }
 
 impl ToExpr for Overflow {
   fn to_expr(&self) -> PropertyTuple {
+    // 两个方向不同时按方向输出，Scroll 组件据此确定可滚动的方向，如 overflow: hidden scroll => overflowX: hidden, overflowY: scroll
+    if self.id == "overflow" && self.x != self.y {
+      return PropertyTuple::Array(vec![
+        ("overflowX".to_string(), generate_overflow_expr(&self.x)),
+        ("overflowY".to_string(), generate_overflow_expr(&self.y)),
+      ])
+    }
     PropertyTuple::One(
       self.id.to_string(),
-      match &self.value {
-        EnumValue::Hidden => generate_expr_lit_str!("hidden"),
-        EnumValue::Visible => generate_expr_lit_str!("visible"),
-        EnumValue::Scroll => generate_expr_lit_str!("scroll"),
-        EnumValue::Invalid => generate_invalid_expr!(),
-      }
+      generate_overflow_expr(&self.y)
     )
   }
 
   fn to_rn_expr(&self) -> PropertyTuple {
+    if self.id != "overflow" {
+      push_diagnostic(&self.id, format!("{} is not supported on ReactNative, use overflow instead", self.id));
+      return PropertyTuple::One(self.id.to_string(), generate_invalid_expr!())
+    }
+    // RN 不区分方向，任一方向不可见时裁剪
+    let value = if self.x == self.y {
+      self.y.clone()
+    } else {
+      push_diagnostic(&self.id, "different overflow values for x and y are not supported on ReactNative, the content is clipped in both directions".to_string());
+      EnumValue::Hidden
+    };
     PropertyTuple::One(
       self.id.to_string(),
-      match &self.value {
-        EnumValue::Hidden => generate_expr_lit_str!("hidden"),
-        EnumValue::Visible => generate_expr_lit_str!("visible"),
-        EnumValue::Scroll => generate_expr_lit_str!("scroll"),
-        EnumValue::Invalid => generate_invalid_expr!(),
+      generate_overflow_expr(&value)
+    )
+  }
+
+}
+
+// overflow: scroll、auto 对应的可滚动方向，鸿蒙下普通容器设置 overflow 不会产生滚动，
+// 额外输出 __scrollable__: "vertical" | "horizontal" | "both"，供运行时将 View 替换为 Scroll 组件
+#[derive(Debug, Clone)]
+pub struct Scrollable {
+  pub id: String,
+  pub horizontal: bool,
+  pub vertical: bool,
+}
+
+impl Scrollable {
+  // 按声明顺序合并同一条规则中的 overflow、overflow-x、overflow-y，没有可滚动的方向时返回 None
+  pub fn from_overflows<'a>(overflows: impl Iterator<Item = &'a Overflow>) -> Option<Self> {
+    let (mut horizontal, mut vertical) = (false, false);
+    overflows.for_each(|overflow| match overflow.id.as_str() {
+      "overflowX" => horizontal = overflow.x == EnumValue::Scroll,
+      "overflowY" => vertical = overflow.y == EnumValue::Scroll,
+      _ => {
+        horizontal = overflow.x == EnumValue::Scroll;
+        vertical = overflow.y == EnumValue::Scroll;
       }
+    });
+    match horizontal || vertical {
+      true => Some(Scrollable { id: SCROLLABLE.to_string(), horizontal, vertical }),
+      false => None
+    }
+  }
+}
+
+impl ToExpr for Scrollable {
+  fn to_expr(&self) -> PropertyTuple {
+    let direction = match (self.horizontal, self.vertical) {
+      (true, true) => "both",
+      (true, false) => "horizontal",
+      _ => "vertical",
+    };
+    PropertyTuple::One(
+      self.id.to_string(),
+      generate_expr_lit_str!(direction)
     )
   }
 
+  fn to_rn_expr(&self) -> PropertyTuple {
+    PropertyTuple::One(
+      self.id.to_string(),
+      generate_invalid_expr!()
+    )
+  }
 }
@@ -0,0 +1,45 @@
+use swc_core::ecma::ast::Expr;
+
+use crate::generate_expr_lit_str;
+
+use super::{style_value_type::StyleValueType, traits::{ToExpr, ToStyleValue}, unit::{Platform, PropertyTuple}};
+
+
+// 开启 passthrough_unrecognized 时包装转换结果，转换失败（Invalid）的声明按原始字符串输出
+#[derive(Debug, Clone)]
+pub struct Passthrough {
+  pub id: String,
+  // 声明的原始值，如 grid-template-columns: 1fr 1fr => "1fr 1fr"
+  pub raw: String,
+  pub value: Box<StyleValueType>
+}
+
+impl Passthrough {
+  pub fn new(id: String, raw: String, value: StyleValueType) -> Self {
+    Passthrough {
+      id,
+      raw,
+      value: Box::new(value)
+    }
+  }
+
+  fn passthrough(&self, platform: Platform) -> PropertyTuple {
+    match self.value.to_expr(platform) {
+      PropertyTuple::One(_, Expr::Invalid(_)) => PropertyTuple::One(self.id.to_string(), generate_expr_lit_str!(self.raw.clone())),
+      PropertyTuple::Array(props) if !props.is_empty() && props.iter().all(|(_, expr)| matches!(expr, Expr::Invalid(_))) => {
+        PropertyTuple::One(self.id.to_string(), generate_expr_lit_str!(self.raw.clone()))
+      },
+      prop => prop
+    }
+  }
+}
+
+impl ToExpr for Passthrough {
+  fn to_expr(&self) -> PropertyTuple {
+    self.passthrough(Platform::Harmony)
+  }
+
+  fn to_rn_expr(&self) -> PropertyTuple {
+    self.passthrough(Platform::ReactNative)
+  }
+}
@@ -0,0 +1,59 @@
+use lightningcss::{properties::Property, stylesheet::PrinterOptions};
+
+use crate::{generate_expr_enum, generate_expr_lit_str, generate_invalid_expr};
+
+use super::{traits::ToExpr, unit::PropertyTuple};
+
+
+#[derive(Debug, Clone)]
+pub enum EnumValue {
+  Auto,
+  None,
+  Invalid
+}
+
+#[derive(Debug, Clone)]
+pub struct PointerEvents {
+  pub id: String,
+  pub value: EnumValue
+}
+
+impl From<(String, &Property<'_>)> for PointerEvents {
+  fn from(value: (String, &Property<'_>)) -> Self {
+    // lightningcss 不识别 pointer-events，按字符串解析
+    let value_str = value.1.value_to_css_string(PrinterOptions::default()).unwrap_or_default();
+    PointerEvents {
+      id: value.0,
+      value: match value_str.trim() {
+        "auto" => EnumValue::Auto,
+        "none" => EnumValue::None,
+        _ => EnumValue::Invalid
+      }
+    }
+  }
+}
+
+impl ToExpr for PointerEvents {
+  fn to_expr(&self) -> PropertyTuple {
+    // 鸿蒙通过 hitTestBehavior 控制触摸测试
+    PropertyTuple::One(
+      "hitTestBehavior".to_string(),
+      match self.value {
+        EnumValue::Auto => generate_expr_enum!("HitTestMode", "Default"),
+        EnumValue::None => generate_expr_enum!("HitTestMode", "None"),
+        EnumValue::Invalid => generate_invalid_expr!()
+      }
+    )
+  }
+
+  fn to_rn_expr(&self) -> PropertyTuple {
+    PropertyTuple::One(
+      self.id.to_string(),
+      match self.value {
+        EnumValue::Auto => generate_expr_lit_str!("auto"),
+        EnumValue::None => generate_expr_lit_str!("none"),
+        EnumValue::Invalid => generate_invalid_expr!()
+      }
+    )
+  }
+}
@@ -0,0 +1,103 @@
+use lightningcss::{properties::Property, stylesheet::PrinterOptions};
+use swc_core::{common::DUMMY_SP, ecma::ast::*};
+
+use crate::{diagnostics::push_diagnostic, generate_expr_enum, generate_expr_lit_str, generate_invalid_expr};
+
+use super::{traits::ToExpr, unit::PropertyTuple};
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EnumValue {
+  Auto,
+  // scroll-behavior
+  Smooth,
+  // overscroll-behavior
+  Contain,
+  None,
+  Invalid
+}
+
+// scroll-behavior、overscroll-behavior，仅对 Scroll、List 等滚动容器生效
+#[derive(Debug, Clone)]
+pub struct Scroll {
+  pub id: String,
+  pub value: EnumValue
+}
+
+impl From<(String, &Property<'_>)> for Scroll {
+  fn from(value: (String, &Property<'_>)) -> Self {
+    // lightningcss 不识别 scroll-behavior、overscroll-behavior，按字符串解析
+    let value_str = value.1.value_to_css_string(PrinterOptions::default()).unwrap_or_default();
+    let keywords = value_str.split_whitespace().collect::<Vec<&str>>();
+    let is_overscroll = value.0.starts_with("overscrollBehavior");
+    let scroll_value = match keywords.as_slice() {
+      // overscroll-behavior: auto contain 两个方向不同时无法对应到滚动容器的边缘效果
+      [x, y] if is_overscroll && x != y => {
+        push_diagnostic(&value.0, format!("different values for x and y ({}) are not supported, use overscroll-behavior-x or overscroll-behavior-y instead", value_str));
+        EnumValue::Invalid
+      },
+      [keyword] | [keyword, _] => match (*keyword, is_overscroll) {
+        ("auto", _) => EnumValue::Auto,
+        ("smooth", false) => EnumValue::Smooth,
+        ("contain", true) => EnumValue::Contain,
+        ("none", true) => EnumValue::None,
+        _ => EnumValue::Invalid
+      },
+      _ => EnumValue::Invalid
+    };
+    Scroll {
+      id: value.0,
+      value: scroll_value
+    }
+  }
+}
+
+// 滚动到边缘后不再传递给父级滚动容器
+fn generate_nested_scroll_expr() -> Expr {
+  Expr::Object(ObjectLit {
+    span: DUMMY_SP,
+    props: ["scrollForward", "scrollBackward"].iter().map(|key| {
+      PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+        key: PropName::Ident(Ident::new((*key).into(), DUMMY_SP)),
+        value: Box::new(generate_expr_enum!("NestedScrollMode", "SELF_ONLY")),
+      })))
+    }).collect()
+  })
+}
+
+impl ToExpr for Scroll {
+  fn to_expr(&self) -> PropertyTuple {
+    if self.id == "scrollBehavior" {
+      return PropertyTuple::One(
+        self.id.to_string(),
+        match self.value {
+          EnumValue::Auto => generate_expr_lit_str!("auto"),
+          EnumValue::Smooth => generate_expr_lit_str!("smooth"),
+          _ => generate_invalid_expr!()
+        }
+      )
+    }
+    // overscroll-behavior 对应滚动容器的边缘效果：auto => 回弹，none => 无效果，contain、none 不传递给父级滚动容器
+    match self.value {
+      EnumValue::Auto => PropertyTuple::One("edgeEffect".to_string(), generate_expr_enum!("EdgeEffect", "Spring")),
+      EnumValue::Contain => PropertyTuple::Array(vec![
+        ("edgeEffect".to_string(), generate_expr_enum!("EdgeEffect", "Spring")),
+        ("nestedScroll".to_string(), generate_nested_scroll_expr()),
+      ]),
+      EnumValue::None => PropertyTuple::Array(vec![
+        ("edgeEffect".to_string(), generate_expr_enum!("EdgeEffect", "None")),
+        ("nestedScroll".to_string(), generate_nested_scroll_expr()),
+      ]),
+      _ => PropertyTuple::One(self.id.to_string(), generate_invalid_expr!())
+    }
+  }
+
+  fn to_rn_expr(&self) -> PropertyTuple {
+    // RN 中通过 ScrollView 的 bounces、overScrollMode 等属性控制
+    push_diagnostic(&self.id, format!("{} is not supported on ReactNative", self.id));
+    PropertyTuple::One(
+      self.id.to_string(),
+      generate_invalid_expr!()
+    )
+  }
+}
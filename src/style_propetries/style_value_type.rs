@@ -1,6 +1,6 @@
 use crate::generate_expr_based_on_platform;
 
-use super::{animation::Animation, aspect_ratio::AspactRatio, background::Background, background_image::BackgroundImage, background_position::BackgroundPosition, background_repeat::BackgroundRepeat, background_size::BackgroundSize, border::Border, border_color::BorderColor, border_radius::BorderRadius, border_style::BorderStyle, border_width::BorderWidth, box_shadow::BoxShadow, color::ColorProperty, display::Display, expr::Expr, flex::Flex, flex_align::FlexAlign, flex_basis::FlexBasis, flex_direction::FlexDirection, flex_wrap::FlexWrap, font_size::FontSize, font_style::FontStyle, font_weight::FontWeight, gap::Gap, item_align::ItemAlign, length_value::LengthValueProperty, letter_spacing::LetterSpacing, line_height::LineHeight, marin_padding::MarginPadding, max_size::MaxSizeProperty, normal::Normal, number::NumberProperty, overflow::Overflow, size::SizeProperty, text_align::TextAlign, text_decoration::TextDecoration, text_overflow::TextOverflow, text_shadow::TextShadow, text_transform::TextTransform, traits::{ToExpr, ToStyleValue}, transform::Transform, transform_origin::TransformOrigin, unit::{Platform, PropertyTuple}, vertical_align::VerticalAlign};
+use super::{animation::Animation, aspect_ratio::AspectRatio, background::Background, background_image::BackgroundImage, background_position::BackgroundPosition, background_repeat::BackgroundRepeat, background_size::BackgroundSize, border::Border, border_color::BorderColor, border_radius::BorderRadius, border_style::BorderStyle, border_width::BorderWidth, box_shadow::BoxShadow, filter::Filter, mask::Mask, scroll::Scroll, direction::Direction, outline::Outline, content::Content, passthrough::Passthrough, color::ColorProperty, display::Display, opacity::Opacity, visibility::Visibility, pointer_events::PointerEvents, expr::Expr, flex::Flex, flex_align::FlexAlign, flex_basis::FlexBasis, flex_direction::FlexDirection, flex_wrap::FlexWrap, font_size::FontSize, font_family::FontFamily, font_style::FontStyle, font_weight::FontWeight, gap::Gap, item_align::ItemAlign, length_value::LengthValueProperty, letter_spacing::LetterSpacing, line_height::LineHeight, marin_padding::MarginPadding, max_size::MaxSizeProperty, normal::Normal, number::NumberProperty, overflow::{Overflow, Scrollable}, size::SizeProperty, text_align::TextAlign, text_decoration::TextDecoration, text_overflow::TextOverflow, text_shadow::TextShadow, text_stroke::TextStroke, text_transform::TextTransform, white_space::WhiteSpace, line_clamp::LineClamp, word_break::WordBreak, traits::{ToExpr, ToStyleValue}, transform::Transform, transform_origin::TransformOrigin, transition::TransitionConfig, unit::{Platform, PropertyTuple}, vertical_align::VerticalAlign};
 
 
 #[derive(Debug, Clone)]
@@ -19,10 +19,15 @@ pub enum StyleValueType {
   FlexBasis(FlexBasis),
   FlexDirection(FlexDirection),
   FlexWrap(FlexWrap),
-  AspectRatio(AspactRatio),
+  AspectRatio(AspectRatio),
   Display(Display),
+  Opacity(Opacity),
+  Visibility(Visibility),
+  PointerEvents(PointerEvents),
   Gap(Gap),
   Overflow(Overflow),
+  Scroll(Scroll),
+  Direction(Direction),
   FontSize(FontSize),
   FontStyle(FontStyle),
   FontWeight(FontWeight),
@@ -30,8 +35,11 @@ pub enum StyleValueType {
   TextAlign(TextAlign),
   TextDecoration(TextDecoration),
   TextShadow(TextShadow),
+  TextStroke(TextStroke),
   TextTransform(TextTransform),
   TextOverflow(TextOverflow),
+  WhiteSpace(WhiteSpace),
+  LineClamp(LineClamp),
   LetterSpacing(LetterSpacing),
   VerticalAlign(VerticalAlign),
   BorderColor(BorderColor),
@@ -39,15 +47,24 @@ pub enum StyleValueType {
   BorderRadius(BorderRadius),
   BorderStyle(BorderStyle),
   Border(Border),
+  Outline(Outline),
   Transform(Transform),
   TransformOrigin(TransformOrigin),
+  TransitionConfig(TransitionConfig),
+  Scrollable(Scrollable),
   BackgroundRepeat(BackgroundRepeat),
   BackgroundPosition(BackgroundPosition),
   BackgroundSize(BackgroundSize),
   BackgroundImage(BackgroundImage),
   Background(Background),
   Animation(Animation),
-  BoxShadow(BoxShadow)
+  BoxShadow(BoxShadow),
+  Filter(Filter),
+  Mask(Mask),
+  Content(Content),
+  WordBreak(WordBreak),
+  FontFamily(FontFamily),
+  Passthrough(Passthrough)
 }
 
 impl ToStyleValue for StyleValueType {
@@ -101,12 +118,27 @@ impl ToStyleValue for StyleValueType {
       StyleValueType::Display(value) => {
         generate_expr_based_on_platform!(platform, value)
       },
+      StyleValueType::Opacity(value) => {
+        generate_expr_based_on_platform!(platform, value)
+      },
+      StyleValueType::Visibility(value) => {
+        generate_expr_based_on_platform!(platform, value)
+      },
+      StyleValueType::PointerEvents(value) => {
+        generate_expr_based_on_platform!(platform, value)
+      },
       StyleValueType::Gap(value) => {
         generate_expr_based_on_platform!(platform, value)
       },
       StyleValueType::Overflow(value) => {
         generate_expr_based_on_platform!(platform, value)
       },
+      StyleValueType::Scroll(value) => {
+        generate_expr_based_on_platform!(platform, value)
+      },
+      StyleValueType::Direction(value) => {
+        generate_expr_based_on_platform!(platform, value)
+      },
       StyleValueType::FontSize(value) => {
         generate_expr_based_on_platform!(platform, value)
       },
@@ -128,9 +160,18 @@ impl ToStyleValue for StyleValueType {
       StyleValueType::TextShadow(value) => {
         generate_expr_based_on_platform!(platform, value)
       },
+      StyleValueType::TextStroke(value) => {
+        generate_expr_based_on_platform!(platform, value)
+      },
       StyleValueType::TextOverflow(value) => {
         generate_expr_based_on_platform!(platform, value)
       }
+      StyleValueType::WhiteSpace(value) => {
+        generate_expr_based_on_platform!(platform, value)
+      }
+      StyleValueType::LineClamp(value) => {
+        generate_expr_based_on_platform!(platform, value)
+      }
       StyleValueType::TextTransform(value) => {
         generate_expr_based_on_platform!(platform, value)
       },
@@ -161,6 +202,12 @@ impl ToStyleValue for StyleValueType {
       StyleValueType::TransformOrigin(value) => {
         generate_expr_based_on_platform!(platform, value)
       }
+      StyleValueType::TransitionConfig(value) => {
+        generate_expr_based_on_platform!(platform, value)
+      }
+      StyleValueType::Scrollable(value) => {
+        generate_expr_based_on_platform!(platform, value)
+      }
       StyleValueType::BackgroundRepeat(value) => {
         generate_expr_based_on_platform!(platform, value)
       }
@@ -182,6 +229,27 @@ impl ToStyleValue for StyleValueType {
       StyleValueType::BoxShadow(value) => {
         generate_expr_based_on_platform!(platform, value)
       }
+      StyleValueType::Filter(value) => {
+        generate_expr_based_on_platform!(platform, value)
+      }
+      StyleValueType::Mask(value) => {
+        generate_expr_based_on_platform!(platform, value)
+      }
+      StyleValueType::Outline(value) => {
+        generate_expr_based_on_platform!(platform, value)
+      }
+      StyleValueType::Content(value) => {
+        generate_expr_based_on_platform!(platform, value)
+      }
+      StyleValueType::WordBreak(value) => {
+        generate_expr_based_on_platform!(platform, value)
+      }
+      StyleValueType::FontFamily(value) => {
+        generate_expr_based_on_platform!(platform, value)
+      }
+      StyleValueType::Passthrough(value) => {
+        generate_expr_based_on_platform!(platform, value)
+      }
     }
   }
 }
\ No newline at end of file
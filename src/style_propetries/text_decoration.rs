@@ -1,26 +1,28 @@
-use lightningcss::{properties::{Property, text}, traits::ToCss, stylesheet::PrinterOptions, targets::{Targets, Features}};
+use lightningcss::{properties::{Property, text}, values::{color::CssColor, length::LengthValue, percentage::DimensionPercentage}};
 
 use swc_core::ecma::ast::*;
 use swc_core::common::DUMMY_SP;
-use crate::{style_propetries::traits::ToExpr, generate_invalid_expr, generate_expr_lit_str, generate_prop_name};
+use crate::{diagnostics::push_diagnostic, style_propetries::traits::ToExpr, generate_expr_enum, generate_expr_lit_str, generate_prop_name};
 
-use super::unit::{convert_color_keywords_to_hex, PropertyTuple};
+use super::unit::{convert_css_color, generate_expr_by_length_value, Platform, PropertyTuple};
 
 
+// text-decoration 及其 line、style、color、thickness 子属性合并为一个值，id 统一为 textDecoration
 #[derive(Debug, Clone)]
 pub struct TextDecoration {
   pub id: String,
-  pub line: Option<TextDecorationLine>,
+  // 装饰线，可同时存在多条，如 underline line-through；为空时表示 none
+  pub line: Option<Vec<TextDecorationLine>>,
   pub style: Option<TextDecorationStyle>,
-  pub color: Option<TextDecorationColor>
+  pub color: Option<TextDecorationColor>,
+  pub thickness: Option<LengthValue>
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum TextDecorationLine {
   Underline,
   LineThrough,
   Overline,
-  None
 }
 #[derive(Debug, Clone)]
 pub enum TextDecorationStyle {
@@ -33,29 +35,44 @@ pub enum TextDecorationStyle {
 #[derive(Debug, Clone)]
 pub struct TextDecorationColor(String);
 
+impl TextDecoration {
+  // 同一条规则中后声明的子属性覆盖先声明的
+  pub fn merge(&mut self, other: TextDecoration) {
+    if other.line.is_some() {
+      self.line = other.line;
+    }
+    if other.style.is_some() {
+      self.style = other.style;
+    }
+    if other.color.is_some() {
+      self.color = other.color;
+    }
+    if other.thickness.is_some() {
+      self.thickness = other.thickness;
+    }
+  }
+}
+
 impl ToExpr for TextDecoration {
   fn to_expr(&self) -> PropertyTuple {
     let mut props = vec![];
 
-    if let Some(line) = &self.line {
+    if let Some(lines) = &self.line {
+      // 鸿蒙只支持一条装饰线，按 underline、line-through、overline 的顺序取第一条
+      let line = [TextDecorationLine::Underline, TextDecorationLine::LineThrough, TextDecorationLine::Overline]
+        .into_iter()
+        .find(|line| lines.contains(line));
+      if lines.len() > 1 {
+        push_diagnostic(&self.id, "multiple decoration lines are not supported on Harmony, only the first one will be used".to_string());
+      }
       props.push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
         key: generate_prop_name!("type"),
-        value: Expr::Member(MemberExpr {
-          span: DUMMY_SP,
-          obj: Box::new(Expr::Ident(Ident::new("TextDecorationType".into(), DUMMY_SP))),
-          prop: MemberProp::Ident(Ident {
-            span: DUMMY_SP,
-            sym: match line {
-              TextDecorationLine::Underline => "Underline",
-              TextDecorationLine::LineThrough => "LineThrough",
-              TextDecorationLine::Overline => "Overline",
-              _ => "None",
-            }
-            .into(),
-            optional: false,
-          }),
-        })
-        .into(),
+        value: Box::new(generate_expr_enum!("TextDecorationType", match line {
+          Some(TextDecorationLine::Underline) => "Underline",
+          Some(TextDecorationLine::LineThrough) => "LineThrough",
+          Some(TextDecorationLine::Overline) => "Overline",
+          None => "None",
+        })),
       }))));
     }
 
@@ -66,6 +83,26 @@ impl ToExpr for TextDecoration {
       }))));
     }
 
+    if let Some(style) = &self.style {
+      props.push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+        key: generate_prop_name!("style"),
+        value: Box::new(generate_expr_enum!("TextDecorationStyle", match style {
+          TextDecorationStyle::Solid => "SOLID",
+          TextDecorationStyle::Double => "DOUBLE",
+          TextDecorationStyle::Dotted => "DOTTED",
+          TextDecorationStyle::Dashed => "DASHED",
+          TextDecorationStyle::Wavy => "WAVY",
+        })),
+      }))));
+    }
+
+    if let Some(thickness) = &self.thickness {
+      props.push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+        key: generate_prop_name!("thickness"),
+        value: Box::new(generate_expr_by_length_value(thickness, Platform::Harmony)),
+      }))));
+    }
+
     PropertyTuple::One(
       self.id.to_string(),
       Expr::Object(ObjectLit {
@@ -78,16 +115,22 @@ impl ToExpr for TextDecoration {
   fn to_rn_expr(&self) -> PropertyTuple {
     let mut props: Vec<(String, Expr)> = vec![];
 
-    if let Some(line) = &self.line {
-      props.push(
-        ("textDecorationLine".to_string(),
-        match line {
-          TextDecorationLine::Underline => generate_expr_lit_str!("underline"),
-          TextDecorationLine::LineThrough => generate_expr_lit_str!("line-through"),
-          TextDecorationLine::Overline => generate_invalid_expr!(),
-          TextDecorationLine::None => generate_expr_lit_str!("none")
-        })
-      )
+    if let Some(lines) = &self.line {
+      if lines.contains(&TextDecorationLine::Overline) {
+        push_diagnostic(&self.id, "overline is not supported on ReactNative".to_string());
+      }
+      // RN 支持 underline、line-through 及两者的组合
+      let value = [(TextDecorationLine::Underline, "underline"), (TextDecorationLine::LineThrough, "line-through")]
+        .into_iter()
+        .filter(|(line, _)| lines.contains(line))
+        .map(|(_, name)| name)
+        .collect::<Vec<_>>()
+        .join(" ");
+      match (value.is_empty(), lines.is_empty()) {
+        (false, _) => props.push(("textDecorationLine".to_string(), generate_expr_lit_str!(value))),
+        (true, true) => props.push(("textDecorationLine".to_string(), generate_expr_lit_str!("none"))),
+        (true, false) => {}
+      }
     };
     if let Some(style) = &self.style {
       props.push(
@@ -107,117 +150,73 @@ impl ToExpr for TextDecoration {
         generate_expr_lit_str!(color.0.clone()))
       )
     }
+    if self.thickness.is_some() {
+      push_diagnostic(&self.id, "text-decoration-thickness is not supported on ReactNative".to_string());
+    }
     PropertyTuple::Array(props)
   }
 }
 
+fn convert_line(value: &text::TextDecorationLine) -> Vec<TextDecorationLine> {
+  [
+    (text::TextDecorationLine::Underline, TextDecorationLine::Underline),
+    (text::TextDecorationLine::LineThrough, TextDecorationLine::LineThrough),
+    (text::TextDecorationLine::Overline, TextDecorationLine::Overline),
+  ]
+    .into_iter()
+    .filter(|(flag, _)| value.contains(*flag))
+    .map(|(_, line)| line)
+    .collect()
+}
+
+fn convert_style(value: &text::TextDecorationStyle) -> TextDecorationStyle {
+  match value {
+    text::TextDecorationStyle::Solid => TextDecorationStyle::Solid,
+    text::TextDecorationStyle::Double => TextDecorationStyle::Double,
+    text::TextDecorationStyle::Dotted => TextDecorationStyle::Dotted,
+    text::TextDecorationStyle::Dashed => TextDecorationStyle::Dashed,
+    text::TextDecorationStyle::Wavy => TextDecorationStyle::Wavy,
+  }
+}
+
+// 如果为 currentColor，则不设置color
+fn convert_color(value: &CssColor) -> Option<TextDecorationColor> {
+  match value {
+    CssColor::CurrentColor => None,
+    _ => Some(TextDecorationColor(convert_css_color(value)))
+  }
+}
+
+// auto、from-font 由平台决定粗细，百分比相对于字体无法换算，均不输出
+fn convert_thickness(value: &text::TextDecorationThickness) -> Option<LengthValue> {
+  match value {
+    text::TextDecorationThickness::LengthPercentage(DimensionPercentage::Dimension(length_value)) => Some(length_value.clone()),
+    _ => None
+  }
+}
+
 impl From<(String, &Property<'_>)> for TextDecoration {
   fn from(prop: (String, &Property<'_>)) -> Self {
+    let mut text_decoration = TextDecoration {
+      id: "textDecoration".to_string(),
+      line: None,
+      style: None,
+      color: None,
+      thickness: None
+    };
     match prop.1 {
       Property::TextDecoration(value, _) => {
-        let line = match value.line {
-          text::TextDecorationLine::LineThrough => TextDecorationLine::LineThrough,
-          text::TextDecorationLine::Overline => TextDecorationLine::Overline,
-          text::TextDecorationLine::Underline => TextDecorationLine::Underline,
-          _ => TextDecorationLine::None,
-        };
-        let style = match value.style {
-          text::TextDecorationStyle::Solid => TextDecorationStyle::Solid,
-          text::TextDecorationStyle::Double => TextDecorationStyle::Double,
-          text::TextDecorationStyle::Dotted => TextDecorationStyle::Dotted,
-          text::TextDecorationStyle::Dashed => TextDecorationStyle::Dashed,
-          text::TextDecorationStyle::Wavy => TextDecorationStyle::Wavy,
-        };
-        let color_string = value.color.to_css_string(PrinterOptions {
-          minify: false,
-          targets: Targets {
-            include: Features::HexAlphaColors,
-            ..Targets::default()
-          },
-          ..PrinterOptions::default()
-        });
-        let color: Option<TextDecorationColor>;
-        if let Ok(c) = color_string {
-          // 如果c为"currentColor"，则不设置color
-          if c == "currentColor" {
-            color = None
-          } else {
-            color = Some(TextDecorationColor(convert_color_keywords_to_hex(c)));
-          }
-        } else {
-          color = None
-        }
-        TextDecoration {
-          id: prop.0,
-          line: Some(line),
-          style: Some(style),
-          color: color
-        }
+        text_decoration.line = Some(convert_line(&value.line));
+        text_decoration.style = Some(convert_style(&value.style));
+        text_decoration.color = convert_color(&value.color);
+        text_decoration.thickness = convert_thickness(&value.thickness);
       },
-      Property::TextDecorationLine(value, _) => {
-        let line = match *value {
-          text::TextDecorationLine::LineThrough => TextDecorationLine::LineThrough,
-          text::TextDecorationLine::Overline => TextDecorationLine::Overline,
-          text::TextDecorationLine::Underline => TextDecorationLine::Underline,
-          _ => TextDecorationLine::None,
-        };
-        TextDecoration {
-          id: prop.0,
-          line: Some(line),
-          style: None,
-          color: None
-        }
-      }
-      Property::TextDecorationStyle(value, _) => {
-        let style = match *value {
-          text::TextDecorationStyle::Solid => TextDecorationStyle::Solid,
-          text::TextDecorationStyle::Double => TextDecorationStyle::Double,
-          text::TextDecorationStyle::Dotted => TextDecorationStyle::Dotted,
-          text::TextDecorationStyle::Dashed => TextDecorationStyle::Dashed,
-          text::TextDecorationStyle::Wavy => TextDecorationStyle::Wavy,
-        };
-        TextDecoration {
-          id: prop.0,
-          line: None,
-          style: Some(style),
-          color: None
-        }
-      }
-      Property::TextDecorationColor(value, _) => {
-        let color_string = value.to_css_string(PrinterOptions {
-          minify: false,
-          targets: Targets {
-            include: Features::HexAlphaColors,
-            ..Targets::default()
-          },
-          ..PrinterOptions::default()
-        });
-        let color: Option<TextDecorationColor>;
-        if let Ok(c) = color_string {
-          // 如果c为"currentColor"，则不设置color
-          if c == "currentColor" {
-            color = None
-          } else {
-            color = Some(TextDecorationColor(convert_color_keywords_to_hex(c)));
-          }
-        } else {
-          color = None
-        }
-        TextDecoration {
-          id: prop.0,
-          line: None,
-          style: None,
-          color: color
-        }
-      }
-      _ => {
-        TextDecoration {
-          id: prop.0,
-          line: None,
-          style: None,
-          color: None
-        }
-      }
+      Property::TextDecorationLine(value, _) => text_decoration.line = Some(convert_line(value)),
+      Property::TextDecorationStyle(value, _) => text_decoration.style = Some(convert_style(value)),
+      Property::TextDecorationColor(value, _) => text_decoration.color = convert_color(value),
+      Property::TextDecorationThickness(value) => text_decoration.thickness = convert_thickness(value),
+      _ => {}
     }
+    text_decoration
   }
 }
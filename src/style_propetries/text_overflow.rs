@@ -2,7 +2,7 @@ use lightningcss::properties::{Property, overflow};
 
 use swc_core::ecma::ast::*;
 use swc_core::common::DUMMY_SP;
-use crate::{generate_invalid_expr, style_propetries::traits::ToExpr};
+use crate::{generate_expr_lit_str, generate_invalid_expr, style_propetries::traits::ToExpr};
 
 use super::unit::PropertyTuple;
 
@@ -43,9 +43,14 @@ impl ToExpr for TextOverflow {
   }
   
   fn to_rn_expr(&self) -> PropertyTuple {
+    // RN 的 Text 通过 ellipsizeMode 控制截断方式，需配合 numberOfLines 使用
     PropertyTuple::One(
-      "overflow".to_string(),
-      generate_invalid_expr!()
+      "ellipsizeMode".to_string(),
+      match self {
+        TextOverflow::Clip => generate_expr_lit_str!("clip"),
+        TextOverflow::Ellipsis => generate_expr_lit_str!("tail"),
+        TextOverflow::None => generate_invalid_expr!(),
+      }
     )
   }
 }
@@ -1,8 +1,8 @@
-use lightningcss::{properties::Property, values::{length::Length, color::CssColor}, traits::ToCss};
+use lightningcss::{properties::Property, values::{length::Length, color::CssColor}};
 
 use swc_core::ecma::ast::*;
 use swc_core::common::DUMMY_SP;
-use crate::{style_propetries::traits::ToExpr, generate_prop_name, generate_expr_by_length, generate_string_by_css_color};
+use crate::{diagnostics::push_diagnostic, style_propetries::traits::ToExpr, generate_prop_name, generate_expr_by_length, generate_string_by_css_color, generate_invalid_expr};
 
 use super::unit::PropertyTuple;
 
@@ -10,77 +10,67 @@ use super::unit::PropertyTuple;
 #[derive(Debug, Clone)]
 pub struct TextShadow {
   pub id: String,
-  pub offset_x: Option<Length>,
-  pub offset_y: Option<Length>,
-  pub blur_radius: Option<Length>,
-  pub color: Option<CssColor>
+  // 逗号分隔的多个阴影，按声明顺序排列
+  pub shadows: Vec<TextShadowItem>
 }
 
-impl TextShadow {
-  pub fn new(id: String) -> Self {
-    Self {
-      id,
-      offset_x: None,
-      offset_y: None,
-      blur_radius: None,
-      color: None
-    }
-  }
-
-  pub fn set_offset_x(&mut self, offset_x: Length) {
-    self.offset_x = Some(offset_x);
-  }
-
-  pub fn set_offset_y(&mut self, offset_y: Length) {
-    self.offset_y = Some(offset_y);
-  }
-
-  pub fn set_blur_radius(&mut self, blur_radius: Length) {
-    self.blur_radius = Some(blur_radius);
-  }
+#[derive(Debug, Clone)]
+pub struct TextShadowItem {
+  pub offset_x: Length,
+  pub offset_y: Length,
+  pub blur_radius: Length,
+  pub color: CssColor
+}
 
-  pub fn set_color(&mut self, color: CssColor) {
-    self.color = Some(color);
+impl TextShadowItem {
+  fn to_harmony_expr(&self) -> Expr {
+    Expr::Object(ObjectLit {
+      span: DUMMY_SP,
+      props: vec![
+        ("radius", generate_expr_by_length!(&self.blur_radius, Platform::Harmony)),
+        ("color", generate_string_by_css_color!(self.color)),
+        ("offsetX", generate_expr_by_length!(&self.offset_x, Platform::Harmony)),
+        ("offsetY", generate_expr_by_length!(&self.offset_y, Platform::Harmony)),
+      ].into_iter().map(|(key, value)| {
+        PropOrSpread::Prop(Box::new(Prop::KeyValue(
+          KeyValueProp {
+            key: generate_prop_name!(key),
+            value: Box::new(value),
+          }
+        )))
+      }).collect()
+    })
   }
 }
 
 impl ToExpr for TextShadow {
     fn to_expr(&self) -> PropertyTuple {
+      // 鸿蒙的 textShadow 同时支持单个阴影对象和阴影数组
       PropertyTuple::One(
         "textShadow".to_string(),
-        Expr::Object(ObjectLit {
-          span: DUMMY_SP,
-          props: vec![
-            PropOrSpread::Prop(Box::new(Prop::KeyValue(
-              KeyValueProp {
-                key: generate_prop_name!("radius"),
-                value: Box::new(generate_expr_by_length!(self.offset_x.as_ref().unwrap(), Platform::Harmony)),
-              }
-            ))),
-            PropOrSpread::Prop(Box::new(Prop::KeyValue(
-              KeyValueProp {
-                key: generate_prop_name!("color"),
-                value: Box::new(generate_string_by_css_color!(self.color.as_ref().unwrap())),
-              }
-            ))),
-            PropOrSpread::Prop(Box::new(Prop::KeyValue(
-              KeyValueProp {
-                key: generate_prop_name!("offsetX"),
-                value: Box::new(generate_expr_by_length!(self.offset_x.as_ref().unwrap(), Platform::Harmony)),
-              }
-            ))),
-            PropOrSpread::Prop(Box::new(Prop::KeyValue(
-              KeyValueProp {
-                key: generate_prop_name!("offsetY"),
-                value: Box::new(generate_expr_by_length!(self.offset_y.as_ref().unwrap(), Platform::Harmony)),
-              }
-            ))),
-          ]
-        })
+        match self.shadows.as_slice() {
+          [] => generate_invalid_expr!(),
+          [shadow] => shadow.to_harmony_expr(),
+          shadows => Expr::Array(ArrayLit {
+            span: DUMMY_SP,
+            elems: shadows.iter().map(|shadow| Some(ExprOrSpread {
+              spread: None,
+              expr: Box::new(shadow.to_harmony_expr())
+            })).collect()
+          })
+        }
       )
     }
 
     fn to_rn_expr(&self) -> PropertyTuple {
+      // RN 只支持一个阴影，取最先声明（绘制在最上层）的阴影
+      let shadow = match self.shadows.first() {
+        Some(shadow) => shadow,
+        None => return PropertyTuple::One("textShadow".to_string(), generate_invalid_expr!())
+      };
+      if self.shadows.len() > 1 {
+        push_diagnostic(&self.id, "multiple text shadows are not supported on ReactNative, only the first one will be used".to_string());
+      }
       PropertyTuple::Array(
         vec![
           ("textShadowOffset".to_string(), Expr::Object(ObjectLit {
@@ -89,19 +79,19 @@ impl ToExpr for TextShadow {
               PropOrSpread::Prop(Box::new(Prop::KeyValue(
                 KeyValueProp {
                   key: generate_prop_name!("width"),
-                  value: Box::new(generate_expr_by_length!(self.offset_x.as_ref().unwrap(), Platform::ReactNative)),
+                  value: Box::new(generate_expr_by_length!(&shadow.offset_x, Platform::ReactNative)),
                 }
               ))),
               PropOrSpread::Prop(Box::new(Prop::KeyValue(
                 KeyValueProp {
                   key: generate_prop_name!("height"),
-                  value: Box::new(generate_expr_by_length!(self.offset_y.as_ref().unwrap(), Platform::ReactNative)),
+                  value: Box::new(generate_expr_by_length!(&shadow.offset_y, Platform::ReactNative)),
                 }
               ))),
             ],
           })),
-          ("textShadowColor".to_string(), generate_string_by_css_color!(self.color.as_ref().unwrap())),
-          ("textShadowRadius".to_string(), generate_expr_by_length!(self.blur_radius.as_ref().unwrap(), Platform::ReactNative)),
+          ("textShadowColor".to_string(), generate_string_by_css_color!(shadow.color)),
+          ("textShadowRadius".to_string(), generate_expr_by_length!(&shadow.blur_radius, Platform::ReactNative)),
         ]
       )
     }
@@ -109,18 +99,19 @@ impl ToExpr for TextShadow {
 
 impl From<(String, &Property<'_>)> for TextShadow {
   fn from(prop: (String, &Property<'_>)) -> Self {
-    let text_shadow = TextShadow::new(prop.0);
-    match prop.1 {
-      Property::TextShadow(value) => {
-        value.into_iter().fold(text_shadow, |mut acc, val| {
-          acc.set_offset_x(val.x_offset.clone());
-          acc.set_offset_y(val.y_offset.clone());
-          acc.set_blur_radius(val.blur.clone());
-          acc.set_color(val.color.clone());
-          acc
-        })
+    TextShadow {
+      id: prop.0,
+      shadows: match prop.1 {
+        Property::TextShadow(value) => {
+          value.iter().map(|val| TextShadowItem {
+            offset_x: val.x_offset.clone(),
+            offset_y: val.y_offset.clone(),
+            blur_radius: val.blur.clone(),
+            color: val.color.clone()
+          }).collect()
+        }
+        _ => vec![]
       }
-      _ => text_shadow
     }
   }
 }
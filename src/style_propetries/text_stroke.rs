@@ -0,0 +1,98 @@
+use lightningcss::{properties::Property, stylesheet::PrinterOptions, traits::Parse, values::{color::CssColor, length::Length}};
+
+use swc_core::ecma::ast::*;
+use swc_core::common::DUMMY_SP;
+use crate::{diagnostics::push_diagnostic, generate_expr_by_length, generate_invalid_expr, generate_prop_name, generate_string_by_css_color};
+
+use super::{traits::ToExpr, unit::PropertyTuple};
+
+
+// -webkit-text-stroke 及其 width、color 子属性合并为一个值，id 统一为 textStroke
+#[derive(Debug, Clone)]
+pub struct TextStroke {
+  pub id: String,
+  pub width: Option<Length>,
+  pub color: Option<CssColor>
+}
+
+impl TextStroke {
+  // 同一条规则中后声明的子属性覆盖先声明的
+  pub fn merge(&mut self, other: TextStroke) {
+    if other.width.is_some() {
+      self.width = other.width;
+    }
+    if other.color.is_some() {
+      self.color = other.color;
+    }
+  }
+}
+
+impl From<(String, &Property<'_>)> for TextStroke {
+  fn from(prop: (String, &Property<'_>)) -> Self {
+    // lightningcss 不识别 -webkit-text-stroke，按字符串解析，宽度和颜色的顺序不限
+    let value_str = prop.1.value_to_css_string(PrinterOptions::default()).unwrap_or_default();
+    let value_str = value_str.trim();
+    let mut text_stroke = TextStroke {
+      id: "textStroke".to_string(),
+      width: None,
+      color: None
+    };
+    match prop.0.as_str() {
+      "WebkitTextStrokeWidth" => text_stroke.width = Length::parse_string(value_str).ok(),
+      "WebkitTextStrokeColor" => text_stroke.color = CssColor::parse_string(value_str).ok(),
+      _ => {
+        // 颜色可能带空格，如 rgb(0, 0, 0)，因此只尝试把首个或最后一个片段作为宽度
+        let (first, rest) = value_str.split_once(char::is_whitespace).unwrap_or((value_str, ""));
+        let (init, last) = value_str.rsplit_once(char::is_whitespace).unwrap_or(("", value_str));
+        if let Ok(width) = Length::parse_string(first) {
+          text_stroke.width = Some(width);
+          text_stroke.color = CssColor::parse_string(rest.trim()).ok();
+        } else if let Ok(width) = Length::parse_string(last) {
+          text_stroke.width = Some(width);
+          text_stroke.color = CssColor::parse_string(init.trim()).ok();
+        } else {
+          text_stroke.color = CssColor::parse_string(value_str).ok();
+        }
+      }
+    }
+    text_stroke
+  }
+}
+
+impl ToExpr for TextStroke {
+  fn to_expr(&self) -> PropertyTuple {
+    let mut props = vec![];
+    if let Some(width) = &self.width {
+      props.push(("width", generate_expr_by_length!(width, Platform::Harmony)));
+    }
+    if let Some(color) = &self.color {
+      props.push(("color", generate_string_by_css_color!(color)));
+    }
+    PropertyTuple::One(
+      self.id.to_string(),
+      if props.is_empty() {
+        generate_invalid_expr!()
+      } else {
+        Expr::Object(ObjectLit {
+          span: DUMMY_SP,
+          props: props.into_iter().map(|(key, value)| {
+            PropOrSpread::Prop(Box::new(Prop::KeyValue(
+              KeyValueProp {
+                key: generate_prop_name!(key),
+                value: Box::new(value),
+              }
+            )))
+          }).collect()
+        })
+      }
+    )
+  }
+
+  fn to_rn_expr(&self) -> PropertyTuple {
+    push_diagnostic(&self.id, "-webkit-text-stroke is not supported on ReactNative".to_string());
+    PropertyTuple::One(
+      self.id.to_string(),
+      generate_invalid_expr!()
+    )
+  }
+}
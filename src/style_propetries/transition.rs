@@ -0,0 +1,172 @@
+use lightningcss::{properties::Property, values::easing::EasingFunction};
+
+use swc_core::ecma::ast::*;
+use swc_core::common::DUMMY_SP;
+use crate::{generate_expr_lit_num, generate_expr_lit_str, generate_invalid_expr, generate_prop_name};
+
+use super::{traits::ToExpr, unit::{convert_time_to_ms, generate_expr_by_easing_function, PropertyTuple}};
+
+// 鸿蒙可由 animateTo 驱动过渡的属性
+const ANIMATABLE_PROPERTIES: [&str; 2] = ["transform", "opacity"];
+
+// transition 及其子属性合并为 transitionConfig，供鸿蒙运行时调用 animateTo
+// 原始的 transition 声明仍按字符串输出
+#[derive(Debug, Clone)]
+pub struct TransitionConfig {
+  pub id: String,
+  pub properties: Option<Vec<String>>,
+  // 单位：毫秒
+  pub durations: Option<Vec<f32>>,
+  pub delays: Option<Vec<f32>>,
+  pub timing_functions: Option<Vec<EasingFunction>>
+}
+
+// 单个属性的过渡参数
+struct TransitionItem<'a> {
+  property: &'a str,
+  duration: f32,
+  delay: f32,
+  timing_function: EasingFunction
+}
+
+impl TransitionConfig {
+  // 同一条规则中后声明的子属性覆盖先声明的
+  pub fn merge(&mut self, other: TransitionConfig) {
+    if other.properties.is_some() {
+      self.properties = other.properties;
+    }
+    if other.durations.is_some() {
+      self.durations = other.durations;
+    }
+    if other.delays.is_some() {
+      self.delays = other.delays;
+    }
+    if other.timing_functions.is_some() {
+      self.timing_functions = other.timing_functions;
+    }
+  }
+
+  // 按 css 的规则展开各属性的过渡参数，子属性的列表长度不足时循环取值
+  fn get_items(&self) -> Vec<TransitionItem<'_>> {
+    let properties = match &self.properties {
+      Some(properties) => properties,
+      None => return vec![]
+    };
+    let cycle = |values: &Option<Vec<f32>>, index: usize| values.as_ref().filter(|values| !values.is_empty()).map_or(0.0, |values| values[index % values.len()]);
+    properties.iter().enumerate().flat_map(|(index, property)| {
+      let timing_function = self.timing_functions.as_ref().filter(|values| !values.is_empty()).map_or(EasingFunction::Ease, |values| values[index % values.len()].clone());
+      let duration = cycle(&self.durations, index);
+      let delay = cycle(&self.delays, index);
+      // all 包含 transform、opacity
+      let animatable = match property.as_str() {
+        "all" => ANIMATABLE_PROPERTIES.to_vec(),
+        property => ANIMATABLE_PROPERTIES.into_iter().filter(|name| *name == property).collect()
+      };
+      animatable.into_iter().map(move |property| TransitionItem {
+        property,
+        duration,
+        delay,
+        timing_function: timing_function.clone()
+      })
+    }).collect()
+  }
+}
+
+impl From<(String, &Property<'_>)> for TransitionConfig {
+  fn from(prop: (String, &Property<'_>)) -> Self {
+    let mut transition_config = TransitionConfig {
+      id: "transitionConfig".to_string(),
+      properties: None,
+      durations: None,
+      delays: None,
+      timing_functions: None
+    };
+    match prop.1 {
+      Property::Transition(transitions, _) => {
+        transition_config.properties = Some(transitions.iter().map(|transition| transition.property.name().to_string()).collect());
+        transition_config.durations = Some(transitions.iter().map(|transition| convert_time_to_ms(&transition.duration)).collect());
+        transition_config.delays = Some(transitions.iter().map(|transition| convert_time_to_ms(&transition.delay)).collect());
+        transition_config.timing_functions = Some(transitions.iter().map(|transition| transition.timing_function.clone()).collect());
+      },
+      Property::TransitionProperty(properties, _) => {
+        transition_config.properties = Some(properties.iter().map(|property| property.name().to_string()).collect());
+      },
+      Property::TransitionDuration(durations, _) => {
+        transition_config.durations = Some(durations.iter().map(convert_time_to_ms).collect());
+      },
+      Property::TransitionDelay(delays, _) => {
+        transition_config.delays = Some(delays.iter().map(convert_time_to_ms).collect());
+      },
+      Property::TransitionTimingFunction(timing_functions, _) => {
+        transition_config.timing_functions = Some(timing_functions.to_vec());
+      },
+      _ => {}
+    }
+    transition_config
+  }
+}
+
+impl ToExpr for TransitionConfig {
+  fn to_expr(&self) -> PropertyTuple {
+    // 时长、延迟、曲线相同的属性合并为一组，对应一次 animateTo 调用
+    let mut groups: Vec<(f32, f32, EasingFunction, Vec<&str>)> = vec![];
+    self.get_items().into_iter().filter(|item| item.duration > 0.0).for_each(|item| {
+      let group = groups.iter_mut().find(|(duration, delay, timing_function, _)| {
+        *duration == item.duration && *delay == item.delay && *timing_function == item.timing_function
+      });
+      match group {
+        Some((_, _, _, properties)) => {
+          if !properties.contains(&item.property) {
+            properties.push(item.property);
+          }
+        },
+        None => groups.push((item.duration, item.delay, item.timing_function, vec![item.property]))
+      }
+    });
+    let configs = groups.into_iter().map(|(duration, delay, timing_function, properties)| {
+      Expr::Object(ObjectLit {
+        span: DUMMY_SP,
+        props: vec![
+          ("duration", generate_expr_lit_num!(duration as f64)),
+          ("delay", generate_expr_lit_num!(delay as f64)),
+          ("curve", generate_expr_by_easing_function(&timing_function)),
+          ("properties", Expr::Array(ArrayLit {
+            span: DUMMY_SP,
+            elems: properties.into_iter().map(|property| Some(ExprOrSpread {
+              spread: None,
+              expr: Box::new(generate_expr_lit_str!(property))
+            })).collect()
+          })),
+        ].into_iter().map(|(key, value)| {
+          PropOrSpread::Prop(Box::new(Prop::KeyValue(
+            KeyValueProp {
+              key: generate_prop_name!(key),
+              value: Box::new(value),
+            }
+          )))
+        }).collect()
+      })
+    }).collect::<Vec<_>>();
+    PropertyTuple::One(
+      self.id.to_string(),
+      // 只有一组时同样输出为数组，运行时无需区分对象与数组
+      match configs.is_empty() {
+        true => generate_invalid_expr!(),
+        false => Expr::Array(ArrayLit {
+          span: DUMMY_SP,
+          elems: configs.into_iter().map(|config| Some(ExprOrSpread {
+            spread: None,
+            expr: Box::new(config)
+          })).collect()
+        })
+      }
+    )
+  }
+
+  fn to_rn_expr(&self) -> PropertyTuple {
+    PropertyTuple::One(
+      self.id.to_string(),
+      generate_invalid_expr!()
+    )
+  }
+}
@@ -1,9 +1,9 @@
-use lightningcss::{values::length::LengthValue, traits::ToCss, stylesheet::PrinterOptions};
+use lightningcss::{values::{calc::{Calc, MathFunction}, color::CssColor, easing::EasingFunction, length::{LengthPercentage, LengthValue}, percentage::DimensionPercentage, time::Time}, traits::{Parse, ToCss, TrySign}, stylesheet::PrinterOptions, targets::{Features, Targets}};
 use pcre2::bytes::Regex;
 
 use swc_core::ecma::ast::*;
 use swc_core::common::DUMMY_SP;
-use crate::{constants::{CONVERT_STYLE_PX_FN, RN_CONVERT_STYLE_PX_FN, RN_CONVERT_STYLE_VU_FN}, generate_expr_lit_num, generate_expr_lit_str, utils::fix_rgba};
+use crate::{constants::{CONVERT_STYLE_PX_FN, CURRENT_COLOR, RN_CONVERT_STYLE_PX_FN, RN_CONVERT_STYLE_VU_FN}, generate_expr_lit_num, generate_expr_lit_str, options::{with_transform_options, ColorFormat, RoundingMode}, utils::fix_rgba};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Platform {
@@ -28,37 +28,42 @@ pub fn generate_expr_by_length_value(length_value: &LengthValue, platform: Platf
       match platform {
         Platform::ReactNative => {
           handler = Some(RN_CONVERT_STYLE_PX_FN.to_string());
-          args.push(generate_expr_lit_num!(*num as f64))
+          args.push(generate_expr_lit_num!(round_length(convert_f32_to_f64(*num))))
         },
         Platform::Harmony => {
           handler = Some(CONVERT_STYLE_PX_FN.to_string());
-          args.push(generate_expr_lit_num!(*num as f64))
+          args.push(generate_expr_lit_num!(round_length(convert_f32_to_f64(*num))))
           // return generate_expr_lit_str!(format!("{}lpx", num))
         }
       }
     },
+    // rem、em 在编译期按配置的字号换算成 px
     LengthValue::Rem(num) => {
-      match platform {
-        Platform::ReactNative => {
-          handler = Some(RN_CONVERT_STYLE_PX_FN.to_string());
-          args.push(generate_expr_lit_num!((*num * 16.0) as f64))
-        },
-        Platform::Harmony => {
-          handler = Some(CONVERT_STYLE_PX_FN.to_string());
-          args.push(generate_expr_lit_num!((*num * 16.0) as f64))
-        }
-      }
+      let px = round_length(convert_f32_to_f64(*num) * with_transform_options(|options| options.root_font_size));
+      handler = match platform {
+        Platform::ReactNative => Some(RN_CONVERT_STYLE_PX_FN.to_string()),
+        Platform::Harmony => Some(CONVERT_STYLE_PX_FN.to_string())
+      };
+      args.push(generate_expr_lit_num!(px))
+    },
+    LengthValue::Em(num) => {
+      let px = round_length(convert_f32_to_f64(*num) * with_transform_options(|options| options.em_font_size));
+      handler = match platform {
+        Platform::ReactNative => Some(RN_CONVERT_STYLE_PX_FN.to_string()),
+        Platform::Harmony => Some(CONVERT_STYLE_PX_FN.to_string())
+      };
+      args.push(generate_expr_lit_num!(px))
     },
     LengthValue::Vh(num) => {
       match platform {
         Platform::ReactNative => {
           handler = Some(RN_CONVERT_STYLE_VU_FN.to_string());
-          args.push(generate_expr_lit_num!(*num as f64));
+          args.push(generate_expr_lit_num!(round_length(convert_f32_to_f64(*num))));
           args.push(generate_expr_lit_str!("vh"));
         },
         Platform::Harmony => {
           handler = Some(CONVERT_STYLE_PX_FN.to_string());
-          args.push(generate_expr_lit_num!(*num as f64));
+          args.push(generate_expr_lit_num!(round_length(convert_f32_to_f64(*num))));
           args.push(generate_expr_lit_str!("vh"));
         }
       }
@@ -67,52 +72,47 @@ pub fn generate_expr_by_length_value(length_value: &LengthValue, platform: Platf
       match platform {
         Platform::ReactNative => {
           handler = Some(RN_CONVERT_STYLE_VU_FN.to_string());
-          args.push(generate_expr_lit_num!(*num as f64));
+          args.push(generate_expr_lit_num!(round_length(convert_f32_to_f64(*num))));
           args.push(generate_expr_lit_str!("vw"));
         },
         Platform::Harmony => {
           handler = Some(CONVERT_STYLE_PX_FN.to_string());
-          args.push(generate_expr_lit_num!(*num as f64));
+          args.push(generate_expr_lit_num!(round_length(convert_f32_to_f64(*num))));
           args.push(generate_expr_lit_str!("vw"));
         }
       }
     },
-    LengthValue::Vmin(num) => {
-      match platform {
-        Platform::ReactNative => {
-          handler = Some(RN_CONVERT_STYLE_VU_FN.to_string());
-          args.push(generate_expr_lit_num!(*num as f64))
-        },
-        Platform::Harmony => {
-          handler = Some(CONVERT_STYLE_PX_FN.to_string());
-          args.push(generate_expr_lit_num!(*num as f64));
-          args.push(generate_expr_lit_str!("vmin"));
-        }
-      }
+    // vmin、vmax 与 vw、vh 一样交给运行时换算，由运行时取视口宽高中的较小、较大值
+    LengthValue::Vmin(num) | LengthValue::Vmax(num) => {
+      let unit = match length_value {
+        LengthValue::Vmin(_) => "vmin",
+        _ => "vmax"
+      };
+      handler = match platform {
+        Platform::ReactNative => Some(RN_CONVERT_STYLE_VU_FN.to_string()),
+        Platform::Harmony => Some(CONVERT_STYLE_PX_FN.to_string())
+      };
+      args.push(generate_expr_lit_num!(round_length(convert_f32_to_f64(*num))));
+      args.push(generate_expr_lit_str!(unit));
     },
-    LengthValue::Vmax(num) => {
-      match platform {
-        Platform::ReactNative => {
-          handler = Some(RN_CONVERT_STYLE_VU_FN.to_string());
-          args.push(generate_expr_lit_num!(*num as f64))
-        },
-        Platform::Harmony => {
-          handler = Some(CONVERT_STYLE_PX_FN.to_string());
-          args.push(generate_expr_lit_num!(*num as f64));
-          args.push(generate_expr_lit_str!("vmax"));
-        }
-      }
+    // in、cm、mm、Q、pt、pc 在编译期换算成 px
+    LengthValue::In(_) | LengthValue::Cm(_) | LengthValue::Mm(_) | LengthValue::Q(_) | LengthValue::Pt(_) | LengthValue::Pc(_) => {
+      handler = match platform {
+        Platform::ReactNative => Some(RN_CONVERT_STYLE_PX_FN.to_string()),
+        Platform::Harmony => Some(CONVERT_STYLE_PX_FN.to_string())
+      };
+      args.push(generate_expr_lit_num!(round_length(convert_absolute_length_to_px(length_value))))
     },
     LengthValue::Ch(num) => {
       match platform {
         Platform::ReactNative => {
           handler = Some(RN_CONVERT_STYLE_VU_FN.to_string());
-          args.push(generate_expr_lit_num!(*num as f64));
+          args.push(generate_expr_lit_num!(round_length(convert_f32_to_f64(*num))));
           args.push(generate_expr_lit_str!("PX"));
         },
         Platform::Harmony => {
           handler = Some(CONVERT_STYLE_PX_FN.to_string());
-          args.push(generate_expr_lit_num!(*num as f64));
+          args.push(generate_expr_lit_num!(round_length(convert_f32_to_f64(*num))));
           args.push(generate_expr_lit_str!("PX"));
         }
       }
@@ -138,10 +138,224 @@ pub fn generate_expr_by_length_value(length_value: &LengthValue, platform: Platf
   }
 }
 
+// 绝对长度单位按 CSS 规范固定的 96 DPI 换算：1in = 96px = 2.54cm = 72pt = 6pc，1cm = 10mm = 40Q
+// 换算结果与设备的物理尺寸无关，与浏览器中的表现一致
+const PX_PER_IN: f64 = 96.0;
+const PX_PER_CM: f64 = PX_PER_IN / 2.54;
+const PX_PER_MM: f64 = PX_PER_CM / 10.0;
+const PX_PER_Q: f64 = PX_PER_CM / 40.0;
+const PX_PER_PT: f64 = PX_PER_IN / 72.0;
+const PX_PER_PC: f64 = PX_PER_IN / 6.0;
+
+fn convert_absolute_length_to_px(length_value: &LengthValue) -> f64 {
+  match length_value {
+    LengthValue::In(num) => convert_f32_to_f64(*num) * PX_PER_IN,
+    LengthValue::Cm(num) => convert_f32_to_f64(*num) * PX_PER_CM,
+    LengthValue::Mm(num) => convert_f32_to_f64(*num) * PX_PER_MM,
+    LengthValue::Q(num) => convert_f32_to_f64(*num) * PX_PER_Q,
+    LengthValue::Pt(num) => convert_f32_to_f64(*num) * PX_PER_PT,
+    LengthValue::Pc(num) => convert_f32_to_f64(*num) * PX_PER_PC,
+    _ => 0.0,
+  }
+}
+
+// 长度数值按配置的精度取整，未配置 precision 时原样返回
+pub fn round_length(num: f64) -> f64 {
+  with_transform_options(|options| match options.precision {
+    Some(precision) => {
+      let factor = 10f64.powi(precision as i32);
+      // 先消除浮点误差，避免 0.29 * 100 = 28.999999999999996 向下取整为 28
+      let scaled = (num * factor * 1e9).round() / 1e9;
+      let rounded = match options.rounding {
+        RoundingMode::Round => scaled.round(),
+        RoundingMode::Floor => scaled.floor(),
+        RoundingMode::Ceil => scaled.ceil(),
+      };
+      rounded / factor
+    },
+    None => num
+  })
+}
+
+// 能在运行时换算成数值的长度单位，与 generate_expr_by_length_value 的处理保持一致
+fn is_runtime_length(length_value: &LengthValue) -> bool {
+  matches!(length_value, LengthValue::Px(_) | LengthValue::Rem(_) | LengthValue::Em(_) | LengthValue::Vh(_) | LengthValue::Vw(_) | LengthValue::Vmin(_) | LengthValue::Vmax(_) | LengthValue::Ch(_))
+    || matches!(length_value, LengthValue::In(_) | LengthValue::Cm(_) | LengthValue::Mm(_) | LengthValue::Q(_) | LengthValue::Pt(_) | LengthValue::Pc(_))
+}
+
+// 根据 calc()、min()、max()、clamp() 生成 calc 字符串，其中的长度逐个换算，如
+// calc(100% - 10px) => `calc(100% - ${convertNumber2VP(10)})`，min(10px, 2vw) => `min(${convertNumber2VP(10)}, ${convertNumber2VP(2, "vw")})`
+// 单位换算的结果可能是带单位的字符串，不能直接参与算术运算
+pub fn generate_expr_by_calc(calc: &Calc<LengthPercentage>, platform: Platform) -> Expr {
+  let mut parts = vec![];
+  match calc {
+    Calc::Function(_) => push_calc_parts(calc, platform, &mut parts),
+    _ => {
+      parts.push(CalcPart::Str("calc(".to_string()));
+      push_calc_parts(calc, platform, &mut parts);
+      parts.push(CalcPart::Str(")".to_string()));
+    }
+  }
+  generate_tpl_by_calc_parts(parts)
+}
+
+// 解析 calc 字符串后生成表达式，无法解析时原样输出
+pub fn generate_expr_by_calc_string(value: &str, platform: Platform) -> Expr {
+  match Calc::<LengthPercentage>::parse_string(value) {
+    Ok(calc) => generate_expr_by_calc(&calc, platform),
+    Err(_) => generate_expr_lit_str!(value),
+  }
+}
+
+enum CalcPart {
+  Str(String),
+  Expr(Expr),
+}
+
+fn push_calc_parts(calc: &Calc<LengthPercentage>, platform: Platform, parts: &mut Vec<CalcPart>) {
+  match calc {
+    Calc::Value(value) => match value.as_ref() {
+      DimensionPercentage::Dimension(length_value) if is_runtime_length(length_value) => parts.push(CalcPart::Expr(generate_expr_by_length_value(length_value, platform))),
+      DimensionPercentage::Calc(calc) => push_calc_parts(calc, platform, parts),
+      value => parts.push(CalcPart::Str(value.to_css_string(PrinterOptions::default()).unwrap())),
+    },
+    Calc::Number(num) => parts.push(CalcPart::Str(num.to_string())),
+    Calc::Sum(a, b) => {
+      push_calc_parts(a, platform.clone(), parts);
+      match b.is_sign_negative() {
+        true => {
+          parts.push(CalcPart::Str(" - ".to_string()));
+          push_calc_parts(&(b.as_ref().clone() * -1.0), platform, parts);
+        },
+        false => {
+          parts.push(CalcPart::Str(" + ".to_string()));
+          push_calc_parts(b, platform, parts);
+        }
+      }
+    },
+    Calc::Product(num, calc) => {
+      let is_sum = matches!(calc.as_ref(), Calc::Sum(..));
+      if num.abs() >= 1.0 {
+        parts.push(CalcPart::Str(format!("{} * ", num)));
+      }
+      if is_sum {
+        parts.push(CalcPart::Str("(".to_string()));
+      }
+      push_calc_parts(calc, platform, parts);
+      if is_sum {
+        parts.push(CalcPart::Str(")".to_string()));
+      }
+      if num.abs() < 1.0 {
+        parts.push(CalcPart::Str(format!(" / {}", 1.0 / num)));
+      }
+    },
+    Calc::Function(function) => {
+      let (name, calcs) = match function.as_ref() {
+        MathFunction::Calc(calc) => ("calc", vec![calc]),
+        MathFunction::Min(calcs) => ("min", calcs.iter().collect()),
+        MathFunction::Max(calcs) => ("max", calcs.iter().collect()),
+        MathFunction::Clamp(min, value, max) => ("clamp", vec![min, value, max]),
+        function => {
+          parts.push(CalcPart::Str(function.to_css_string(PrinterOptions::default()).unwrap()));
+          return
+        }
+      };
+      parts.push(CalcPart::Str(format!("{}(", name)));
+      for (index, calc) in calcs.into_iter().enumerate() {
+        if index > 0 {
+          parts.push(CalcPart::Str(", ".to_string()));
+        }
+        push_calc_parts(calc, platform.clone(), parts);
+      }
+      parts.push(CalcPart::Str(")".to_string()));
+    },
+  }
+}
+
+// 相邻的字符串合并为模板字符串的 quasis，表达式作为插值
+fn generate_tpl_by_calc_parts(parts: Vec<CalcPart>) -> Expr {
+  let mut quasis = vec![String::new()];
+  let mut exprs = vec![];
+  for part in parts {
+    match part {
+      CalcPart::Str(str) => quasis.last_mut().unwrap().push_str(&str),
+      CalcPart::Expr(expr) => {
+        exprs.push(Box::new(expr));
+        quasis.push(String::new());
+      }
+    }
+  }
+  let len = quasis.len();
+  Expr::Tpl(Tpl {
+    span: DUMMY_SP,
+    exprs,
+    quasis: quasis.into_iter().enumerate().map(|(index, quasi)| TplElement {
+      span: DUMMY_SP,
+      tail: index == len - 1,
+      cooked: Some(quasi.clone().into()),
+      raw: quasi.into(),
+    }).collect(),
+  })
+}
+
+
+// f32 直接转 f64 会带上精度误差，如 0.1 => 0.10000000149011612，经字符串转换保留原始精度
+pub fn convert_f32_to_f64(num: f32) -> f64 {
+  num.to_string().parse::<f64>().unwrap()
+}
+
+// 时间统一换算为毫秒
+pub fn convert_time_to_ms(time: &Time) -> f32 {
+  match time {
+    Time::Seconds(s) => s * 1000.0,
+    Time::Milliseconds(ms) => *ms,
+  }
+}
+
+// 根据缓动函数生成对应的表达式
+// 关键字直接输出字符串：'ease'、'linear' 等
+// cubic-bezier()、steps() 输出 { type, params }，由运行时构造对应的曲线
+pub fn generate_expr_by_easing_function(easing_function: &EasingFunction) -> Expr {
+  let to_num_expr = |num: f32| generate_expr_lit_num!(convert_f32_to_f64(num));
+  let (easing_type, params) = match easing_function {
+    EasingFunction::CubicBezier { x1, y1, x2, y2 } => (
+      "cubic-bezier",
+      vec![to_num_expr(*x1), to_num_expr(*y1), to_num_expr(*x2), to_num_expr(*y2)]
+    ),
+    EasingFunction::Steps { count, position } => (
+      "steps",
+      vec![
+        generate_expr_lit_num!(*count as f64),
+        generate_expr_lit_str!(position.to_css_string(PrinterOptions::default()).unwrap()),
+      ]
+    ),
+    _ => return generate_expr_lit_str!(easing_function.to_css_string(PrinterOptions::default()).unwrap())
+  };
+
+  Expr::Object(ObjectLit {
+    span: DUMMY_SP,
+    props: vec![
+      PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+        key: PropName::Ident(Ident::new("type".into(), DUMMY_SP)),
+        value: Box::new(generate_expr_lit_str!(easing_type))
+      }))),
+      PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+        key: PropName::Ident(Ident::new("params".into(), DUMMY_SP)),
+        value: Box::new(Expr::Array(ArrayLit {
+          span: DUMMY_SP,
+          elems: params.into_iter().map(|param| Some(ExprOrSpread {
+            spread: None,
+            expr: Box::new(param)
+          })).collect()
+        }))
+      })))
+    ]
+  })
+}
 
 pub fn generate_expr_with_css_input(input: String, platform: Platform) -> Expr {
   // 定义匹配 '16px' 的正则表达式
-  let re = Regex::new(r"(-?(?P<num>\d+(\.\d*)?|\.\d+))(?P<unit>(px)|(vw)|(vh)|(pX)|(PX)|(Px))").unwrap();
+  let re = Regex::new(r"(-?(?P<num>\d+(\.\d*)?|\.\d+))(?P<unit>(px)|(vw)|(vh)|(rem)|(em)|(pX)|(PX)|(Px))").unwrap();
   let bytes = input.as_bytes();
   // 使用正则表达式进行匹配
   if let Ok(caps) = re.captures(bytes) {
@@ -177,12 +391,15 @@ pub fn generate_expr_with_css_input(input: String, platform: Platform) -> Expr {
               };
               args.push(generate_expr_lit_num!(number));
             },
-            "rem" => {
+            "rem" | "em" => {
               handler = match platform {
                 Platform::ReactNative => Some(RN_CONVERT_STYLE_PX_FN.to_string()),
                 Platform::Harmony => Some(CONVERT_STYLE_PX_FN.to_string())
               };
-              args.push(generate_expr_lit_num!(number * 16.0));
+              let font_size = with_transform_options(|options| {
+                if unit == "rem" { options.root_font_size } else { options.em_font_size }
+              });
+              args.push(generate_expr_lit_num!(number * font_size));
             },
             "pX" | "PX" | "Px" => {
               handler = match platform {
@@ -227,6 +444,39 @@ pub fn generate_expr_with_css_input(input: String, platform: Platform) -> Expr {
 }
 
 
+// css 颜色转换为平台支持的颜色字符串，所有输出颜色的属性统一经过此处处理，保证两端的颜色格式一致
+// lab()、lch()、oklab()、oklch()、color() 等色彩空间先转换到 sRGB，hsl()、hwb() 在解析时已转换为 sRGB
+pub fn convert_css_color(color: &CssColor) -> String {
+  let color = match color {
+    CssColor::CurrentColor | CssColor::RGBA(_) => color.clone(),
+    _ => color.to_rgb().unwrap_or(color.clone())
+  };
+  let (color_format, platform) = with_transform_options(|options| (options.color_format.clone(), options.platform.clone()));
+  match (&color, color_format) {
+    (CssColor::RGBA(rgba), ColorFormat::Hex8) => match platform {
+      Platform::Harmony => format!("#{:02X}{:02X}{:02X}{:02X}", rgba.alpha, rgba.red, rgba.green, rgba.blue),
+      Platform::ReactNative => format!("#{:02X}{:02X}{:02X}{:02X}", rgba.red, rgba.green, rgba.blue, rgba.alpha),
+    },
+    (CssColor::RGBA(rgba), ColorFormat::Hex) if rgba.alpha == 255 => {
+      format!("#{:02X}{:02X}{:02X}", rgba.red, rgba.green, rgba.blue)
+    },
+    (CssColor::RGBA(rgba), ColorFormat::Rgb) if rgba.alpha == 255 => {
+      format!("rgb({}, {}, {})", rgba.red, rgba.green, rgba.blue)
+    },
+    (CssColor::RGBA(rgba), ColorFormat::Hex | ColorFormat::Rgb) => {
+      format!("rgba({}, {}, {}, {})", rgba.red, rgba.green, rgba.blue, (rgba.alpha_f32() * 100.0).round() / 100.0)
+    },
+    _ => convert_color_keywords_to_hex(color.to_css_string(PrinterOptions {
+      minify: false,
+      targets: Targets {
+        include: Features::HexAlphaColors,
+        ..Targets::default()
+      },
+      ..PrinterOptions::default()
+    }).unwrap())
+  }
+}
+
 // 处理将color关键字转换为hex
 // 参考颜色关键字：https://www.w3.org/TR/css-color-3/#svg-color
 pub fn convert_color_keywords_to_hex(color: String) -> String {
@@ -378,7 +628,7 @@ pub fn convert_color_keywords_to_hex(color: String) -> String {
     "whitesmoke" => "#F5F5F5".to_string(),
     "yellow" => "#FFFF00".to_string(),
     "yellowgreen" => "#9ACD32".to_string(),
-    "currentColor" => "".to_string(),
+    "currentColor" => CURRENT_COLOR.to_string(),
     _ => color
   };
 
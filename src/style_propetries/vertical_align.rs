@@ -1,11 +1,11 @@
-use lightningcss::properties::{Property, font};
+use lightningcss::{properties::{Property, font}, values::{length::LengthPercentage, percentage::{DimensionPercentage, Percentage}}};
 
 use swc_core::ecma::ast::*;
 use swc_core::common::DUMMY_SP;
 
-use crate::{generate_expr_lit_str, generate_invalid_expr};
+use crate::{diagnostics::push_diagnostic, generate_expr_by_length_percentage, generate_expr_lit_str, generate_invalid_expr};
 
-use super::{traits::ToExpr, unit::PropertyTuple};
+use super::{traits::ToExpr, unit::{Platform, PropertyTuple}};
 
 #[derive(Debug, Clone)]
 pub struct VerticalAlign {
@@ -23,16 +23,34 @@ pub enum EnumValue {
   Middle,
   Top,
   Bottom,
+  // 相对基线的偏移，正值上移
+  Offset(LengthPercentage),
+  // 同一条规则中没有长度的行高时，百分比无法换算
+  Percentage(Percentage),
   Invalid
 }
 
 impl ToExpr for VerticalAlign {
   fn to_expr(&self) -> PropertyTuple {
+    match &self.value {
+      // 鸿蒙通过 baselineOffset 设置文本相对基线的偏移
+      EnumValue::Offset(offset) => {
+        return PropertyTuple::One(
+          "baselineOffset".to_string(),
+          generate_expr_by_length_percentage!(offset, Platform::Harmony)
+        )
+      },
+      EnumValue::Percentage(_) => {
+        push_diagnostic(&self.id, "percentage vertical-align requires a line-height length in the same rule".to_string());
+        return PropertyTuple::One("baselineOffset".to_string(), generate_invalid_expr!())
+      },
+      _ => {}
+    }
     PropertyTuple::One(
       "verticalAlign".to_string(),
       {
         match self.value {
-          EnumValue::Baseline | EnumValue::Sub | EnumValue::Super | EnumValue::TextTop | EnumValue::TextBottom | EnumValue::Invalid => generate_invalid_expr!(),
+          EnumValue::Baseline | EnumValue::Sub | EnumValue::Super | EnumValue::TextTop | EnumValue::TextBottom | EnumValue::Offset(_) | EnumValue::Percentage(_) | EnumValue::Invalid => generate_invalid_expr!(),
           EnumValue::Middle | EnumValue::Top | EnumValue::Bottom => {
             Expr::Member(MemberExpr {
               span: DUMMY_SP,
@@ -65,14 +83,29 @@ impl ToExpr for VerticalAlign {
         EnumValue::TextTop | EnumValue::Top => generate_expr_lit_str!("top"),
         EnumValue::TextBottom | EnumValue::Bottom => generate_expr_lit_str!("bottom"),
         EnumValue::Middle => generate_expr_lit_str!("center"),
+        EnumValue::Offset(_) | EnumValue::Percentage(_) => {
+          push_diagnostic(&self.id, "length and percentage vertical-align are not supported on ReactNative".to_string());
+          generate_invalid_expr!()
+        },
         EnumValue::Invalid => generate_invalid_expr!(),
       }
     )
   }
 }
 
-impl From<(String, &Property<'_>)> for VerticalAlign {
-  fn from(prop: (String, &Property<'_>)) -> Self {
+// 百分比相对于行高，按同一条规则中以长度声明的行高换算
+fn resolve_vertical_align_percentage(percentage: &Percentage, line_height: Option<&Property<'_>>) -> EnumValue {
+  match line_height {
+    Some(Property::LineHeight(font::LineHeight::Length(DimensionPercentage::Dimension(length_value)))) => {
+      EnumValue::Offset(DimensionPercentage::Dimension(length_value.clone() * percentage.0))
+    },
+    _ => EnumValue::Percentage(percentage.clone())
+  }
+}
+
+// (属性名, 属性值, 同一条规则中声明的行高)
+impl From<(String, &Property<'_>, Option<&Property<'_>>)> for VerticalAlign {
+  fn from(prop: (String, &Property<'_>, Option<&Property<'_>>)) -> Self {
     VerticalAlign {
       id: prop.0,
       value: match prop.1 {
@@ -90,8 +123,9 @@ impl From<(String, &Property<'_>)> for VerticalAlign {
                 font::VerticalAlignKeyword::Bottom => EnumValue::Bottom,
               }
             },
-            font::VerticalAlign::Length(_) => EnumValue::Invalid,
-        }
+            font::VerticalAlign::Length(DimensionPercentage::Percentage(percentage)) => resolve_vertical_align_percentage(percentage, prop.2),
+            font::VerticalAlign::Length(length) => EnumValue::Offset(length.clone()),
+          }
         }
         _ => EnumValue::Invalid,
       }
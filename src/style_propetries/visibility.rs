@@ -0,0 +1,50 @@
+use lightningcss::properties::{display, Property};
+
+use crate::{generate_expr_enum, generate_expr_lit_str, generate_invalid_expr};
+
+use super::{traits::ToExpr, unit::PropertyTuple};
+
+
+#[derive(Debug, Clone)]
+pub struct Visibility {
+  pub id: String,
+  pub value: Option<display::Visibility>
+}
+
+impl From<(String, &Property<'_>)> for Visibility {
+  fn from(value: (String, &Property<'_>)) -> Self {
+    Visibility {
+      id: value.0,
+      value: match value.1 {
+        Property::Visibility(value) => Some(*value),
+        _ => None
+      }
+    }
+  }
+}
+
+impl ToExpr for Visibility {
+  fn to_expr(&self) -> PropertyTuple {
+    PropertyTuple::One(
+      self.id.to_string(),
+      match self.value {
+        Some(display::Visibility::Visible) => generate_expr_enum!("Visibility", "Visible"),
+        Some(display::Visibility::Hidden) => generate_expr_enum!("Visibility", "Hidden"),
+        // collapse 不占位，对应鸿蒙的 Visibility.None
+        Some(display::Visibility::Collapse) => generate_expr_enum!("Visibility", "None"),
+        None => generate_invalid_expr!()
+      }
+    )
+  }
+
+  fn to_rn_expr(&self) -> PropertyTuple {
+    // RN 不支持 visibility，隐藏时降级为 display: none（不再占位）
+    PropertyTuple::One(
+      "display".to_string(),
+      match self.value {
+        Some(display::Visibility::Hidden) | Some(display::Visibility::Collapse) => generate_expr_lit_str!("none"),
+        _ => generate_invalid_expr!()
+      }
+    )
+  }
+}
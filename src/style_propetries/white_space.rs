@@ -0,0 +1,53 @@
+use lightningcss::{properties::{Property, text}, stylesheet::PrinterOptions, traits::ToCss};
+
+use crate::{generate_expr_lit_num, generate_expr_lit_str, generate_invalid_expr};
+
+use super::{traits::ToExpr, unit::PropertyTuple};
+
+
+#[derive(Debug, Clone)]
+pub struct WhiteSpace {
+  pub id: String,
+  pub value: Option<text::WhiteSpace>
+}
+
+impl From<(String, &Property<'_>)> for WhiteSpace {
+  fn from(value: (String, &Property<'_>)) -> Self {
+    WhiteSpace {
+      id: value.0,
+      value: match value.1 {
+        Property::WhiteSpace(value) => Some(*value),
+        _ => None
+      }
+    }
+  }
+}
+
+impl ToExpr for WhiteSpace {
+  fn to_expr(&self) -> PropertyTuple {
+    match &self.value {
+      Some(value) => {
+        let mut props = vec![
+          (self.id.to_string(), generate_expr_lit_str!(value.to_css_string(PrinterOptions::default()).unwrap()))
+        ];
+        // nowrap 不换行，等同于单行显示
+        if *value == text::WhiteSpace::NoWrap {
+          props.push(("maxLines".to_string(), generate_expr_lit_num!(1.0)));
+        }
+        PropertyTuple::Array(props)
+      },
+      None => PropertyTuple::One(self.id.to_string(), generate_invalid_expr!())
+    }
+  }
+
+  fn to_rn_expr(&self) -> PropertyTuple {
+    PropertyTuple::One(
+      "numberOfLines".to_string(),
+      match &self.value {
+        // RN 的 Text 通过 numberOfLines 控制行数，这里只做提示，由运行时转为组件属性
+        Some(text::WhiteSpace::NoWrap) => generate_expr_lit_num!(1.0),
+        _ => generate_invalid_expr!()
+      }
+    )
+  }
+}
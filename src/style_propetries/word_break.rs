@@ -0,0 +1,80 @@
+use lightningcss::properties::{Property, text};
+
+use crate::{diagnostics::push_diagnostic, generate_expr_enum, generate_invalid_expr};
+
+use super::{traits::ToExpr, unit::PropertyTuple};
+
+
+#[derive(Debug, Clone)]
+pub enum EnumValue {
+  WordBreak(text::WordBreak),
+  // overflow-wrap 及其别名 word-wrap
+  OverflowWrap(text::OverflowWrap),
+  Hyphens(text::Hyphens),
+}
+
+// word-break、overflow-wrap、hyphens：控制文本的断行方式，鸿蒙统一转为 wordBreak
+#[derive(Debug, Clone)]
+pub struct WordBreak {
+  pub id: String,
+  pub value: Option<EnumValue>
+}
+
+impl From<(String, &Property<'_>)> for WordBreak {
+  fn from(value: (String, &Property<'_>)) -> Self {
+    WordBreak {
+      id: value.0,
+      value: match value.1 {
+        Property::WordBreak(value) => Some(EnumValue::WordBreak(*value)),
+        Property::OverflowWrap(value) | Property::WordWrap(value) => Some(EnumValue::OverflowWrap(*value)),
+        Property::Hyphens(value, _) => Some(EnumValue::Hyphens(*value)),
+        _ => None
+      }
+    }
+  }
+}
+
+impl ToExpr for WordBreak {
+  fn to_expr(&self) -> PropertyTuple {
+    PropertyTuple::One(
+      "wordBreak".to_string(),
+      match &self.value {
+        Some(EnumValue::WordBreak(value)) => match value {
+          text::WordBreak::Normal => generate_expr_enum!("WordBreak", "NORMAL"),
+          text::WordBreak::BreakAll => generate_expr_enum!("WordBreak", "BREAK_ALL"),
+          text::WordBreak::BreakWord => generate_expr_enum!("WordBreak", "BREAK_WORD"),
+          // 鸿蒙没有 keep-all，CJK 文本仍会在字符间断行
+          text::WordBreak::KeepAll => {
+            push_diagnostic(&self.id, "keep-all is not supported on Harmony and will be ignored".to_string());
+            generate_invalid_expr!()
+          }
+        },
+        Some(EnumValue::OverflowWrap(value)) => match value {
+          text::OverflowWrap::Normal => generate_expr_enum!("WordBreak", "NORMAL"),
+          text::OverflowWrap::Anywhere | text::OverflowWrap::BreakWord => generate_expr_enum!("WordBreak", "BREAK_WORD"),
+        },
+        Some(EnumValue::Hyphens(value)) => {
+          if *value == text::Hyphens::Auto {
+            push_diagnostic(&self.id, "hyphens: auto is not supported on Harmony and will be ignored".to_string());
+          }
+          generate_invalid_expr!()
+        },
+        None => generate_invalid_expr!()
+      }
+    )
+  }
+
+  fn to_rn_expr(&self) -> PropertyTuple {
+    // RN 的 Text 默认在单词过长时断行，效果等同于 break-word，其余断行方式无法通过样式实现
+    match &self.value {
+      Some(EnumValue::WordBreak(text::WordBreak::BreakAll)) => push_diagnostic(&self.id, "break-all is not supported on ReactNative and will be ignored".to_string()),
+      Some(EnumValue::WordBreak(text::WordBreak::KeepAll)) => push_diagnostic(&self.id, "keep-all is not supported on ReactNative and will be ignored".to_string()),
+      Some(EnumValue::Hyphens(text::Hyphens::Auto)) => push_diagnostic(&self.id, "hyphens: auto is not supported on ReactNative and will be ignored".to_string()),
+      _ => {}
+    }
+    PropertyTuple::One(
+      self.id.to_string(),
+      generate_invalid_expr!()
+    )
+  }
+}
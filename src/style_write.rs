@@ -1,6 +1,4 @@
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
-
-use lightningcss::properties::Property;
+use std::{cell::RefCell, rc::Rc};
 
 use swc_core::ecma::{
     ast::Program,
@@ -8,32 +6,33 @@ use swc_core::ecma::{
   };
 
 use crate::{
-  style_parser::StyleValue, style_propetries::unit::Platform, visitor::{JSXMutVisitor, JSXRecord, ModuleMutVisitor, SpanKey}
+  options::with_transform_options, style_parser::{ContainerStyle, StyleData, StyleMap}, style_propetries::unit::Platform, visitor::{JSXMutVisitor, JSXRecord, ModuleMutVisitor, StaticStyleHoistVisitor}
 };
 
-pub struct StyleWrite<'i> {
+pub struct StyleWrite {
   pub module: Rc<RefCell<Program>>,
   pub jsx_record: Rc<RefCell<JSXRecord>>,
-  pub pesudo_style_record: Rc<RefCell<HashMap<SpanKey, Vec<(String, Vec<(String, Property<'i>)>)>>>>,
-  pub all_style: Rc<RefCell<HashMap<String, StyleValue>>>,
+  pub all_style: Rc<RefCell<StyleMap>>,
+  pub dark_style: Rc<RefCell<StyleMap>>,
+  pub container_style: Rc<RefCell<ContainerStyle>>,
   pub is_enable_nesting: bool,
   pub is_entry: bool,
 }
 
-impl<'i> StyleWrite<'i> {
+impl StyleWrite {
   pub fn new(
     module: Rc<RefCell<Program>>,
     jsx_record: Rc<RefCell<JSXRecord>>,
-    pesudo_style_record: Rc<RefCell<HashMap<SpanKey, Vec<(String, Vec<(String, Property<'i>)>)>>>>,
-    all_style: Rc<RefCell<HashMap<String, StyleValue>>>,
+    style_data: &StyleData,
     is_enable_nesting: bool,
     is_entry: bool,
   ) -> Self {
     StyleWrite {
       module,
       jsx_record,
-      pesudo_style_record,
-      all_style,
+      all_style: style_data.all_style.clone(),
+      dark_style: style_data.dark_style.clone(),
+      container_style: style_data.container_style.clone(),
       is_enable_nesting,
       is_entry,
     }
@@ -46,7 +45,6 @@ impl<'i> StyleWrite<'i> {
         JSXMutVisitor::new(
           self.jsx_record.clone(), 
           self.all_style.clone(),
-          self.pesudo_style_record.clone(),
           taro_components.clone(),
           platform.clone()
         );
@@ -57,11 +55,21 @@ impl<'i> StyleWrite<'i> {
     }
     // 插入样式表
     {
-      let mut insert_mut_visitor = ModuleMutVisitor::new(self.all_style.clone(),platform.clone(), self.is_enable_nesting, self.is_entry);
+      let mut insert_mut_visitor = ModuleMutVisitor::new(self.all_style.clone(), self.dark_style.clone(), self.container_style.clone(), platform.clone(), self.is_enable_nesting, self.is_entry);
       self
         .module
         .borrow_mut()
         .visit_mut_with(&mut insert_mut_visitor);
     }
+    // 类组件的静态样式对象提升到模块作用域，嵌套样式在运行时按节点树合并，含嵌套样式时不做提升
+    if with_transform_options(|options| options.hoist_static_styles) && !self.is_enable_nesting && !self.is_entry {
+      if let Program::Module(module) = &mut *self.module.borrow_mut() {
+        let mut hoist_visitor = StaticStyleHoistVisitor::new();
+        module.visit_mut_with(&mut hoist_visitor);
+        if !hoist_visitor.class_names.is_empty() {
+          hoist_visitor.insert_hoisted_style(module);
+        }
+      }
+    }
   }
 }
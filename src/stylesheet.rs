@@ -0,0 +1,209 @@
+use std::{collections::BTreeMap, fmt};
+
+use serde::{de::{MapAccess, Visitor}, Deserialize, Deserializer, Serialize, Serializer};
+use swc_core::{
+  common::{sync::Lrc, SourceMap, DUMMY_SP},
+  ecma::{
+    ast::*,
+    codegen::{text_writer::JsWriter, Emitter},
+  },
+};
+
+use crate::{style_parser::{ContainerStyle, StyleMap}, style_propetries::{style_value_type::StyleValueType, unit::Platform}, visitor::parse_style_values};
+
+// 样式表 JSON 的结构版本，结构发生不兼容变更时递增
+const STYLESHEET_VERSION: u32 = 1;
+
+// 转换后的样式值，字面量按 JSON 原生类型输出，运行时计算的表达式（如 convertNumber2VP(10)）输出为 { "$expr": "..." }
+// 反序列化时按声明顺序尝试各变体，Expr 需在 Object 之前
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum StyleSheetValue {
+  Null,
+  Bool(bool),
+  Number(f64),
+  String(String),
+  Array(Vec<StyleSheetValue>),
+  Expr {
+    #[serde(rename = "$expr")]
+    expr: String
+  },
+  // 对象保持属性的原始顺序，如 transform 中各变换的顺序
+  Object(#[serde(serialize_with = "serialize_entries", deserialize_with = "deserialize_entries")] Vec<(String, StyleSheetValue)>),
+}
+
+fn serialize_entries<S: Serializer>(entries: &[(String, StyleSheetValue)], serializer: S) -> Result<S::Ok, S::Error> {
+  serializer.collect_map(entries.iter().map(|(key, value)| (key, value)))
+}
+
+fn deserialize_entries<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<(String, StyleSheetValue)>, D::Error> {
+  struct EntriesVisitor;
+
+  impl<'de> Visitor<'de> for EntriesVisitor {
+    type Value = Vec<(String, StyleSheetValue)>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+      formatter.write_str("a style object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+      let mut entries = vec![];
+      while let Some(entry) = map.next_entry()? {
+        entries.push(entry);
+      }
+      Ok(entries)
+    }
+  }
+
+  deserializer.deserialize_map(EntriesVisitor)
+}
+
+// 选择器 => { 属性 => 值 }，均按 key 排序，保证输出稳定
+pub type StyleSheetRules = BTreeMap<String, BTreeMap<String, StyleSheetValue>>;
+
+// 无法在编译期求值的 @container 规则及其样式，由运行时根据容器尺寸判断是否生效
+#[derive(Debug, Clone, Serialize)]
+pub struct StyleSheetContainerRules {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub name: Option<String>,
+  pub condition: String,
+  pub styles: StyleSheetRules,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StyleSheet {
+  pub version: u32,
+  pub platform: String,
+  pub styles: StyleSheetRules,
+  // 暗色模式下的完整样式，仅在存在 prefers-color-scheme: dark 时输出
+  #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+  pub dark_styles: StyleSheetRules,
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub container_styles: Vec<StyleSheetContainerRules>,
+}
+
+impl StyleSheet {
+  pub fn new(all_style: &StyleMap, dark_style: &StyleMap, container_style: &ContainerStyle, platform: Platform) -> Self {
+    StyleSheet {
+      version: STYLESHEET_VERSION,
+      platform: match platform {
+        Platform::Harmony => "Harmony".to_string(),
+        Platform::ReactNative => "ReactNative".to_string(),
+      },
+      styles: generate_rules(all_style, platform.clone()),
+      dark_styles: generate_rules(dark_style, platform.clone()),
+      container_styles: container_style.iter().map(|(query, style)| StyleSheetContainerRules {
+        name: query.name.clone(),
+        condition: query.condition.clone(),
+        styles: generate_rules(style, platform.clone()),
+      }).collect(),
+    }
+  }
+
+  pub fn to_json(&self) -> String {
+    serde_json::to_string_pretty(self).unwrap()
+  }
+}
+
+pub fn generate_rules(style: &StyleMap, platform: Platform) -> StyleSheetRules {
+  style.iter().map(|(selector, value)| {
+    (selector.to_string(), generate_declarations(value.to_vec(), platform.clone()).into_iter().collect())
+  }).collect()
+}
+
+// 样式值转换为 (属性, 值)，按转换后的声明顺序排列
+pub fn generate_declarations(value: Vec<StyleValueType>, platform: Platform) -> Vec<(String, StyleSheetValue)> {
+  parse_style_values(value, platform).into_iter().filter_map(|prop| {
+    match prop {
+      PropOrSpread::Prop(prop) => match *prop {
+        Prop::KeyValue(KeyValueProp { key, value }) => get_prop_name(&key).map(|key| (key, expr_to_value(&value))),
+        _ => None
+      },
+      _ => None
+    }
+  }).collect()
+}
+
+fn get_prop_name(key: &PropName) -> Option<String> {
+  match key {
+    PropName::Ident(ident) => Some(ident.sym.to_string()),
+    PropName::Str(str) => Some(str.value.to_string()),
+    PropName::Num(num) => Some(num.value.to_string()),
+    // 伪类等计算属性，如 ["::after"]
+    PropName::Computed(ComputedPropName { expr, .. }) => match &**expr {
+      Expr::Lit(Lit::Str(str)) => Some(str.value.to_string()),
+      _ => None
+    },
+    _ => None
+  }
+}
+
+fn expr_to_value(expr: &Expr) -> StyleSheetValue {
+  match expr {
+    Expr::Lit(Lit::Str(str)) => StyleSheetValue::String(str.value.to_string()),
+    Expr::Lit(Lit::Num(num)) => StyleSheetValue::Number(num.value),
+    Expr::Lit(Lit::Bool(bool)) => StyleSheetValue::Bool(bool.value),
+    Expr::Lit(Lit::Null(_)) => StyleSheetValue::Null,
+    // -10
+    Expr::Unary(UnaryExpr { op: UnaryOp::Minus, arg, .. }) => match &**arg {
+      Expr::Lit(Lit::Num(num)) => StyleSheetValue::Number(-num.value),
+      _ => StyleSheetValue::Expr { expr: emit_expr(expr) }
+    },
+    Expr::Paren(ParenExpr { expr, .. }) => expr_to_value(expr),
+    Expr::Array(ArrayLit { elems, .. }) => StyleSheetValue::Array(elems.iter().map(|elem| {
+      match elem {
+        Some(ExprOrSpread { expr, spread: None }) => expr_to_value(expr),
+        _ => StyleSheetValue::Null
+      }
+    }).collect()),
+    Expr::Object(ObjectLit { props, .. }) => {
+      let entries = props.iter().map(|prop| {
+        match prop {
+          PropOrSpread::Prop(prop) => match &**prop {
+            Prop::KeyValue(KeyValueProp { key, value }) => get_prop_name(key).map(|key| (key, expr_to_value(value))),
+            _ => None
+          },
+          _ => None
+        }
+      }).collect::<Option<Vec<(String, StyleSheetValue)>>>();
+      match entries {
+        Some(entries) => StyleSheetValue::Object(entries),
+        // 含展开运算符等无法静态表示的对象
+        None => StyleSheetValue::Expr { expr: emit_expr(expr) }
+      }
+    },
+    _ => StyleSheetValue::Expr { expr: emit_expr(expr) }
+  }
+}
+
+// 表达式转代码字符串
+pub fn emit_expr(expr: &Expr) -> String {
+  let cm: Lrc<SourceMap> = Default::default();
+  let module = Module {
+    span: DUMMY_SP,
+    body: vec![ModuleItem::Stmt(Stmt::Expr(ExprStmt {
+      span: DUMMY_SP,
+      expr: Box::new(Expr::Paren(ParenExpr {
+        span: DUMMY_SP,
+        expr: Box::new(expr.clone()),
+      })),
+    }))],
+    shebang: None,
+  };
+  let mut buf = Vec::new();
+  {
+    let writer = Box::new(JsWriter::new(cm.clone(), "\n", &mut buf, None));
+    let mut emitter = Emitter {
+      cfg: swc_core::ecma::codegen::Config::default().with_minify(true),
+      cm: cm.clone(),
+      wr: writer,
+      comments: None,
+    };
+    emitter.emit_module(&module).unwrap();
+  }
+  let code = String::from_utf8(buf).unwrap();
+  // 去掉包裹的括号及结尾的分号
+  let code = code.trim().trim_end_matches(';');
+  code.strip_prefix('(').and_then(|code| code.strip_suffix(')')).unwrap_or(code).to_string()
+}
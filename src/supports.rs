@@ -0,0 +1,70 @@
+use lightningcss::{properties::{Property, PropertyId}, rules::supports::SupportsCondition, stylesheet::{ParserOptions, PrinterOptions}, traits::ToCss};
+
+use crate::{diagnostics::without_diagnostics, interner::intern_property_name, options::with_transform_options, parse_style_properties::parse_style_properties, style_propetries::unit::Platform, visitor::parse_raw_style_values};
+
+// 各平台内置的特性支持表，key 为 `属性` 或 `属性: 值`
+const HARMONY_SUPPORTS_FEATURES: [(&str, bool); 10] = [
+  ("display: flex", true),
+  ("display: grid", false),
+  ("position: sticky", false),
+  ("gap", true),
+  ("aspect-ratio", true),
+  ("backdrop-filter", true),
+  ("-webkit-backdrop-filter", true),
+  ("filter", true),
+  ("mask-image", true),
+  ("-webkit-line-clamp", true),
+];
+
+const RN_SUPPORTS_FEATURES: [(&str, bool); 10] = [
+  ("display: flex", true),
+  ("display: grid", false),
+  ("position: sticky", false),
+  ("gap", true),
+  ("aspect-ratio", true),
+  ("backdrop-filter", false),
+  ("-webkit-backdrop-filter", false),
+  ("filter", false),
+  ("mask-image", false),
+  ("-webkit-line-clamp", true),
+];
+
+// 查询特性支持情况：配置的特性表优先于内置特性表，各表中先查 `属性: 值`，再查 `属性`，
+// 均未命中时按声明能否转换为目标平台的样式判断
+fn is_feature_supported(property_id: &PropertyId, value: &str, platform: &Platform) -> bool {
+  let property = property_id.to_css_string(PrinterOptions::default()).unwrap();
+  let keys = [format!("{}: {}", property, value), property];
+  let builtin_features = match platform {
+    Platform::Harmony => HARMONY_SUPPORTS_FEATURES,
+    Platform::ReactNative => RN_SUPPORTS_FEATURES,
+  };
+  with_transform_options(|options| keys.iter().find_map(|key| options.supports_features.get(key).copied()))
+    .or_else(|| keys.iter().find_map(|key| builtin_features.iter().find(|(feature, _)| feature == key).map(|(_, supported)| *supported)))
+    .unwrap_or_else(|| is_declaration_convertible(property_id, value, platform))
+}
+
+// 声明能否解析并转换为目标平台的样式，如 color: red 支持，color: foo 不支持
+fn is_declaration_convertible(property_id: &PropertyId, value: &str, platform: &Platform) -> bool {
+  let Ok(property) = Property::parse_string(property_id.clone(), value, ParserOptions::default()) else {
+    return false
+  };
+  if matches!(property, Property::Unparsed(_) | Property::Custom(_)) {
+    return false
+  }
+  without_diagnostics(|| {
+    let style_values = parse_style_properties(&vec![(intern_property_name(&property), property)], None);
+    !style_values.is_empty() && !parse_raw_style_values(style_values, platform.clone()).is_empty()
+  })
+}
+
+// 编译期计算 @supports 条件，决定是否保留其中的规则
+pub fn evaluate_supports_condition(condition: &SupportsCondition, platform: &Platform) -> bool {
+  match condition {
+    SupportsCondition::Not(condition) => !evaluate_supports_condition(condition, platform),
+    SupportsCondition::And(conditions) => conditions.iter().all(|condition| evaluate_supports_condition(condition, platform)),
+    SupportsCondition::Or(conditions) => conditions.iter().any(|condition| evaluate_supports_condition(condition, platform)),
+    SupportsCondition::Declaration { property_id, value } => is_feature_supported(property_id, value.trim(), platform),
+    // selector()、font-tech() 等条件无法在编译期判断
+    SupportsCondition::Selector(_) | SupportsCondition::Unknown(_) => false,
+  }
+}
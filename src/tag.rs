@@ -0,0 +1,65 @@
+use crate::{options::with_transform_options, utils::split_top_level};
+
+// 组件名对应的标签名，如 View => view、ScrollView => scroll-view，与 Taro 小程序端的标签名一致
+// 成员表达式的组件以 - 连接，如 Card.Header => card-header
+fn get_component_tag(name: &str) -> String {
+  let mut tag = String::new();
+  for c in name.chars() {
+    if c == '.' {
+      tag.push('-');
+      continue
+    }
+    if c.is_ascii_uppercase() && !tag.is_empty() && !tag.ends_with('-') {
+      tag.push('-');
+    }
+    tag.push(c.to_ascii_lowercase());
+  }
+  tag
+}
+
+// 节点的标签名，在 tagMap 中配置了映射的组件取映射后的标签名，如 { View: 'div' } 时 View => div
+pub fn get_element_tag(name: &str) -> String {
+  with_transform_options(|options| match options.tag_map.get(name) {
+    Some(tag) => tag.to_ascii_lowercase(),
+    None => get_component_tag(name)
+  })
+}
+
+// 选择器中的标签名统一为映射后的标签名，如 { View: 'div' } 时 view、div => div，保证两种写法匹配相同的节点
+pub fn normalize_tag(tag: &str) -> String {
+  let tag = tag.to_ascii_lowercase();
+  with_transform_options(|options| {
+    options.tag_map
+      .iter()
+      .filter(|(name, _)| get_component_tag(name) == tag)
+      .map(|(_, mapped_tag)| mapped_tag.to_ascii_lowercase())
+      .min()
+      .unwrap_or(tag)
+  })
+}
+
+// 复合选择器拆分为标签名及其余部分，如 view.item => (Some(view), .item)
+pub fn split_tag(compound: &str) -> (Option<&str>, &str) {
+  if !compound.starts_with(|c: char| c.is_ascii_alphabetic()) {
+    return (None, compound)
+  }
+  let index = compound.find(|c: char| !c.is_ascii_alphanumeric() && c != '-').unwrap_or(compound.len());
+  (Some(&compound[..index]), &compound[index..])
+}
+
+// 选择器中是否含有标签选择器，如 view .item、.list > text
+pub fn has_tag_selector(selector: &str) -> bool {
+  split_top_level(selector, ' ').iter().any(|compound| split_tag(compound).0.is_some())
+}
+
+// 选择器中的标签名统一为映射后的标签名，如 { View: 'div' } 时 view > .item => div > .item
+pub fn normalize_tag_selector(selector: &str) -> String {
+  split_top_level(selector, ' ')
+    .iter()
+    .map(|compound| match split_tag(compound) {
+      (Some(tag), rest) => format!("{}{}", normalize_tag(tag), rest),
+      (None, _) => compound.to_string()
+    })
+    .collect::<Vec<String>>()
+    .join(" ")
+}
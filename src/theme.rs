@@ -0,0 +1,84 @@
+use std::cell::RefCell;
+
+use swc_core::{common::DUMMY_SP, ecma::ast::*};
+
+use crate::{constants::THEME, options::with_transform_options, style_propetries::unit::{generate_expr_with_css_input, Platform}, utils::to_camel_case};
+
+thread_local! {
+  // :root 中声明的 css 变量，(变量名, 变量值)，如 ("--primary-color", "#f00")
+  static THEME_VARIABLES: RefCell<Vec<(String, String)>> = const { RefCell::new(Vec::new()) };
+}
+
+// 变量名转换为 theme 对象的 key，如 --primary-color => primaryColor
+fn get_theme_key(name: &str) -> String {
+  to_camel_case(name.trim_start_matches("--"), false)
+}
+
+// 记录 :root 中的 css 变量，后声明的覆盖先声明的
+pub fn push_theme_variable(name: String, value: String) {
+  THEME_VARIABLES.with(|cell| {
+    let mut variables = cell.borrow_mut();
+    match variables.iter_mut().find(|(key, _)| key == &name) {
+      Some(variable) => variable.1 = value,
+      None => variables.push((name, value))
+    }
+  });
+}
+
+pub fn clear_theme_variables() {
+  THEME_VARIABLES.with(|cell| cell.borrow_mut().clear());
+}
+
+// 开启主题提取且变量在 :root 中声明时，var(--xxx) 编译为 theme.xxx
+pub fn get_theme_member_expr(name: &str) -> Option<Expr> {
+  if !with_transform_options(|options| options.extract_theme) {
+    return None
+  }
+  THEME_VARIABLES.with(|cell| {
+    cell.borrow().iter().find(|(key, _)| key == name).map(|(key, _)| {
+      Expr::Member(MemberExpr {
+        span: DUMMY_SP,
+        obj: Box::new(Expr::Ident(Ident::new(THEME.into(), DUMMY_SP))),
+        prop: MemberProp::Ident(Ident::new(get_theme_key(key).into(), DUMMY_SP)),
+      })
+    })
+  })
+}
+
+// 生成 export const theme = { ... }，运行时可直接修改 theme 的属性实现主题切换
+pub fn generate_theme_decl(platform: Platform) -> Option<ModuleItem> {
+  if !with_transform_options(|options| options.extract_theme) {
+    return None
+  }
+  let props = THEME_VARIABLES.with(|cell| {
+    cell.borrow().iter().map(|(key, value)| {
+      PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+        key: PropName::Ident(Ident::new(get_theme_key(key).into(), DUMMY_SP)),
+        value: Box::new(generate_expr_with_css_input(value.trim().to_string(), platform.clone())),
+      })))
+    }).collect::<Vec<PropOrSpread>>()
+  });
+  if props.is_empty() {
+    return None
+  }
+  Some(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+    span: DUMMY_SP,
+    decl: Decl::Var(Box::new(VarDecl {
+      span: DUMMY_SP,
+      kind: VarDeclKind::Const,
+      declare: false,
+      decls: vec![VarDeclarator {
+        span: DUMMY_SP,
+        name: Pat::Ident(BindingIdent {
+          id: Ident::new(THEME.into(), DUMMY_SP),
+          type_ann: None,
+        }),
+        init: Some(Box::new(Expr::Object(ObjectLit {
+          span: DUMMY_SP,
+          props,
+        }))),
+        definite: false,
+      }],
+    })),
+  })))
+}
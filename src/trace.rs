@@ -0,0 +1,23 @@
+use std::sync::Once;
+
+use tracing_subscriber::{fmt::format::FmtSpan, EnvFilter};
+
+// 日志过滤条件的环境变量，语法同 RUST_LOG，如 PARSE_CSS_TO_STYLESHEET_LOG=debug、PARSE_CSS_TO_STYLESHEET_LOG=parse_css_to_stylesheet::style_parser=trace
+pub const TRACE_ENV: &str = "PARSE_CSS_TO_STYLESHEET_LOG";
+
+static INIT: Once = Once::new();
+
+// 设置了环境变量时初始化日志输出到 stderr，span 结束时输出耗时
+// 调用方已设置全局 subscriber 时（如 rust 调用方自行接入 tracing）保持不变
+pub fn init_tracing() {
+  INIT.call_once(|| {
+    let Ok(filter) = EnvFilter::try_from_env(TRACE_ENV) else {
+      return
+    };
+    let _ = tracing_subscriber::fmt()
+      .with_env_filter(filter)
+      .with_span_events(FmtSpan::CLOSE)
+      .with_writer(std::io::stderr)
+      .try_init();
+  });
+}
@@ -0,0 +1,425 @@
+use std::{cell::RefCell, rc::Rc, time::Instant};
+
+use indexmap::IndexMap;
+use tracing::{debug, debug_span, info_span};
+
+use swc_core::{
+  common::{comments::{Comment, CommentKind, SingleThreadedComments}, sync::Lrc, util::take::Take, SourceMap},
+  ecma::{ast::{Module, Program}, codegen::{text_writer::JsWriter, Config, Emitter}, visit::VisitMutWith}
+};
+
+use crate::{
+  arkui::generate_arkui_styles,
+  declaration::generate_style_declaration,
+  diagnostics::{push_diagnostic, take_diagnostics, Diagnostic},
+  document::{JSXDocument, SourceSyntax},
+  interner::{take_interner_stats, InternerStats},
+  ir::StyleIr,
+  scope::{generate_scope_id_with_hash, take_scoped_classes},
+  stats::{record_phase, take_transform_stats, time_phase, TransformStats},
+  options::{set_transform_options, with_transform_options, CommentsMode, QuoteStyle, TransformOptions},
+  report::generate_match_report,
+  style_parser::{parse_styles, ClassUsage, FontFaceItem, ParsedStyles, StyleParser},
+  style_propetries::unit::Platform,
+  style_write::StyleWrite,
+  trace::init_tracing,
+  stylesheet::StyleSheet,
+  visitor::{GeneratedClassNameVisitor, ImportantInlineStyleVisitor, QuoteStyleVisitor},
+};
+
+// 一次转换的结果
+pub struct TransformResult {
+  // 转换后的组件代码，输入为 AST 时为空
+  pub code: String,
+  // 转换后的 AST，输入为 AST 时输出
+  pub program: Option<Program>,
+  // @font-face 声明的字体清单
+  pub font_faces: Vec<FontFaceItem>,
+  pub diagnostics: Vec<Diagnostic>,
+  // 转换后的样式表 JSON，开启 stylesheet 时输出
+  pub stylesheet: Option<String>,
+  // ArkUI @Styles 代码，开启 arkui 时输出
+  pub arkui: Option<String>,
+  // 样式对象的 TypeScript 类型声明，开启 dts 时输出
+  pub dts: Option<String>,
+  // 节点与样式规则的匹配报告 JSON，开启 match_report 时输出
+  pub match_report: Option<String>,
+  // 计算后的样式中间表示，开启 ir 时输出
+  pub ir: Option<StyleIr>,
+  // 转换后代码的 source map JSON，开启 source_map 时输出
+  pub map: Option<String>,
+  // 静态 className 的类名与样式的对应情况
+  pub class_usage: ClassUsage,
+  // 字符串驻留的统计信息
+  pub interner_stats: InternerStats,
+  // 规则数量、节点数量、各阶段耗时等性能统计
+  pub stats: TransformStats,
+  // 作用域模式下的类名映射，原始类名 => 添加作用域前缀后的类名
+  pub scoped_classes: IndexMap<String, String>,
+}
+
+// 组件样式转换入口，串联 JSX 解析、样式解析计算、样式写入与代码生成
+// Transformer::new(component, styles).platform(Platform::Harmony).options(options).transform()
+pub struct Transformer {
+  component: String,
+  // 上游 swc 插件传入的 AST，存在时不再解析 component
+  program: Option<Program>,
+  styles: Vec<String>,
+  platform: Platform,
+  options: TransformOptions,
+  is_entry: bool,
+  syntax: SourceSyntax,
+  stylesheet: bool,
+  arkui: bool,
+  dts: bool,
+  match_report: bool,
+  ir: bool,
+  filename: Option<String>,
+  source_map: bool,
+}
+
+impl Transformer {
+  pub fn new(component: String, styles: Vec<String>) -> Self {
+    Transformer {
+      component,
+      program: None,
+      styles,
+      platform: Platform::Harmony,
+      options: TransformOptions::default(),
+      is_entry: false,
+      syntax: SourceSyntax::Tsx,
+      stylesheet: false,
+      arkui: false,
+      dts: false,
+      match_report: false,
+      ir: false,
+      filename: None,
+      source_map: false,
+    }
+  }
+
+  // 转换已解析的 AST，用于 swc 插件，转换结果通过 TransformResult.program 输出
+  pub fn from_program(program: Program, styles: Vec<String>) -> Self {
+    Transformer {
+      program: Some(program),
+      ..Transformer::new(String::new(), styles)
+    }
+  }
+
+  pub fn platform(mut self, platform: Platform) -> Self {
+    self.platform = platform;
+    self
+  }
+
+  pub fn options(mut self, options: TransformOptions) -> Self {
+    self.options = options;
+    self
+  }
+
+  // 是否是入口文件
+  pub fn entry(mut self, is_entry: bool) -> Self {
+    self.is_entry = is_entry;
+    self
+  }
+
+  pub fn syntax(mut self, syntax: SourceSyntax) -> Self {
+    self.syntax = syntax;
+    self
+  }
+
+  // 是否额外输出转换后的样式表 JSON
+  pub fn stylesheet(mut self, stylesheet: bool) -> Self {
+    self.stylesheet = stylesheet;
+    self
+  }
+
+  // 是否额外输出 ArkUI @Styles 代码，仅 Harmony 平台可用
+  pub fn arkui(mut self, arkui: bool) -> Self {
+    self.arkui = arkui;
+    self
+  }
+
+  // 是否额外输出样式对象的 TypeScript 类型声明（.d.ts）
+  pub fn dts(mut self, dts: bool) -> Self {
+    self.dts = dts;
+    self
+  }
+
+  // 是否额外输出节点与样式规则的匹配报告 JSON
+  pub fn match_report(mut self, match_report: bool) -> Self {
+    self.match_report = match_report;
+    self
+  }
+
+  // 是否额外输出计算后的样式中间表示，含各规则、各节点的样式及动画、暗色模式等变体
+  pub fn ir(mut self, ir: bool) -> Self {
+    self.ir = ir;
+    self
+  }
+
+  // 组件的文件名，作为 source map、匹配报告及样式中间表示中的文件名
+  pub fn filename(mut self, filename: String) -> Self {
+    self.filename = Some(filename);
+    self
+  }
+
+  // 是否额外输出转换后代码的 source map
+  pub fn source_map(mut self, source_map: bool) -> Self {
+    self.source_map = source_map;
+    self
+  }
+
+  pub fn transform(self) -> TransformResult {
+    let platform = self.platform.clone();
+    self.transform_platforms(vec![platform]).pop().unwrap().1
+  }
+
+  // 组件代码及样式只解析一次，选择器的匹配结果缓存在 JSXDocument 中，按各平台分别转换样式、写入样式并生成代码，
+  // 用于同时构建鸿蒙与 React Native 的应用，结果按 platforms 的顺序排列；解析阶段的诊断信息、作用域类名及耗时计入每个平台的结果
+  pub fn transform_platforms(mut self, platforms: Vec<Platform>) -> Vec<(Platform, TransformResult)> {
+    init_tracing();
+    let _span = info_span!("transform", file = self.filename.as_deref().unwrap_or("<anonymous>"), platforms = ?platforms).entered();
+    let mut options = self.options.clone();
+    if options.scoped && options.scope_id.is_none() {
+      options.scope_id = Some(generate_scope_id_with_hash(&self.component, &options.scope_hash_algorithm, options.scope_hash_length));
+    }
+    options.platform = platforms.first().cloned().unwrap_or(self.platform.clone());
+    set_transform_options(options.clone());
+    take_diagnostics();
+    take_scoped_classes();
+    take_interner_stats();
+    take_transform_stats();
+
+    // 解析组件文件
+    let cm: Lrc<SourceMap> = Default::default();
+    let comments = SingleThreadedComments::default();
+    let mut document = JSXDocument::with_syntax(self.syntax.clone());
+    document.filename = self.filename.clone();
+    let is_program_input = self.program.is_some();
+    let program = self.program.take();
+    let component = std::mem::take(&mut self.component);
+    time_phase("parse_component", || debug_span!("parse_component").in_scope(|| match program {
+      Some(program) => document.parse_program(program, &comments),
+      None => document.parse(component, cm.clone(), &comments),
+    }));
+    debug!(elements = document.jsx_record.as_ref().map_or(0, |jsx_record| jsx_record.len()), "component parsed");
+
+    // 解析样式文件
+    let css = self.styles.join("\n");
+    // 各样式文件在拼接后的样式中的起始行
+    let style_starts = self.styles.iter().scan(0, |start, style| {
+      let current = *start;
+      *start += style.matches('\n').count() as u32 + 1;
+      Some(current)
+    }).collect::<Vec<u32>>();
+    let styles = time_phase("parse_style", || debug_span!("parse_style", styles = self.styles.len()).in_scope(|| parse_styles(&css, style_starts)));
+    let parse_diagnostics = take_diagnostics();
+    let parse_scoped_classes = take_scoped_classes();
+    let parse_phases = take_transform_stats().phases;
+
+    platforms.into_iter().map(|platform| {
+      let _platform_span = debug_span!("platform", platform = ?platform).entered();
+      set_transform_options(TransformOptions { platform: platform.clone(), ..options.clone() });
+      let mut result = self.transform_document(&document, &styles, &cm, &comments, platform.clone(), is_program_input);
+      result.diagnostics = parse_diagnostics.iter().cloned().chain(result.diagnostics).collect();
+      let mut scoped_classes = parse_scoped_classes.clone();
+      scoped_classes.extend(result.scoped_classes);
+      result.scoped_classes = scoped_classes;
+      result.stats.phases.splice(0..0, parse_phases.iter().cloned());
+      (platform, result)
+    }).collect()
+  }
+
+  // 按平台转换样式、写入样式并生成代码
+  fn transform_document<'i>(&self, document: &'i JSXDocument, styles: &ParsedStyles<'i>, cm: &Lrc<SourceMap>, comments: &SingleThreadedComments, platform: Platform, is_program_input: bool) -> TransformResult {
+    let is_entry = self.is_entry;
+    let mut is_enable_nesting = true;
+
+    let mut style_parser = StyleParser::new(document, platform.clone(), is_entry);
+    let style_data = time_phase("calc_style", || debug_span!("calc_style").in_scope(|| {
+      style_parser.parse(styles);
+      style_parser.calc()
+    }));
+    debug!(rules = style_data.all_style.borrow().len(), "style calculated");
+
+    // 判断计算的结果是否会含有嵌套选择器
+    if is_enable_nesting {
+      is_enable_nesting = style_data.has_nesting;
+    }
+
+    let stylesheet = match self.stylesheet {
+      true => Some(StyleSheet::new(&style_data.all_style.borrow(), &style_data.dark_style.borrow(), &style_data.container_style.borrow(), platform.clone()).to_json()),
+      false => None
+    };
+
+    let arkui = match (self.arkui, &platform) {
+      (true, Platform::Harmony) => Some(generate_arkui_styles(&style_data.all_style.borrow())),
+      (true, _) => {
+        push_diagnostic("arkui", "ArkUI code generation is only available on Harmony".to_string());
+        None
+      },
+      _ => None
+    };
+
+    let dts = match self.dts {
+      true => Some(generate_style_declaration(&style_data.all_style.borrow(), platform.clone())),
+      false => None
+    };
+
+    let match_report = match self.match_report {
+      true => Some(generate_match_report(
+        document,
+        &style_data.generated_classes.borrow(),
+        &style_data.all_style.borrow(),
+        cm,
+        self.filename.clone(),
+        platform.clone(),
+      )),
+      false => None
+    };
+
+    let ir = match self.ir {
+      true => Some(StyleIr::new(
+        document,
+        &style_data,
+        &style_parser.keyframes.borrow(),
+        cm,
+        self.filename.clone(),
+        platform.clone(),
+      )),
+      false => None
+    };
+
+    let write_span = debug_span!("write_style").entered();
+    let write_start = Instant::now();
+    let program = Rc::new(RefCell::new(document.program.as_ref().unwrap().clone()));
+    let jsx_record = Rc::new(RefCell::new(document.jsx_record.as_ref().unwrap().clone()));
+    // 节点添加编译期生成的类名
+    program.borrow_mut().visit_mut_with(&mut GeneratedClassNameVisitor::new(&style_data.generated_classes.borrow()));
+    // 类样式中的 !important 声明覆盖内联样式
+    program.borrow_mut().visit_mut_with(&mut ImportantInlineStyleVisitor::new(&style_data.important_record));
+    let mut style_write = StyleWrite::new(
+      program.clone(),
+      jsx_record.clone(),
+      &style_data,
+      is_enable_nesting,
+      is_entry,
+    );
+    style_write.write(platform, document.taro_components.clone());
+    record_phase("write_style", write_start.elapsed());
+    drop(write_span);
+    let nodes_visited = jsx_record.borrow().len();
+
+    // 输入为 AST 时直接返回 AST，其 span 指向上游的 SourceMap，无法在此生成代码
+    let program = program.replace(Program::Module(Module::dummy()));
+    if is_program_input {
+      let interner_stats = take_interner_stats();
+      let stats = collect_transform_stats(nodes_visited, &interner_stats);
+      return TransformResult {
+        code: String::new(),
+        program: Some(program),
+        font_faces: style_data.font_faces,
+        diagnostics: take_diagnostics(),
+        stylesheet,
+        arkui,
+        dts,
+        match_report,
+        ir,
+        map: None,
+        class_usage: style_data.class_usage,
+        interner_stats,
+        stats,
+        scoped_classes: take_scoped_classes(),
+      }
+    }
+
+    // ast 转代码
+    let _codegen_span = debug_span!("codegen").entered();
+    let codegen_start = Instant::now();
+    let (codegen_config, codegen_comments, quote_style) = with_transform_options(|options| {
+      let mut config = Config::default();
+      config.target = options.target;
+      config.ascii_only = options.ascii_only;
+      (config, options.comments.clone(), options.quote_style.clone())
+    });
+    if codegen_comments == CommentsMode::License {
+      retain_license_comments(comments);
+    }
+    let mut program = program;
+    if let Some(quote_style) = quote_style {
+      program.visit_mut_with(&mut QuoteStyleVisitor {
+        quote: match quote_style {
+          QuoteStyle::Double => '"',
+          QuoteStyle::Single => '\''
+        }
+      });
+    }
+    let mut buf = Vec::new();
+    let mut mappings = Vec::new();
+    {
+      let writer = Box::new(JsWriter::new(cm.clone(), "\n", &mut buf, match self.source_map {
+        true => Some(&mut mappings),
+        false => None
+      }));
+      let mut emitter = Emitter {
+        cfg: codegen_config,
+        cm: cm.clone(),
+        wr: writer,
+        comments: match codegen_comments {
+          CommentsMode::None => None,
+          _ => Some(comments)
+        },
+      };
+      emitter.emit_program(&program).unwrap();
+    }
+    let code = String::from_utf8(buf).unwrap().replace("\r\n", "\n");
+    let map = match self.source_map {
+      true => {
+        let mut map = Vec::new();
+        cm.build_source_map(&mappings).to_writer(&mut map).unwrap();
+        Some(String::from_utf8(map).unwrap())
+      },
+      false => None
+    };
+
+    record_phase("codegen", codegen_start.elapsed());
+
+    let interner_stats = take_interner_stats();
+    let stats = collect_transform_stats(nodes_visited, &interner_stats);
+    TransformResult {
+      code,
+      program: None,
+      font_faces: style_data.font_faces,
+      diagnostics: take_diagnostics(),
+      stylesheet,
+      arkui,
+      dts,
+      match_report,
+      ir,
+      map,
+      class_usage: style_data.class_usage,
+      interner_stats,
+      stats,
+      scoped_classes: take_scoped_classes(),
+    }
+  }
+}
+
+// 仅保留版权注释，如 /*! ... */ 及包含 @license、@preserve 的块注释
+fn retain_license_comments(comments: &SingleThreadedComments) {
+  let is_license = |comment: &Comment| {
+    comment.kind == CommentKind::Block && (comment.text.starts_with('!') || comment.text.contains("@license") || comment.text.contains("@preserve"))
+  };
+  let (mut leading, mut trailing) = comments.borrow_all_mut();
+  leading.values_mut().chain(trailing.values_mut()).for_each(|comments| comments.retain(is_license));
+}
+
+// 汇总本次转换的统计信息
+fn collect_transform_stats(nodes_visited: usize, interner_stats: &InternerStats) -> TransformStats {
+  TransformStats {
+    nodes_visited,
+    interner_lookups: interner_stats.lookups,
+    interner_hits: interner_stats.hits,
+    ..take_transform_stats()
+  }
+}
@@ -71,6 +71,18 @@ pub fn prefix_style_key(s: String, platform: Platform) -> String {
   }
 }
 
+// 统一样式属性名为驼峰命名，各属性转换时既有取自 property_id 的中划线命名，也有写死的驼峰命名
+// margin-top => marginTop，-webkit-line-clamp => WebkitLineClamp，-ms-transform => msTransform，--color 自定义属性保持不变
+pub fn normalize_style_key(key: &str) -> String {
+  if key.starts_with("--") || !key.contains('-') {
+    return key.to_string()
+  }
+  match key.strip_prefix("-ms-") {
+    Some(key) => format!("ms{}", to_camel_case(key, true)),
+    None => to_camel_case(key, false)
+  }
+}
+
 struct ObjectVisitor {
   pub attributes: HashMap<String, Box<Expr>>,
 }
@@ -232,3 +244,169 @@ fn split_classes(input: &str) -> TSelector {
 pub fn is_tailwind_arbitrary(input: &str) -> bool {
   input.contains('[') && input.contains(']')
 }
+
+// 是否是仅由类名组成的选择器，如 .a、.a.b
+pub fn is_class_selector(input: &str) -> bool {
+  input.starts_with('.') && !input.contains(|c: char| c.is_whitespace() || ">+~:[]#*".contains(c))
+}
+
+// 是否是复合类选择器，如 .a.b
+pub fn is_compound_class_selector(input: &str) -> bool {
+  is_class_selector(input) && input.matches('.').count() > 1
+}
+
+// 选择器中出现的所有类名，如 .a .b.c:hover => [a, b, c]，含转义字符（如 tailwind 任意值）时无法准确还原类名，返回 None
+pub fn get_selector_classes(selector: &str) -> Option<Vec<String>> {
+  if selector.contains('\\') || is_tailwind_arbitrary(selector) {
+    return None
+  }
+  Some(
+    selector
+      .split('.')
+      .skip(1)
+      .map(|part| part.chars().take_while(|c| c.is_alphanumeric() || *c == '-' || *c == '_').collect::<String>())
+      .filter(|class| !class.is_empty())
+      .collect()
+  )
+}
+
+// 复合类选择器中的类名按字母排序，.b.a 与 .a.b 视为同一个选择器，如 .container .btn.primary => .container .btn.primary
+pub fn normalize_compound_selector(selector: &str) -> String {
+  selector
+    .split(' ')
+    .map(|part| {
+      if is_compound_class_selector(part) {
+        let mut classes = part.split('.').filter(|class| !class.is_empty()).collect::<Vec<&str>>();
+        classes.sort_unstable();
+        format!(".{}", classes.join("."))
+      } else {
+        part.to_string()
+      }
+    })
+    .collect::<Vec<String>>()
+    .join(" ")
+}
+
+// 按顶层的分隔符切分选择器，忽略括号内的分隔符，如 .a:is(.b, .c) .d => [".a:is(.b, .c)", ".d"]
+pub fn split_top_level(input: &str, separator: char) -> Vec<String> {
+  let mut parts = vec![];
+  let mut current = String::new();
+  let mut depth = 0;
+  for c in input.chars() {
+    match c {
+      '(' | '[' => depth += 1,
+      ')' | ']' => depth -= 1,
+      _ if c == separator && depth == 0 => {
+        parts.push(std::mem::take(&mut current));
+        continue
+      }
+      _ => {}
+    }
+    current.push(c);
+  }
+  parts.push(current);
+  parts.into_iter().map(|part| part.trim().to_string()).filter(|part| !part.is_empty()).collect()
+}
+
+// 查找函数式伪类，返回 (伪类起始位置, 参数, 伪类结束位置)，如 .a:is(.b, .c) => (2, ".b, .c", 13)
+fn find_functional_pseudo(selector: &str, name: &str) -> Option<(usize, String, usize)> {
+  let start = selector.find(&format!(":{}(", name))?;
+  let args_start = start + name.len() + 2;
+  let mut depth = 1;
+  for (index, c) in selector[args_start..].char_indices() {
+    match c {
+      '(' => depth += 1,
+      ')' => depth -= 1,
+      _ => {}
+    }
+    if depth == 0 {
+      let args_end = args_start + index;
+      return Some((start, selector[args_start..args_end].to_string(), args_end + 1))
+    }
+  }
+  None
+}
+
+// 展开 :is()、:where()，参数仅支持类选择器，如 .a:is(.b, .c) => [".a.b", ".a.c"]
+// 展开后的选择器沿用原选择器的优先级：:is() 取参数中最高的优先级，:where() 不计入优先级，均与展开前一致
+// 参数中含有其他选择器时无法展开，返回 None
+pub fn expand_functional_pseudo(selector: &str) -> Option<Vec<String>> {
+  let found = ["is", "where"]
+    .iter()
+    .filter_map(|name| find_functional_pseudo(selector, name))
+    .min_by_key(|(start, _, _)| *start);
+  let Some((start, args, end)) = found else {
+    return Some(vec![selector.to_string()])
+  };
+  let args = split_top_level(&args, ',');
+  if args.is_empty() || !args.iter().all(|arg| is_class_selector(arg)) {
+    return None
+  }
+  let mut result = vec![];
+  for arg in args {
+    let expanded = expand_functional_pseudo(&format!("{}{}{}", &selector[..start], arg, &selector[end..]))?;
+    for selector in expanded {
+      if !result.contains(&selector) {
+        result.push(selector);
+      }
+    }
+  }
+  Some(result)
+}
+
+// 拆分含 :not() 的复合选择器，返回 (去除 :not() 后的选择器, 被排除的类选择器)，如 .a:not(.b, .c) => (".a", [".b", ".c"])
+// :not() 的参数仅支持类选择器，含有其他选择器时返回 None
+pub fn split_negation(compound: &str) -> Option<(String, Vec<String>)> {
+  let mut base = compound.to_string();
+  let mut negations = vec![];
+  while let Some((start, args, end)) = find_functional_pseudo(&base, "not") {
+    let args = split_top_level(&args, ',');
+    if args.is_empty() || !args.iter().all(|arg| is_class_selector(arg)) {
+      return None
+    }
+    negations.extend(args);
+    base = format!("{}{}", &base[..start], &base[end..]);
+  }
+  Some((base, negations))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{expand_functional_pseudo, normalize_style_key, split_negation};
+
+  #[test]
+  fn normalize_kebab_case_key() {
+    assert_eq!(normalize_style_key("margin-top"), "marginTop");
+    assert_eq!(normalize_style_key("border-top-left-radius"), "borderTopLeftRadius");
+  }
+
+  #[test]
+  fn keep_camel_case_key() {
+    assert_eq!(normalize_style_key("marginTop"), "marginTop");
+    assert_eq!(normalize_style_key("color"), "color");
+  }
+
+  #[test]
+  fn normalize_vendor_prefixed_key() {
+    assert_eq!(normalize_style_key("-webkit-line-clamp"), "WebkitLineClamp");
+    assert_eq!(normalize_style_key("-ms-transform"), "msTransform");
+  }
+
+  #[test]
+  fn keep_custom_property_key() {
+    assert_eq!(normalize_style_key("--main-color"), "--main-color");
+  }
+
+  #[test]
+  fn expand_is_and_where() {
+    assert_eq!(expand_functional_pseudo(".a:is(.b, .c)"), Some(vec![".a.b".to_string(), ".a.c".to_string()]));
+    assert_eq!(expand_functional_pseudo(":where(.a) > .b"), Some(vec![".a > .b".to_string()]));
+    assert_eq!(expand_functional_pseudo(".a:is(div)"), None);
+  }
+
+  #[test]
+  fn split_not() {
+    assert_eq!(split_negation(".a:not(.b, .c.d)"), Some((".a".to_string(), vec![".b".to_string(), ".c.d".to_string()])));
+    assert_eq!(split_negation(".a:not(:first-child)"), None);
+  }
+}
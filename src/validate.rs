@@ -0,0 +1,208 @@
+use std::convert::Infallible;
+
+use lightningcss::{
+  declaration::DeclarationBlock,
+  properties::{animation::AnimationIterationCount, border::BorderSideWidth, custom::TokenOrValue, font, size::{MaxSize, Size}, align::GapValue, Property, PropertyId},
+  rules::{CssRule, Location},
+  stylesheet::{PrinterOptions, StyleSheet},
+  traits::{Sign, ToCss, Zero},
+  values::{alpha::AlphaValue, length::{Length, LengthPercentage, LengthPercentageOrAuto, LengthValue}, percentage::{DimensionPercentage, Percentage}},
+  visit_types,
+  visitor::{Visit, VisitTypes, Visitor},
+};
+
+use crate::{diagnostics::{push_diagnostic, push_error}, keyframes::get_keyframes_name, location::DeclarationLocator, options::with_transform_options, style_parser::format_source_position, utils::normalize_style_key};
+
+// 检查属性值：无法解析的值（如 width: foo）在转换时丢弃，输出带声明位置的诊断信息，严格模式下为错误；
+// 超出取值范围的值，如负数的宽度、大于 1 的透明度、负数的动画次数，截断到合法范围并输出带声明位置的诊断信息
+pub fn validate_stylesheet(stylesheet: &mut StyleSheet, css: &str, style_starts: &[u32]) {
+  let mut visitor = ValidateVisitor { css, style_starts };
+  stylesheet.visit(&mut visitor).unwrap();
+}
+
+struct ValidateVisitor<'a> {
+  css: &'a str,
+  style_starts: &'a [u32],
+}
+
+impl<'a, 'i> Visitor<'i> for ValidateVisitor<'a> {
+  type Error = Infallible;
+  const TYPES: VisitTypes = visit_types!(RULES);
+
+  fn visit_rule(&mut self, rule: &mut CssRule<'i>) -> Result<(), Self::Error> {
+    match rule {
+      CssRule::Style(style) => {
+        let selector = style.selectors.to_css_string(PrinterOptions::default()).unwrap_or_default();
+        let mut locator = DeclarationLocator::new(self.css, &style.loc, 1);
+        self.validate_declarations(&selector, &mut style.declarations, &style.loc, &mut locator);
+      },
+      CssRule::Keyframes(keyframes_rule) => {
+        let loc = keyframes_rule.loc;
+        // 各帧的声明在 @keyframes 的第二层块中，按帧的顺序依次匹配
+        let mut locator = DeclarationLocator::new(self.css, &loc, 2);
+        let name = format!("@keyframes {}", get_keyframes_name(&keyframes_rule.name));
+        keyframes_rule.keyframes.iter_mut().for_each(|keyframe| self.validate_declarations(&name, &mut keyframe.declarations, &loc, &mut locator));
+      }
+      _ => {}
+    }
+    // 嵌套规则及 @media、@supports 等规则中的样式
+    rule.visit_children(self)
+  }
+}
+
+impl<'a> ValidateVisitor<'a> {
+  fn validate_declarations(&self, selector: &str, declarations: &mut DeclarationBlock, rule_loc: &Location, locator: &mut DeclarationLocator) {
+    let strict = with_transform_options(|options| options.strict);
+    declarations.declarations.iter_mut().chain(declarations.important_declarations.iter_mut()).for_each(|property| {
+      let value = property.value_to_css_string(PrinterOptions::default()).unwrap_or_default();
+      // 未在源码中找到声明时使用规则的位置
+      let loc = locator.locate(property).unwrap_or(*rule_loc);
+      if is_invalid_value(property) {
+        let message = format!(
+          "{} {{ {}: {} }} has an invalid value at {}, the declaration is ignored",
+          selector,
+          property.property_id().to_css_string(PrinterOptions::default()).unwrap_or_default(),
+          value,
+          format_source_position(loc.line, loc.column, self.style_starts)
+        );
+        match strict {
+          true => push_error(&normalize_style_key(property.property_id().name()), message),
+          false => push_diagnostic(&normalize_style_key(property.property_id().name()), message)
+        }
+      } else if clamp_property(property) {
+        push_diagnostic(
+          &normalize_style_key(property.property_id().name()),
+          format!(
+            "{} is out of range at {}, clamped to {}",
+            value,
+            format_source_position(loc.line, loc.column, self.style_starts),
+            property.value_to_css_string(PrinterOptions::default()).unwrap_or_default()
+          )
+        );
+      }
+    });
+  }
+}
+
+// 已知属性中 lightningcss 无法解析的值，引用了 var()、env() 的值在运行时计算，不视为无法解析
+pub fn is_invalid_value(property: &Property) -> bool {
+  match property {
+    Property::Unparsed(unparsed) => {
+      !matches!(unparsed.property_id, PropertyId::Custom(_))
+        && !unparsed.value.0.iter().any(|token| matches!(token, TokenOrValue::Var(_) | TokenOrValue::Env(_)))
+    },
+    _ => false
+  }
+}
+
+// 将属性值截断到合法范围，返回是否做了截断
+fn clamp_property(property: &mut Property) -> bool {
+  match property {
+    Property::Width(size) | Property::Height(size) | Property::MinWidth(size) | Property::MinHeight(size) => match size {
+      Size::LengthPercentage(length_percentage) => clamp_length_percentage(length_percentage),
+      _ => false
+    },
+    Property::MaxWidth(size) | Property::MaxHeight(size) => match size {
+      MaxSize::LengthPercentage(length_percentage) => clamp_length_percentage(length_percentage),
+      _ => false
+    },
+    Property::PaddingTop(padding) | Property::PaddingRight(padding) | Property::PaddingBottom(padding) | Property::PaddingLeft(padding) => {
+      clamp_length_percentage_or_auto(padding)
+    },
+    Property::Padding(padding) => {
+      [&mut padding.top, &mut padding.right, &mut padding.bottom, &mut padding.left].into_iter().map(clamp_length_percentage_or_auto).fold(false, |changed, clamped| changed | clamped)
+    },
+    Property::BorderTopWidth(width) | Property::BorderRightWidth(width) | Property::BorderBottomWidth(width) | Property::BorderLeftWidth(width) => {
+      clamp_border_side_width(width)
+    },
+    Property::BorderWidth(width) => {
+      [&mut width.top, &mut width.right, &mut width.bottom, &mut width.left].into_iter().map(clamp_border_side_width).fold(false, |changed, clamped| changed | clamped)
+    },
+    Property::Border(border) => clamp_border_side_width(&mut border.width),
+    Property::BorderTop(border) => clamp_border_side_width(&mut border.width),
+    Property::BorderRight(border) => clamp_border_side_width(&mut border.width),
+    Property::BorderBottom(border) => clamp_border_side_width(&mut border.width),
+    Property::BorderLeft(border) => clamp_border_side_width(&mut border.width),
+    Property::FontSize(font::FontSize::Length(length_percentage)) => clamp_length_percentage(length_percentage),
+    Property::LineHeight(line_height) => match line_height {
+      font::LineHeight::Number(num) => clamp_number(num, 0.0, f32::MAX),
+      font::LineHeight::Length(length_percentage) => clamp_length_percentage(length_percentage),
+      font::LineHeight::Normal => false
+    },
+    Property::FlexGrow(num, _) | Property::FlexShrink(num, _) => clamp_number(num, 0.0, f32::MAX),
+    Property::Flex(flex, _) => {
+      let grow = clamp_number(&mut flex.grow, 0.0, f32::MAX);
+      let shrink = clamp_number(&mut flex.shrink, 0.0, f32::MAX);
+      grow || shrink
+    },
+    Property::RowGap(gap) | Property::ColumnGap(gap) => clamp_gap_value(gap),
+    Property::Gap(gap) => {
+      let row = clamp_gap_value(&mut gap.row);
+      let column = clamp_gap_value(&mut gap.column);
+      row || column
+    },
+    Property::Opacity(AlphaValue(alpha)) => clamp_number(alpha, 0.0, 1.0),
+    Property::AnimationIterationCount(iteration_counts, _) => {
+      iteration_counts.iter_mut().map(clamp_iteration_count).fold(false, |changed, clamped| changed | clamped)
+    },
+    Property::Animation(animations, _) => {
+      animations.iter_mut().map(|animation| clamp_iteration_count(&mut animation.iteration_count)).fold(false, |changed, clamped| changed | clamped)
+    },
+    _ => false
+  }
+}
+
+fn clamp_number(num: &mut f32, min: f32, max: f32) -> bool {
+  let clamped = num.clamp(min, max);
+  let changed = clamped != *num;
+  *num = clamped;
+  changed
+}
+
+fn clamp_length_value(length_value: &mut LengthValue) -> bool {
+  if length_value.sign() < 0.0 {
+    *length_value = LengthValue::zero();
+    return true
+  }
+  false
+}
+
+// calc() 的结果在编译期无法确定，不做检查
+fn clamp_length_percentage(length_percentage: &mut LengthPercentage) -> bool {
+  match length_percentage {
+    DimensionPercentage::Dimension(length_value) => clamp_length_value(length_value),
+    DimensionPercentage::Percentage(percentage) if percentage.0 < 0.0 => {
+      *percentage = Percentage(0.0);
+      true
+    },
+    _ => false
+  }
+}
+
+fn clamp_length_percentage_or_auto(value: &mut LengthPercentageOrAuto) -> bool {
+  match value {
+    LengthPercentageOrAuto::LengthPercentage(length_percentage) => clamp_length_percentage(length_percentage),
+    LengthPercentageOrAuto::Auto => false
+  }
+}
+
+fn clamp_border_side_width(width: &mut BorderSideWidth) -> bool {
+  match width {
+    BorderSideWidth::Length(Length::Value(length_value)) => clamp_length_value(length_value),
+    _ => false
+  }
+}
+
+fn clamp_gap_value(gap: &mut GapValue) -> bool {
+  match gap {
+    GapValue::LengthPercentage(length_percentage) => clamp_length_percentage(length_percentage),
+    GapValue::Normal => false
+  }
+}
+
+fn clamp_iteration_count(iteration_count: &mut AnimationIterationCount) -> bool {
+  match iteration_count {
+    AnimationIterationCount::Number(num) => clamp_number(num, 0.0, f32::MAX),
+    AnimationIterationCount::Infinite => false
+  }
+}
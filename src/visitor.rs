@@ -1,12 +1,13 @@
 use std::{
-  cell::RefCell, collections::{BTreeMap, HashMap}, hash::{Hash, Hasher}, rc::Rc, vec
+  cell::RefCell, collections::{BTreeMap, HashMap, HashSet}, hash::{Hash, Hasher}, rc::Rc, vec
 };
 
+use ego_tree::{NodeId, Tree};
 use html5ever::{tendril::StrTendril, Attribute};
 use indexmap::IndexMap;
-use lightningcss::properties::Property;
+use lightningcss::{properties::Property, stylesheet::{ParserOptions, PrinterOptions, StyleAttribute}};
 use swc_core::{
-  atoms::Atom, common::{Span, DUMMY_SP}, ecma::{
+  atoms::Atom, common::{util::take::Take, BytePos, Span, DUMMY_SP}, ecma::{
     utils::quote_ident,
     visit::{
       noop_visit_mut_type, noop_visit_type, Visit, VisitAll, VisitAllWith, VisitMut, VisitMutWith,
@@ -15,10 +16,11 @@ use swc_core::{
   }
 };
 use swc_core::ecma::ast::*;
+use tracing::trace;
 
 use crate::{
-  constants::{CALC_STATIC_STYLE, COMBINE_NESTING_STYLE, CONVERT_STYLE_PX_FN, ENV_FUN, GLOBAL_SHARED, HM_STYLE, INNER_STYLE, INNER_STYLE_DATA, NESTING_STYLE, NESTINT_STYLE_DATA, RN_CONVERT_STYLE_PX_FN, RN_CONVERT_STYLE_VU_FN, SUPPORT_PSEUDO_KEYS, VAR_FUN}, scraper::Element, style_parser::StyleValue, style_propetries::{style_value_type::StyleValueType, traits::ToStyleValue, unit::{Platform, PropertyTuple}}, utils::{
-    create_qualname, get_callee_attributes, is_starts_with_uppercase, is_tailwind_arbitrary, prefix_style_key, recursion_jsx_member, split_selector, TSelector
+  api_version::check_harmony_api_level, constants::{CALC_STATIC_STYLE, COMBINE_NESTING_STYLE, CONVERT_STYLE_PX_FN, COLOR_SCHEME_FUN, CONTAINER_QUERY_FUN, ENV_FUN, GLOBAL_SHARED, HM_STYLE, HOC_WRAPPERS, HOISTED_STYLE, HOISTED_STYLE_DATA, INNER_STYLE, INNER_STYLE_DATA, NESTING_STYLE, NESTINT_STYLE_DATA, RN_CONVERT_STYLE_PX_FN, RN_CONVERT_STYLE_VU_FN, SUPPORT_PSEUDO_KEYS, VAR_FUN}, scraper::Element, diagnostics::push_diagnostic, important::ImportantRecord, interner::{intern, intern_property_name}, scope::scope_class_list, minify::minify_style_entries, parse_style_properties::parse_style_properties, shorthand::expand_shorthand, options::with_transform_options, container::ContainerQuery, style_parser::{ContainerStyle, GeneratedClassRecord, StyleMap}, theme::generate_theme_decl, style_propetries::{style_value_type::StyleValueType, traits::ToStyleValue, unit::{Platform, PropertyTuple}}, utils::{
+    create_qualname, get_callee_attributes, is_compound_class_selector, normalize_style_key, is_starts_with_uppercase, is_tailwind_arbitrary, prefix_style_key, recursion_jsx_member, split_selector, TSelector
   }
 };
 
@@ -44,7 +46,7 @@ impl Hash for SpanKey {
   }
 }
 
-pub type JSXRecord = HashMap<SpanKey, Element>;
+pub type JSXRecord = IndexMap<SpanKey, Element>;
 
 struct VarChecker {
   found: bool,
@@ -61,41 +63,692 @@ impl Visit for VarChecker {
 }
 
 pub struct CollectVisitor {
+  // Taro 组件在组件代码中的名称，如 View、别名导入的 V、命名空间导入的 Components.View
   pub taro_components: Vec<String>,
+  // 组件代码中的名称 => 组件名，如 V => View、Components.View => View
+  pub component_names: HashMap<String, String>,
+  // 命名空间导入的名称，如 import * as Components from '@tarojs/components'
+  component_namespaces: Vec<String>,
+  // 模块顶层由组件组成的对象的成员 => 成员的值，如 const UI = { Box: View } => UI.Box => View
+  component_members: Vec<(String, String)>,
+  // 模块顶层的字符串常量，如 const cls = 'card'，用于编译期计算 className
+  pub const_strings: HashMap<Id, String>,
 }
 
 impl CollectVisitor {
   pub fn new() -> Self {
     CollectVisitor {
       taro_components: vec![],
+      component_names: HashMap::new(),
+      component_namespaces: vec![],
+      component_members: vec![],
+      const_strings: HashMap::new(),
     }
   }
+
+  // 收集由组件组成的对象，如 const UI = { View, Box: Components.View }，成员的值为标识符或命名空间的成员
+  fn collect_component_members(&mut self, var_decl: &VarDecl) {
+    if var_decl.kind != VarDeclKind::Const {
+      return;
+    }
+    var_decl.decls.iter().for_each(|decl| {
+      let (Pat::Ident(binding_ident), Some(init)) = (&decl.name, &decl.init) else {
+        return
+      };
+      let Expr::Object(object) = &**init else {
+        return
+      };
+      object.props.iter().for_each(|prop| {
+        let PropOrSpread::Prop(prop) = prop else {
+          return
+        };
+        let member = match &**prop {
+          Prop::Shorthand(ident) => Some((ident.sym.to_string(), ident.sym.to_string())),
+          Prop::KeyValue(KeyValueProp { key, value }) => {
+            let key = match key {
+              PropName::Ident(ident) => Some(ident.sym.to_string()),
+              PropName::Str(str) => Some(str.value.to_string()),
+              _ => None
+            };
+            let value = match &**value {
+              Expr::Ident(ident) => Some(ident.sym.to_string()),
+              Expr::Member(MemberExpr { obj, prop: MemberProp::Ident(prop), .. }) => match &**obj {
+                Expr::Ident(obj) => Some(format!("{}.{}", obj.sym, prop.sym)),
+                _ => None
+              },
+              _ => None
+            };
+            key.zip(value)
+          },
+          _ => None
+        };
+        if let Some((key, value)) = member {
+          self.component_members.push((format!("{}.{}", binding_ident.id.sym, key), value));
+        }
+      });
+    });
+  }
+
+  // 对象的成员为 Taro 组件时按组件处理，如 <UI.Box /> 与 <View /> 相同
+  fn resolve_component_members(&mut self) {
+    let component_members = std::mem::take(&mut self.component_members);
+    component_members.into_iter().for_each(|(local, value)| {
+      if self.taro_components.contains(&local) {
+        return
+      }
+      let name = match value.split_once('.') {
+        Some((namespace, name)) if self.component_namespaces.iter().any(|component_namespace| component_namespace == namespace) => Some(name.to_string()),
+        Some(_) => None,
+        None if self.taro_components.contains(&value) => Some(self.component_names.get(&value).cloned().unwrap_or(value)),
+        None => None
+      };
+      if let Some(name) = name {
+        self.component_names.insert(local.clone(), name);
+        self.taro_components.push(local);
+      }
+    });
+  }
+
+  fn collect_const_strings(&mut self, var_decl: &VarDecl) {
+    if var_decl.kind != VarDeclKind::Const {
+      return;
+    }
+    var_decl.decls.iter().for_each(|decl| {
+      if let (Pat::Ident(binding_ident), Some(init)) = (&decl.name, &decl.init) {
+        let value = match &**init {
+          Expr::Lit(Lit::Str(str)) => Some(str.value.to_string()),
+          // 不含表达式的模板字符串，如 `card`
+          Expr::Tpl(tpl) if tpl.exprs.is_empty() => tpl.quasis.first().map(|quasi| quasi.raw.to_string()),
+          _ => None
+        };
+        if let Some(value) = value {
+          self.const_strings.insert(binding_ident.id.to_id(), value);
+        }
+      }
+    });
+  }
 }
 
 impl Visit for CollectVisitor {
+  fn visit_module(&mut self, module: &Module) {
+    module.body.iter().for_each(|item| {
+      match item {
+        ModuleItem::Stmt(Stmt::Decl(Decl::Var(var_decl))) | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl: Decl::Var(var_decl), .. })) => {
+          self.collect_const_strings(var_decl);
+          self.collect_component_members(var_decl);
+        },
+        _ => {}
+      }
+    });
+    module.visit_children_with(self);
+    // 需在收集完导入的组件后处理
+    self.resolve_component_members();
+  }
+
   fn visit_import_decl(&mut self, n: &ImportDecl) {
-    if n.src.value.to_string().starts_with("@tarojs/components") {
-      for specifier in &n.specifiers {
-        match specifier {
-          ImportSpecifier::Named(named_specifier) => self
-            .taro_components
-            .push(named_specifier.local.sym.to_string()),
+    if !is_component_source(&n.src.value) {
+      return
+    }
+    for specifier in &n.specifiers {
+      match specifier {
+        ImportSpecifier::Named(named_specifier) => {
+          let local = named_specifier.local.sym.to_string();
+          // 别名导入，如 import { View as V }
+          let name = match &named_specifier.imported {
+            Some(ModuleExportName::Ident(ident)) => ident.sym.to_string(),
+            Some(ModuleExportName::Str(str)) => str.value.to_string(),
+            None => local.clone()
+          };
+          self.component_names.insert(local.clone(), name);
+          self.taro_components.push(local);
+        },
+        ImportSpecifier::Namespace(namespace_specifier) => self
+          .component_namespaces
+          .push(namespace_specifier.local.sym.to_string()),
+        _ => {}
+      }
+    }
+  }
+
+  // 命名空间导入的组件，如 <Components.View />
+  fn visit_jsx_element_name(&mut self, n: &JSXElementName) {
+    if let JSXElementName::JSXMemberExpr(expr) = n {
+      if let JSXObject::Ident(ident) = &expr.obj {
+        let local = recursion_jsx_member(expr);
+        if self.component_namespaces.contains(&ident.sym.to_string()) && !self.taro_components.contains(&local) {
+          self.component_names.insert(local.clone(), expr.prop.sym.to_string());
+          self.taro_components.push(local);
+        }
+      }
+    }
+  }
+}
+
+// 节点的名称，处理标识符及成员表达式，如 <View />、<Components.View />、<UI.Box />、<Card.Header />
+fn get_jsx_element_name(name: &JSXElementName) -> Option<String> {
+  match name {
+    JSXElementName::Ident(ident) => Some(ident.sym.to_string()),
+    JSXElementName::JSXMemberExpr(expr) => Some(recursion_jsx_member(expr)),
+    _ => None
+  }
+}
+
+// 是否按 Taro 组件插入 __hmStyle，小写的标识符为原生标签，成员表达式未解析为 Taro 组件时按自定义组件处理，如 <Card.Header />
+fn is_taro_element(name: &str, taro_components: &[String]) -> bool {
+  taro_components.iter().any(|component| component == name) || (!is_starts_with_uppercase(name) && !name.contains('.'))
+}
+
+// 是否是组件库的模块，除 @tarojs/components 外可通过 componentSources 配置，如重新导出 Taro 组件的业务组件库
+fn is_component_source(source: &str) -> bool {
+  if source.starts_with("@tarojs/components") {
+    return true
+  }
+  with_transform_options(|options| {
+    options.component_sources.iter().any(|component_source| {
+      source == component_source || source.starts_with(&format!("{}/", component_source))
+    })
+  })
+}
+
+// 将引用模块顶层字符串常量的 className 替换为字面量，如 className={cls} => className="card"
+// 标识符经过 resolver 处理，通过 (sym, ctxt) 区分同名的局部变量，如箭头函数参数 (cls) => ...
+pub struct ConstClassNameVisitor<'a> {
+  pub const_strings: &'a HashMap<Id, String>,
+}
+
+impl<'a> ConstClassNameVisitor<'a> {
+  pub fn new(const_strings: &'a HashMap<Id, String>) -> Self {
+    ConstClassNameVisitor { const_strings }
+  }
+
+  fn get_const_string(&self, expr: &Expr) -> Option<String> {
+    match expr {
+      Expr::Ident(ident) => self.const_strings.get(&ident.to_id()).cloned(),
+      _ => None
+    }
+  }
+}
+
+impl<'a> VisitMut for ConstClassNameVisitor<'a> {
+  fn visit_mut_jsx_attr(&mut self, attr: &mut JSXAttr) {
+    if let (JSXAttrName::Ident(ident), Some(JSXAttrValue::JSXExprContainer(JSXExprContainer { expr: JSXExpr::Expr(expr), .. }))) = (&attr.name, &attr.value) {
+      if ident.sym == "className" {
+        if let Some(value) = self.get_const_string(expr) {
+          attr.value = Some(JSXAttrValue::Lit(Lit::Str(value.into())));
+        }
+      }
+    }
+    attr.visit_mut_children_with(self);
+  }
+
+  // React.createElement(View, { className: cls })
+  fn visit_mut_key_value_prop(&mut self, prop: &mut KeyValueProp) {
+    let is_class_name = match &prop.key {
+      PropName::Ident(ident) => ident.sym == "className",
+      PropName::Str(str) => str.value == "className",
+      _ => false
+    };
+    if is_class_name {
+      if let Some(value) = self.get_const_string(&prop.value) {
+        *prop.value = Expr::Lit(Lit::Str(value.into()));
+      }
+    }
+    prop.visit_mut_children_with(self);
+  }
+}
+
+// 作用域模式下，className 中的类名添加作用域前缀，如 className="title" => className="s1a2b3c4d_title"
+// 动态 className 中的字符串、模板字符串、对象的 key（如 classNames({ active: isActive })）同样处理
+pub struct ScopedClassNameVisitor;
+
+impl ScopedClassNameVisitor {
+  fn scope_class_name_expr(expr: &mut Expr) {
+    expr.visit_mut_with(&mut ClassNameStringScoper);
+  }
+}
+
+impl VisitMut for ScopedClassNameVisitor {
+  fn visit_mut_jsx_attr(&mut self, attr: &mut JSXAttr) {
+    if let JSXAttrName::Ident(ident) = &attr.name {
+      if ident.sym == "className" {
+        match &mut attr.value {
+          Some(JSXAttrValue::Lit(Lit::Str(str))) => {
+            *str = scope_class_list(&str.value, true, true).into();
+          },
+          Some(JSXAttrValue::JSXExprContainer(JSXExprContainer { expr: JSXExpr::Expr(expr), .. })) => {
+            ScopedClassNameVisitor::scope_class_name_expr(expr);
+          },
           _ => {}
         }
+        return;
       }
     }
+    attr.visit_mut_children_with(self);
+  }
+
+  // React.createElement(View, { className: 'title' })
+  fn visit_mut_key_value_prop(&mut self, prop: &mut KeyValueProp) {
+    let is_class_name = match &prop.key {
+      PropName::Ident(ident) => ident.sym == "className",
+      PropName::Str(str) => str.value == "className",
+      _ => false
+    };
+    if is_class_name {
+      ScopedClassNameVisitor::scope_class_name_expr(&mut prop.value);
+      return;
+    }
+    prop.visit_mut_children_with(self);
+  }
+}
+
+struct ClassNameStringScoper;
+
+impl VisitMut for ClassNameStringScoper {
+  fn visit_mut_str(&mut self, str: &mut Str) {
+    *str = scope_class_list(&str.value, true, true).into();
+  }
+
+  // `item ${active}` 中与表达式相连的类名不完整，不做处理
+  fn visit_mut_tpl(&mut self, tpl: &mut Tpl) {
+    let count = tpl.quasis.len();
+    tpl.quasis.iter_mut().enumerate().for_each(|(index, quasi)| {
+      let raw = scope_class_list(&quasi.raw, index == 0, index == count - 1);
+      quasi.cooked = Some(raw.clone().into());
+      quasi.raw = raw.into();
+    });
+    tpl.exprs.visit_mut_with(self);
+  }
+
+  fn visit_mut_prop_name(&mut self, prop_name: &mut PropName) {
+    match prop_name {
+      PropName::Ident(ident) => *prop_name = PropName::Str(scope_class_list(&ident.sym, true, true).into()),
+      PropName::Str(str) => *str = scope_class_list(&str.value, true, true).into(),
+      _ => {}
+    }
+  }
+
+  // 成员表达式的属性不是类名，如 styles.title
+  fn visit_mut_member_prop(&mut self, _: &mut MemberProp) {}
+}
+
+// 从静态 className 的节点的内联样式中移除类样式中 !important 声明的属性，使 !important 覆盖内联样式，如
+// .a { color: red !important } <View className="a" style={{ color: 'blue', width: 10 }}> => style={{ width: 10 }}
+// 内联样式为变量等动态值时无法移除，给出诊断
+pub struct ImportantInlineStyleVisitor<'a> {
+  important_record: &'a ImportantRecord,
+}
+
+impl<'a> ImportantInlineStyleVisitor<'a> {
+  pub fn new(important_record: &'a ImportantRecord) -> Self {
+    ImportantInlineStyleVisitor { important_record }
+  }
+}
+
+impl<'a> VisitMut for ImportantInlineStyleVisitor<'a> {
+  noop_visit_mut_type!();
+
+  fn visit_mut_jsx_element(&mut self, jsx: &mut JSXElement) {
+    if let Some(important) = self.important_record.get(&SpanKey(jsx.span)) {
+      let is_important = |key: &str| important.iter().any(|(name, _)| name == key);
+      let style_value = jsx.opening.attrs.iter_mut().find_map(|attr| match attr {
+        JSXAttrOrSpread::JSXAttr(JSXAttr { name: JSXAttrName::Ident(ident), value: Some(value), .. }) if ident.sym == "style" => Some(value),
+        _ => None
+      });
+      match style_value {
+        Some(JSXAttrValue::Lit(Lit::Str(str))) => {
+          let style = parse_inline_style_properties(&str.value)
+            .into_iter()
+            .filter(|(name, _)| !is_important(name))
+            .filter_map(|(_, property)| property.to_css_string(false, PrinterOptions::default()).ok())
+            .collect::<Vec<_>>()
+            .join("; ");
+          *str = Str::from(style);
+        },
+        Some(JSXAttrValue::JSXExprContainer(JSXExprContainer { expr: JSXExpr::Expr(expr), .. })) => match &mut **expr {
+          Expr::Object(ObjectLit { props, .. }) => {
+            props.retain(|prop| match prop {
+              PropOrSpread::Prop(prop) => match &**prop {
+                Prop::KeyValue(KeyValueProp { key: PropName::Ident(ident), .. }) => !is_important(&ident.sym),
+                Prop::KeyValue(KeyValueProp { key: PropName::Str(str), .. }) => !is_important(&str.value),
+                _ => true
+              },
+              _ => true
+            });
+            if props.iter().any(|prop| matches!(prop, PropOrSpread::Spread(_))) {
+              push_dynamic_inline_style_diagnostics(important);
+            }
+          },
+          _ => push_dynamic_inline_style_diagnostics(important)
+        },
+        _ => {}
+      }
+    }
+    jsx.visit_mut_children_with(self);
+  }
+}
+
+fn push_dynamic_inline_style_diagnostics(important: &[(Atom, String)]) {
+  important.iter().for_each(|(name, class)| {
+    push_diagnostic(name, format!("{} !important in .{} may be overridden by the dynamic inline style", name, class));
+  });
+}
+
+// 节点添加编译期生成的类名（兄弟选择器、文本样式继承），如 className="item" => className="item __sibling_s1a2b3c4d"
+// 没有 className 的节点添加 className 属性
+pub struct GeneratedClassNameVisitor<'a> {
+  generated_classes: &'a GeneratedClassRecord,
+}
+
+impl<'a> GeneratedClassNameVisitor<'a> {
+  pub fn new(generated_classes: &'a GeneratedClassRecord) -> Self {
+    GeneratedClassNameVisitor { generated_classes }
+  }
+}
+
+impl<'a> VisitMut for GeneratedClassNameVisitor<'a> {
+  noop_visit_mut_type!();
+
+  fn visit_mut_jsx_element(&mut self, jsx: &mut JSXElement) {
+    if let Some(classes) = self.generated_classes.get(&SpanKey(jsx.span)) {
+      let class_attr = jsx.opening.attrs.iter_mut().find_map(|attr| match attr {
+        JSXAttrOrSpread::JSXAttr(attr) if matches!(&attr.name, JSXAttrName::Ident(ident) if ident.sym == "className") => Some(attr),
+        _ => None
+      });
+      match class_attr {
+        Some(JSXAttr { value: Some(value), .. }) => {
+          let str = match value {
+            JSXAttrValue::Lit(Lit::Str(str)) => Some(str),
+            JSXAttrValue::JSXExprContainer(JSXExprContainer { expr: JSXExpr::Expr(expr), .. }) => match &mut **expr {
+              Expr::Lit(Lit::Str(str)) => Some(str),
+              _ => None
+            },
+            _ => None
+          };
+          if let Some(str) = str {
+            *str = Str::from(format!("{} {}", str.value, classes.join(" ")));
+          }
+        }
+        Some(_) => {}
+        None => {
+          jsx.opening.attrs.push(JSXAttrOrSpread::JSXAttr(JSXAttr {
+            span: DUMMY_SP,
+            name: JSXAttrName::Ident(Ident::new("className".into(), DUMMY_SP)),
+            value: Some(JSXAttrValue::Lit(Lit::Str(Str::from(classes.join(" "))))),
+          }));
+        }
+      }
+    }
+    jsx.visit_mut_children_with(self);
+  }
+}
+
+// 收集代码中字符串字面量的所有单词，动态 className 的类名（如 cond ? 'a' : 'b'）也包含在内
+pub struct StringLiteralVisitor {
+  pub words: HashSet<String>,
+}
+
+impl StringLiteralVisitor {
+  pub fn new() -> Self {
+    StringLiteralVisitor {
+      words: HashSet::new(),
+    }
+  }
+}
+
+impl Visit for StringLiteralVisitor {
+  noop_visit_type!();
+
+  fn visit_str(&mut self, str: &Str) {
+    self.words.extend(str.value.split_whitespace().map(|word| word.to_string()));
+  }
+
+  fn visit_tpl_element(&mut self, tpl_element: &TplElement) {
+    self.words.extend(tpl_element.raw.split_whitespace().map(|word| word.to_string()));
+  }
+}
+
+// 收集 className 中可能出现的类名，包括动态 className 中的字符串、模板字符串、对象的 key，
+// 如 className={cond ? 'a' : 'b'}、className={classNames({ active: isActive })}，用于检查没有对应样式的类名
+pub struct ClassNameLiteralVisitor {
+  pub classes: HashSet<String>,
+}
+
+impl ClassNameLiteralVisitor {
+  pub fn new() -> Self {
+    ClassNameLiteralVisitor {
+      classes: HashSet::new(),
+    }
+  }
+}
+
+impl Visit for ClassNameLiteralVisitor {
+  noop_visit_type!();
+
+  fn visit_jsx_attr(&mut self, attr: &JSXAttr) {
+    if matches!(&attr.name, JSXAttrName::Ident(ident) if ident.sym == "className") {
+      match &attr.value {
+        Some(JSXAttrValue::Lit(Lit::Str(str))) => self.classes.extend(str.value.split_whitespace().map(|class| class.to_string())),
+        Some(JSXAttrValue::JSXExprContainer(JSXExprContainer { expr: JSXExpr::Expr(expr), .. })) => expr.visit_with(&mut ClassNameStringCollector { classes: &mut self.classes }),
+        _ => {}
+      }
+      return;
+    }
+    attr.visit_children_with(self);
+  }
+
+  // React.createElement(View, { className: cond ? 'a' : 'b' })
+  fn visit_key_value_prop(&mut self, prop: &KeyValueProp) {
+    let is_class_name = match &prop.key {
+      PropName::Ident(ident) => ident.sym == "className",
+      PropName::Str(str) => str.value == "className",
+      _ => false
+    };
+    if is_class_name {
+      prop.value.visit_with(&mut ClassNameStringCollector { classes: &mut self.classes });
+      return;
+    }
+    prop.visit_children_with(self);
+  }
+}
+
+struct ClassNameStringCollector<'a> {
+  classes: &'a mut HashSet<String>,
+}
+
+impl<'a> Visit for ClassNameStringCollector<'a> {
+  noop_visit_type!();
+
+  fn visit_str(&mut self, str: &Str) {
+    self.classes.extend(str.value.split_whitespace().map(|class| class.to_string()));
+  }
+
+  // `item ${active}` 中与表达式相连的类名不完整，不计入
+  fn visit_tpl(&mut self, tpl: &Tpl) {
+    let count = tpl.quasis.len();
+    tpl.quasis.iter().enumerate().for_each(|(index, quasi)| {
+      let words = quasi.raw.split_whitespace().collect::<Vec<_>>();
+      let start = usize::from(index > 0 && !quasi.raw.starts_with(char::is_whitespace));
+      let end = words.len() - usize::from(index < count - 1 && !quasi.raw.ends_with(char::is_whitespace) && words.len() > start);
+      self.classes.extend(words.iter().take(end).skip(start).map(|class| class.to_string()));
+    });
+    tpl.exprs.visit_with(self);
+  }
+
+  fn visit_prop_name(&mut self, prop_name: &PropName) {
+    match prop_name {
+      PropName::Ident(ident) => { self.classes.insert(ident.sym.to_string()); },
+      PropName::Str(str) => self.classes.extend(str.value.split_whitespace().map(|class| class.to_string())),
+      _ => {}
+    }
+  }
+
+  // 成员表达式的属性不是类名，如 styles.title
+  fn visit_member_prop(&mut self, _: &MemberProp) {}
+
+  // 函数调用的参数中，仅字符串、对象等可能是类名，调用的函数名不是
+  fn visit_callee(&mut self, _: &Callee) {}
+}
+
+// 上游 swc 插件生成或压缩后的代码中，JSX 节点的 span 可能为 DUMMY_SP 或与其他节点重复，无法作为节点的唯一标识
+// 按节点的遍历顺序为这类节点分配合成的 span，位于 swc 保留的 dummy 区间（从 u32::MAX 向下分配），代码生成时视为无源码位置
+pub struct SpanNormalizeVisitor {
+  seen: HashSet<SpanKey>,
+  // 已遍历的 JSX 节点数量，即节点在结构上的序号
+  index: u32,
+}
+
+impl SpanNormalizeVisitor {
+  pub fn new() -> Self {
+    SpanNormalizeVisitor { seen: HashSet::new(), index: 0 }
+  }
+
+  fn normalize(&mut self, span: &mut Span) {
+    self.index += 1;
+    if span.is_dummy() || self.seen.contains(&SpanKey(*span)) {
+      let pos = BytePos(u32::MAX - self.index);
+      *span = Span::new(pos, pos, span.ctxt);
+    }
+    self.seen.insert(SpanKey(*span));
+  }
+}
+
+impl VisitMut for SpanNormalizeVisitor {
+  noop_visit_mut_type!();
+
+  fn visit_mut_jsx_element(&mut self, jsx: &mut JSXElement) {
+    self.normalize(&mut jsx.span);
+    jsx.visit_mut_children_with(self);
+  }
+
+  fn visit_mut_call_expr(&mut self, call_expr: &mut CallExpr) {
+    if check_is_jsx_callee(call_expr) {
+      self.normalize(&mut call_expr.span);
+    }
+    call_expr.visit_mut_children_with(self);
+  }
+}
+
+// 节点树中的节点，用于兄弟选择器的匹配
+#[derive(Debug, Clone, PartialEq)]
+pub enum JSXNode {
+  Root,
+  Element(SpanKey),
+  // 表达式容器，如 {list.map(...)}、{cond && <View />}，其中渲染的节点数量及顺序在编译期无法确定
+  Expression,
+  // 属性中的 JSX，如 <List renderItem={() => <View />} />，由组件在其内部渲染，与所在节点之间的父子关系在编译期无法确定
+  RenderProp,
+}
+
+// 属性值中是否包含 JSX
+struct JSXChecker {
+  found: bool,
+}
+
+impl Visit for JSXChecker {
+  noop_visit_type!();
+
+  fn visit_jsx_element(&mut self, _: &JSXElement) {
+    self.found = true;
+  }
+
+  fn visit_jsx_fragment(&mut self, _: &JSXFragment) {
+    self.found = true;
+  }
+}
+
+// 按 JSX 的嵌套关系构建节点树，Fragment 的子节点视为其父节点的子节点
+pub struct JSXTreeVisitor {
+  pub tree: Tree<JSXNode>,
+  current: NodeId,
+  // 是否将属性中的 JSX（如 render props）加入节点树
+  render_props: bool,
+}
+
+impl JSXTreeVisitor {
+  pub fn new() -> Self {
+    let tree = Tree::new(JSXNode::Root);
+    let current = tree.root().id();
+    let render_props = with_transform_options(|options| options.render_props);
+    JSXTreeVisitor { tree, current, render_props }
+  }
+
+  fn append(&mut self, node: JSXNode) -> NodeId {
+    self.tree.get_mut(self.current).unwrap().append(node).id()
+  }
+
+  fn visit_children_in(&mut self, id: NodeId, children: &[JSXElementChild]) {
+    let parent = std::mem::replace(&mut self.current, id);
+    children.iter().for_each(|child| child.visit_with(self));
+    self.current = parent;
+  }
+}
+
+impl Visit for JSXTreeVisitor {
+  noop_visit_type!();
+
+  // 属性中的 JSX（如 render props）不属于子节点，开启 render_props 时作为节点的后代进入节点树
+  fn visit_jsx_element(&mut self, jsx: &JSXElement) {
+    let id = self.append(JSXNode::Element(SpanKey(jsx.span)));
+    if self.render_props {
+      let parent = std::mem::replace(&mut self.current, id);
+      jsx.opening.attrs.iter().for_each(|attr| {
+        let JSXAttrOrSpread::JSXAttr(JSXAttr { value: Some(value), .. }) = attr else {
+          return
+        };
+        let mut checker = JSXChecker { found: false };
+        value.visit_with(&mut checker);
+        if !checker.found {
+          return
+        }
+        let render_prop = self.append(JSXNode::RenderProp);
+        let owner = std::mem::replace(&mut self.current, render_prop);
+        match value {
+          // footer={<View />} 与 footer=<View /> 相同，节点确定
+          JSXAttrValue::JSXExprContainer(JSXExprContainer { expr: JSXExpr::Expr(expr), .. }) if matches!(&**expr, Expr::JSXElement(_) | Expr::JSXFragment(_)) => expr.visit_with(self),
+          // renderItem={() => <View />} 中的 JSX 在函数调用时渲染，节点数量在编译期无法确定
+          JSXAttrValue::JSXExprContainer(container) => container.visit_with(self),
+          _ => value.visit_children_with(self)
+        }
+        self.current = owner;
+      });
+      self.current = parent;
+    }
+    self.visit_children_in(id, &jsx.children);
+  }
+
+  fn visit_jsx_fragment(&mut self, jsx: &JSXFragment) {
+    self.visit_children_in(self.current, &jsx.children);
+  }
+
+  fn visit_jsx_expr_container(&mut self, container: &JSXExprContainer) {
+    if let JSXExpr::Expr(expr) = &container.expr {
+      let id = self.append(JSXNode::Expression);
+      let parent = std::mem::replace(&mut self.current, id);
+      expr.visit_with(self);
+      self.current = parent;
+    }
+  }
+
+  fn visit_jsx_spread_child(&mut self, child: &JSXSpreadChild) {
+    let id = self.append(JSXNode::Expression);
+    let parent = std::mem::replace(&mut self.current, id);
+    child.expr.visit_with(self);
+    self.current = parent;
   }
 }
 
 pub struct AstVisitor<'a> {
   pub taro_components: &'a [String],
+  // 组件代码中的名称 => 组件名，节点按组件名记录，如 <V /> 记录为 View
+  pub component_names: &'a HashMap<String, String>,
   pub jsx_record: &'a mut JSXRecord,
 }
 
 impl<'a> AstVisitor<'a> {
-  pub fn new(jsx_record: &'a mut JSXRecord, taro_components: &'a [String]) -> Self {
+  pub fn new(jsx_record: &'a mut JSXRecord, taro_components: &'a [String], component_names: &'a HashMap<String, String>) -> Self {
     AstVisitor {
       taro_components,
+      component_names,
       jsx_record,
     }
   }
@@ -119,6 +772,7 @@ impl<'a> AstVisitor<'a> {
         )
       }
     };
+    let name = self.component_names.get(&name).cloned().unwrap_or(name);
     let qual_name = create_qualname(name.as_str());
     let mut attributes = Vec::new();
     for attr in &jsx_element.opening.attrs {
@@ -234,7 +888,7 @@ impl<'a> VisitAll for AstVisitor<'a> {
 
   fn visit_jsx_element(&mut self, jsx: &JSXElement) {
     let element = self.create_element(JSXElementOrJSXCallee::JSXElement(jsx));
-    if let JSXElementName::Ident(_) = &jsx.opening.name {
+    if get_jsx_element_name(&jsx.opening.name).is_some() {
       // let name = ident.sym.to_string();
       // if is_starts_with_uppercase(name.as_str()) {
       //   if self.taro_components.contains(&name) {
@@ -279,32 +933,94 @@ impl<'a> VisitAll for AstVisitor<'a> {
 }
 
 
+// 解析字符串形式的内联样式，如 style="color: red; background: url(data:image/png;base64,...)"
+// 使用 lightningcss 解析声明列表，!important 声明排在普通声明之后，以覆盖普通声明
+pub fn parse_inline_style_properties(style: &str) -> Vec<(Atom, Property<'_>)> {
+  match StyleAttribute::parse(style, ParserOptions::default()) {
+    Ok(attribute) => {
+      let declarations = attribute.declarations;
+      declarations.declarations.iter()
+        .chain(declarations.important_declarations.iter())
+        .flat_map(expand_shorthand)
+        .map(|property| {
+          (
+            intern_property_name(&property),
+            property
+          )
+        })
+        .collect()
+    },
+    Err(error) => {
+      push_diagnostic("style", format!("failed to parse inline style \"{}\": {}", style, error));
+      vec![]
+    }
+  }
+}
+
+// 字符串形式的内联样式转换为样式对象，以便与 className 生成的样式在运行时合并
+pub fn parse_inline_style(style: &str, platform: Platform) -> Expr {
+  let properties = parse_inline_style_properties(style);
+  Expr::Object(ObjectLit {
+    span: DUMMY_SP,
+    props: parse_style_values(parse_style_properties(&properties, None), platform),
+  })
+}
+
 pub fn parse_style_values(value: Vec<StyleValueType>, platform: Platform) -> Vec<PropOrSpread> {
+  generate_style_props(value, platform, true)
+}
+
+// 不应用 style_key_map 的属性名，供 ArkUI 代码生成等按内置属性名识别属性的场景使用
+pub fn parse_raw_style_values(value: Vec<StyleValueType>, platform: Platform) -> Vec<PropOrSpread> {
+  generate_style_props(value, platform, false)
+}
+
+fn generate_style_props(value: Vec<StyleValueType>, platform: Platform, use_key_map: bool) -> Vec<PropOrSpread> {
   
   let mut prop_or_spread = vec![];
+  let style_key_map = if use_key_map { with_transform_options(|options| options.style_key_map.clone()) } else { HashMap::new() };
 
   // 使用有序表
   let mut index_map = IndexMap::new();
-  
+  // 归一化后的属性名 => 原始属性名，用于检测不同写法的同名属性，如 margin-top 与 marginTop
+  let mut raw_keys: HashMap<String, String> = HashMap::new();
+
+  let mut insert = |id: String, expr: Expr| {
+    if let Expr::Invalid(_) = expr { return }
+    let key = normalize_style_key(&prefix_style_key(id.clone(), platform.clone()));
+    // 按配置的鸿蒙 API 版本降级或丢弃属性
+    let expr = match platform {
+      Platform::Harmony => match check_harmony_api_level(&key, expr) {
+        Some(Expr::Invalid(_)) | None => return,
+        Some(expr) => expr
+      },
+      Platform::ReactNative => expr
+    };
+    if let Some(raw_key) = raw_keys.get(&key) {
+      if *raw_key != id {
+        push_diagnostic(&key, format!("{} conflicts with {}, the later one takes effect", id, raw_key));
+      }
+    }
+    // 同名属性后者覆盖前者
+    raw_keys.insert(key.clone(), id);
+    index_map.insert(key, Box::new(expr));
+  };
+
   value.into_iter().for_each(|style_value| {
     let prop = style_value.to_expr(platform.clone());
     match prop {
-      PropertyTuple::One(id, expr) => {
-        if let Expr::Invalid(_) = expr { return }
-        index_map.insert(prefix_style_key(id, platform.clone()), Box::new(expr));
-      }
+      PropertyTuple::One(id, expr) => insert(id, expr),
       PropertyTuple::Array(prop_arr) => {
-        prop_arr.into_iter().for_each(|(id, expr)| {
-          if let Expr::Invalid(_) = expr { return }
-          index_map.insert(prefix_style_key(id, platform.clone()), Box::new(expr));
-        })
+        prop_arr.into_iter().for_each(|(id, expr)| insert(id, expr))
       }
     }
   });
 
   index_map.into_iter().for_each(|(id, expr)| {
+    // 按配置映射为运行时使用的属性名
+    let id = style_key_map.get(&id).cloned().unwrap_or(id);
     prop_or_spread.push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
-      key: PropName::Ident(Ident::new(id.into(), DUMMY_SP)),
+      key: PropName::Ident(Ident::new(intern(&id), DUMMY_SP)),
       value: expr,
     }))))
   });
@@ -313,8 +1029,8 @@ pub fn parse_style_values(value: Vec<StyleValueType>, platform: Platform) -> Vec
 }
 
 
-// 插入运行时所需的引入
-pub fn insert_import_module_decl(module: &mut Module, last_import_index: usize, platform: Platform) -> usize {
+// 插入运行时所需的引入，has_dark_style 为 true 时引入暗色模式的运行时方法，has_container_query 为 true 时引入容器查询的运行时方法
+pub fn insert_import_module_decl(module: &mut Module, last_import_index: usize, platform: Platform, has_dark_style: bool, has_container_query: bool) -> usize {
   let mut last_index = last_import_index;
   match platform {
     Platform::ReactNative => {
@@ -437,7 +1153,17 @@ pub fn insert_import_module_decl(module: &mut Module, last_import_index: usize,
               imported: None,
               is_type_only: false,
             })
-          ],
+          ].into_iter().chain(has_dark_style.then(|| ImportSpecifier::Named(ImportNamedSpecifier {
+            span: DUMMY_SP,
+            local: Ident::new(COLOR_SCHEME_FUN.into(), DUMMY_SP),
+            imported: None,
+            is_type_only: false,
+          }))).chain(has_container_query.then(|| ImportSpecifier::Named(ImportNamedSpecifier {
+            span: DUMMY_SP,
+            local: Ident::new(CONTAINER_QUERY_FUN.into(), DUMMY_SP),
+            imported: None,
+            is_type_only: false,
+          }))).collect(),
           src: Box::new(Str::from("@tarojs/runtime")),
           type_only: false,
           with: None,
@@ -449,7 +1175,9 @@ pub fn insert_import_module_decl(module: &mut Module, last_import_index: usize,
 }
 
 pub struct ModuleMutVisitor {
-  pub all_style: Rc<RefCell<HashMap<String, StyleValue>>>,
+  pub all_style: Rc<RefCell<StyleMap>>,
+  pub dark_style: Rc<RefCell<StyleMap>>,
+  pub container_style: Rc<RefCell<ContainerStyle>>,
   pub platform: Platform,
   pub is_enable_nesting: bool,
   pub is_entry: bool,
@@ -457,12 +1185,14 @@ pub struct ModuleMutVisitor {
 
 impl ModuleMutVisitor {
   pub fn new(
-    all_style: Rc<RefCell<HashMap<String, StyleValue>>>, 
+    all_style: Rc<RefCell<StyleMap>>, 
+    dark_style: Rc<RefCell<StyleMap>>,
+    container_style: Rc<RefCell<ContainerStyle>>,
     platform: Platform, 
     is_enable_nesting: bool,
     is_entry: bool
   ) -> Self {
-    ModuleMutVisitor { all_style, platform, is_enable_nesting, is_entry }
+    ModuleMutVisitor { all_style, dark_style, container_style, platform, is_enable_nesting, is_entry }
   }
 }
 
@@ -477,51 +1207,78 @@ impl ModuleMutVisitor {
           is_enable_nesting
         }
       }
+      // 为返回的 JSX 包裹 __combine_nesting_style__，条件表达式分别处理每个分支，返回是否存在 JSX
+      // 如：return a ? <A /> : <B /> => return a ? __combine_nesting_style__(<A />) : __combine_nesting_style__(<B />)
+      fn combine_nesting_style(&self, expr: &mut Box<Expr>) -> bool {
+        let is_return_jsx_like = match &mut **expr {
+          // JSX，包括 <></>、<React.Fragment></React.Fragment>
+          Expr::JSXElement(_) |
+          Expr::JSXFragment(_) |
+          Expr::JSXMember(_) => true,
+          // React.createElement
+          Expr::Call(call_expr) => check_is_jsx_callee(call_expr),
+          // return (<View />)
+          Expr::Paren(ParenExpr { expr, .. }) => return self.combine_nesting_style(expr),
+          // return a ? <A /> : <B />
+          Expr::Cond(CondExpr { cons, alt, .. }) => {
+            let is_cons_jsx_like = self.combine_nesting_style(cons);
+            let is_alt_jsx_like = self.combine_nesting_style(alt);
+            return is_cons_jsx_like || is_alt_jsx_like
+          },
+          // return a && <A />、return a || <A />
+          Expr::Bin(BinExpr { op: BinaryOp::LogicalAnd | BinaryOp::LogicalOr | BinaryOp::NullishCoalescing, right, .. }) => return self.combine_nesting_style(right),
+          _ => false
+        };
+        if is_return_jsx_like {
+          let jsx = Box::new((**expr).take());
+          **expr = Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: Callee::Expr(Box::new(Expr::Ident(quote_ident!(COMBINE_NESTING_STYLE)))),
+            args: vec![
+              ExprOrSpread { expr: jsx, spread: None },
+              ExprOrSpread { 
+                expr: Box::new(
+                  match self.is_enable_nesting {
+                    true => Expr::Call(CallExpr {
+                      span: DUMMY_SP,
+                      callee: Callee::Expr(Box::new(Expr::Ident(quote_ident!(NESTING_STYLE)))),
+                      args: vec![],
+                      type_args: None
+                    }),
+                    false => Expr::Lit(Lit::Null(Null { span: DUMMY_SP }))
+                  }
+                ), 
+                spread: None }
+            ],
+            type_args: None,
+          });
+        }
+        is_return_jsx_like
+      }
     }
     impl VisitMut for MyVisitor {
       fn visit_mut_function(&mut self, _: &mut Function) {}
       fn visit_mut_arrow_expr(&mut self, _: &mut ArrowExpr) {}
+      // 箭头函数组件，函数体为表达式时直接处理返回的 JSX，如：const Index = () => <View />
+      fn visit_mut_block_stmt_or_expr(&mut self, body: &mut BlockStmtOrExpr) {
+        match body {
+          BlockStmtOrExpr::Expr(expr) => {
+            if !self.combine_nesting_style(expr) {
+              expr.visit_mut_with(self)
+            }
+          },
+          BlockStmtOrExpr::BlockStmt(block) => block.visit_mut_with(self)
+        }
+      }
       fn visit_mut_return_stmt(&mut self, stmt: &mut ReturnStmt) {
         let arg = &mut stmt.arg;
         if let Some(expr_in_box) = arg {
-          let is_return_jsx_like = match &mut **expr_in_box {
-            // JSX
-            Expr::JSXElement(_) |
-            Expr::JSXFragment(_) |
-            Expr::JSXMember(_) => true,
-            // React.createElement
-            Expr::Call(call_expr) => check_is_jsx_callee(call_expr),
-            _ => false
-          };
-          if is_return_jsx_like {
-            let expr = arg.take().unwrap();
-            *arg = Some(Box::new(Expr::Call(CallExpr {
-              span: DUMMY_SP,
-              callee: Callee::Expr(Box::new(Expr::Ident(quote_ident!(COMBINE_NESTING_STYLE)))),
-              args: vec![
-                ExprOrSpread { expr, spread: None },
-                ExprOrSpread { 
-                  expr: Box::new(
-                    match self.is_enable_nesting {
-                      true => Expr::Call(CallExpr {
-                        span: DUMMY_SP,
-                        callee: Callee::Expr(Box::new(Expr::Ident(quote_ident!(NESTING_STYLE)))),
-                        args: vec![],
-                        type_args: None
-                      }),
-                      false => Expr::Lit(Lit::Null(Null { span: DUMMY_SP }))
-                    }
-                  ), 
-                  spread: None }
-              ],
-              type_args: None,
-            })))
-          } else {
+          if !self.combine_nesting_style(expr_in_box) {
             // 高阶函数 return () => jsx
             match &mut **expr_in_box {
               // export const Index = () => {}
               Expr::Arrow(ArrowExpr { body, .. }) => {
-                body.visit_mut_children_with(self)
+                body.visit_mut_with(self)
               },
               // export const Index = withXxxx(() => {})
               Expr::Call(call) => {
@@ -537,6 +1294,19 @@ impl ModuleMutVisitor {
         }
       }
       fn visit_mut_call_expr(&mut self,n: &mut CallExpr) {
+        // useCallback、useMemo 定义的渲染函数，如：const renderItem = useCallback(() => <View />, [])
+        // 其返回的 JSX 通过 {renderItem()} 渲染在组件的 JSX 树中，跟随组件返回的 JSX 合成嵌套样式，不单独处理
+        if let Callee::Expr(callee) = &n.callee {
+          let is_hook_call = match &**callee {
+            Expr::Ident(ident) => ident.sym == "useCallback" || ident.sym == "useMemo",
+            // React.useCallback、React.useMemo
+            Expr::Member(MemberExpr { prop: MemberProp::Ident(ident), .. }) => ident.sym == "useCallback" || ident.sym == "useMemo",
+            _ => false
+          };
+          if is_hook_call {
+            return
+          }
+        }
         n.args.iter_mut().for_each(|arg| {
           arg.expr.visit_mut_children_with(self);
           match &mut *arg.expr {
@@ -573,7 +1343,7 @@ impl ModuleMutVisitor {
     body.visit_mut_children_with(&mut &mut self.get_nesting_visitor());
   }
   fn enable_nesting_for_arrow_function (&self, body: &mut Box<BlockStmtOrExpr>) {
-    body.visit_mut_children_with(&mut &mut self.get_nesting_visitor());
+    body.visit_mut_with(&mut &mut self.get_nesting_visitor());
   }
   fn enable_nesting_for_call_expr (&self, call: &mut CallExpr) {
     call.visit_mut_with(&mut &mut self.get_nesting_visitor());
@@ -584,6 +1354,12 @@ impl ModuleMutVisitor {
       Expr::Arrow(ArrowExpr { body, .. }) => {
         self.enable_nesting_for_arrow_function(body);
       },
+      // export default memo(forwardRef(() => {}))，解开高阶组件找到真正的组件
+      Expr::Call(call) if is_hoc_call(call) => {
+        call.args.iter_mut().for_each(|arg| {
+          self.enable_nesting_for_expr(&mut arg.expr);
+        });
+      },
       // export default withXxxx(() => {})
       Expr::Call(call) => {
         self.enable_nesting_for_call_expr(call);
@@ -592,6 +1368,10 @@ impl ModuleMutVisitor {
         // export default function () {}
         self.enable_nesting_for_function(function);
       },
+      // export default observer(class extends Component {})
+      Expr::Class(ClassExpr { class, .. }) => {
+        self.enable_nesting_for_class(class);
+      },
       Expr::Paren(ParenExpr { expr, .. }) => {
         match &mut **expr {
           // export default (() => {})()
@@ -621,6 +1401,14 @@ impl VisitMut for ModuleMutVisitor {
     // println!("visit_mut_module {:?}", self.platform);
     let binding = self.all_style.borrow_mut();
     let style_entries: BTreeMap<_, _> = binding.iter().collect();
+    let dark_binding = self.dark_style.borrow();
+    if !dark_binding.is_empty() && self.platform != Platform::Harmony {
+      push_diagnostic("prefers-color-scheme", "@media (prefers-color-scheme: dark) is not supported on ReactNative and will be ignored".to_string());
+    }
+    let container_binding = self.container_style.borrow();
+    if !container_binding.is_empty() && self.platform != Platform::Harmony {
+      push_diagnostic("@container", "@container rules that can not be resolved at compile time are not supported on ReactNative and will be ignored".to_string());
+    }
 
     // __inner_style__普通样式对象
     let mut final_style_entries: BTreeMap<String, Vec<PropOrSpread>> = BTreeMap::new();
@@ -658,7 +1446,19 @@ impl VisitMut for ModuleMutVisitor {
         })));
         insert_value.push(prop)
       } else {
-        insert_value = parse_style_values(value.to_vec(),self.platform.clone())
+        insert_value = parse_style_values(value.to_vec(),self.platform.clone());
+        // 含暗色模式样式时，由运行时根据当前颜色模式选择 light 或 dark 样式
+        if self.platform == Platform::Harmony {
+          if let Some(dark_value) = dark_binding.get(key.as_str()) {
+            insert_value = vec![generate_color_scheme_style(insert_value, parse_style_values(dark_value.to_vec(), self.platform.clone()))];
+          }
+          // 容器查询的条件样式排在后面，生效时覆盖普通样式
+          container_binding.iter().for_each(|(query, container_style)| {
+            if let Some(container_value) = container_style.get(key.as_str()) {
+              insert_value.push(generate_container_query_style(query, parse_style_values(container_value.to_vec(), self.platform.clone())));
+            }
+          });
+        }
       }
 
       // 判断是否嵌套样式
@@ -682,6 +1482,9 @@ impl VisitMut for ModuleMutVisitor {
       let _key= if is_tailwind_arbitrary(insert_key.as_str()) {
         // 如果是 tailwindcss 的任意类，进行如下转换 例如：.bg-[rgba(0,0,0,0.5)] => bg-[rgba(0,0,0,0.5)] , .w-[100px] => w-[100px]
         insert_key[1..].to_string()
+      } else if is_compound_class_selector(insert_key.as_str()) {
+        // 复合类选择器保留类名之间的 .，如 .btn.primary => btn.primary，避免与类名 btnprimary 混淆
+        insert_key[1..].to_string()
       } else {
         insert_key.replace(".", "")
       };
@@ -697,6 +1500,10 @@ impl VisitMut for ModuleMutVisitor {
 
     });
 
+    if with_transform_options(|options| options.minify) {
+      minify_style_entries(&mut final_style_entries, &mut nesting_style_entries);
+    }
+
     // 将 inner_style_stmt 插入到 module 的最后一条 import 语句之后
     let mut last_import_index = 0;
     for (index, stmt) in module.body.iter_mut().enumerate() {
@@ -768,8 +1575,13 @@ impl VisitMut for ModuleMutVisitor {
     }
     last_import_index += 1;
     // 插入平台所需的运行时代码， 如： import { calcDynamicStyle } from '@tarojs/runtime'
-    last_import_index = insert_import_module_decl(module, last_import_index, self.platform.clone());
+    last_import_index = insert_import_module_decl(module, last_import_index, self.platform.clone(), self.platform == Platform::Harmony && !dark_binding.is_empty(), self.platform == Platform::Harmony && !container_binding.is_empty());
     last_import_index += 1;
+    // 插入主题对象 export const theme = { ... }
+    if let Some(theme_decl) = generate_theme_decl(self.platform.clone()) {
+      module.body.insert(last_import_index, theme_decl);
+      last_import_index += 1;
+    }
   
     let mut var_checker = VarChecker { found: false };
     module.visit_with(&mut var_checker);
@@ -790,6 +1602,21 @@ impl VisitMut for ModuleMutVisitor {
           .collect::<Vec<PropOrSpread>>()
           .into(),
       }));
+      // React Native 下使用 StyleSheet.create 创建样式对象，如：StyleSheet.create({ "a": { ... } })
+      let style_object = if self.platform == Platform::ReactNative && with_transform_options(|options| options.rn_style_sheet_create) {
+        Box::new(Expr::Call(CallExpr {
+          span: DUMMY_SP,
+          callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
+            span: DUMMY_SP,
+            obj: Box::new(Expr::Ident(quote_ident!("StyleSheet"))),
+            prop: MemberProp::Ident(quote_ident!("create")),
+          }))),
+          args: vec![ExprOrSpread { expr: style_object, spread: None }],
+          type_args: None,
+        }))
+      } else {
+        style_object
+      };
 
       if self.is_entry {
         // 入口文件注入全局公共样式
@@ -887,6 +1714,41 @@ impl VisitMut for ModuleMutVisitor {
 }
 
 fn generate_stylesheet(fn_name: String, fn_data_name: String, style_object: Box<Expr>) -> (Stmt, Stmt) {
+  // { ...__global_shared__.__inner_style__?.(), ...{ ... } }
+  let value = Box::new(Expr::Object(ObjectLit {
+    span: DUMMY_SP,
+    props: vec![
+      PropOrSpread::Spread(SpreadElement {
+        dot3_token: DUMMY_SP,
+        // ...__global_shared__.__inner_style__?.()
+        expr: Box::new(Expr::OptChain(OptChainExpr {
+          span: DUMMY_SP,
+          optional: true,
+          base: Box::new(OptChainBase::Call(OptCall {
+            span: DUMMY_SP,
+            callee: Box::new(Expr::Member(
+              MemberExpr {
+                span: DUMMY_SP,
+                obj: Box::new(Expr::Ident(Ident::new(GLOBAL_SHARED.into(), DUMMY_SP))),
+                prop: MemberProp::Ident(Ident::new(fn_name.clone().into(), DUMMY_SP)),
+              }
+            )),
+            args: vec![],
+            type_args: None
+          }))
+        })),
+      }),
+      PropOrSpread::Spread(SpreadElement {
+        dot3_token: DUMMY_SP,
+        expr: style_object
+      })
+    ]
+  }));
+  generate_cached_function(fn_name, fn_data_name, value)
+}
+
+// 生成只在首次调用时计算的函数，如 function __inner_style__() { if (__inner_style_data__) return __inner_style_data__; __inner_style_data__ = value; return __inner_style_data__; }
+fn generate_cached_function(fn_name: String, fn_data_name: String, value: Box<Expr>) -> (Stmt, Stmt) {
 
   let ident  = Ident::new(fn_data_name.clone().into(), DUMMY_SP);
 
@@ -930,35 +1792,7 @@ fn generate_stylesheet(fn_name: String, fn_data_name: String, style_object: Box<
                   id: ident.clone(),
                   type_ann: None
                 })),
-                right: Box::new(Expr::Object(ObjectLit {
-                  span: DUMMY_SP,
-                  props: vec![
-                    PropOrSpread::Spread(SpreadElement {
-                      dot3_token: DUMMY_SP,
-                      // ...__global_shared__.__inner_style__?.()
-                      expr: Box::new(Expr::OptChain(OptChainExpr {
-                        span: DUMMY_SP,
-                        optional: true,
-                        base: Box::new(OptChainBase::Call(OptCall {
-                          span: DUMMY_SP,
-                          callee: Box::new(Expr::Member(
-                            MemberExpr {
-                              span: DUMMY_SP,
-                              obj: Box::new(Expr::Ident(Ident::new(GLOBAL_SHARED.into(), DUMMY_SP))),
-                              prop: MemberProp::Ident(Ident::new(fn_name.clone().into(), DUMMY_SP)),
-                            }
-                          )),
-                          args: vec![],
-                          type_args: None
-                        }))
-                      })),
-                    }),
-                    PropOrSpread::Spread(SpreadElement {
-                      dot3_token: DUMMY_SP,
-                      expr: style_object
-                    })
-                  ]
-                }))
+                right: value
               })
             )
           }
@@ -1002,6 +1836,64 @@ fn generate_stylesheet(fn_name: String, fn_data_name: String, style_object: Box<
 
 
 // 挂载全局入口样式
+// 生成 ...__color_scheme__({ light: { ... }, dark: { ... } })
+fn generate_color_scheme_style(light: Vec<PropOrSpread>, dark: Vec<PropOrSpread>) -> PropOrSpread {
+  let generate_prop = |key: &str, props: Vec<PropOrSpread>| {
+    PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+      key: PropName::Ident(Ident::new(key.into(), DUMMY_SP)),
+      value: Box::new(Expr::Object(ObjectLit {
+        span: DUMMY_SP,
+        props
+      })),
+    })))
+  };
+  PropOrSpread::Spread(SpreadElement {
+    dot3_token: DUMMY_SP,
+    expr: Box::new(Expr::Call(CallExpr {
+      span: DUMMY_SP,
+      callee: Callee::Expr(Box::new(Expr::Ident(Ident::new(COLOR_SCHEME_FUN.into(), DUMMY_SP)))),
+      args: vec![ExprOrSpread {
+        spread: None,
+        expr: Box::new(Expr::Object(ObjectLit {
+          span: DUMMY_SP,
+          props: vec![generate_prop("light", light), generate_prop("dark", dark)]
+        }))
+      }],
+      type_args: None
+    }))
+  })
+}
+
+// 生成 ...__container_query__({ name: "card", condition: "(width >= 300px)", style: { ... } })，未指定容器名时不含 name
+fn generate_container_query_style(query: &ContainerQuery, style: Vec<PropOrSpread>) -> PropOrSpread {
+  let generate_prop = |key: &str, value: Expr| {
+    PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+      key: PropName::Ident(Ident::new(key.into(), DUMMY_SP)),
+      value: Box::new(value),
+    })))
+  };
+  let props = query.name
+    .iter()
+    .map(|name| generate_prop("name", Expr::Lit(Lit::Str(Str::from(name.as_str())))))
+    .chain([
+      generate_prop("condition", Expr::Lit(Lit::Str(Str::from(query.condition.as_str())))),
+      generate_prop("style", Expr::Object(ObjectLit { span: DUMMY_SP, props: style })),
+    ])
+    .collect();
+  PropOrSpread::Spread(SpreadElement {
+    dot3_token: DUMMY_SP,
+    expr: Box::new(Expr::Call(CallExpr {
+      span: DUMMY_SP,
+      callee: Callee::Expr(Box::new(Expr::Ident(Ident::new(CONTAINER_QUERY_FUN.into(), DUMMY_SP)))),
+      args: vec![ExprOrSpread {
+        spread: None,
+        expr: Box::new(Expr::Object(ObjectLit { span: DUMMY_SP, props }))
+      }],
+      type_args: None
+    }))
+  })
+}
+
 fn generate_common_stylesheet(attr_name: String, style_object: Box<Expr>) -> Stmt {
   let common_inner_style = Stmt::Expr(
     ExprStmt {
@@ -1046,6 +1938,25 @@ fn generate_common_stylesheet(attr_name: String, style_object: Box<Expr>) -> Stm
 }
 
 
+// 判断是否为高阶组件包装调用，如 memo(App)、React.memo(App)、connect(mapState)(App)
+fn is_hoc_call (call_expr: &CallExpr) -> bool {
+  fn get_callee_name (expr: &Expr) -> Option<String> {
+    match expr {
+      Expr::Ident(ident) => Some(ident.sym.to_string()),
+      Expr::Member(MemberExpr { prop: MemberProp::Ident(ident), .. }) => Some(ident.sym.to_string()),
+      // connect(mapState)(App)
+      Expr::Call(CallExpr { callee: Callee::Expr(expr), .. }) => get_callee_name(expr),
+      _ => None
+    }
+  }
+  if let Callee::Expr(expr) = &call_expr.callee {
+    if let Some(name) = get_callee_name(expr) {
+      return HOC_WRAPPERS.contains(&name.as_str())
+    }
+  }
+  false
+}
+
 fn check_is_jsx_callee (call_expr: &CallExpr) -> bool {
   if let Callee::Expr(expr) = &call_expr.callee {
     if let Expr::Member(member) = &**expr {
@@ -1069,28 +1980,25 @@ pub enum EtsDirection {
   Row,
   Column,
 }
-pub struct JSXMutVisitor<'i> {
+pub struct JSXMutVisitor {
   pub jsx_record: Rc<RefCell<JSXRecord>>,
-  pub all_style: Rc<RefCell<HashMap<String, StyleValue>>>, 
-  pub pesudo_style_record: Rc<RefCell<HashMap<SpanKey, Vec<(String, Vec<(String, Property<'i>)>)>>>>,
+  pub all_style: Rc<RefCell<StyleMap>>, 
   pub taro_components: Vec<String>,
   pub platform: Platform,
   // 半编译模式组件
   is_compile_mode: bool
 }
 
-impl<'i> JSXMutVisitor<'i> {
+impl JSXMutVisitor {
   pub fn new(
     jsx_record: Rc<RefCell<JSXRecord>>,
-    all_style: Rc<RefCell<HashMap<String, StyleValue>>>, 
-    pesudo_style_record: Rc<RefCell<HashMap<SpanKey, Vec<(String, Vec<(String, Property<'i>)>)>>>>,
+    all_style: Rc<RefCell<StyleMap>>, 
     taro_components: Vec<String>,
     platform: Platform
   ) -> Self {
     JSXMutVisitor {
       jsx_record,
       all_style,
-      pesudo_style_record,
       taro_components,
       platform,
       is_compile_mode: false
@@ -1420,14 +2328,15 @@ impl VisitMut for ObjectFinder {
   }
 }
 
-impl<'i> VisitMut for JSXMutVisitor<'i> {
+impl VisitMut for JSXMutVisitor {
   noop_visit_mut_type!();
 
   fn visit_mut_call_expr(&mut self, n: &mut CallExpr) {
     if check_is_jsx_callee(n) {
     
       let span_key = SpanKey(n.span);
-      if let Some(_) = self.jsx_record.borrow_mut().get(&span_key) {
+      if let Some(element) = self.jsx_record.borrow_mut().get(&span_key) {
+        trace!(tag = element.name(), span = ?n.span, "write element style");
         let jsx_element_or_callee = JSXElementOrJSXCallee::JSXCallee(&n);
         let (class_attr_value, _) = self.get_jsx_element_or_callee_calss_value_and_dynamic_class_styles(&jsx_element_or_callee);
         // 插入静态style
@@ -1475,7 +2384,8 @@ impl<'i> VisitMut for JSXMutVisitor<'i> {
   fn visit_mut_jsx_element(&mut self, n: &mut JSXElement) {
     let span_key = SpanKey(n.span);
 
-    if let Some(_) = self.jsx_record.borrow_mut().get(&span_key) {
+    if let Some(element) = self.jsx_record.borrow_mut().get(&span_key) {
+      trace!(tag = element.name(), span = ?n.span, "write element style");
       // 将 style_record 中的样式添加到 JSXElement 的 style 属性中
       let jsx_element_or_callee = JSXElementOrJSXCallee::JSXElement(&n);
       let (class_attr_value, _) = self.get_jsx_element_or_callee_calss_value_and_dynamic_class_styles(&jsx_element_or_callee);
@@ -1491,6 +2401,21 @@ impl<'i> VisitMut for JSXMutVisitor<'i> {
         }
       }
 
+      // 字符串形式的内联样式转换为样式对象
+      for attr in n.opening.attrs.iter_mut() {
+        if let JSXAttrOrSpread::JSXAttr(JSXAttr { name: JSXAttrName::Ident(ident), value, .. }) = attr {
+          if ident.sym == "style" {
+            if let Some(JSXAttrValue::Lit(Lit::Str(str))) = value {
+              let style = parse_inline_style(&str.value, self.platform.clone());
+              *value = Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+                span: DUMMY_SP,
+                expr: JSXExpr::Expr(Box::new(style)),
+              }));
+            }
+          }
+        }
+      }
+
       // 半编译模式下，识别能识别出来的静态样式，设置harmonyDirection
       if self.is_compile_mode {
         self.compile_mode_inject(n);
@@ -1523,12 +2448,8 @@ impl<'i> VisitMut for JSXMutVisitor<'i> {
       // 判断是否Taro组件还是自定义组件
       // Taro组件插入__hmStyle__属性
       // 自定义组件插入__styleSheet属性
-      if let JSXElementName::Ident(ident) = &n.opening.name {
-        let name = ident.sym.to_string();
-        if 
-          (is_starts_with_uppercase(name.as_str()) && self.taro_components.contains(&name))
-          || !is_starts_with_uppercase(name.as_str())
-         {
+      if let Some(name) = get_jsx_element_name(&n.opening.name) {
+        if is_taro_element(&name, &self.taro_components) {
           let mut should_insert = false;
           if let Some(_) = &class_attr_value {
             should_insert = true
@@ -1589,3 +2510,180 @@ impl<'i> VisitMut for JSXMutVisitor<'i> {
     n.visit_mut_children_with(self);
   }
 }
+
+// 类组件中静态 className 的样式对象提升到模块作用域，首次使用时计算并缓存，避免每次 render 重新创建
+// 仅处理 JSXMutVisitor 为 jsx_record 中的节点写入的 calcStaticStyle(__inner_style__(), "a b")，如：
+// __styleSheet={{ key: "a b", value: calcStaticStyle(__inner_style__(), "a b") }} => __styleSheet={__hoisted_style__()["a b"]}
+// __hmStyle={calcStaticStyle(__inner_style__(), "a b")} => __hmStyle={__hoisted_style__()["a b"].value}
+pub struct StaticStyleHoistVisitor {
+  // 提升的 className，按首次出现的顺序排列
+  pub class_names: Vec<String>,
+  in_class: bool,
+}
+
+impl StaticStyleHoistVisitor {
+  pub fn new() -> Self {
+    StaticStyleHoistVisitor {
+      class_names: vec![],
+      in_class: false,
+    }
+  }
+
+  // calcStaticStyle(__inner_style__(), "a b") 中的 className
+  fn get_static_class_name(expr: &Expr) -> Option<String> {
+    let Expr::Call(CallExpr { callee: Callee::Expr(callee), args, .. }) = expr else {
+      return None
+    };
+    if !matches!(&**callee, Expr::Ident(ident) if ident.sym == CALC_STATIC_STYLE) || args.len() != 2 {
+      return None
+    }
+    let is_inner_style = matches!(
+      &*args[0].expr,
+      Expr::Call(CallExpr { callee: Callee::Expr(callee), args, .. }) if args.is_empty() && matches!(&**callee, Expr::Ident(ident) if ident.sym == INNER_STYLE)
+    );
+    match (is_inner_style, &*args[1].expr) {
+      (true, Expr::Lit(Lit::Str(str))) => Some(str.value.to_string()),
+      _ => None
+    }
+  }
+
+  // { key: "a b", value: calcStaticStyle(__inner_style__(), "a b") } 中的 className
+  fn get_style_sheet_class_name(object: &ObjectLit) -> Option<String> {
+    let [PropOrSpread::Prop(key), PropOrSpread::Prop(value)] = object.props.as_slice() else {
+      return None
+    };
+    match (&**key, &**value) {
+      (Prop::KeyValue(key), Prop::KeyValue(value)) if matches!(&key.key, PropName::Ident(ident) if ident.sym == "key") && matches!(&value.key, PropName::Ident(ident) if ident.sym == "value") => {
+        let class_name = StaticStyleHoistVisitor::get_static_class_name(&value.value)?;
+        match &*key.value {
+          Expr::Lit(Lit::Str(str)) if str.value == class_name.as_str() => Some(class_name),
+          _ => None
+        }
+      },
+      _ => None
+    }
+  }
+
+  // __hoisted_style__()["a b"]
+  fn generate_hoisted_expr(&mut self, class_name: String) -> Expr {
+    if !self.class_names.contains(&class_name) {
+      self.class_names.push(class_name.clone());
+    }
+    Expr::Member(MemberExpr {
+      span: DUMMY_SP,
+      obj: Box::new(Expr::Call(CallExpr {
+        span: DUMMY_SP,
+        callee: Callee::Expr(Box::new(Expr::Ident(Ident::new(HOISTED_STYLE.into(), DUMMY_SP)))),
+        args: vec![],
+        type_args: None,
+      })),
+      prop: MemberProp::Computed(ComputedPropName {
+        span: DUMMY_SP,
+        expr: Box::new(Expr::Lit(Lit::Str(class_name.into()))),
+      }),
+    })
+  }
+
+  // 在 function __inner_style__ 之后插入 let __hoisted_style_data__; function __hoisted_style__() { ... }
+  // __hoisted_style_data__ = { "a b": { key: "a b", value: calcStaticStyle(__inner_style__(), "a b") } }
+  pub fn insert_hoisted_style(&self, module: &mut Module) {
+    let Some(index) = module.body.iter().position(|item| {
+      matches!(item, ModuleItem::Stmt(Stmt::Decl(Decl::Fn(FnDecl { ident, .. }))) if ident.sym == INNER_STYLE)
+    }) else {
+      return
+    };
+    let style_object = Box::new(Expr::Object(ObjectLit {
+      span: DUMMY_SP,
+      props: self.class_names.iter().map(|class_name| {
+        PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+          key: PropName::Str(Str::from(class_name.as_str())),
+          value: Box::new(Expr::Object(ObjectLit {
+            span: DUMMY_SP,
+            props: vec![
+              PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                key: PropName::Ident(Ident::new("key".into(), DUMMY_SP)),
+                value: Box::new(Expr::Lit(Lit::Str(class_name.as_str().into()))),
+              }))),
+              PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                key: PropName::Ident(Ident::new("value".into(), DUMMY_SP)),
+                value: Box::new(ObjectFinder::get_fun_call_expr(Expr::Lit(Lit::Str(class_name.as_str().into())))),
+              }))),
+            ],
+          })),
+        })))
+      }).collect(),
+    }));
+    let (identifier, style_func) = generate_cached_function(HOISTED_STYLE.to_string(), HOISTED_STYLE_DATA.to_string(), style_object);
+    module.body.insert(index + 1, ModuleItem::Stmt(identifier));
+    module.body.insert(index + 2, ModuleItem::Stmt(style_func));
+  }
+}
+
+impl VisitMut for StaticStyleHoistVisitor {
+  noop_visit_mut_type!();
+
+  fn visit_mut_class(&mut self, class: &mut Class) {
+    let in_class = self.in_class;
+    self.in_class = true;
+    class.visit_mut_children_with(self);
+    self.in_class = in_class;
+  }
+
+  fn visit_mut_expr(&mut self, expr: &mut Expr) {
+    if self.in_class {
+      if let Expr::Object(object) = expr {
+        if let Some(class_name) = StaticStyleHoistVisitor::get_style_sheet_class_name(object) {
+          *expr = self.generate_hoisted_expr(class_name);
+          return;
+        }
+      }
+      if let Some(class_name) = StaticStyleHoistVisitor::get_static_class_name(expr) {
+        *expr = Expr::Member(MemberExpr {
+          span: DUMMY_SP,
+          obj: Box::new(self.generate_hoisted_expr(class_name)),
+          prop: MemberProp::Ident(quote_ident!("value")),
+        });
+        return;
+      }
+    }
+    expr.visit_mut_children_with(self);
+  }
+}
+
+// 按配置的引号输出生成的字符串，组件代码中原有的字符串（raw 不为空）保持原样
+// JSX 属性值中的字符串不支持转义，保持 swc 的默认输出
+pub struct QuoteStyleVisitor {
+  pub quote: char,
+}
+
+impl VisitMut for QuoteStyleVisitor {
+  noop_visit_mut_type!();
+
+  fn visit_mut_str(&mut self, str: &mut Str) {
+    if str.raw.is_some() {
+      return;
+    }
+    let mut raw = String::with_capacity(str.value.len() + 2);
+    raw.push(self.quote);
+    str.value.chars().for_each(|c| match c {
+      '\\' => raw.push_str("\\\\"),
+      '\n' => raw.push_str("\\n"),
+      '\r' => raw.push_str("\\r"),
+      '\t' => raw.push_str("\\t"),
+      c if c == self.quote => {
+        raw.push('\\');
+        raw.push(c);
+      },
+      c => raw.push(c)
+    });
+    raw.push(self.quote);
+    str.raw = Some(raw.into());
+  }
+
+  fn visit_mut_jsx_attr_value(&mut self, value: &mut JSXAttrValue) {
+    if let JSXAttrValue::Lit(_) = value {
+      return;
+    }
+    value.visit_mut_children_with(self);
+  }
+}
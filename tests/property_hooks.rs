@@ -0,0 +1,39 @@
+use parse_css_to_stylesheet::{clear_property_hooks, register_property_hook, transform_snapshot, PropertyTuple};
+use swc_core::{common::DUMMY_SP, ecma::ast::{Expr, Lit, Number}};
+
+const COMPONENT: &str = r#"import { View } from '@tarojs/components'
+export default function Index() {
+  return <View className="item" />
+}
+"#;
+
+fn number(value: f64) -> Expr {
+  Expr::Lit(Lit::Num(Number { span: DUMMY_SP, value, raw: None }))
+}
+
+#[test]
+fn custom_property() {
+  register_property_hook(|property_id, _| match property_id {
+    "--brand-elevation" => Some(PropertyTuple::Array(vec![
+      ("shadowRadius".to_string(), number(4.0)),
+      ("shadowOpacity".to_string(), number(0.2)),
+    ])),
+    _ => None
+  });
+  let code = transform_snapshot(COMPONENT, ".item { --brand-elevation: 2; width: 10px; }", "Harmony");
+  clear_property_hooks();
+  assert!(code.contains("shadowRadius: 4"));
+  assert!(code.contains("shadowOpacity: 0.2"));
+  assert!(code.contains("width"));
+}
+
+#[test]
+fn override_builtin() {
+  register_property_hook(|property_id, _| match property_id {
+    "width" => Some(PropertyTuple::One("width".to_string(), number(42.0))),
+    _ => None
+  });
+  let code = transform_snapshot(COMPONENT, ".item { width: 10px; }", "Harmony");
+  clear_property_hooks();
+  assert!(code.contains("width: 42"));
+}
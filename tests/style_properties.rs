@@ -0,0 +1,127 @@
+use parse_css_to_stylesheet::transform_snapshot;
+
+const COMPONENT: &str = r#"import { View } from '@tarojs/components'
+export default function Index() {
+  return <View className="item" />
+}
+"#;
+
+// 多个类名及内联样式的组件，用于类样式之间、类样式与内联样式的层叠
+const CASCADE_COMPONENT: &str = r#"import { View } from '@tarojs/components'
+export default function Index() {
+  return <View className="item">
+    <View className="item other" />
+    <View className="item" style={{ color: 'green', width: 20 }} />
+    <View className="item" style="color: green; width: 20px" />
+  </View>
+}
+"#;
+
+// 每个属性模块一个用例，分别固定 Harmony 与 ReactNative 的转换结果
+macro_rules! snapshot_test {
+  ($name:ident, $css:expr) => {
+    snapshot_test!($name, COMPONENT, $css);
+  };
+  ($name:ident, $component:expr, $css:expr) => {
+    #[test]
+    fn $name() {
+      insta::assert_snapshot!(concat!(stringify!($name), "_harmony"), transform_snapshot($component, $css, "Harmony"));
+      insta::assert_snapshot!(concat!(stringify!($name), "_rn"), transform_snapshot($component, $css, "ReactNative"));
+    }
+  };
+}
+
+snapshot_test!(animation, ".item { animation: fade 1s ease-in infinite; } @keyframes fade { from { opacity: 0; } to { opacity: 1; } }");
+snapshot_test!(animation_shorthand_keywords, ".item { animation: 2s ease-in 1s infinite alternate both paused slidein, 1s fade; } @keyframes slidein { from { opacity: 0; } to { opacity: 1; } }");
+snapshot_test!(animation_implicit_frames, ".item { opacity: 0.5; animation: fade 1s; } @keyframes fade { 30% { opacity: 1; transform: scale(2); } 60% { opacity: 0.2; } }");
+snapshot_test!(aspect_ratio, ".item { aspect-ratio: 16 / 9; }");
+snapshot_test!(aspect_ratio_auto, ".item { aspect-ratio: auto 4 / 3; } .empty { aspect-ratio: auto; }");
+snapshot_test!(background, ".item { background: #fff url(a.png) no-repeat center / cover; }");
+snapshot_test!(background_image, ".item { background-image: linear-gradient(to right, red, blue); }");
+snapshot_test!(background_image_radial, ".item { background-image: radial-gradient(circle, red, blue); }");
+snapshot_test!(background_image_repeating, ".item { background-image: repeating-linear-gradient(45deg, red 0%, blue 10%); }");
+snapshot_test!(background_image_conic, ".item { background-image: conic-gradient(from 90deg at 30% 40%, red, blue 50%, red); }");
+snapshot_test!(background_image_corner, ".item { background-image: linear-gradient(to right bottom, red, blue); }");
+snapshot_test!(background_position, ".item { background-position: 10px 20px; }");
+snapshot_test!(background_repeat, ".item { background-repeat: repeat-x; }");
+snapshot_test!(background_size, ".item { background-size: 100% auto; }");
+snapshot_test!(border, ".item { border: 1px solid #333; }");
+snapshot_test!(border_color, ".item { border-color: red blue; }");
+snapshot_test!(border_radius, ".item { border-radius: 4px 8px; }");
+snapshot_test!(border_style, ".item { border-style: dashed; }");
+snapshot_test!(border_width, ".item { border-width: 1px 2px; }");
+snapshot_test!(box_shadow, ".item { box-shadow: 0 2px 4px rgba(0, 0, 0, 0.2); }");
+snapshot_test!(calc, ".item { width: calc(100% - 10px); height: calc(10px + 2vw); left: calc(10px - (2vw + 3px) * 2); letter-spacing: calc(1px + 0.5vw); }");
+snapshot_test!(calc_math_functions, ".item { margin-top: min(10px, 5vw); padding-left: clamp(10px, 2vw, 40px); max-width: max(50%, calc(100px - 2vw)); }");
+snapshot_test!(color, ".item { color: #ff0000; background-color: rgba(0, 0, 0, 0.5); }");
+snapshot_test!(color_space, ".item { color: hsl(120 100% 50%); border-color: lab(50% 40 59.5); text-decoration: underline oklch(70% 0.1 200); }");
+snapshot_test!(content, ".item:before { content: \"hi\"; }");
+snapshot_test!(current_color, ".item { color: red; border: 1px solid currentColor; } .other { border-color: currentColor; }");
+snapshot_test!(direction, ".item { direction: rtl; writing-mode: vertical-rl; }");
+snapshot_test!(display, ".item { display: flex; }");
+snapshot_test!(display_fallback, ".item { display: grid; } .title { display: inline-flex; }");
+snapshot_test!(expr, ".item { width: var(--width); }");
+snapshot_test!(filter, ".item { filter: blur(4px) brightness(0.5); }");
+snapshot_test!(flex, ".item { flex: 1; }");
+snapshot_test!(flex_align, ".item { justify-content: space-between; align-content: center; }");
+snapshot_test!(flex_basis, ".item { flex-basis: 50%; }");
+snapshot_test!(flex_direction, ".item { flex-direction: column; }");
+snapshot_test!(flex_wrap, ".item { flex-wrap: wrap; }");
+snapshot_test!(font_family, ".item { font-family: \"PingFang SC\", sans-serif; }");
+snapshot_test!(font_size, ".item { font-size: 14px; }");
+snapshot_test!(font_style, ".item { font-style: italic; }");
+snapshot_test!(font_weight, ".item { font-weight: bold; }");
+snapshot_test!(gap, ".item { gap: 8px 4px; }");
+snapshot_test!(gap_percentage, ".item { width: 200px; height: 100px; row-gap: 10%; column-gap: calc(5% + 4px); }");
+snapshot_test!(important_across_classes, CASCADE_COMPONENT, ".item { color: red !important; width: 10px; } .other { color: blue; }");
+snapshot_test!(important_over_inline_style, CASCADE_COMPONENT, ".item { color: red !important; height: 10px; }");
+snapshot_test!(invalid_value, ".item { width: foo; color: notacolor; height: ; margin-top: 1xx; background-position: 1xx; flex-direction: 1xx; }\n.title { width: var(--w); color: red; }");
+snapshot_test!(item_align, ".item { align-items: center; align-self: flex-end; }");
+snapshot_test!(layer, "@layer base, theme; @layer theme { .item { color: red; height: 1px !important; } } @layer base { .item { color: blue; width: 2px; height: 2px !important; } } .item { width: 3px; }");
+snapshot_test!(layer_nesting, CASCADE_COMPONENT, "@layer base { .item.other { color: red; width: 1px; } .item .other { height: 2px; } } .item { color: blue; } .other { height: 3px; }");
+snapshot_test!(length_value, ".item { position: absolute; top: 10px; left: 5%; }");
+snapshot_test!(length_value_float, ".item { width: 10.5px; height: calc(10.5px + 2vw); margin-top: 0.3rem; padding-left: 0.1vw; }");
+snapshot_test!(length_value_units, ".item { width: 10vmin; height: 5vmax; margin-top: 1in; padding-top: 10mm; padding-left: 4Q; font-size: 12pt; }");
+snapshot_test!(letter_spacing, ".item { letter-spacing: 2px; }");
+snapshot_test!(letter_spacing_em, ".item { font-size: 20px; letter-spacing: 0.1em; word-spacing: -2px; } .normal { letter-spacing: normal; word-spacing: 0.5em; }");
+snapshot_test!(letter_spacing_em_fallback, ".item { letter-spacing: 0.1em; } .keyword { font-size: larger; word-spacing: 0.2em; }");
+snapshot_test!(line_clamp, ".item { -webkit-line-clamp: 2; }");
+snapshot_test!(line_height, ".item { line-height: 20px; }");
+snapshot_test!(line_height_multiple, ".item { font-size: 14px; line-height: 1.5; } .percent { line-height: 150%; } .calc { font-size: calc(10px + 1vw); line-height: 1.2em; }");
+snapshot_test!(line_height_multiple_precision, ".item { font-size: 12px; line-height: 1.83; letter-spacing: 0.15em; }");
+snapshot_test!(lint, ".item { float: left; display: inline-block; position: fixed; } #title { color: red; } .item[data-active] { color: blue; }");
+snapshot_test!(margin_padding, ".item { margin: 1px 2px; padding: 4px; }");
+snapshot_test!(mask, ".item { mask-image: url(a.png); mask-size: cover; mask-position: center; mask-repeat: no-repeat; }");
+snapshot_test!(max_size, ".item { max-width: 100px; max-height: 50%; }");
+snapshot_test!(media_query, ".item { color: black; @media (orientation: landscape) { width: 1px; } } @media only screen and (prefers-color-scheme:dark) { .item { color: white; } } @media (min-width: 100px) { .item { color: red; } }");
+snapshot_test!(nesting, ".item { color: red; &:hover { color: blue; } @media (prefers-color-scheme: dark) { color: white; } }");
+snapshot_test!(normal, ".item { position: relative; }");
+snapshot_test!(number, ".item { z-index: 2; flex-grow: 1; }");
+snapshot_test!(opacity, ".item { opacity: 0.5; }");
+snapshot_test!(outline, ".item { outline: 1px solid red; }");
+snapshot_test!(overflow, ".item { overflow: hidden; }");
+snapshot_test!(overflow_axis, ".item { overflow: hidden scroll; overflow-x: auto; }");
+snapshot_test!(passthrough, ".item { grid-template-columns: 1fr 1fr; }");
+snapshot_test!(pointer_events, ".item { pointer-events: none; }");
+snapshot_test!(scroll, ".item { scroll-behavior: smooth; overscroll-behavior: contain; }");
+snapshot_test!(size, ".item { width: 100px; height: 50%; }");
+snapshot_test!(supports, "@supports (color: red) { .item { color: red; } } @supports (display: grid) { .item { display: grid; } } @supports (width: foo) { .item { width: 1px; } } @supports not (color: foo) { .item { height: 2px; } }");
+snapshot_test!(syntax_error, ".item { width: 10px; }\n..bad { color: red; }\n.item { height: 5px; }");
+snapshot_test!(text_align, ".item { text-align: center; }");
+snapshot_test!(text_decoration, ".item { text-decoration: underline red; }");
+snapshot_test!(text_decoration_longhand, ".item { text-decoration-line: underline line-through; text-decoration-style: dashed; text-decoration-color: red; text-decoration-thickness: 2px; }");
+snapshot_test!(text_overflow, ".item { text-overflow: ellipsis; }");
+snapshot_test!(text_shadow, ".item { text-shadow: 1px 1px 2px #000; }");
+snapshot_test!(text_shadow_multiple, ".item { text-shadow: 1px 1px 2px #000, 0 0 4px rgba(255, 0, 0, 0.5); }");
+snapshot_test!(text_stroke, ".item { -webkit-text-stroke: 1px rgb(255, 0, 0); -webkit-text-stroke-color: blue; }");
+snapshot_test!(text_transform, ".item { text-transform: uppercase; }");
+snapshot_test!(transform, ".item { transform: translate(10px, 20px) rotate(45deg) scale(1.5); }");
+snapshot_test!(transform_origin, ".item { transform-origin: left top; }");
+snapshot_test!(transition, ".item { transition: opacity 0.3s ease-in 100ms, transform 0.3s ease-in 0.1s, width 1s; } .title { transition-property: all; transition-duration: 1s, 2s; transition-timing-function: cubic-bezier(0.1, 0.2, 0.3, 0.4); }");
+snapshot_test!(value_out_of_range, ".item { width: -10px; padding: 4px -2px; opacity: 1.5; animation-iteration-count: -2; }\n.title { border: -1px solid red; }");
+snapshot_test!(value_out_of_range_keyframes, ".item { animation: fade 1s; }\n@keyframes fade {\n  from { opacity: -1; }\n  to { /* max */ opacity: 2; }\n}");
+snapshot_test!(vertical_align, ".item { vertical-align: middle; }");
+snapshot_test!(vertical_align_length, ".item { vertical-align: 4px; } .title { line-height: 20px; vertical-align: -25%; } .side { vertical-align: 10%; }");
+snapshot_test!(visibility, ".item { visibility: hidden; }");
+snapshot_test!(white_space, ".item { white-space: nowrap; }");
+snapshot_test!(word_break, ".item { word-break: break-all; }");
@@ -0,0 +1,113 @@
+use parse_css_to_stylesheet::{Diagnostic, DiagnosticSeverity, Platform, StyleIr, StylePrecedence, StyleSheetValue, TransformOptions, Transformer};
+
+const COMPONENT: &str = r#"import { View } from '@tarojs/components'
+export default function Index() {
+  return <View className="item" />
+}
+"#;
+
+#[test]
+fn transform_with_builder() {
+  let options = TransformOptions {
+    root_font_size: 20.0,
+    ..TransformOptions::default()
+  };
+  let result = Transformer::new(COMPONENT.to_string(), vec![".item { width: 2rem; color: red; } .unused { color: blue; }".to_string()])
+    .platform(Platform::ReactNative)
+    .options(options)
+    .transform();
+  assert!(result.code.contains("width: scalePx2dp(40)"));
+  assert!(result.diagnostics.is_empty());
+  assert_eq!(result.class_usage.used, vec!["item".to_string()]);
+  assert_eq!(result.class_usage.unused, vec!["unused".to_string()]);
+}
+
+#[test]
+fn deserialize_ir() {
+  let result = Transformer::new(COMPONENT.to_string(), vec![".item { width: 10px; transform: translate(10px, 20px) rotate(45deg); animation: fade 1s; } @keyframes fade { from { opacity: 0; } to { opacity: 1; } }".to_string()])
+    .ir(true)
+    .transform();
+  let json = result.ir.unwrap().to_json();
+  let ir = StyleIr::from_json(&json).unwrap();
+  assert_eq!(ir.rules[0].selector, ".item");
+  assert!(matches!(ir.rules[0].properties[0], (ref key, StyleSheetValue::Expr { .. }) if key == "width"));
+  assert_eq!(ir.nodes.len(), 1);
+  assert_eq!(ir.keyframes[0].name, "fade");
+  assert_eq!(ir.to_json(), json);
+}
+
+#[test]
+fn supports_features_override_builtin() {
+  let options = TransformOptions {
+    supports_features: [("display".to_string(), true)].into_iter().collect(),
+    ..TransformOptions::default()
+  };
+  let result = Transformer::new(COMPONENT.to_string(), vec!["@supports (display: grid) { .item { width: 1px; } }".to_string()])
+    .options(options)
+    .transform();
+  assert!(result.code.contains("width: convertNumber2VP(1)"));
+}
+
+#[test]
+fn strict_invalid_values() {
+  let options = TransformOptions {
+    strict: true,
+    ..TransformOptions::default()
+  };
+  let result = Transformer::new(COMPONENT.to_string(), vec![".box{width:foo;color:notacolor;height:;margin-top:1xx}".to_string()])
+    .options(options)
+    .transform();
+  let errors = result.diagnostics.iter()
+    .filter(|diagnostic| diagnostic.severity == DiagnosticSeverity::Error)
+    .map(|diagnostic| (diagnostic.property.as_str(), diagnostic.message.as_str()))
+    .collect::<Vec<_>>();
+  assert_eq!(errors, vec![
+    ("width", ".box { width: foo } has an invalid value at line 1, column 6, the declaration is ignored"),
+    ("color", ".box { color: notacolor } has an invalid value at line 1, column 16, the declaration is ignored"),
+    ("height", ".box { height:  } has an invalid value at line 1, column 32, the declaration is ignored"),
+    ("marginTop", ".box { margin-top: 1xx } has an invalid value at line 1, column 40, the declaration is ignored"),
+  ]);
+}
+
+#[test]
+fn page_precedence_nesting() {
+  let component = r#"import { View } from '@tarojs/components'
+export default function Index() {
+  return <View className="wrap list">
+    <View className="box x" />
+  </View>
+}
+"#;
+  let options = TransformOptions {
+    global_style_count: 1,
+    style_precedence: StylePrecedence::Page,
+    ..TransformOptions::default()
+  };
+  let result = Transformer::new(component.to_string(), vec![
+    ".box.x { color: red; width: 1px; } .wrap .box { height: 2px; }".to_string(),
+    ".box { color: blue; } .list .box { height: 3px; }".to_string(),
+  ])
+    .options(options)
+    .transform();
+  let mut snapshot = result.code;
+  for diagnostic in result.diagnostics {
+    snapshot.push_str(&format!("// [{}] {}\n", diagnostic.property, diagnostic.message));
+  }
+  insta::assert_snapshot!(snapshot);
+}
+
+#[test]
+fn transform_platforms_matches_single_platform() {
+  let styles = vec![".item { width: 10px; color: red; } .item .text { float: left; } .bad { width: foo; }".to_string()];
+  let results = Transformer::new(COMPONENT.to_string(), styles.clone())
+    .transform_platforms(vec![Platform::Harmony, Platform::ReactNative]);
+  for (platform, result) in results {
+    let single = Transformer::new(COMPONENT.to_string(), styles.clone())
+      .platform(platform)
+      .transform();
+    assert_eq!(result.code, single.code);
+    let messages = |diagnostics: &[Diagnostic]| diagnostics.iter().map(|diagnostic| diagnostic.message.clone()).collect::<Vec<_>>();
+    assert_eq!(messages(&result.diagnostics), messages(&single.diagnostics));
+    assert_eq!(result.stats.selector_lookups, single.stats.selector_lookups);
+  }
+}